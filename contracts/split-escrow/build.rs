@@ -0,0 +1,39 @@
+//! # Build Script for Split Escrow Contract
+//!
+//! I'm generating the wasm's contract metadata here so it can't drift from
+//! Cargo.toml: `contractmeta!` only takes literals, so the version,
+//! repository and git revision are written out as a file that `lib.rs`
+//! includes.
+
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    let version = env::var("CARGO_PKG_VERSION").unwrap();
+    let repository = env::var("CARGO_PKG_REPOSITORY").unwrap();
+    let revision = git(&["rev-parse", "HEAD"]).unwrap_or_else(|| "unknown".into());
+
+    // Rebuild when the checked-out commit moves
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={git_dir}/HEAD");
+        println!("cargo:rerun-if-changed={git_dir}/refs");
+        println!("cargo:rerun-if-changed={git_dir}/packed-refs");
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let meta = format!(
+        "contractmeta!(key = \"version\", val = {version:?});\n\
+         contractmeta!(key = \"source_repo\", val = {repository:?});\n\
+         contractmeta!(key = \"source_rev\", val = {revision:?});\n"
+    );
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("contract_meta.rs");
+    fs::write(out, meta).unwrap();
+}
+
+/// Run git and return its trimmed output, if it succeeded
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8(output.stdout).ok()?.trim().into())
+}
@@ -0,0 +1,82 @@
+//! # Circuit Breaker for Split Escrow Contract
+//!
+//! I'm counting split creations and released volume per ledger or per day.
+//! When a count hits the admin's cap the breaker trips, and new splits and
+//! large releases are held until the admin resets it. This limits how much
+//! an exploit or a spam storm can get through before anyone reacts.
+//!
+//! The call that hits the cap still goes through. Failing it would roll
+//! back the trip along with everything else.
+
+use soroban_sdk::Env;
+
+use crate::events;
+use crate::storage::{self, DAY_SECONDS};
+use crate::types::{BreakerConfig, BreakerState, BreakerWindow};
+
+/// The window the current ledger falls in
+fn window_id(env: &Env, config: &BreakerConfig) -> u64 {
+    match config.window {
+        BreakerWindow::Ledger => env.ledger().sequence() as u64,
+        BreakerWindow::Day => env.ledger().timestamp() / DAY_SECONDS,
+    }
+}
+
+/// The stored counts, started over if the window has moved on
+fn current_state(env: &Env, config: &BreakerConfig) -> BreakerState {
+    let mut state = storage::get_breaker_state(env);
+    let id = window_id(env, config);
+    if state.window_id != id {
+        state.window_id = id;
+        state.creations = 0;
+        state.volume = 0;
+    }
+    state
+}
+
+/// Store the counts, tripping the breaker if a cap was reached
+fn save(env: &Env, config: &BreakerConfig, mut state: BreakerState) {
+    let over = (config.max_creations > 0 && state.creations >= config.max_creations)
+        || (config.max_volume > 0 && state.volume >= config.max_volume);
+    if over && !state.tripped {
+        state.tripped = true;
+        events::emit_breaker_tripped(env, state.creations, state.volume);
+    }
+    storage::set_breaker_state(env, &state);
+}
+
+/// Whether the breaker is holding new splits
+pub fn blocks_creation(env: &Env) -> bool {
+    storage::get_breaker_config(env).is_some() && storage::get_breaker_state(env).tripped
+}
+
+/// Whether the breaker is holding a release of `amount`
+pub fn blocks_release(env: &Env, amount: i128) -> bool {
+    match storage::get_breaker_config(env) {
+        Some(config) => storage::get_breaker_state(env).tripped && amount >= config.large_release,
+        None => false,
+    }
+}
+
+/// Count a new split
+pub fn record_creation(env: &Env) {
+    if let Some(config) = storage::get_breaker_config(env) {
+        let mut state = current_state(env, &config);
+        state.creations += 1;
+        save(env, &config, state);
+    }
+}
+
+/// Count `amount` of escrow token leaving in a release
+pub fn record_release(env: &Env, amount: i128) {
+    if let Some(config) = storage::get_breaker_config(env) {
+        let mut state = current_state(env, &config);
+        state.volume += amount;
+        save(env, &config, state);
+    }
+}
+
+/// Clear the trip and the counts
+pub fn reset(env: &Env) {
+    storage::set_breaker_state(env, &BreakerState::default());
+}
@@ -0,0 +1,331 @@
+//! # Disputes for Split Escrow Contract
+//!
+//! I'm keeping disputes, objections and bond slashing behind the
+//! `disputes` feature, so an escrow-only deployment can leave them out.
+//! Without it no dispute can ever be opened, and every check for an open
+//! dispute in the core contract simply finds none.
+
+use soroban_sdk::{contractimpl, token, Address, BytesN, Env, String, Vec};
+
+use crate::lifecycle::{self, Action};
+use crate::types::{BondSlash, Dispute, Error, Split, SplitStatus, TransferKind};
+use crate::{events, shares, storage, SplitEscrowContract, SplitEscrowContractClient};
+
+#[contractimpl]
+impl SplitEscrowContract {
+    /// Complain that a split's creator isn't holding up their end
+    ///
+    /// Any participant can open one dispute per split before it's released,
+    /// or afterwards while it still holds retainage. Until the arbiter
+    /// rules, the split can't be released and the creator's bond and
+    /// retainage stay locked.
+    pub fn open_dispute(env: Env, split_id: u64, participant: Address) -> Result<(), Error> {
+        participant.require_auth();
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        Self::open_dispute_internal(&env, &split, &participant)
+    }
+
+    /// Open a dispute as a participant or their delegate
+    pub fn open_dispute_as(
+        env: Env,
+        actor: Address,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<(), Error> {
+        Self::require_actor(&env, &actor, &participant)?;
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        Self::open_dispute_internal(&env, &split, &participant)
+    }
+
+    /// Object to a split's pending release, sending it to the arbiter
+    ///
+    /// Any participant can object while a delayed release request or a
+    /// proposed payout plan is waiting. The objection opens a dispute, so
+    /// nothing is paid out until the arbiter rules. Only the hash of the
+    /// reason is stored.
+    pub fn object_to_release(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        reason_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        participant.require_auth();
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if storage::get_release_request(&env, split_id).is_none()
+            && storage::get_release_proposal(&env, split_id).is_none()
+        {
+            return Err(Error::ActionNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        Self::open_dispute_internal(&env, &split, &participant)?;
+        storage::set_objection(&env, split_id, &reason_hash);
+        events::emit_release_objection(&env, split_id, &participant, &reason_hash);
+
+        Ok(())
+    }
+
+    /// Get the reason hash of the objection behind a split's open dispute
+    pub fn get_objection(env: Env, split_id: u64) -> Option<BytesN<32>> {
+        storage::get_objection(&env, split_id)
+    }
+
+    /// Rule on a split's open dispute
+    ///
+    /// Only the arbiter set through `AdminAction::SetArbiter` can rule. If
+    /// the creator is at fault, `slash_bps` of their bond goes to the
+    /// participants who paid in (see `slash_bond`), the rest goes back to
+    /// the creator, and an unreleased split is cancelled so everyone can
+    /// reclaim their deposits. Any retainage goes back to the participants
+    /// in proportion to what they paid. On an insured split, whatever a
+    /// participant paid that escrow can no longer refund becomes claimable
+    /// from the insurance pool. Otherwise the dispute is simply closed and
+    /// the split carries on.
+    pub fn resolve_dispute(
+        env: Env,
+        split_id: u64,
+        creator_at_fault: bool,
+        slash_bps: u32,
+    ) -> Result<(), Error> {
+        let arbiter = storage::get_arbiter(&env).ok_or(Error::InvalidConfig)?;
+        arbiter.require_auth();
+        Self::check_no_emergency(&env)?;
+
+        if slash_bps as i128 > shares::BPS_DENOMINATOR || (!creator_at_fault && slash_bps > 0) {
+            return Err(Error::InvalidSlash);
+        }
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if storage::get_dispute(&env, split_id).is_none() {
+            return Err(Error::NoDispute);
+        }
+
+        storage::remove_dispute(&env, split_id);
+        if creator_at_fault {
+            let mut split = storage::get_split(&env, split_id);
+            if matches!(
+                split.status,
+                SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed
+            ) {
+                lifecycle::transition(&env, &mut split, SplitStatus::Cancelled, Action::Cancel);
+                storage::set_split(&env, split_id, &split);
+                Self::record_cancel(&env, split_id, &String::from_str(&env, "dispute"));
+            }
+            Self::slash_bond(&env, &split, slash_bps);
+            Self::return_retainage(&env, &split);
+            if storage::is_insured(&env, split_id) {
+                Self::record_insured_losses(&env, &split);
+            }
+        }
+        events::emit_dispute_resolved(&env, split_id, creator_at_fault);
+
+        Ok(())
+    }
+
+    /// Get the record of a split's bond slash, if its creator was slashed
+    pub fn get_bond_slash(env: Env, split_id: u64) -> Option<BondSlash> {
+        storage::get_bond_slash(&env, split_id)
+    }
+
+    /// Get the open dispute on a split, if any
+    pub fn get_dispute(env: Env, split_id: u64) -> Option<Dispute> {
+        storage::get_dispute(&env, split_id)
+    }
+}
+
+impl SplitEscrowContract {
+    /// Open a dispute on behalf of one of the split's participants
+    fn open_dispute_internal(env: &Env, split: &Split, participant: &Address) -> Result<(), Error> {
+        if !split.participants.iter().any(|p| p.address == *participant) {
+            return Err(Error::ParticipantNotFound);
+        }
+        // Once paid out, only insurance or retainage is left to make
+        // participants whole
+        let retained = storage::get_retainage(env, split.id).is_some_and(|r| r.held > 0);
+        if split.status == SplitStatus::Released && !storage::is_insured(env, split.id) && !retained
+        {
+            return Err(Error::SplitReleased);
+        }
+        if storage::get_dispute(env, split.id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+
+        storage::set_dispute(
+            env,
+            split.id,
+            &Dispute {
+                opened_by: participant.clone(),
+                opened_at: env.ledger().timestamp(),
+            },
+        );
+        events::emit_dispute_opened(env, split.id, participant);
+
+        Ok(())
+    }
+
+    /// Slash `slash_bps` of a split's bond to the participants who paid in
+    ///
+    /// The slashed part is shared in proportion to what each participant
+    /// paid, with stroops left over by flooring going to the first-listed
+    /// payer; the rest of the bond goes back to the creator. Every slash is
+    /// kept as a `BondSlash` record for the audit trail.
+    fn slash_bond(env: &Env, split: &Split, slash_bps: u32) {
+        let bond = storage::take_bond(env, split.id);
+        if bond <= 0 {
+            return;
+        }
+
+        let paid_total: i128 = split.participants.iter().map(|p| p.amount_paid).sum();
+        let slashed = if paid_total > 0 {
+            bond * slash_bps as i128 / shares::BPS_DENOMINATOR
+        } else {
+            0
+        };
+        let payouts = Self::shares_of_paid(env, split, slashed);
+
+        let token_address = storage::get_token(env);
+        let token_client = token::Client::new(env, &token_address);
+        let contract_address = env.current_contract_address();
+        storage::add_total_escrowed(env, &token_address, -bond);
+        for (address, cut) in payouts.iter() {
+            if cut > 0 {
+                let refund_to = Self::refund_recipient(env, &address);
+                token_client.transfer(&contract_address, &refund_to, &cut);
+                Self::log_transfer(
+                    env,
+                    split.id,
+                    TransferKind::BondSlash,
+                    &refund_to,
+                    &token_address,
+                    cut,
+                );
+            }
+        }
+
+        let returned = bond - slashed;
+        if returned > 0 {
+            token_client.transfer(&contract_address, &split.creator, &returned);
+            Self::log_transfer(
+                env,
+                split.id,
+                TransferKind::BondReturn,
+                &split.creator,
+                &token_address,
+                returned,
+            );
+            events::emit_bond_returned(env, split.id, &split.creator, returned);
+        }
+
+        storage::set_bond_slash(
+            env,
+            split.id,
+            &BondSlash {
+                slash_bps,
+                slashed,
+                returned,
+                payouts,
+                slashed_at: env.ledger().timestamp(),
+            },
+        );
+        events::emit_bond_slashed(env, split.id, slashed, slash_bps);
+    }
+
+    /// Divide `amount` between the participants who paid in, in proportion
+    /// to what each paid
+    ///
+    /// Stroops left over by flooring go to the first-listed payer.
+    fn shares_of_paid(env: &Env, split: &Split, amount: i128) -> Vec<(Address, i128)> {
+        let paid_total: i128 = split.participants.iter().map(|p| p.amount_paid).sum();
+        let mut payouts: Vec<(Address, i128)> = Vec::new(env);
+        let mut left_over = amount;
+        let mut first_payer = None;
+        for p in split.participants.iter() {
+            if p.amount_paid <= 0 {
+                continue;
+            }
+            let cut = amount * p.amount_paid / paid_total;
+            left_over -= cut;
+            if first_payer.is_none() {
+                first_payer = Some(payouts.len());
+            }
+            payouts.push_back((p.address, cut));
+        }
+        if let Some(i) = first_payer {
+            let (address, cut) = payouts.get(i).unwrap();
+            payouts.set(i, (address, cut + left_over));
+        }
+        payouts
+    }
+
+    /// Send a split's retainage back to the participants who paid in
+    fn return_retainage(env: &Env, split: &Split) {
+        let Some(mut retainage) = storage::get_retainage(env, split.id) else {
+            return;
+        };
+        let held = retainage.held;
+        if held <= 0 {
+            return;
+        }
+        retainage.held = 0;
+        storage::set_retainage(env, split.id, &retainage);
+
+        let token_address = storage::get_token(env);
+        storage::add_total_escrowed(env, &token_address, -held);
+        let token_client = token::Client::new(env, &token_address);
+        let contract_address = env.current_contract_address();
+        for (address, cut) in Self::shares_of_paid(env, split, held).iter() {
+            if cut > 0 {
+                let refund_to = Self::refund_recipient(env, &address);
+                token_client.transfer(&contract_address, &refund_to, &cut);
+                Self::log_transfer(
+                    env,
+                    split.id,
+                    TransferKind::Refund,
+                    &refund_to,
+                    &token_address,
+                    cut,
+                );
+            }
+        }
+        events::emit_retainage_returned(env, split.id, held);
+    }
+
+    /// Record what each participant of an insured split stands to lose
+    ///
+    /// Held balances are still refundable from escrow, and payments
+    /// settled off-chain never went through it, so only what a participant
+    /// paid into escrow beyond what they can get back is claimable from the
+    /// pool. A split's losses are recorded once; a later dispute on the
+    /// same split can't top up claims already paid.
+    fn record_insured_losses(env: &Env, split: &Split) {
+        if storage::has_recorded_losses(env, split.id) {
+            return;
+        }
+        storage::set_losses_recorded(env, split.id);
+        for p in split.participants.iter() {
+            let refundable = storage::get_held_balance(env, split.id, &p.address)
+                + storage::get_foreign_held(env, split.id, &p.address)
+                    .iter()
+                    .map(|d| d.credited)
+                    .sum::<i128>();
+            let offchain = storage::get_offchain_settled(env, split.id, &p.address);
+            let loss = p.amount_paid - offchain - refundable;
+            if loss > 0 {
+                storage::set_insurance_claim(env, split.id, &p.address, loss);
+            }
+        }
+    }
+}
@@ -2,17 +2,42 @@
 //!
 //! I'm defining all contract events here for off-chain tracking and indexing.
 //! These events are crucial for the backend to sync with on-chain state.
+//!
+//! Every payload is wrapped as `(seq, split_seq, data)`. `seq` counts every
+//! event the contract has emitted and `split_seq` counts the events of the
+//! split involved (0 for events that aren't about one split), so indexers
+//! can order events and spot gaps or duplicates after a retry.
+
+use soroban_sdk::{symbol_short, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
+
+use crate::storage;
+use crate::types::{
+    FeeExemption, FeeSchedule, FeeTarget, PauseFlags, PayoutShare, Role, ScheduledAction,
+    Settlement,
+};
 
-use soroban_sdk::{symbol_short, Address, Env};
-use soroban_sdk::{contractevent, Address};
+/// Publish a deployment-wide event under the next global sequence number
+fn publish<D: IntoVal<Env, Val>>(env: &Env, topic: Symbol, data: D) {
+    let seq = storage::next_event_seq(env, None);
+    let data: Val = data.into_val(env);
+    env.events().publish((topic,), (seq, 0u64, data));
+}
+
+/// Publish an event about a split under the next global and per-split
+/// sequence numbers
+fn publish_split<D: IntoVal<Env, Val>>(env: &Env, topic: Symbol, split_id: u64, data: D) {
+    let seq = storage::next_event_seq(env, None);
+    let split_seq = storage::next_event_seq(env, Some(split_id));
+    let data: Val = data.into_val(env);
+    env.events().publish((topic,), (seq, split_seq, data));
+}
 
 /// Emit when the contract is initialized
 ///
 /// I'm emitting this once during contract setup so indexers
 /// know when the contract became operational.
 pub fn emit_initialized(env: &Env, admin: &Address) {
-    env.events()
-        .publish((symbol_short!("init"),), (admin.clone(),));
+    publish(env, symbol_short!("init"), (admin.clone(),));
 }
 
 /// Emit when a new split is created
@@ -20,23 +45,428 @@ pub fn emit_initialized(env: &Env, admin: &Address) {
 /// I'm including the key details so the backend can create
 /// a corresponding record without querying the contract.
 pub fn emit_split_created(env: &Env, split_id: u64, creator: &Address, total_amount: i128) {
-    env.events().publish(
-        (symbol_short!("created"),),
+    publish_split(
+        env,
+        symbol_short!("created"),
+        split_id,
         (split_id, creator.clone(), total_amount),
     );
 }
 
+/// Emit the hash of the terms a split was created with
+pub fn emit_terms_hash(env: &Env, split_id: u64, hash: &BytesN<32>) {
+    publish_split(
+        env,
+        symbol_short!("terms"),
+        split_id,
+        (split_id, hash.clone()),
+    );
+}
+
+/// Emit when an operator stages a split on behalf of a creator
+pub fn emit_split_staged(env: &Env, split_id: u64, operator: &Address, creator: &Address) {
+    publish_split(
+        env,
+        symbol_short!("staged"),
+        split_id,
+        (split_id, operator.clone(), creator.clone()),
+    );
+}
+
+/// Emit when a creator accepts a split an operator staged for them
+pub fn emit_split_accepted(env: &Env, split_id: u64, creator: &Address) {
+    publish_split(
+        env,
+        symbol_short!("accepted"),
+        split_id,
+        (split_id, creator.clone()),
+    );
+}
+
+/// Emit when someone joins an open split
+pub fn emit_split_joined(env: &Env, split_id: u64, participant: &Address, headcount: u32) {
+    publish_split(
+        env,
+        symbol_short!("joined"),
+        split_id,
+        (split_id, participant.clone(), headcount),
+    );
+}
+
+/// Emit when an open split stops taking members
+pub fn emit_split_locked(env: &Env, split_id: u64, headcount: u32) {
+    publish_split(
+        env,
+        symbol_short!("locked"),
+        split_id,
+        (split_id, headcount),
+    );
+}
+
+/// Emit when someone contributes to a crowdfunded split
+pub fn emit_contribution(env: &Env, split_id: u64, contributor: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("chip_in"),
+        split_id,
+        (split_id, contributor.clone(), amount),
+    );
+}
+
+/// Emit when a child split is linked under a parent
+pub fn emit_split_linked(env: &Env, parent_id: u64, child_id: u64) {
+    publish_split(
+        env,
+        symbol_short!("linked"),
+        parent_id,
+        (parent_id, child_id),
+    );
+}
+
+/// Emit when a child split is detached from its parent
+pub fn emit_split_unlinked(env: &Env, parent_id: u64, child_id: u64) {
+    publish_split(
+        env,
+        symbol_short!("unlinked"),
+        parent_id,
+        (parent_id, child_id),
+    );
+}
+
+/// Emit when a creator forgives what's left unpaid on a split
+pub fn emit_remaining_forgiven(env: &Env, split_id: u64, forgiven: i128, new_total: i128) {
+    publish_split(
+        env,
+        symbol_short!("forgiven"),
+        split_id,
+        (split_id, forgiven, new_total),
+    );
+}
+
+/// Emit when a creator absorbs one participant's unpaid remainder
+pub fn emit_written_off(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("write_off"),
+        split_id,
+        (split_id, participant.clone(), amount),
+    );
+}
+
+/// Emit when an unpaid participant's invitation lapses
+pub fn emit_invitation_lapsed(env: &Env, split_id: u64, participant: &Address, share: i128) {
+    publish_split(
+        env,
+        symbol_short!("lapsed"),
+        split_id,
+        (split_id, participant.clone(), share),
+    );
+}
+
+/// Emit when the creator hands a lapsed slot to someone else
+pub fn emit_slot_reassigned(env: &Env, split_id: u64, from: &Address, to: &Address) {
+    publish_split(
+        env,
+        symbol_short!("reassign"),
+        split_id,
+        (split_id, from.clone(), to.clone()),
+    );
+}
+
+/// Emit when the creator spreads a lapsed slot's share over the others
+pub fn emit_slot_rebalanced(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("rebalance"),
+        split_id,
+        (split_id, participant.clone(), amount),
+    );
+}
+
+/// Emit the description hash of a private split
+///
+/// I'm echoing the hash so anyone holding the plaintext can verify
+/// it against the event log without querying the contract.
+pub fn emit_description_hash(env: &Env, split_id: u64, hash: &BytesN<32>) {
+    publish_split(
+        env,
+        symbol_short!("desc_hash"),
+        split_id,
+        (split_id, hash.clone()),
+    );
+}
+
+/// Emit when the creator pins a receipt reference to a split
+pub fn emit_receipt_set(env: &Env, split_id: u64, receipt_ref: &Bytes) {
+    publish_split(
+        env,
+        symbol_short!("receipt"),
+        split_id,
+        (split_id, receipt_ref.clone()),
+    );
+}
+
+/// Emit when the creator sets the metadata URI (an empty URI clears it)
+pub fn emit_metadata_uri_set(env: &Env, split_id: u64, uri: &String) {
+    publish_split(
+        env,
+        symbol_short!("meta_uri"),
+        split_id,
+        (split_id, uri.clone()),
+    );
+}
+
+/// Emit when the creator labels a participant (an empty label clears it)
+pub fn emit_label_set(env: &Env, split_id: u64, participant: &Address, label: &String) {
+    publish_split(
+        env,
+        symbol_short!("label"),
+        split_id,
+        (split_id, participant.clone(), label.clone()),
+    );
+}
+
+/// Emit when a split crosses a funding milestone (25, 50, 75 or 100%)
+pub fn emit_funding_milestone(env: &Env, split_id: u64, pct: u32, collected: i128) {
+    publish_split(
+        env,
+        symbol_short!("milestone"),
+        split_id,
+        (split_id, pct, collected),
+    );
+}
+
+/// Emit when a late release hands part of the payout back to participants
+pub fn emit_late_penalty(env: &Env, split_id: u64, bps: u32, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("late_pen"),
+        split_id,
+        (split_id, bps, amount),
+    );
+}
+
+/// Emit when a deposit is rounded up for the split's charity
+pub fn emit_rounded_up(env: &Env, split_id: u64, participant: &Address, extra: i128) {
+    publish_split(
+        env,
+        symbol_short!("round_up"),
+        split_id,
+        (split_id, participant.clone(), extra),
+    );
+}
+
+/// Emit when a released split's round-ups go to its charity
+pub fn emit_donated(env: &Env, split_id: u64, charity: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("donated"),
+        split_id,
+        (split_id, charity.clone(), amount),
+    );
+}
+
+/// Emit when the creator appends a note to a split
+pub fn emit_note_added(env: &Env, split_id: u64, text: &String) {
+    publish_split(
+        env,
+        symbol_short!("note"),
+        split_id,
+        (split_id, text.clone()),
+    );
+}
+
 /// Emit when a deposit is received
 ///
 /// I'm emitting this for each deposit so the backend can
 /// track partial payments and update participant status.
 pub fn emit_deposit_received(env: &Env, split_id: u64, participant: &Address, amount: i128) {
-    env.events().publish(
-        (symbol_short!("deposit"),),
+    publish_split(
+        env,
+        symbol_short!("deposit"),
+        split_id,
+        (split_id, participant.clone(), amount),
+    );
+}
+
+/// Emit when an allowlisted contract deposits for a participant
+///
+/// The matching `deposit` event follows, credited to the participant.
+pub fn emit_deposit_via(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    invoker: &Address,
+    amount: i128,
+) {
+    publish_split(
+        env,
+        symbol_short!("dep_via"),
+        split_id,
+        (split_id, participant.clone(), invoker.clone(), amount),
+    );
+}
+
+/// Emit when a bridge credits a participant for a classic payment
+pub fn emit_bridge_deposit(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    payment_ref: &BytesN<32>,
+    amount: i128,
+) {
+    publish_split(
+        env,
+        symbol_short!("bridged"),
+        split_id,
+        (split_id, participant.clone(), payment_ref.clone(), amount),
+    );
+}
+
+/// Emit when a participant registers (`Some`) or revokes (`None`) a delegate
+pub fn emit_delegate_set(env: &Env, participant: &Address, delegate: Option<Address>) {
+    publish(
+        env,
+        symbol_short!("delegate"),
+        (participant.clone(), delegate),
+    );
+}
+
+/// Emit when a participant sets (`Some`) or clears (`None`) a refund address
+pub fn emit_refund_address_set(env: &Env, participant: &Address, refund_to: Option<Address>) {
+    publish(
+        env,
+        symbol_short!("refund_to"),
+        (participant.clone(), refund_to),
+    );
+}
+
+/// Emit when an address sets (`Some`) or lifts (`None`) its spending cap
+pub fn emit_spending_cap_set(env: &Env, address: &Address, cap: Option<i128>) {
+    publish(env, symbol_short!("spend_cap"), (address.clone(), cap));
+}
+
+/// Emit when an abandoned completed split is cancelled so everyone can be
+/// refunded
+pub fn emit_split_abandoned(env: &Env, split_id: u64, refundable: i128) {
+    publish_split(
+        env,
+        symbol_short!("abandoned"),
+        split_id,
+        (split_id, refundable),
+    );
+}
+
+/// Emit when a paid participant approves cancelling a split
+pub fn emit_cancel_approved(env: &Env, split_id: u64, participant: &Address) {
+    publish_split(
+        env,
+        symbol_short!("cncl_appr"),
+        split_id,
+        (split_id, participant.clone()),
+    );
+}
+
+/// Emit when a release holds part of its payout back as retainage
+pub fn emit_retainage_held(env: &Env, split_id: u64, amount: i128, claimable_at: u64) {
+    publish_split(
+        env,
+        symbol_short!("retained"),
+        split_id,
+        (split_id, amount, claimable_at),
+    );
+}
+
+/// Emit when the creator claims a split's retainage
+pub fn emit_retainage_claimed(env: &Env, split_id: u64, creator: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("ret_claim"),
+        split_id,
+        (split_id, creator.clone(), amount),
+    );
+}
+
+/// Emit when a split's retainage goes back to its participants
+pub fn emit_retainage_returned(env: &Env, split_id: u64, amount: i128) {
+    publish_split(env, symbol_short!("ret_back"), split_id, (split_id, amount));
+}
+
+/// Emit when the creator changes a participant's units
+pub fn emit_units_set(env: &Env, split_id: u64, participant: &Address, units: u32) {
+    publish_split(
+        env,
+        symbol_short!("units"),
+        split_id,
+        (split_id, participant.clone(), units),
+    );
+}
+
+/// Emit when a unit-priced split's units and price are fixed
+pub fn emit_units_locked(env: &Env, split_id: u64, unit_price: i128) {
+    publish_split(
+        env,
+        symbol_short!("unit_lock"),
+        split_id,
+        (split_id, unit_price),
+    );
+}
+
+/// Emit when a creator changes who may deposit through `deposit_via`
+pub fn emit_depositor_set(env: &Env, split_id: u64, invoker: &Address, allowed: bool) {
+    publish_split(
+        env,
+        symbol_short!("depositor"),
+        split_id,
+        (split_id, invoker.clone(), allowed),
+    );
+}
+
+/// Emit when an IOU payment is confirmed by both parties
+///
+/// No tokens move for IOU splits, so this event is the only
+/// on-chain record that the payment happened.
+pub fn emit_iou_paid(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("iou_paid"),
+        split_id,
         (split_id, participant.clone(), amount),
     );
 }
 
+/// Emit when the creator marks a share as settled outside the contract
+///
+/// No tokens move, so this is the on-chain record of the settlement;
+/// `confirmed` says whether the participant co-signed it.
+pub fn emit_settled_offchain(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    amount: i128,
+    confirmed: bool,
+) {
+    publish_split(
+        env,
+        symbol_short!("offchain"),
+        split_id,
+        (split_id, participant.clone(), amount, confirmed),
+    );
+}
+
+/// Emit a payment reminder for the participants who still owe money
+///
+/// I'm listing the unpaid addresses so notification bots don't have to
+/// read the split to know whom to nudge.
+pub fn emit_payment_reminder(env: &Env, split_id: u64, unpaid: &Vec<Address>) {
+    publish_split(
+        env,
+        symbol_short!("remind"),
+        split_id,
+        (split_id, unpaid.clone()),
+    );
+}
+
 /// Emit when funds are released to the creator
 ///
 /// I'm including the total amount released for reconciliation
@@ -48,40 +478,622 @@ pub fn emit_funds_released(
     amount: i128,
     timestamp: u64,
 ) {
-    env.events().publish(
-        (symbol_short!("released"),),
+    publish_split(
+        env,
+        symbol_short!("released"),
+        split_id,
         (split_id, recipient.clone(), amount, timestamp),
     );
 }
 
+/// Emit after a batch release with how many splits paid out and how much
+pub fn emit_batch_released(env: &Env, released: u32, skipped: u32, amount: i128) {
+    publish(env, symbol_short!("batch_rel"), (released, skipped, amount));
+}
+
+/// Emit when a release is requested on a split with a release delay
+///
+/// Participants who object have until `ready_at` to open a dispute.
+pub fn emit_release_requested(env: &Env, split_id: u64, ready_at: u64) {
+    publish_split(
+        env,
+        symbol_short!("rel_req"),
+        split_id,
+        (split_id, ready_at),
+    );
+}
+
+/// Emit when a creator schedules (or reschedules) a split's release
+pub fn emit_release_scheduled(env: &Env, split_id: u64, release_at: u64) {
+    publish_split(
+        env,
+        symbol_short!("rel_sched"),
+        split_id,
+        (split_id, release_at),
+    );
+}
+
+/// Emit when a creator proposes how a split's payout is divided
+pub fn emit_release_proposed(env: &Env, split_id: u64, plan: &Vec<PayoutShare>) {
+    publish_split(
+        env,
+        symbol_short!("rel_prop"),
+        split_id,
+        (split_id, plan.clone()),
+    );
+}
+
+/// Emit when the confirmer accepts a proposed payout plan
+pub fn emit_release_confirmed(env: &Env, split_id: u64, confirmer: &Address) {
+    publish_split(
+        env,
+        symbol_short!("rel_conf"),
+        split_id,
+        (split_id, confirmer.clone()),
+    );
+}
+
+/// Emit for each recipient paid under a confirmed payout plan
+pub fn emit_plan_paid(env: &Env, split_id: u64, recipient: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("plan_pay"),
+        split_id,
+        (split_id, recipient.clone(), amount),
+    );
+}
+
+/// Emit when an admin action is queued behind the timelock
+///
+/// I'm including the action itself so watchers can review exactly what
+/// will happen before it becomes executable.
+pub fn emit_action_scheduled(env: &Env, scheduled: &ScheduledAction) {
+    publish(
+        env,
+        symbol_short!("act_sched"),
+        (scheduled.id, scheduled.action.clone(), scheduled.ready_at),
+    );
+}
+
+/// Emit when a queued admin action is executed
+pub fn emit_action_executed(env: &Env, action_id: u64) {
+    publish(env, symbol_short!("act_exec"), (action_id,));
+}
+
+/// Emit when a queued admin action is cancelled
+pub fn emit_action_cancelled(env: &Env, action_id: u64) {
+    publish(env, symbol_short!("act_cancl"), (action_id,));
+}
+
+/// Emit when the admin registers a fee module
+pub fn emit_fee_module_set(env: &Env, module: &Address, recipient: &Address) {
+    publish(
+        env,
+        symbol_short!("fee_mod"),
+        (module.clone(), recipient.clone()),
+    );
+}
+
+/// Emit when a release fee is withheld and sent to the fee recipient
+pub fn emit_fee_collected(env: &Env, split_id: u64, recipient: &Address, fee: i128) {
+    publish_split(
+        env,
+        symbol_short!("fee"),
+        split_id,
+        (split_id, recipient.clone(), fee),
+    );
+}
+
 /// Emit when escrow is completed (fully funded)
 pub fn emit_escrow_completed(env: &Env, split_id: u64, total_amount: i128) {
-    env.events()
-        .publish((symbol_short!("completed"),), (split_id, total_amount));
+    publish_split(
+        env,
+        symbol_short!("completed"),
+        split_id,
+        (split_id, total_amount),
+    );
+}
+
+/// Emit when a completion hook call fails
+///
+/// The split still completes; this only tells the integrator their hook
+/// needs attention.
+pub fn emit_hook_failed(env: &Env, split_id: u64, hook: &Address) {
+    publish_split(
+        env,
+        symbol_short!("hook_fail"),
+        split_id,
+        (split_id, hook.clone()),
+    );
+}
+
+/// Emit when a split that just completed couldn't be released right away
+///
+/// The split stays at Completed and can be released later; `error` is the
+/// `Error` code the release was refused with.
+pub fn emit_release_failed(env: &Env, split_id: u64, error: u32) {
+    publish_split(env, symbol_short!("rel_fail"), split_id, (split_id, error));
 }
 
 /// Emit when a split is cancelled
 ///
 /// I'm emitting this so the backend can trigger refund processing
-/// for any participants who have already deposited.
-pub fn emit_split_cancelled(env: &Env, split_id: u64) {
-    env.events()
-        .publish((symbol_short!("cancel"),), (split_id,));
+/// for any participants who have already deposited. The reason rides
+/// along so their refund notice can say why the split died.
+pub fn emit_split_cancelled(env: &Env, split_id: u64, reason: &String) {
+    publish_split(
+        env,
+        symbol_short!("cancel"),
+        split_id,
+        (split_id, reason.clone()),
+    );
+}
+
+/// Emit when a split expires before being fully funded
+pub fn emit_split_expired(env: &Env, split_id: u64) {
+    publish_split(env, symbol_short!("expired"), split_id, (split_id,));
+}
+
+/// Emit when a creator pushes a split's deadline back
+///
+/// I'm publishing both deadlines (None where the split has none) plus the
+/// running extension count so clients can show how often it was extended.
+pub fn emit_deadline_extended(
+    env: &Env,
+    split_id: u64,
+    deadline: Option<u64>,
+    deadline_ledger: Option<u32>,
+    extensions: u32,
+) {
+    publish_split(
+        env,
+        symbol_short!("extended"),
+        split_id,
+        (split_id, deadline, deadline_ledger, extensions),
+    );
+}
+
+/// Emit when a creator lowers a split's total after creation
+pub fn emit_total_reduced(env: &Env, split_id: u64, old_total: i128, new_total: i128) {
+    publish_split(
+        env,
+        symbol_short!("total_cut"),
+        split_id,
+        (split_id, old_total, new_total),
+    );
+}
+
+/// Emit when a creator proposes a higher total for a split
+///
+/// I'm listing who still has to accept so their wallets can prompt them.
+pub fn emit_increase_proposed(env: &Env, split_id: u64, new_total: i128, pending: &Vec<Address>) {
+    publish_split(
+        env,
+        symbol_short!("inc_prop"),
+        split_id,
+        (split_id, new_total, pending.clone()),
+    );
+}
+
+/// Emit when a participant accepts a proposed total increase
+pub fn emit_increase_accepted(env: &Env, split_id: u64, participant: &Address) {
+    publish_split(
+        env,
+        symbol_short!("inc_ok"),
+        split_id,
+        (split_id, participant.clone()),
+    );
+}
+
+/// Emit when an accepted increase raises a split's total
+pub fn emit_total_increased(env: &Env, split_id: u64, old_total: i128, new_total: i128) {
+    publish_split(
+        env,
+        symbol_short!("total_up"),
+        split_id,
+        (split_id, old_total, new_total),
+    );
+}
+
+/// Emit when a creator locks a bond on a new split
+pub fn emit_bond_locked(env: &Env, split_id: u64, creator: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("bond_lock"),
+        split_id,
+        (split_id, creator.clone(), amount),
+    );
+}
+
+/// Emit when a creator's bond goes back to them
+pub fn emit_bond_returned(env: &Env, split_id: u64, creator: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("bond_back"),
+        split_id,
+        (split_id, creator.clone(), amount),
+    );
+}
+
+/// Emit when a dispute ruling slashes a creator's bond
+///
+/// The per-participant breakdown is kept in the split's `BondSlash` record.
+pub fn emit_bond_slashed(env: &Env, split_id: u64, slashed: i128, slash_bps: u32) {
+    publish_split(
+        env,
+        symbol_short!("bond_slsh"),
+        split_id,
+        (split_id, slashed, slash_bps),
+    );
+}
+
+/// Emit when a participant opens a dispute against a split's creator
+pub fn emit_dispute_opened(env: &Env, split_id: u64, participant: &Address) {
+    publish_split(
+        env,
+        symbol_short!("dispute"),
+        split_id,
+        (split_id, participant.clone()),
+    );
+}
+
+/// Emit when a participant objects to a pending release
+///
+/// Only the hash of the reason goes on-chain; the dispute it opens is
+/// announced separately with `dispute`.
+pub fn emit_release_objection(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    reason_hash: &BytesN<32>,
+) {
+    publish_split(
+        env,
+        symbol_short!("objection"),
+        split_id,
+        (split_id, participant.clone(), reason_hash.clone()),
+    );
+}
+
+/// Emit when the arbiter rules on a dispute
+pub fn emit_dispute_resolved(env: &Env, split_id: u64, creator_at_fault: bool) {
+    publish_split(
+        env,
+        symbol_short!("ruling"),
+        split_id,
+        (split_id, creator_at_fault),
+    );
+}
+
+/// Emit when an insured split's creator pays the premium into the pool
+pub fn emit_premium_paid(env: &Env, split_id: u64, premium: i128) {
+    publish_split(
+        env,
+        symbol_short!("ins_prem"),
+        split_id,
+        (split_id, premium),
+    );
+}
+
+/// Emit when a participant draws compensation from the insurance pool
+pub fn emit_insurance_claimed(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("ins_claim"),
+        split_id,
+        (split_id, participant.clone(), amount),
+    );
+}
+
+/// Emit when the circuit breaker trips
+pub fn emit_breaker_tripped(env: &Env, creations: u32, volume: i128) {
+    publish(env, symbol_short!("breaker"), (creations, volume));
+}
+
+/// Emit when the admin resets a tripped circuit breaker
+pub fn emit_breaker_reset(env: &Env) {
+    publish(env, symbol_short!("brk_reset"), ());
+}
+
+/// Emit when the admin sets the tiered fee schedule
+pub fn emit_fee_schedule_set(env: &Env, schedule: &FeeSchedule) {
+    publish(
+        env,
+        symbol_short!("fee_tiers"),
+        (schedule.recipient.clone(), schedule.tiers.clone()),
+    );
+}
+
+/// Emit when the admin exempts a creator or token from fees, or lifts it
+pub fn emit_fee_exemption(env: &Env, exemption: &FeeExemption, exempt: bool) {
+    publish(env, symbol_short!("fee_exmpt"), (exemption.clone(), exempt));
+}
+
+/// Emit when a split or creator gets (`Some`) or loses (`None`) a fee override
+pub fn emit_fee_override(env: &Env, target: &FeeTarget, bps: Option<u32>) {
+    publish(env, symbol_short!("fee_ovr"), (target.clone(), bps));
+}
+
+/// Emit when a creator puts up the expiry bounty for a split
+pub fn emit_bounty_staked(env: &Env, split_id: u64, creator: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("bnty_lock"),
+        split_id,
+        (split_id, creator.clone(), amount),
+    );
+}
+
+/// Emit when an unclaimed expiry bounty goes back to the creator
+pub fn emit_bounty_returned(env: &Env, split_id: u64, creator: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("bnty_back"),
+        split_id,
+        (split_id, creator.clone(), amount),
+    );
+}
+
+/// Emit when a keeper is paid a bounty for expiring a split
+pub fn emit_bounty_paid(env: &Env, split_id: u64, keeper: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("bounty"),
+        split_id,
+        (split_id, keeper.clone(), amount),
+    );
+}
+
+/// Emit when the admin freezes or unfreezes a split
+pub fn emit_split_frozen(env: &Env, split_id: u64, frozen: bool) {
+    publish_split(env, symbol_short!("frozen"), split_id, (split_id, frozen));
+}
+
+/// Emit when a scheduled pull takes a participant's deposit
+pub fn emit_pull_succeeded(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("pull_ok"),
+        split_id,
+        (split_id, participant.clone(), amount),
+    );
+}
+
+/// Emit when a scheduled pull couldn't take a participant's deposit
+pub fn emit_pull_failed(env: &Env, split_id: u64, participant: &Address, error: u32) {
+    publish_split(
+        env,
+        symbol_short!("pull_fail"),
+        split_id,
+        (split_id, participant.clone(), error),
+    );
+}
+
+/// Emit when a share token transfer hands a position to someone else
+pub fn emit_share_transferred(env: &Env, split_id: u64, from: &Address, to: &Address) {
+    publish_split(
+        env,
+        symbol_short!("share_xfr"),
+        split_id,
+        (split_id, from.clone(), to.clone()),
+    );
+}
+
+/// Emit when a creator sets aside a rent budget for a split
+pub fn emit_rent_locked(env: &Env, split_id: u64, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("rent_lock"),
+        split_id,
+        (split_id, amount),
+    );
+}
+
+/// Emit when a keeper bumps a split's entries and is paid from its budget
+pub fn emit_rent_bumped(env: &Env, split_id: u64, keeper: &Address, fee: i128) {
+    publish_split(
+        env,
+        symbol_short!("rent_bump"),
+        split_id,
+        (split_id, keeper.clone(), fee),
+    );
+}
+
+/// Emit when the unused part of a rent budget goes back to the creator
+pub fn emit_rent_returned(env: &Env, split_id: u64, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("rent_back"),
+        split_id,
+        (split_id, amount),
+    );
+}
+
+/// Emit when the admin imports a split from an older deployment
+pub fn emit_split_imported(env: &Env, split_id: u64, creator: &Address, held: i128) {
+    publish_split(
+        env,
+        symbol_short!("imported"),
+        split_id,
+        (split_id, creator.clone(), held),
+    );
+}
+
+/// Emit when the admin changes the storage TTL parameters
+pub fn emit_ttl_set(env: &Env, threshold: u32, extend_to: u32) {
+    publish(env, symbol_short!("ttl_set"), (threshold, extend_to));
+}
+
+/// Emit when the admin declares or lifts an emergency
+pub fn emit_emergency(env: &Env, active: bool) {
+    publish(env, symbol_short!("emergency"), (active,));
+}
+
+/// Emit when the admin changes the granular pause switches
+pub fn emit_pause_flags(env: &Env, flags: &PauseFlags) {
+    publish(env, symbol_short!("pause_set"), (flags.clone(),));
+}
+
+/// Emit when the admin pauses or unpauses the contract
+pub fn emit_paused(env: &Env, paused: bool) {
+    publish(env, symbol_short!("paused"), (paused,));
+}
+
+/// Emit when the admin grants or revokes a role
+pub fn emit_role_set(env: &Env, role: Role, account: &Address, granted: bool) {
+    publish(
+        env,
+        symbol_short!("role_set"),
+        (role, account.clone(), granted),
+    );
+}
+
+/// Emit when a split entry is rewritten in the current storage layout
+pub fn emit_split_migrated(env: &Env, split_id: u64) {
+    publish_split(env, symbol_short!("migrated"), split_id, (split_id,));
+}
+
+/// Emit when a deposit arrives in a token other than the escrow token
+pub fn emit_foreign_deposit(
+    env: &Env,
+    split_id: u64,
+    participant: &Address,
+    token: &Address,
+    amount: i128,
+    credited: i128,
+) {
+    publish_split(
+        env,
+        symbol_short!("fx_dep"),
+        split_id,
+        (
+            split_id,
+            participant.clone(),
+            token.clone(),
+            amount,
+            credited,
+        ),
+    );
+}
+
+/// Emit when escrowed funds in another token leave the contract
+///
+/// I'm using one event for both releases and refunds; `to` tells them apart.
+pub fn emit_foreign_payout(
+    env: &Env,
+    split_id: u64,
+    to: &Address,
+    token: &Address,
+    amount: i128,
+    fee: i128,
+) {
+    publish_split(
+        env,
+        symbol_short!("fx_pay"),
+        split_id,
+        (split_id, to.clone(), token.clone(), amount, fee),
+    );
+}
+
+/// Emit when a release is swapped into another asset for the creator
+pub fn emit_payout_swapped(
+    env: &Env,
+    split_id: u64,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: i128,
+    amount_out: i128,
+) {
+    publish_split(
+        env,
+        symbol_short!("swapped"),
+        split_id,
+        (
+            split_id,
+            token_in.clone(),
+            token_out.clone(),
+            amount_in,
+            amount_out,
+        ),
+    );
+}
+
+/// Emit when released funds are parked for a recipient to claim
+pub fn emit_payout_claimable(
+    env: &Env,
+    split_id: u64,
+    recipient: &Address,
+    amount: i128,
+    expires_at: u64,
+) {
+    publish_split(
+        env,
+        symbol_short!("claimable"),
+        split_id,
+        (split_id, recipient.clone(), amount, expires_at),
+    );
+}
+
+/// Emit when a payout bucket is emptied, by its recipient or by the
+/// creator after expiry
+pub fn emit_payout_claimed(env: &Env, split_id: u64, to: &Address, amount: i128) {
+    publish_split(
+        env,
+        symbol_short!("claimed"),
+        split_id,
+        (split_id, to.clone(), amount),
+    );
 }
 
 /// Emit when a refund is processed
 ///
 /// I'm tracking each refund individually for audit purposes.
-#[allow(dead_code)]
 pub fn emit_refund_processed(env: &Env, split_id: u64, participant: &Address, amount: i128) {
-    env.events().publish(
-        (symbol_short!("refund"),),
+    publish_split(
+        env,
+        symbol_short!("refund"),
+        split_id,
         (split_id, participant.clone(), amount),
     );
 }
 
-#[contractevent]
-pub fn escrow_created(split_id: String, creator: Address, total_amount: i128);
+/// Emit after each chunk of pushed refunds on a dead split
+///
+/// `processed` is how many participants have been visited so far, so
+/// indexers can show how far a large refund has got.
+pub fn emit_refunds_processed(env: &Env, split_id: u64, processed: u32, refunded: i128) {
+    publish_split(
+        env,
+        symbol_short!("refunds"),
+        split_id,
+        (split_id, processed, refunded),
+    );
+}
+
+/// Emit when a group is created
+pub fn emit_group_created(env: &Env, group_id: u64, creator: &Address) {
+    publish(env, symbol_short!("grp_new"), (group_id, creator.clone()));
+}
+
+/// Emit when a group's debts are simplified
+///
+/// I'm publishing the whole plan so clients can render the
+/// "settle up" payments straight from the event stream.
+pub fn emit_debts_simplified(env: &Env, group_id: u64, plan: &Vec<Settlement>) {
+    publish(env, symbol_short!("simplify"), (group_id, plan.clone()));
+}
+
+/// Emit when an event is created
+pub fn emit_event_created(env: &Env, event_id: u64, creator: &Address) {
+    publish(env, symbol_short!("evt_new"), (event_id, creator.clone()));
+}
 
-#[contractevent]
-pub fn payment_received(split_id: String, participant: Address, amount: i128);
+/// Emit when a split is attached to an event
+pub fn emit_event_split_added(env: &Env, event_id: u64, split_id: u64) {
+    publish_split(
+        env,
+        symbol_short!("evt_add"),
+        split_id,
+        (event_id, split_id),
+    );
+}
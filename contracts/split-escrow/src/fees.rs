@@ -0,0 +1,134 @@
+//! # Fees Module for Split Escrow Contract
+//!
+//! I'm keeping fee policy out of the escrow itself: the admin registers a
+//! fee module contract and the escrow asks it what to charge at release.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::shares::{self, Apportioned, BPS_DENOMINATOR};
+use crate::storage;
+use crate::types::{Error, FeeExemption, FeeSchedule, FeeTarget, Split};
+
+/// Most bands a tiered fee schedule can have
+pub const MAX_FEE_TIERS: u32 = 10;
+
+/// Interface a fee module contract must implement
+///
+/// `compute_fee` receives the gross amount about to leave escrow and returns
+/// the fee to withhold from it. It must be between 0 and `amount`.
+#[allow(dead_code)]
+#[contractclient(name = "FeeModuleClient")]
+pub trait FeeModule {
+    fn compute_fee(env: Env, split_id: u64, token: Address, amount: i128) -> i128;
+}
+
+/// Check that a schedule's tiers start at 0, rise strictly and charge at
+/// most 100%
+pub fn validate_schedule(schedule: &FeeSchedule) -> Result<(), Error> {
+    let tiers = &schedule.tiers;
+    if tiers.is_empty() || tiers.len() > MAX_FEE_TIERS {
+        return Err(Error::InvalidConfig);
+    }
+    let mut floor = -1;
+    for (i, tier) in tiers.iter().enumerate() {
+        if (i == 0 && tier.min_total != 0)
+            || tier.min_total <= floor
+            || tier.bps as i128 > BPS_DENOMINATOR
+        {
+            return Err(Error::InvalidConfig);
+        }
+        floor = tier.min_total;
+    }
+    Ok(())
+}
+
+/// The schedule's rate for a split of `total`
+pub fn tier_bps(schedule: &FeeSchedule, total: i128) -> u32 {
+    let mut bps = 0;
+    for tier in schedule.tiers.iter() {
+        if tier.min_total > total {
+            break;
+        }
+        bps = tier.bps;
+    }
+    bps
+}
+
+/// Who receives release fees: the fee module's recipient, else the
+/// schedule's
+pub fn fee_recipient(env: &Env) -> Option<Address> {
+    match storage::get_fee_config(env) {
+        Some(config) => Some(config.recipient),
+        None => storage::get_fee_schedule(env).map(|s| s.recipient),
+    }
+}
+
+/// Work out what to charge on a release
+///
+/// A fee override for the split, or else for its creator, beats
+/// everything else. Exempt creators and tokens are never charged.
+/// Otherwise a registered fee module decides. Without one, the tiered
+/// schedule applies the rate of the split's total to `amount`; with
+/// neither the release is free. A module that traps or returns an
+/// out-of-range fee blocks the release instead of guessing.
+pub fn compute_release_fee(
+    env: &Env,
+    split: &Split,
+    token: &Address,
+    amount: i128,
+) -> Result<Apportioned, Error> {
+    // Rates are apportioned under the deployment's rounding policy; a
+    // module's fee is taken as given
+    let policy = storage::get_rounding_policy(env);
+    let fee_override = storage::get_fee_override(env, &FeeTarget::Split(split.id))
+        .or_else(|| storage::get_fee_override(env, &FeeTarget::Creator(split.creator.clone())));
+    if let Some(bps) = fee_override {
+        return Ok(shares::apportion(
+            amount,
+            bps as i128,
+            BPS_DENOMINATOR,
+            policy,
+        ));
+    }
+
+    if storage::is_fee_exempt(env, &FeeExemption::Creator(split.creator.clone()))
+        || storage::is_fee_exempt(env, &FeeExemption::Token(token.clone()))
+    {
+        return Ok(exact_fee(amount, 0));
+    }
+
+    let config = match storage::get_fee_config(env) {
+        Some(config) => config,
+        None => {
+            let bps = storage::get_fee_schedule(env)
+                .map_or(0, |schedule| tier_bps(&schedule, split.total_amount));
+            return Ok(shares::apportion(
+                amount,
+                bps as i128,
+                BPS_DENOMINATOR,
+                policy,
+            ));
+        }
+    };
+
+    let client = FeeModuleClient::new(env, &config.module);
+    let fee = match client.try_compute_fee(&split.id, token, &amount) {
+        Ok(Ok(fee)) => fee,
+        _ => return Err(Error::FeeModuleFailed),
+    };
+
+    if fee < 0 || fee > amount {
+        return Err(Error::InvalidFee);
+    }
+
+    Ok(exact_fee(amount, fee))
+}
+
+/// A fee of exactly `fee`, with no rounding dust
+fn exact_fee(amount: i128, fee: i128) -> Apportioned {
+    Apportioned {
+        part: fee,
+        rest: amount - fee,
+        dust: 0,
+    }
+}
@@ -0,0 +1,35 @@
+//! # Hooks Module for Split Escrow Contract
+//!
+//! I'm defining the interfaces of external contracts the escrow calls out
+//! to, so integrators know exactly what to implement.
+
+use soroban_sdk::{contractclient, Env};
+
+use crate::events;
+use crate::types::Split;
+
+/// Interface a completion hook contract must implement
+///
+/// The escrow calls `on_split_completed` once, when the split first reaches
+/// Completed (or goes straight to Released).
+#[allow(dead_code)]
+#[contractclient(name = "CompletionHookClient")]
+pub trait CompletionHook {
+    fn on_split_completed(env: Env, split_id: u64, total: i128);
+}
+
+/// Invoke the split's completion hook, if it has one
+///
+/// I'm using the non-trapping client call so a broken or malicious hook can
+/// never block deposits or payouts; a failure is only reported as an event.
+pub fn notify_completed(env: &Env, split: &Split) {
+    if let Some(hook) = &split.completion_hook {
+        let client = CompletionHookClient::new(env, hook);
+        if client
+            .try_on_split_completed(&split.id, &split.total_amount)
+            .is_err()
+        {
+            events::emit_hook_failed(env, split.id, hook);
+        }
+    }
+}
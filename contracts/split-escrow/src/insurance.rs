@@ -0,0 +1,65 @@
+//! # Insurance Claims for Split Escrow Contract
+//!
+//! I'm keeping claims against the insurance pool behind the `insurance`
+//! feature. Premiums still flow into the pool without it; there's just no
+//! way to draw on it.
+
+use soroban_sdk::{contractimpl, token, Address, Env};
+
+use crate::types::{Error, TransferKind};
+use crate::{events, storage, SplitEscrowContract, SplitEscrowContractClient};
+
+#[contractimpl]
+impl SplitEscrowContract {
+    /// Draw a participant's recorded loss on a split from the insurance pool
+    ///
+    /// The payout is capped by the pool's `max_claim` and by what's left in
+    /// the pool. A claim is settled in one go: anything the cap or an empty
+    /// pool cut off is not owed later.
+    pub fn claim_insurance(env: Env, split_id: u64, participant: Address) -> Result<i128, Error> {
+        participant.require_auth();
+        Self::check_no_emergency(&env)?;
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let config = storage::get_insurance_config(&env).ok_or(Error::InvalidConfig)?;
+        let loss = storage::get_insurance_claim(&env, split_id, &participant);
+        let pool = storage::get_insurance_pool(&env);
+        let payout = loss.min(config.max_claim).min(pool);
+        if payout <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        storage::take_insurance_claim(&env, split_id, &participant);
+        storage::add_insurance_pool(&env, -payout);
+        let token_address = storage::get_token(&env);
+        storage::add_total_escrowed(&env, &token_address, -payout);
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &participant,
+            &payout,
+        );
+        Self::log_transfer(
+            &env,
+            split_id,
+            TransferKind::Insurance,
+            &participant,
+            &token_address,
+            payout,
+        );
+        events::emit_insurance_claimed(&env, split_id, &participant, payout);
+
+        Ok(payout)
+    }
+
+    /// Get the loss a participant can still claim from the pool for a split
+    pub fn get_insurance_claim(env: Env, split_id: u64, participant: Address) -> i128 {
+        storage::get_insurance_claim(&env, split_id, &participant)
+    }
+
+    /// Get the insurance pool's balance
+    pub fn get_insurance_pool(env: Env) -> i128 {
+        storage::get_insurance_pool(&env)
+    }
+}
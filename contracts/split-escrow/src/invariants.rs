@@ -0,0 +1,78 @@
+//! # Invariants Module for Split Escrow Contract
+//!
+//! I'm writing down what must always hold for a split, so monitoring and
+//! tests can ask the contract itself instead of re-deriving the rules.
+
+use soroban_sdk::Env;
+
+use crate::storage;
+use crate::types::{Split, SplitMode, SplitStatus};
+
+/// Whether every accounting and status rule holds for a split
+pub fn holds(env: &Env, split: &Split) -> bool {
+    participants_consistent(env, split)
+        && totals_consistent(split)
+        && balances_cover(env, split)
+        && status_consistent(split)
+}
+
+/// Each participant's paid amount fits their share and matches `has_paid`
+///
+/// An open split nobody has joined yet is the one case where the shares
+/// don't add up to the total.
+fn participants_consistent(env: &Env, split: &Split) -> bool {
+    let mut shares = 0;
+    for p in split.participants.iter() {
+        if p.amount_paid < 0 || p.amount_paid > p.share_amount {
+            return false;
+        }
+        if p.has_paid != (p.amount_paid >= p.share_amount) {
+            return false;
+        }
+        shares += p.share_amount;
+    }
+    shares == split.total_amount
+        || (split.participants.is_empty() && storage::is_joinable(env, split.id))
+}
+
+/// The split's aggregates agree with its participants
+fn totals_consistent(split: &Split) -> bool {
+    let paid: i128 = split.participants.iter().map(|p| p.amount_paid).sum();
+
+    paid == split.amount_collected
+        && split.amount_released >= 0
+        && split.amount_released <= split.amount_collected
+        && split.fees_paid >= 0
+        && split.fees_paid <= split.amount_released
+}
+
+/// What the split says is in escrow is backed by per-participant balances
+///
+/// IOU splits never hold tokens and pass-through splits forward every
+/// deposit, so only escrow splits have anything to back.
+fn balances_cover(env: &Env, split: &Split) -> bool {
+    let mut held = 0;
+    for p in split.participants.iter() {
+        held += storage::get_held_balance(env, split.id, &p.address);
+        for d in storage::get_foreign_held(env, split.id, &p.address).iter() {
+            held += d.credited;
+        }
+    }
+
+    match split.mode {
+        SplitMode::Escrow => held == split.amount_collected - split.amount_released,
+        SplitMode::Iou => held == 0 && split.amount_released == 0,
+        SplitMode::PassThrough => held == 0 && split.amount_released == split.amount_collected,
+    }
+}
+
+/// The status matches how much has been paid
+fn status_consistent(split: &Split) -> bool {
+    let funded = split.amount_collected >= split.total_amount;
+    match split.status {
+        SplitStatus::Pending => split.amount_collected == 0,
+        SplitStatus::Active => split.amount_collected > 0,
+        SplitStatus::Completed | SplitStatus::Released => funded,
+        SplitStatus::Cancelled | SplitStatus::Expired => true,
+    }
+}
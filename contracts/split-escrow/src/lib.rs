@@ -8,22 +8,54 @@
 //! - Accept deposits from participants
 //! - Release funds when split is complete
 //! - Cancel and refund if needed
+//! - Group splits and simplify the debts between members
+//! - Collect a trip's splits under one event with combined totals
 
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, token, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contractmeta, panic_with_error, token, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Map, String, Symbol, Vec,
+};
 
+mod breaker;
+#[cfg(feature = "disputes")]
+mod disputes;
 mod events;
+mod fees;
+mod hooks;
+#[cfg(feature = "insurance")]
+mod insurance;
+mod invariants;
+mod lifecycle;
+mod migration;
+mod oracle;
+mod refcode;
+mod router;
+mod settlement;
+mod share_token;
+pub mod shares;
+mod stats;
 mod storage;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 mod types;
 
 #[cfg(test)]
 mod test;
 
 pub use events::*;
+pub use fees::{FeeModule, FeeModuleClient};
+pub use hooks::{CompletionHook, CompletionHookClient};
+pub use oracle::{PriceOracle, PriceOracleClient};
+pub use router::{SwapRouter, SwapRouterClient};
+pub use share_token::{ShareToken, ShareTokenClient};
 pub use storage::*;
 pub use types::*;
 
+use lifecycle::Action;
+use shares::Apportioned;
+
 /// The main Split Escrow contract
 ///
 /// I'm keeping the initial implementation minimal - just the structure and
@@ -32,6 +64,73 @@ pub use types::*;
 #[contract]
 pub struct SplitEscrowContract;
 
+/// Contract semver, taken from Cargo.toml like the `version` metadata entry
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+contractmeta!(key = "name", val = "split-escrow");
+// `version`, `source_repo` and `source_rev`, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/contract_meta.rs"));
+
+/// Longest string (in bytes) the contract will hash for verification
+const MAX_HASHED_STRING_LEN: usize = 256;
+
+/// Longest receipt reference accepted (fits CIDv1 and raw digests)
+const MAX_RECEIPT_REF_LEN: u32 = 64;
+
+/// Youngest a split can be and still earn its keeper an expiry bounty
+pub const MIN_BOUNTY_AGE: u64 = storage::DAY_SECONDS;
+
+/// Shortest abandoned-split timeout the admin can set
+pub const MIN_ABANDON_TIMEOUT: u64 = 30 * storage::DAY_SECONDS;
+
+/// Shortest timelock delay the admin can set
+pub const MIN_TIMELOCK_DELAY: u64 = storage::DAY_SECONDS;
+
+/// Longest metadata URI accepted, in bytes
+pub const MAX_METADATA_URI_LEN: u32 = 256;
+
+/// Longest cancellation reason accepted, in bytes
+pub const MAX_CANCEL_REASON_LEN: u32 = 140;
+
+/// Most tags a single split can carry
+pub const MAX_TAGS: u32 = 5;
+
+/// Longest participant label accepted, in bytes
+pub const MAX_LABEL_LEN: u32 = 32;
+
+/// Funding percentages that get their own `milestone` event
+pub const FUNDING_MILESTONES: [u32; 4] = [25, 50, 75, 100];
+
+/// Longest creator note accepted, in bytes
+pub const MAX_NOTE_LEN: u32 = 140;
+
+/// Most notes a split can hold
+pub const MAX_NOTES: u32 = 20;
+
+/// Longest display ticker accepted, in bytes
+pub const MAX_DISPLAY_SYMBOL_LEN: u32 = 12;
+
+/// Longest event name accepted
+pub const MAX_EVENT_NAME_LEN: u32 = 64;
+
+/// Most splits one event can collect, so its views stay within budget
+pub const MAX_EVENT_SPLITS: u32 = 50;
+
+/// Most splits one group can collect, so its views stay within budget
+pub const MAX_GROUP_SPLITS: u32 = 50;
+
+/// Most people an open split can take
+pub const MAX_HEADCOUNT: u32 = 100;
+
+/// Most child splits one parent can have
+pub const MAX_CHILD_SPLITS: u32 = 20;
+
+/// Most days one creation-time query can span
+pub const MAX_QUERY_DAYS: u64 = 366;
+
+/// Most recipients a proposed payout plan can name
+pub const MAX_PAYOUT_RECIPIENTS: u32 = 10;
+
 #[contractimpl]
 impl SplitEscrowContract {
     /// Initialize the contract with an admin address
@@ -68,248 +167,6357 @@ impl SplitEscrowContract {
         total_amount: i128,
         participant_addresses: Vec<Address>,
         participant_shares: Vec<i128>,
-    ) -> u64 {
-        // Verify the creator is authorizing this call
-        creator.require_auth();
-
-        // Validate inputs
-        if participant_addresses.len() != participant_shares.len() {
-            panic!("Participant addresses and shares must have the same length");
-        }
+    ) -> Result<u64, Error> {
+        Self::create_split_internal(
+            &env,
+            creator,
+            description,
+            total_amount,
+            participant_addresses,
+            participant_shares,
+            SplitOptions::default(),
+        )
+    }
 
-        if participant_addresses.is_empty() {
-            panic!("At least one participant is required");
-        }
+    /// Create a new split with non-default options (mode, etc.)
+    ///
+    /// I'm keeping `create_split` as the simple path and routing every
+    /// optional setting through `SplitOptions` so the signature stays stable.
+    pub fn create_split_with_options(
+        env: Env,
+        creator: Address,
+        description: String,
+        total_amount: i128,
+        participant_addresses: Vec<Address>,
+        participant_shares: Vec<i128>,
+        options: SplitOptions,
+    ) -> Result<u64, Error> {
+        Self::create_split_internal(
+            &env,
+            creator,
+            description,
+            total_amount,
+            participant_addresses,
+            participant_shares,
+            options,
+        )
+    }
 
-        // Validate shares sum to total
-        let mut shares_sum: i128 = 0;
-        for i in 0..participant_shares.len() {
-            shares_sum += participant_shares.get(i).unwrap();
-        }
-        if shares_sum != total_amount {
-            panic!("Participant shares must sum to total amount");
+    /// Stage a split on behalf of a creator from an operator's backend
+    ///
+    /// Only addresses granted the operator role through
+    /// `AdminAction::AddOperator` can call this, and it needs no signature
+    /// from the creator. The split refuses deposits until the creator calls
+    /// `accept_split`. The total is the sum of the shares. Options that pull
+    /// tokens from the creator (`bond`, `insured`, `rent_budget`) aren't
+    /// available here, and neither is settling the creator's own share,
+    /// which needs their signature.
+    pub fn create_split_for(
+        env: Env,
+        operator: Address,
+        creator: Address,
+        description: String,
+        participant_addresses: Vec<Address>,
+        participant_shares: Vec<i128>,
+        options: SplitOptions,
+    ) -> Result<u64, Error> {
+        operator.require_auth();
+        if !storage::is_operator(&env, &operator) {
+            return Err(Error::AccessDenied);
         }
-
-        // Get the next split ID
-        let split_id = storage::get_next_split_id(&env);
-
-        // Create participant entries
-        let mut participants = Vec::new(&env);
-        for i in 0..participant_addresses.len() {
-            let participant = Participant {
-                address: participant_addresses.get(i).unwrap(),
-                share_amount: participant_shares.get(i).unwrap(),
-                amount_paid: 0,
-                has_paid: false,
-            };
-            participants.push_back(participant);
+        if options.bond > 0
+            || options.insured
+            || options.rent_budget > 0
+            || options.creator_share != CreatorShare::Owed
+        {
+            return Err(Error::UnsupportedMode);
         }
 
-        // Create the split
-        let split = Split {
-            id: split_id,
-            creator: creator.clone(),
+        let total_amount = participant_shares
+            .iter()
+            .try_fold(0i128, |sum, share| sum.checked_add(share))
+            .ok_or(Error::InvalidAmount)?;
+        let split_id = Self::store_new_split(
+            &env,
+            creator.clone(),
             description,
             total_amount,
-            amount_collected: 0,
-            amount_released: 0,
-            participants,
-            status: SplitStatus::Pending,
-            created_at: env.ledger().timestamp(),
-        };
+            participant_addresses,
+            participant_shares,
+            options,
+            true,
+        )?;
+        storage::set_awaiting_creator(&env, split_id, true);
+        events::emit_split_staged(&env, split_id, &operator, &creator);
 
-        // Store the split
-        storage::set_split(&env, split_id, &split);
+        Ok(split_id)
+    }
 
-        // Emit creation event
-        events::emit_split_created(&env, split_id, &creator, total_amount);
+    /// Accept a split an operator staged, so it starts taking deposits
+    pub fn accept_split(env: Env, split_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        if !storage::is_awaiting_creator(&env, split_id) {
+            return Err(Error::NotAwaitingAcceptance);
+        }
 
-        split_id
+        storage::set_awaiting_creator(&env, split_id, false);
+        Self::lock_bounty_stake(&env, &split);
+        events::emit_split_accepted(&env, split_id, &split.creator);
+        Ok(())
     }
 
-    /// Deposit funds into a split
-    ///
-    /// I'm allowing partial deposits so participants can pay incrementally.
-    pub fn deposit(env: Env, split_id: u64, participant: Address, amount: i128) {
-        // Verify the participant is authorizing this call
-        participant.require_auth();
+    /// Whether a staged split is still waiting for its creator to accept it
+    pub fn is_awaiting_creator(env: Env, split_id: u64) -> bool {
+        storage::is_awaiting_creator(&env, split_id)
+    }
 
-        // Get the split
-        let mut split = storage::get_split(&env, split_id);
+    /// Whether an address holds the operator role
+    pub fn is_operator(env: Env, operator: Address) -> bool {
+        storage::is_operator(&env, &operator)
+    }
 
-        if amount <= 0 {
-            panic!("Deposit amount must be positive");
+    /// Create a split where everyone owes the same amount
+    ///
+    /// When the total doesn't divide evenly, the first-listed participants
+    /// each absorb one extra stroop (see `shares::equal_share`).
+    pub fn create_equal_split(
+        env: Env,
+        creator: Address,
+        description: String,
+        total_amount: i128,
+        participant_addresses: Vec<Address>,
+    ) -> Result<u64, Error> {
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        // Verify the split is still accepting deposits
-        if split.status != SplitStatus::Pending && split.status != SplitStatus::Active {
-            panic!("Split is not accepting deposits");
+        let count = participant_addresses.len();
+        let mut participant_shares = Vec::new(&env);
+        for i in 0..count {
+            participant_shares.push_back(shares::equal_share(total_amount, count, i));
         }
+        Self::create_split_internal(
+            &env,
+            creator,
+            description,
+            total_amount,
+            participant_addresses,
+            participant_shares,
+            SplitOptions::default(),
+        )
+    }
 
-        // Find the participant in the split
-        let mut found = false;
-        let mut updated_participants = Vec::new(&env);
+    /// Create a split whose shares are whole units (nights, seats, items)
+    ///
+    /// `units[i]` belongs to the i-th participant. I'm pricing a unit at
+    /// the floored `total_amount / sum(units)` and spreading the leftover
+    /// stroops like `shares::weighted_shares`. The creator can still change
+    /// units with `set_units`; deposits open once `lock_units` fixes them.
+    pub fn create_unit_split(
+        env: Env,
+        creator: Address,
+        description: String,
+        total_amount: i128,
+        participant_addresses: Vec<Address>,
+        units: Vec<u32>,
+    ) -> Result<u64, Error> {
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if participant_addresses.len() != units.len() {
+            return Err(Error::SharesMismatch);
+        }
+        let (participant_shares, unit_price) = Self::unit_amounts(&env, total_amount, &units)?;
 
-        for i in 0..split.participants.len() {
-            let mut p = split.participants.get(i).unwrap();
-            if p.address == participant {
-                found = true;
-                let remaining = p.share_amount - p.amount_paid;
-                if amount > remaining {
-                    panic!("Deposit exceeds remaining amount owed");
-                }
+        let split_id = Self::create_split_internal(
+            &env,
+            creator,
+            description,
+            total_amount,
+            participant_addresses,
+            participant_shares,
+            SplitOptions::default(),
+        )?;
+        storage::set_unit_shares(
+            &env,
+            split_id,
+            &UnitShares {
+                units,
+                unit_price,
+                locked: false,
+            },
+        );
+        Ok(split_id)
+    }
 
-                p.amount_paid += amount;
-                p.has_paid = p.amount_paid >= p.share_amount;
-            }
-            updated_participants.push_back(p);
+    /// Change how many units a participant holds before they're locked
+    ///
+    /// Every share is re-derived from the total, so one stay getting
+    /// longer makes everyone else's nights cheaper.
+    pub fn set_units(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        units: u32,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
         }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        Self::check_nonce(&split, expected_nonce)?;
 
-        if !found {
-            panic!("Participant not found in split");
+        let mut unit_shares =
+            storage::get_unit_shares(&env, split_id).ok_or(Error::UnsupportedMode)?;
+        if unit_shares.locked {
+            return Err(Error::UnitsLocked);
         }
+        let index = split
+            .participants
+            .iter()
+            .position(|p| p.address == participant)
+            .ok_or(Error::ParticipantNotFound)? as u32;
 
-        // Transfer tokens from participant to escrow contract
-        let token_address = storage::get_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-        token_client.transfer(&participant, &contract_address, &amount);
+        unit_shares.units.set(index, units);
+        Self::apply_units(&env, &mut split, &mut unit_shares)?;
+        storage::set_split(&env, split_id, &split);
+        storage::set_unit_shares(&env, split_id, &unit_shares);
+        events::emit_units_set(&env, split_id, &participant, units);
+        Ok(())
+    }
 
-        // Update split state
-        split.participants = updated_participants;
-        split.amount_collected += amount;
+    /// Fix a unit-priced split's units and price, so deposits can start
+    pub fn lock_units(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
 
-        // Check if split is now fully funded
-        if split.status == SplitStatus::Pending {
-            split.status = SplitStatus::Active;
+        let mut unit_shares =
+            storage::get_unit_shares(&env, split_id).ok_or(Error::UnsupportedMode)?;
+        if unit_shares.locked {
+            return Err(Error::UnitsLocked);
         }
 
-        // Save the updated split
+        Self::apply_units(&env, &mut split, &mut unit_shares)?;
+        unit_shares.locked = true;
         storage::set_split(&env, split_id, &split);
+        storage::set_unit_shares(&env, split_id, &unit_shares);
+        events::emit_units_locked(&env, split_id, unit_shares.unit_price);
+        Ok(unit_shares.unit_price)
+    }
 
-        // Emit deposit event
-        events::emit_deposit_received(&env, split_id, &participant, amount);
-
-        // Auto-release funds if fully funded
-        if Self::is_fully_funded_internal(&split) {
-            let _ = Self::release_funds_internal(&env, split_id, split);
-        }
+    /// Get a unit-priced split's units and price
+    pub fn get_unit_shares(env: Env, split_id: u64) -> Option<UnitShares> {
+        storage::get_unit_shares(&env, split_id)
     }
 
-    /// Release funds from a completed split to the creator
+    /// Join an open split
     ///
-    /// I'm restricting this to completed splits only for safety.
-    pub fn release_funds(env: Env, split_id: u64) -> Result<(), Error> {
+    /// I'm recomputing every share as an equal cut of the total each time
+    /// someone joins, which is safe because nobody can deposit until the
+    /// split is locked. The split locks itself once it's full.
+    pub fn join_split(env: Env, split_id: u64, participant: Address) -> Result<(), Error> {
         if !storage::has_split(&env, split_id) {
             return Err(Error::SplitNotFound);
         }
+        participant.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
 
-        let split = storage::get_split(&env, split_id);
-        Self::release_funds_internal(&env, split_id, split).map(|_| ())
+        let mut split = storage::get_split(&env, split_id);
+        let cap = storage::get_join_cap(&env, split_id);
+        if cap == 0 {
+            return Err(Error::UnsupportedMode);
+        }
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            _ => {}
+        }
+        if Self::is_past_deadline(&env, &split) {
+            return Err(Error::SplitExpired);
+        }
+        if split.participants.iter().any(|p| p.address == participant) {
+            return Err(Error::InvalidParticipants);
+        }
+
+        split.participants.push_back(Participant {
+            address: participant.clone(),
+            share_amount: 0,
+            amount_paid: 0,
+            has_paid: false,
+        });
+        let headcount = split.participants.len();
+        for i in 0..headcount {
+            let mut p = split.participants.get(i).unwrap();
+            p.share_amount = shares::equal_share(split.total_amount, headcount, i);
+            split.participants.set(i, p);
+        }
+        storage::set_split(&env, split_id, &split);
+        storage::add_to_participant_index(&env, &participant, split_id);
+        stats::record_joined(&env, &participant);
+        events::emit_split_joined(&env, split_id, &participant, headcount);
+
+        if headcount >= cap {
+            storage::set_join_cap(&env, split_id, 0);
+            events::emit_split_locked(&env, split_id, headcount);
+        }
+
+        Ok(())
     }
 
-    /// Release available funds to the creator for partial payments
-    pub fn release_partial(env: Env, split_id: u64) -> Result<i128, Error> {
+    /// Stop an open split taking members, so deposits can start
+    pub fn lock_split(env: Env, split_id: u64) -> Result<(), Error> {
         if !storage::has_split(&env, split_id) {
             return Err(Error::SplitNotFound);
         }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
 
-        let mut split = storage::get_split(&env, split_id);
-
-        if split.status == SplitStatus::Cancelled {
-            return Err(Error::SplitCancelled);
+        if !storage::is_joinable(&env, split_id) {
+            return Err(Error::UnsupportedMode);
         }
-
-        if split.status == SplitStatus::Released {
-            return Err(Error::SplitReleased);
+        if split.participants.is_empty() {
+            return Err(Error::InvalidParticipants);
         }
 
-        if Self::is_fully_funded_internal(&split) {
-            return Err(Error::SplitFullyFunded);
+        storage::set_join_cap(&env, split_id, 0);
+        events::emit_split_locked(&env, split_id, split.participants.len());
+        Ok(())
+    }
+
+    /// How many more people can join an open split (0 once it's locked)
+    pub fn get_open_seats(env: Env, split_id: u64) -> u32 {
+        let cap = storage::get_join_cap(&env, split_id);
+        if cap == 0 || !storage::has_split(&env, split_id) {
+            return 0;
         }
+        cap.saturating_sub(storage::get_split(&env, split_id).participants.len())
+    }
 
-        let available = split.amount_collected - split.amount_released;
-        if available <= 0 {
-            return Err(Error::NoFundsAvailable);
+    /// Deposit funds into a split
+    ///
+    /// I'm allowing partial deposits so participants can pay incrementally.
+    /// Every rejection is an `Error`, so a calling contract can use
+    /// `try_deposit` and recover instead of trapping.
+    pub fn deposit(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        // Verify the participant is authorizing this call
+        participant.require_auth();
+        Self::deposit_internal(&env, split_id, &participant, &participant, amount)
+    }
+
+    /// Deposit toward a participant's share from another contract
+    ///
+    /// Payroll contracts and DAO treasuries fund shares this way. The
+    /// invoker signs and pays, but the deposit is credited to the
+    /// participant exactly as if they'd paid, so refunds go back to them.
+    /// The invoker has to be on the admin's allowlist or on this split's.
+    pub fn deposit_via(
+        env: Env,
+        invoker: Address,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        invoker.require_auth();
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
         }
+        if !storage::is_depositor(&env, &invoker)
+            && !storage::is_split_depositor(&env, split_id, &invoker)
+        {
+            return Err(Error::AccessDenied);
+        }
+        let split = storage::get_split(&env, split_id);
+        let index = Self::check_deposit(&env, &split, &participant, amount)?;
 
-        let token_address = storage::get_token(&env);
-        let token_client = token::Client::new(&env, &token_address);
-        let contract_address = env.current_contract_address();
-        token_client.transfer(&contract_address, &split.creator, &available);
+        events::emit_deposit_via(&env, split_id, &participant, &invoker, amount);
+        Self::take_deposit(&env, split, index, &participant, &invoker, amount, false)
+    }
 
-        split.amount_released += available;
-        storage::set_split(&env, split_id, &split);
+    /// Credit a participant for a payment made from a classic account
+    ///
+    /// Participants on classic wallets pay the bridge with a claimable
+    /// balance or payment. Once the bridge has verified it, it calls this
+    /// with `payment_ref`, a 32-byte reference to that payment, and pays
+    /// the amount in from its own balance. The credit goes to the named
+    /// participant like their own deposit. Each reference can be credited
+    /// only once, and its credit stays on record.
+    pub fn bridge_deposit(
+        env: Env,
+        bridge: Address,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+        payment_ref: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_role(&env, &bridge, Role::Bridge)?;
 
-        events::emit_funds_released(
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if storage::get_bridge_credit(&env, &payment_ref).is_some() {
+            return Err(Error::PaymentAlreadyCredited);
+        }
+        let split = storage::get_split(&env, split_id);
+        let index = Self::check_deposit(&env, &split, &participant, amount)?;
+
+        storage::set_bridge_credit(
             &env,
-            split_id,
-            &split.creator,
-            available,
-            env.ledger().timestamp(),
+            &payment_ref,
+            &BridgeCredit {
+                bridge: bridge.clone(),
+                split_id,
+                participant: participant.clone(),
+                amount,
+                credited_at: env.ledger().timestamp(),
+            },
         );
+        events::emit_bridge_deposit(&env, split_id, &participant, &payment_ref, amount);
+        Self::take_deposit(&env, split, index, &participant, &bridge, amount, false)
+    }
 
-        Ok(available)
+    /// Get the credit a bridge made for a classic payment reference, if any
+    pub fn get_bridge_credit(env: Env, payment_ref: BytesN<32>) -> Option<BridgeCredit> {
+        storage::get_bridge_credit(&env, &payment_ref)
     }
 
-    /// Check if a split is fully funded
-    pub fn is_fully_funded(env: Env, split_id: u64) -> Result<bool, Error> {
+    /// Let a contract deposit for participants on one of your splits
+    ///
+    /// Only the creator can change the split's allowlist. Passing `false`
+    /// takes the invoker off it again.
+    pub fn set_split_depositor(
+        env: Env,
+        split_id: u64,
+        invoker: Address,
+        allowed: bool,
+    ) -> Result<(), Error> {
         if !storage::has_split(&env, split_id) {
             return Err(Error::SplitNotFound);
         }
+        storage::get_split(&env, split_id).creator.require_auth();
 
-        let split = storage::get_split(&env, split_id);
-        Ok(Self::is_fully_funded_internal(&split))
+        storage::set_split_depositor(&env, split_id, &invoker, allowed);
+        events::emit_depositor_set(&env, split_id, &invoker, allowed);
+        Ok(())
     }
 
-    /// Cancel a split and mark for refunds
+    /// Let another address act for you on every split
     ///
-    /// I'm allowing only the creator to cancel, and only if not fully completed.
-    pub fn cancel_split(env: Env, split_id: u64) {
-        let mut split = storage::get_split(&env, split_id);
-
-        // Only the creator can cancel
-        split.creator.require_auth();
-
-        // Can't cancel a completed split that's been released
-        if split.status == SplitStatus::Released {
-            panic!("Cannot cancel a released split");
+    /// The delegate can deposit, accept increases, claim refunds and open
+    /// disputes through the `_as` calls. Anything it does is credited to
+    /// you and refunds still come to you, or to your refund address.
+    /// Registering a new delegate
+    /// replaces the old one.
+    pub fn set_delegate(env: Env, participant: Address, delegate: Address) -> Result<(), Error> {
+        participant.require_auth();
+        if delegate == participant {
+            return Err(Error::InvalidParticipants);
         }
 
-        // Mark as cancelled
-        split.status = SplitStatus::Cancelled;
-        storage::set_split(&env, split_id, &split);
-
-        // Emit cancellation event
-        events::emit_split_cancelled(&env, split_id);
+        storage::set_delegate(&env, &participant, &delegate);
+        events::emit_delegate_set(&env, &participant, Some(delegate));
+        Ok(())
     }
 
-    /// Get split details by ID
-    pub fn get_split(env: Env, split_id: u64) -> Split {
-        storage::get_split(&env, split_id)
+    /// Stop your delegate from acting for you
+    pub fn revoke_delegate(env: Env, participant: Address) {
+        participant.require_auth();
+        storage::remove_delegate(&env, &participant);
+        events::emit_delegate_set(&env, &participant, None);
     }
 
-    /// Get the contract admin
-    pub fn get_admin(env: Env) -> Address {
-        storage::get_admin(&env)
+    /// Get the delegate a participant registered, if any
+    pub fn get_delegate(env: Env, participant: Address) -> Option<Address> {
+        storage::get_delegate(&env, &participant)
     }
 
-    /// Get the token contract address
-    pub fn get_token(env: Env) -> Address {
-        storage::get_token(&env)
+    /// Send all your future refunds to another address
+    ///
+    /// I'm applying this on every split and every refund path, so an
+    /// exchange deposit address or a wallet you rotated to gets the money
+    /// instead of the address you joined with. Your splits still list you.
+    pub fn set_refund_address(
+        env: Env,
+        participant: Address,
+        refund_to: Address,
+    ) -> Result<(), Error> {
+        participant.require_auth();
+        if refund_to == participant {
+            return Err(Error::InvalidParticipants);
+        }
+
+        storage::set_refund_address(&env, &participant, &refund_to);
+        events::emit_refund_address_set(&env, &participant, Some(refund_to));
+        Ok(())
+    }
+
+    /// Go back to receiving refunds at your own address
+    pub fn clear_refund_address(env: Env, participant: Address) {
+        participant.require_auth();
+        storage::remove_refund_address(&env, &participant);
+        events::emit_refund_address_set(&env, &participant, None);
+    }
+
+    /// Get where a participant's refunds go, if not to them
+    pub fn get_refund_address(env: Env, participant: Address) -> Option<Address> {
+        storage::get_refund_address(&env, &participant)
+    }
+
+    /// Cap what other people can make you owe across your open splits
+    ///
+    /// I'm checking the cap whenever someone else puts a share on you: a
+    /// new split listing you, a lapsed slot reassigned to you or a share
+    /// token transferred to you. Lowering it below what you already owe
+    /// only blocks new obligations; nothing existing is touched.
+    pub fn set_spending_cap(env: Env, address: Address, cap: i128) -> Result<(), Error> {
+        address.require_auth();
+        if cap <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        storage::set_spending_cap(&env, &address, cap);
+        events::emit_spending_cap_set(&env, &address, Some(cap));
+        Ok(())
+    }
+
+    /// Let others add you to splits without a limit again
+    pub fn clear_spending_cap(env: Env, address: Address) {
+        address.require_auth();
+        storage::remove_spending_cap(&env, &address);
+        events::emit_spending_cap_set(&env, &address, None);
+    }
+
+    /// Get an address's spending cap, if it set one
+    pub fn get_spending_cap(env: Env, address: Address) -> Option<i128> {
+        storage::get_spending_cap(&env, &address)
+    }
+
+    /// Deposit toward a participant's share as them or their delegate
+    ///
+    /// A delegate pays from its own balance; the deposit is credited to the
+    /// participant.
+    pub fn deposit_as(
+        env: Env,
+        actor: Address,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        Self::require_actor(&env, &actor, &participant)?;
+        Self::deposit_internal(&env, split_id, &participant, &actor, amount)
+    }
+
+    /// Accept a proposed total increase as a participant or their delegate
+    pub fn accept_increase_as(
+        env: Env,
+        actor: Address,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<bool, Error> {
+        Self::require_actor(&env, &actor, &participant)?;
+        Self::accept_increase_internal(&env, split_id, &participant)
+    }
+
+    /// Claim a participant's refund as them or their delegate
+    ///
+    /// The refund is always paid to the participant.
+    pub fn claim_refund_as(
+        env: Env,
+        actor: Address,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<i128, Error> {
+        Self::require_actor(&env, &actor, &participant)?;
+        Self::claim_refund_internal(&env, split_id, &participant)
+    }
+
+    /// Whether an invoker may call `deposit_via` on a split
+    pub fn can_deposit_via(env: Env, split_id: u64, invoker: Address) -> bool {
+        storage::is_depositor(&env, &invoker)
+            || storage::is_split_depositor(&env, split_id, &invoker)
+    }
+
+    /// Consent to have deposits pulled from an allowance on a schedule
+    ///
+    /// The participant also has to approve the escrow to spend the token.
+    /// From `first_due` on, every `interval` seconds a keeper can call
+    /// `process_due` to pull `amount` (or whatever's still owed). Setting a
+    /// new schedule replaces the old one.
+    pub fn set_pull_schedule(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+        interval: u64,
+        first_due: u64,
+    ) -> Result<(), Error> {
+        participant.require_auth();
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if interval == 0 {
+            return Err(Error::InvalidConfig);
+        }
+        let split = storage::get_split(&env, split_id);
+        if split.mode == SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+        if !settlement::is_open(&split) {
+            return Err(Error::ActionNotReady);
+        }
+        if !split.participants.iter().any(|p| p.address == participant) {
+            return Err(Error::ParticipantNotFound);
+        }
+
+        storage::set_pull_schedule(
+            &env,
+            split_id,
+            &participant,
+            &PullSchedule {
+                amount,
+                interval,
+                next_due: first_due,
+            },
+        );
+        Ok(())
+    }
+
+    /// Withdraw consent to scheduled pulls on a split
+    pub fn cancel_pull_schedule(env: Env, split_id: u64, participant: Address) {
+        participant.require_auth();
+        storage::remove_pull_schedule(&env, split_id, &participant);
+    }
+
+    /// Get a participant's pull schedule for a split, if they set one
+    pub fn get_pull_schedule(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+    ) -> Option<PullSchedule> {
+        storage::get_pull_schedule(&env, split_id, &participant)
+    }
+
+    /// Pull every deposit that's come due on a split
+    ///
+    /// Anyone can act as the keeper. Each participant whose schedule is due
+    /// gets a result and a `pull_ok` or `pull_fail` event; one refused
+    /// allowance doesn't stop the others. A failed pull stays due, so the
+    /// next call retries it. A schedule is dropped once its share is paid,
+    /// however it got paid.
+    pub fn process_due(env: Env, split_id: u64) -> Result<Vec<PullResult>, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let now = env.ledger().timestamp();
+        let participants = storage::get_split(&env, split_id).participants;
+        let mut results = Vec::new(&env);
+        for participant in participants.iter() {
+            let address = participant.address;
+            let mut schedule = match storage::get_pull_schedule(&env, split_id, &address) {
+                Some(schedule) if schedule.next_due <= now => schedule,
+                _ => continue,
+            };
+
+            // Earlier pulls may have completed and released the split
+            let split = storage::get_split(&env, split_id);
+            let p = split
+                .participants
+                .iter()
+                .find(|p| p.address == address)
+                .unwrap();
+            // Paid directly, forgiven or written off: nothing left to pull
+            let remaining = p.share_amount - p.amount_paid;
+            if remaining <= 0 {
+                storage::remove_pull_schedule(&env, split_id, &address);
+                continue;
+            }
+            let amount = schedule.amount.min(remaining);
+            let outcome = Self::check_deposit(&env, &split, &address, amount).and_then(|index| {
+                Self::take_deposit(&env, split, index, &address, &address, amount, true)
+            });
+
+            let error = match outcome {
+                Ok(()) => {
+                    if p.amount_paid + amount >= p.share_amount {
+                        storage::remove_pull_schedule(&env, split_id, &address);
+                    } else {
+                        schedule.next_due = schedule.next_due.saturating_add(schedule.interval);
+                        storage::set_pull_schedule(&env, split_id, &address, &schedule);
+                    }
+                    events::emit_pull_succeeded(&env, split_id, &address, amount);
+                    0
+                }
+                Err(err) => {
+                    events::emit_pull_failed(&env, split_id, &address, err as u32);
+                    err as u32
+                }
+            };
+            results.push_back(PullResult {
+                participant: address,
+                amount: if error == 0 { amount } else { 0 },
+                error,
+            });
+        }
+
+        Ok(results)
+    }
+
+    /// Chip in toward a crowdfunded split without being listed on it
+    ///
+    /// I'm recording the contributor as a participant whose share is exactly
+    /// what they put in, and taking that amount off the listed participants'
+    /// unpaid shares in list order. Shares keep adding up to the total, so
+    /// refunds and releases treat a contribution like any other deposit.
+    pub fn contribute(
+        env: Env,
+        split_id: u64,
+        contributor: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        contributor.require_auth();
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if !storage::is_crowdfund(&env, split_id) {
+            return Err(Error::UnsupportedMode);
+        }
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut split = storage::get_split(&env, split_id);
+        let mut contributions = storage::get_contributions(&env, split_id);
+        let existing = contributions
+            .iter()
+            .position(|c| c.contributor == contributor);
+        // Listed participants pay their own share through `deposit`
+        if existing.is_none() && split.participants.iter().any(|p| p.address == contributor) {
+            return Err(Error::InvalidParticipants);
+        }
+        // Every contributor adds a participant entry, so cap them like an
+        // open split's headcount
+        if existing.is_none() && split.participants.len() >= MAX_HEADCOUNT {
+            return Err(Error::InvalidParticipants);
+        }
+
+        let mut left = amount;
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            if left == 0 || p.address == contributor {
+                continue;
+            }
+            // A listed share never drops below what's paid, nor below one
+            // stroop, so nobody is written out of the split
+            let reducible = p.share_amount - p.amount_paid.max(1);
+            if reducible <= 0 {
+                continue;
+            }
+            let taken = if reducible < left { reducible } else { left };
+            p.share_amount -= taken;
+            p.has_paid = p.amount_paid >= p.share_amount;
+            split.participants.set(i, p);
+            left -= taken;
+        }
+        if left > 0 {
+            return Err(Error::ExceedsRemaining);
+        }
+
+        match existing {
+            Some(i) => {
+                let i = i as u32;
+                let mut contribution = contributions.get(i).unwrap();
+                contribution.amount += amount;
+                contributions.set(i, contribution);
+
+                let index = split
+                    .participants
+                    .iter()
+                    .position(|p| p.address == contributor)
+                    .unwrap() as u32;
+                let mut p = split.participants.get(index).unwrap();
+                p.share_amount += amount;
+                split.participants.set(index, p);
+            }
+            None => {
+                contributions.push_back(Contribution {
+                    contributor: contributor.clone(),
+                    amount,
+                });
+                split.participants.push_back(Participant {
+                    address: contributor.clone(),
+                    share_amount: amount,
+                    amount_paid: 0,
+                    has_paid: false,
+                });
+                storage::add_to_participant_index(&env, &contributor, split_id);
+                stats::record_joined(&env, &contributor);
+            }
+        }
+
+        let index = Self::check_deposit(&env, &split, &contributor, amount)?;
+        storage::set_contributions(&env, split_id, &contributions);
+        events::emit_contribution(&env, split_id, &contributor, amount);
+
+        Self::take_deposit(
+            &env,
+            split,
+            index,
+            &contributor,
+            &contributor,
+            amount,
+            false,
+        )
+    }
+
+    /// Get everyone who chipped in to a crowdfunded split, and how much
+    pub fn get_contributions(env: Env, split_id: u64) -> Vec<Contribution> {
+        storage::get_contributions(&env, split_id)
+    }
+
+    /// Deposit in an admin-approved token other than the escrow token
+    ///
+    /// I'm valuing the deposit through the oracle once, at deposit time, and
+    /// crediting that value toward the participant's share. The tokens
+    /// themselves stay in escrow as-is and are forwarded to the creator at
+    /// release (or refunded as-is). Returns the credited amount.
+    pub fn deposit_with_token(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if token == storage::get_token(&env) {
+            Self::deposit(env, split_id, participant, amount)?;
+            return Ok(amount);
+        }
+
+        participant.require_auth();
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+
+        if !storage::is_token_accepted(&env, &token) {
+            return Err(Error::TokenNotAccepted);
+        }
+        // Pass-through splits never hold funds to forward in bulk
+        if split.mode != SplitMode::Escrow {
+            return Err(Error::UnsupportedMode);
+        }
+
+        let credited = oracle::to_reference(&env, &token, amount)?;
+        let index = Self::check_deposit(&env, &split, &participant, credited)?;
+
+        storage::add_total_escrowed(&env, &token, amount);
+        storage::add_foreign_held(
+            &env,
+            split_id,
+            &participant,
+            ForeignDeposit {
+                token: token.clone(),
+                amount,
+                credited,
+            },
+        );
+
+        Self::apply_deposit(&env, &mut split, index, credited);
+
+        // Save the updated split before the token is called
+        storage::set_split(&env, split_id, &split);
+        token::Client::new(&env, &token).transfer(
+            &participant,
+            &env.current_contract_address(),
+            &amount,
+        );
+        Self::recheck_split(&env, &split);
+        stats::record_payment(&env, &split, &participant, credited);
+
+        events::emit_deposit_received(&env, split_id, &participant, credited);
+        events::emit_foreign_deposit(&env, split_id, &participant, &token, amount, credited);
+
+        if Self::is_fully_funded_internal(&split) {
+            Self::complete_or_release(&env, split_id, split);
+        } else if Self::drips(&env, &split) {
+            Self::release_available(&env, &mut split)?;
+        }
+
+        Ok(credited)
+    }
+
+    /// Release funds from a completed split to the creator
+    ///
+    /// I'm restricting this to completed splits only for safety. On a split
+    /// with a `release_delay` this only records a release request; the
+    /// payout happens in `execute_release` once the delay has passed.
+    pub fn release_funds(env: Env, split_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        if storage::get_confirmer(&env, split_id).is_some() {
+            return Err(Error::UnsupportedMode);
+        }
+        let delay = storage::get_release_delay(&env, split_id);
+        if delay == 0 {
+            return Self::release_funds_internal(&env, split_id, split).map(|_| ());
+        }
+
+        Self::check_release(&env, &split)?;
+        if storage::get_release_request(&env, split_id).is_some() {
+            return Err(Error::ActionNotReady);
+        }
+        let now = env.ledger().timestamp();
+        let request = ReleaseRequest {
+            requested_at: now,
+            ready_at: now + delay,
+        };
+        storage::set_release_request(&env, split_id, &request);
+        events::emit_release_requested(&env, split_id, request.ready_at);
+
+        Ok(())
+    }
+
+    /// Release several completed splits in one transaction
+    ///
+    /// Each ID gets its own result, like `expire_batch`: a split that fails
+    /// the `release_funds` checks is skipped with the error it would have
+    /// returned, and the rest still pay out. Splits with a release delay or
+    /// a confirmer are skipped with `UnsupportedMode`, since they have
+    /// their own release steps. A release that passes the checks but then
+    /// fails (a swap, say) aborts the whole batch, so nothing is left half
+    /// paid.
+    pub fn batch_release(env: Env, split_ids: Vec<u64>) -> Result<Vec<BatchResult>, Error> {
+        let mut results = Vec::new(&env);
+        let mut released = 0;
+        let mut total = 0;
+        for split_id in split_ids.iter() {
+            let outcome = match Self::check_batch_release(&env, split_id) {
+                Ok(split) => {
+                    total += Self::release_funds_internal(&env, split_id, split)?;
+                    released += 1;
+                    Ok(())
+                }
+                Err(err) => Err(err),
+            };
+            results.push_back(Self::batch_result(split_id, outcome));
+        }
+        events::emit_batch_released(&env, released, split_ids.len() - released, total);
+
+        Ok(results)
+    }
+
+    /// Pay out a release request whose delay has passed
+    ///
+    /// Anyone can call this, so the creator doesn't have to come back. A
+    /// dispute opened during the delay blocks it until the arbiter rules.
+    pub fn execute_release(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let request = storage::get_release_request(&env, split_id).ok_or(Error::ActionNotFound)?;
+        if env.ledger().timestamp() < request.ready_at {
+            return Err(Error::ActionNotReady);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        let paid = Self::release_funds_internal(&env, split_id, split)?;
+        storage::remove_release_request(&env, split_id);
+        Ok(paid)
+    }
+
+    /// Hold a split's escrow until a set time, like the day of the event
+    ///
+    /// Until `release_at` nothing pays out: a funded split waits at
+    /// Completed and `release_funds` returns `ActionNotReady`. Calling
+    /// again moves the time, but once the split is funded only earlier, so
+    /// the creator can't keep the group's money parked. Splits with their
+    /// own release step (a delay, a confirmer or pass-through deposits)
+    /// can't be scheduled.
+    pub fn schedule_release(env: Env, split_id: u64, release_at: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed => {}
+        }
+        if split.mode != SplitMode::Escrow
+            || storage::get_release_delay(&env, split_id) > 0
+            || storage::get_confirmer(&env, split_id).is_some()
+            || storage::reimburses_creator(&env, split_id)
+        {
+            return Err(Error::UnsupportedMode);
+        }
+        if release_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidDeadline);
+        }
+        if split.status == SplitStatus::Completed
+            && storage::get_scheduled_release(&env, split_id).is_none_or(|at| release_at > at)
+        {
+            return Err(Error::InvalidDeadline);
+        }
+
+        storage::set_scheduled_release(&env, split_id, release_at);
+        events::emit_release_scheduled(&env, split_id, release_at);
+        Ok(())
+    }
+
+    /// Pay out a completed split whose scheduled time has arrived
+    ///
+    /// Anyone can call this, so the payout doesn't wait on the creator.
+    /// Returns what was paid, like `execute_release`.
+    pub fn execute_scheduled_release(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let release_at =
+            storage::get_scheduled_release(&env, split_id).ok_or(Error::ActionNotFound)?;
+        if env.ledger().timestamp() < release_at {
+            return Err(Error::ActionNotReady);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        let paid = Self::release_funds_internal(&env, split_id, split)?;
+        storage::remove_scheduled_release(&env, split_id);
+        Ok(paid)
+    }
+
+    /// Get when a split is scheduled to release, if it is
+    pub fn get_scheduled_release(env: Env, split_id: u64) -> Option<u64> {
+        storage::get_scheduled_release(&env, split_id)
+    }
+
+    /// Get one page of the transfers that left escrow for a split, oldest
+    /// first
+    ///
+    /// Releases, partial releases, refunds, fees, bond returns and slashes,
+    /// insurance payouts and payout claims are all recorded. Pass-through
+    /// deposits go straight to the creator and never appear here.
+    /// Restricted splits return `AccessDenied`, like `get_split`.
+    pub fn get_transfer_history(
+        env: Env,
+        split_id: u64,
+        page: u32,
+    ) -> Result<Vec<TransferRecord>, Error> {
+        Self::viewable_split(&env, split_id, None)?;
+        Ok(storage::get_transfer_page(&env, split_id, page))
+    }
+
+    /// `get_transfer_history` for a member of a restricted split
+    pub fn get_transfer_history_as(
+        env: Env,
+        split_id: u64,
+        page: u32,
+        viewer: Address,
+    ) -> Result<Vec<TransferRecord>, Error> {
+        Self::viewable_split(&env, split_id, Some(&viewer))?;
+        Ok(storage::get_transfer_page(&env, split_id, page))
+    }
+
+    /// Get a split's pending release request, if any
+    pub fn get_release_request(env: Env, split_id: u64) -> Option<ReleaseRequest> {
+        storage::get_release_request(&env, split_id)
+    }
+
+    /// Propose how a split's payout is divided, for its confirmer to accept
+    ///
+    /// Only for splits created with a `confirmer`. The plan divides the net
+    /// escrow-token payout by basis points and replaces any earlier
+    /// proposal; deposits in other tokens still go to the creator.
+    pub fn propose_release(
+        env: Env,
+        split_id: u64,
+        payout_plan: Vec<PayoutShare>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        if storage::get_confirmer(&env, split_id).is_none() {
+            return Err(Error::UnsupportedMode);
+        }
+        Self::check_release(&env, &split)?;
+
+        if payout_plan.is_empty() || payout_plan.len() > MAX_PAYOUT_RECIPIENTS {
+            return Err(Error::InvalidParticipants);
+        }
+        let mut bps = [0u32; MAX_PAYOUT_RECIPIENTS as usize];
+        for (i, share) in payout_plan.iter().enumerate() {
+            bps[i] = share.bps;
+        }
+        let count = payout_plan.len() as usize;
+        let mut amounts = [0i128; MAX_PAYOUT_RECIPIENTS as usize];
+        shares::bps_shares(0, &bps[..count], &mut amounts[..count])?;
+
+        storage::set_release_proposal(
+            &env,
+            split_id,
+            &ReleaseProposal {
+                plan: payout_plan.clone(),
+                proposed_at: env.ledger().timestamp(),
+            },
+        );
+        events::emit_release_proposed(&env, split_id, &payout_plan);
+
+        Ok(())
+    }
+
+    /// Accept the creator's proposed payout plan and release the split
+    ///
+    /// Only the split's confirmer can call this, so the receiving side has
+    /// explicitly acknowledged where the money goes before any of it moves.
+    /// `expected_plan` is the plan they reviewed; if the creator replaced
+    /// the proposal since, nothing is paid.
+    pub fn confirm_release(
+        env: Env,
+        split_id: u64,
+        expected_plan: Vec<PayoutShare>,
+    ) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let confirmer = storage::get_confirmer(&env, split_id).ok_or(Error::UnsupportedMode)?;
+        confirmer.require_auth();
+        let proposal =
+            storage::get_release_proposal(&env, split_id).ok_or(Error::ActionNotFound)?;
+        if proposal.plan != expected_plan {
+            return Err(Error::SharesMismatch);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        events::emit_release_confirmed(&env, split_id, &confirmer);
+        let paid = Self::release_funds_internal(&env, split_id, split)?;
+        storage::remove_release_proposal(&env, split_id);
+        Ok(paid)
+    }
+
+    /// Get a split's payout plan waiting for confirmation, if any
+    pub fn get_release_proposal(env: Env, split_id: u64) -> Option<ReleaseProposal> {
+        storage::get_release_proposal(&env, split_id)
+    }
+
+    /// Have this split's creator paid in another asset
+    ///
+    /// I'm holding a split with a payout asset in Completed instead of
+    /// auto-releasing it from a deposit, so the swap only ever runs in an
+    /// explicit `release_funds` / `release_partial` call and a bad quote
+    /// fails that call rather than someone's deposit. `min_out` is the
+    /// least the creator accepts for the whole escrow-token payout.
+    pub fn set_payout_asset(
+        env: Env,
+        split_id: u64,
+        token: Address,
+        min_out: i128,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+
+        if min_out <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        // A swap pays the creator directly, so it can't feed a bucket
+        if storage::get_claimable_payout(&env, split_id).is_some() {
+            return Err(Error::InvalidConfig);
+        }
+
+        storage::set_payout_asset(&env, split_id, &PayoutAsset { token, min_out });
+        Ok(())
+    }
+
+    /// Leave this split's releases for `recipient` to claim
+    ///
+    /// I'm parking each release in a per-split bucket instead of pushing a
+    /// transfer, for payees that can't receive one yet. The recipient has
+    /// `window` seconds after each release to `claim_payout`; after that the
+    /// creator can `reclaim_payout`. Deposits held in other tokens are still
+    /// forwarded to the creator.
+    pub fn set_claimable_payout(
+        env: Env,
+        split_id: u64,
+        recipient: Address,
+        window: u64,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        storage::get_split(&env, split_id).creator.require_auth();
+
+        // Funds already waiting stay with the recipient they were parked for
+        if window == 0
+            || storage::get_payout_asset(&env, split_id).is_some()
+            || storage::get_payout_bucket(&env, split_id).is_some()
+        {
+            return Err(Error::InvalidConfig);
+        }
+
+        storage::set_claimable_payout(&env, split_id, &ClaimablePayout { recipient, window });
+        Ok(())
+    }
+
+    /// Collect released funds waiting in a split's payout bucket
+    pub fn claim_payout(env: Env, split_id: u64) -> Result<i128, Error> {
+        let bucket = storage::get_payout_bucket(&env, split_id).ok_or(Error::NoFundsAvailable)?;
+        bucket.recipient.require_auth();
+        if storage::get_pause_flags(&env).release {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(&env, split_id)?;
+
+        if env.ledger().timestamp() > bucket.expires_at {
+            return Err(Error::PayoutExpired);
+        }
+
+        Self::empty_payout_bucket(&env, split_id, &bucket, &bucket.recipient);
+        Ok(bucket.amount)
+    }
+
+    /// Return an expired, unclaimed payout bucket to the split creator
+    pub fn reclaim_payout(env: Env, split_id: u64) -> Result<i128, Error> {
+        let bucket = storage::get_payout_bucket(&env, split_id).ok_or(Error::NoFundsAvailable)?;
+        if storage::get_pause_flags(&env).release {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(&env, split_id)?;
+
+        if env.ledger().timestamp() <= bucket.expires_at {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        let creator = storage::get_split(&env, split_id).creator;
+        Self::empty_payout_bucket(&env, split_id, &bucket, &creator);
+        Ok(bucket.amount)
+    }
+
+    /// Get the released funds waiting to be claimed for a split, if any
+    pub fn get_payout_bucket(env: Env, split_id: u64) -> Option<PayoutBucket> {
+        storage::get_payout_bucket(&env, split_id)
+    }
+
+    /// Go back to paying the creator in the escrow token
+    pub fn clear_payout_asset(env: Env, split_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        storage::get_split(&env, split_id).creator.require_auth();
+        storage::remove_payout_asset(&env, split_id);
+        Ok(())
+    }
+
+    /// Release available funds to the creator for partial payments
+    ///
+    /// This is progressive release: a creator who fronted the bill can draw
+    /// whatever has been collected so far, as often as they like, and
+    /// `amount_released` keeps track of what's already gone.
+    pub fn release_partial(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        Self::check_partial_release(&env, &split)?;
+
+        Self::release_available(&env, &mut split)
+    }
+
+    /// Preview what `release_partial` would pay out right now
+    ///
+    /// `net` is what the creator can withdraw after fees. Runs the same
+    /// checks as `release_partial` and returns the same errors.
+    pub fn quote_partial_release(env: Env, split_id: u64) -> Result<ReleaseQuote, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        let amount = Self::check_partial_release(&env, &split)?;
+
+        let native = amount - Self::foreign_holdings(&env, &split).1;
+        let fee = if native > 0 {
+            let token_address = storage::get_token(&env);
+            fees::compute_release_fee(&env, &split, &token_address, native)?.part
+        } else {
+            0
+        };
+
+        Ok(ReleaseQuote {
+            amount,
+            fee,
+            net: native - fee,
+            status: split.status.clone(),
+        })
+    }
+
+    /// Record that a participant settled the rest of their share off-chain
+    ///
+    /// I'm treating the settlement like a pass-through payment: it counts
+    /// as collected and as already released to the creator, so escrow only
+    /// ever pays out tokens it actually holds and refunds never cover it.
+    /// Splits created with `confirm_offchain` also need the participant's
+    /// authorization. Returns the amount settled.
+    pub fn mark_settled_offchain(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        let confirmed = storage::requires_offchain_confirmation(&env, split_id);
+        if confirmed {
+            participant.require_auth();
+        }
+
+        let amount = split
+            .participants
+            .iter()
+            .find(|p| p.address == participant)
+            .map(|p| p.share_amount - p.amount_paid)
+            .ok_or(Error::ParticipantNotFound)?;
+        let index = Self::check_deposit(&env, &split, &participant, amount)?;
+
+        Self::apply_deposit(&env, &mut split, index, amount);
+        if split.mode == SplitMode::Escrow {
+            split.amount_released += amount;
+        }
+        stats::record_payment(&env, &split, &participant, amount);
+        storage::add_offchain_settled(&env, split_id, &participant, amount);
+        events::emit_settled_offchain(&env, split_id, &participant, amount, confirmed);
+
+        // One finishing path, so the split announces its completion once
+        Self::finish_if_funded(&env, split);
+
+        Ok(amount)
+    }
+
+    /// Forgive every unpaid share and complete the split with what came in
+    ///
+    /// I'm shrinking each unpaid share to what was actually paid and the
+    /// total to what was collected, recording the difference per
+    /// participant as forgiven. An escrow split then waits at Completed for
+    /// `release_funds`; one whose money already went to the creator is
+    /// done. Returns the total forgiven.
+    pub fn forgive_remaining(
+        env: Env,
+        split_id: u64,
+        expected_nonce: Option<u64>,
+    ) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        Self::check_nonce(&split, expected_nonce)?;
+
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active => {}
+        }
+        if storage::is_awaiting_creator(&env, split_id) || storage::is_joinable(&env, split_id) {
+            return Err(Error::ActionNotReady);
+        }
+        if storage::get_dispute(&env, split_id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+        if split.amount_collected <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let mut forgiven = 0;
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            let unpaid = p.share_amount - p.amount_paid;
+            if unpaid <= 0 {
+                continue;
+            }
+            storage::set_forgiven(&env, split_id, &p.address, unpaid);
+            p.share_amount = p.amount_paid;
+            p.has_paid = true;
+            split.participants.set(i, p);
+            forgiven += unpaid;
+        }
+        split.total_amount -= forgiven;
+        // A pending raise was negotiated against shares that no longer exist
+        storage::remove_total_increase(&env, split_id);
+        events::emit_remaining_forgiven(&env, split_id, forgiven, split.total_amount);
+
+        if split.mode != SplitMode::Iou && split.amount_collected == split.amount_released {
+            // Nothing left in escrow to release
+            Self::finish_without_escrow(&env, split);
+        } else {
+            lifecycle::transition(&env, &mut split, SplitStatus::Completed, Action::Complete);
+            storage::set_split(&env, split_id, &split);
+            storage::set_completed_at(&env, split_id, env.ledger().timestamp());
+            events::emit_escrow_completed(&env, split_id, split.total_amount);
+            hooks::notify_completed(&env, &split);
+        }
+
+        Ok(forgiven)
+    }
+
+    /// Absorb one defaulting participant's unpaid remainder
+    ///
+    /// I'm shrinking that participant's share to what they paid and the
+    /// total by the same amount, so the others can still complete the
+    /// split. The amount is recorded as forgiven on the participant. If
+    /// everyone else has already paid, the split finishes right away.
+    /// Returns the amount written off.
+    pub fn write_off(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        expected_nonce: Option<u64>,
+    ) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        Self::check_nonce(&split, expected_nonce)?;
+
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active => {}
+        }
+        if storage::is_awaiting_creator(&env, split_id) || storage::is_joinable(&env, split_id) {
+            return Err(Error::ActionNotReady);
+        }
+        if storage::get_dispute(&env, split_id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+
+        let index = split
+            .participants
+            .iter()
+            .position(|p| p.address == participant)
+            .ok_or(Error::ParticipantNotFound)? as u32;
+        let mut p = split.participants.get(index).unwrap();
+        let amount = p.share_amount - p.amount_paid;
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        // Someone has to still owe or have paid something
+        if split.total_amount - amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        p.share_amount = p.amount_paid;
+        p.has_paid = true;
+        split.participants.set(index, p);
+        split.total_amount -= amount;
+        storage::set_forgiven(
+            &env,
+            split_id,
+            &participant,
+            storage::get_forgiven(&env, split_id, &participant) + amount,
+        );
+        storage::remove_total_increase(&env, split_id);
+        events::emit_written_off(&env, split_id, &participant, amount);
+
+        Self::finish_if_funded(&env, split);
+
+        Ok(amount)
+    }
+
+    /// Let an unpaid participant's invitation lapse
+    ///
+    /// A participant accepts an invitation by paying into it. Once the
+    /// split's `invite_window` has passed since creation, anyone can lapse
+    /// the slot of a participant who hasn't paid anything. They can't
+    /// deposit after that, and the creator can `reassign_slot` or
+    /// `rebalance_slot`. Returns the lapsed share.
+    pub fn lapse_invitation(env: Env, split_id: u64, participant: Address) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        Self::check_slot_change(&env, &split)?;
+        let window = storage::get_invite_window(&env, split_id);
+        if window == 0 {
+            return Err(Error::UnsupportedMode);
+        }
+        if env.ledger().timestamp() < split.created_at + window {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        let p = split
+            .participants
+            .iter()
+            .find(|p| p.address == participant)
+            .ok_or(Error::ParticipantNotFound)?;
+        if p.amount_paid > 0 {
+            return Err(Error::HasDeposits);
+        }
+        if p.share_amount == 0 || storage::is_lapsed(&env, split_id, &participant) {
+            return Err(Error::ActionNotFound);
+        }
+
+        storage::set_lapsed(&env, split_id, &participant, true);
+        events::emit_invitation_lapsed(&env, split_id, &participant, p.share_amount);
+        Ok(p.share_amount)
+    }
+
+    /// Whether a participant's invitation on a split has lapsed
+    pub fn is_lapsed(env: Env, split_id: u64, participant: Address) -> bool {
+        storage::is_lapsed(&env, split_id, &participant)
+    }
+
+    /// Give a lapsed participant's slot to someone new
+    ///
+    /// The new address takes over the share exactly as it stood, with
+    /// nothing paid yet.
+    pub fn reassign_slot(env: Env, split_id: u64, from: Address, to: Address) -> Result<(), Error> {
+        let (mut split, index) = Self::lapsed_slot(&env, split_id, &from)?;
+        if split.participants.iter().any(|p| p.address == to) {
+            return Err(Error::InvalidParticipants);
+        }
+
+        let mut p = split.participants.get(index).unwrap();
+        Self::check_spending_cap(&env, &to, p.share_amount)?;
+        p.address = to.clone();
+        split.participants.set(index, p);
+        storage::set_split(&env, split_id, &split);
+        storage::set_lapsed(&env, split_id, &from, false);
+        storage::move_participant_state(&env, &split, &from, &to);
+        stats::record_joined(&env, &to);
+
+        events::emit_slot_reassigned(&env, split_id, &from, &to);
+        Ok(())
+    }
+
+    /// Spread a lapsed participant's share over everyone else
+    ///
+    /// I'm dividing it in proportion to the other shares, with stroops
+    /// left over by flooring going to the first of them, so the total
+    /// doesn't change. The lapsed participant stays listed with a share
+    /// of 0, and the call fails with `SpendingCapExceeded` if anyone's
+    /// larger share would pass their cap. Returns the amount spread.
+    pub fn rebalance_slot(env: Env, split_id: u64, participant: Address) -> Result<i128, Error> {
+        let (mut split, index) = Self::lapsed_slot(&env, split_id, &participant)?;
+        let mut lapsed = split.participants.get(index).unwrap();
+        let amount = lapsed.share_amount;
+
+        let base: i128 = split
+            .participants
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i as u32 != index)
+            .map(|(_, p)| p.share_amount)
+            .sum();
+        if base <= 0 {
+            return Err(Error::InvalidParticipants);
+        }
+
+        let before = split.participants.clone();
+        lapsed.share_amount = 0;
+        lapsed.has_paid = true;
+        split.participants.set(index, lapsed);
+        let mut left_over = amount;
+        let mut first = None;
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            if i == index || p.share_amount == 0 {
+                continue;
+            }
+            let cut = amount * p.share_amount / base;
+            left_over -= cut;
+            p.share_amount += cut;
+            p.has_paid = p.amount_paid >= p.share_amount;
+            split.participants.set(i, p);
+            first.get_or_insert(i);
+        }
+        if let Some(i) = first {
+            let mut p = split.participants.get(i).unwrap();
+            p.share_amount += left_over;
+            p.has_paid = p.amount_paid >= p.share_amount;
+            split.participants.set(i, p);
+        }
+
+        // Someone else lapsing can't push anyone past their spending cap;
+        // the creator listing themselves is their own call
+        for (p, was) in split.participants.iter().zip(before.iter()) {
+            let raised = p.share_amount - was.share_amount;
+            if raised > 0 && p.address != split.creator {
+                Self::check_spending_cap(&env, &p.address, raised)?;
+            }
+        }
+        storage::set_split(&env, split_id, &split);
+
+        events::emit_slot_rebalanced(&env, split_id, &participant, amount);
+        Ok(amount)
+    }
+
+    /// Get how much of a participant's share the creator forgave
+    pub fn get_forgiven(env: Env, split_id: u64, participant: Address) -> i128 {
+        storage::get_forgiven(&env, split_id, &participant)
+    }
+
+    /// Get how much of a participant's share was settled off-chain
+    pub fn get_offchain_settled(env: Env, split_id: u64, participant: Address) -> i128 {
+        storage::get_offchain_settled(&env, split_id, &participant)
+    }
+
+    /// Confirm an off-chain payment on an IOU split
+    ///
+    /// I'm requiring auth from both the creator (who was paid) and the
+    /// participant (who paid) so neither side can rewrite the record alone.
+    /// Once every share is confirmed the split moves to Completed.
+    pub fn mark_paid(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let mut split = storage::get_split(&env, split_id);
+
+        if split.mode != SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+        if storage::get_pause_flags(&env).deposit {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(&env, split_id)?;
+
+        split.creator.require_auth();
+        participant.require_auth();
+
+        match split.status {
+            SplitStatus::Pending | SplitStatus::Active => {}
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let mut found = false;
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            if p.address == participant {
+                if amount > p.share_amount - p.amount_paid {
+                    return Err(Error::InvalidAmount);
+                }
+                p.amount_paid += amount;
+                p.has_paid = p.amount_paid >= p.share_amount;
+                split.participants.set(i, p);
+                found = true;
+                break;
+            }
+        }
+
+        if !found {
+            return Err(Error::ParticipantNotFound);
+        }
+
+        split.amount_collected += amount;
+        let status = if Self::is_fully_funded_internal(&split) {
+            SplitStatus::Completed
+        } else {
+            SplitStatus::Active
+        };
+        lifecycle::transition(&env, &mut split, status, Action::Deposit);
+        storage::set_split(&env, split_id, &split);
+        stats::record_payment(&env, &split, &participant, amount);
+
+        events::emit_iou_paid(&env, split_id, &participant, amount);
+        if split.status == SplitStatus::Completed {
+            events::emit_escrow_completed(&env, split_id, split.total_amount);
+            hooks::notify_completed(&env, &split);
+        }
+
+        Ok(())
+    }
+
+    /// Queue a sensitive admin action behind the timelock
+    ///
+    /// I'm routing upgrades and fee changes through this queue so nothing
+    /// sensitive happens instantly; participants get the full delay to see
+    /// it coming (and leave) before it can be executed.
+    pub fn schedule_action(env: Env, action: AdminAction) -> u64 {
+        storage::get_admin(&env).require_auth();
+        Self::queue_action(&env, action)
+    }
+
+    /// Queue an admin action as a role holder
+    ///
+    /// A fee manager can queue fee changes this way; everything else still
+    /// needs the admin. The action waits out the same timelock.
+    pub fn schedule_action_as(
+        env: Env,
+        caller: Address,
+        action: AdminAction,
+    ) -> Result<u64, Error> {
+        Self::require_action_role(&env, &caller, &action)?;
+        Ok(Self::queue_action(&env, action))
+    }
+
+    /// Execute a queued admin action once its delay has elapsed
+    pub fn execute_action(env: Env, action_id: u64) -> Result<(), Error> {
+        storage::get_admin(&env).require_auth();
+        Self::run_action(&env, action_id)
+    }
+
+    /// Execute a queued admin action as a holder of the role it needs
+    pub fn execute_action_as(env: Env, caller: Address, action_id: u64) -> Result<(), Error> {
+        let scheduled =
+            storage::get_scheduled_action(&env, action_id).ok_or(Error::ActionNotFound)?;
+        Self::require_action_role(&env, &caller, &scheduled.action)?;
+        Self::run_action(&env, action_id)
+    }
+
+    /// Drop a queued admin action without executing it
+    pub fn cancel_action(env: Env, action_id: u64) -> Result<(), Error> {
+        storage::get_admin(&env).require_auth();
+        Self::drop_action(&env, action_id)
+    }
+
+    /// Drop a queued admin action as a holder of the role it needs
+    pub fn cancel_action_as(env: Env, caller: Address, action_id: u64) -> Result<(), Error> {
+        let scheduled =
+            storage::get_scheduled_action(&env, action_id).ok_or(Error::ActionNotFound)?;
+        Self::require_action_role(&env, &caller, &scheduled.action)?;
+        Self::drop_action(&env, action_id)
+    }
+
+    /// Hand a role to another key
+    ///
+    /// Applied immediately. Roles are narrow on purpose: a pauser can only
+    /// stop things, and a fee manager's changes still wait out the timelock.
+    pub fn grant_role(env: Env, role: Role, account: Address) {
+        storage::get_admin(&env).require_auth();
+        storage::set_role(&env, role, &account, true);
+        events::emit_role_set(&env, role, &account, true);
+    }
+
+    /// Take a role away from a key
+    pub fn revoke_role(env: Env, role: Role, account: Address) {
+        storage::get_admin(&env).require_auth();
+        storage::set_role(&env, role, &account, false);
+        events::emit_role_set(&env, role, &account, false);
+    }
+
+    /// Check whether an address holds a role (the admin holds them all)
+    pub fn has_role(env: Env, role: Role, account: Address) -> bool {
+        account == storage::get_admin(&env) || storage::has_role(&env, role, &account)
+    }
+
+    fn queue_action(env: &Env, action: AdminAction) -> u64 {
+        let scheduled = ScheduledAction {
+            id: storage::get_next_action_id(env),
+            action,
+            ready_at: env.ledger().timestamp() + storage::get_timelock_delay(env),
+        };
+        storage::set_scheduled_action(env, &scheduled);
+
+        events::emit_action_scheduled(env, &scheduled);
+
+        scheduled.id
+    }
+
+    fn run_action(env: &Env, action_id: u64) -> Result<(), Error> {
+        let scheduled =
+            storage::get_scheduled_action(env, action_id).ok_or(Error::ActionNotFound)?;
+        if env.ledger().timestamp() < scheduled.ready_at {
+            return Err(Error::ActionNotReady);
+        }
+
+        storage::remove_scheduled_action(env, action_id);
+        events::emit_action_executed(env, action_id);
+
+        match scheduled.action {
+            AdminAction::Upgrade(wasm_hash) => {
+                env.deployer().update_current_contract_wasm(wasm_hash);
+            }
+            AdminAction::SetFeeModule(config) => {
+                storage::set_fee_config(env, &config);
+                events::emit_fee_module_set(env, &config.module, &config.recipient);
+            }
+            AdminAction::RemoveFeeModule => {
+                storage::remove_fee_config(env);
+            }
+            AdminAction::SetFeeSchedule(schedule) => {
+                fees::validate_schedule(&schedule)?;
+                storage::set_fee_schedule(env, &schedule);
+                events::emit_fee_schedule_set(env, &schedule);
+            }
+            AdminAction::RemoveFeeSchedule => {
+                storage::remove_fee_schedule(env);
+            }
+            AdminAction::SetExpiryBounty(bounty) => {
+                if bounty < 0 {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_expiry_bounty(env, bounty);
+            }
+            AdminAction::SetLatePenalty(penalty) => {
+                if penalty.max_bps > shares::BPS_DENOMINATOR as u32
+                    || (penalty.max_bps > 0 && penalty.bps_per_day == 0)
+                {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_late_penalty(env, &penalty);
+            }
+            AdminAction::SetAbandonTimeout(timeout) => {
+                if timeout != 0 && timeout < MIN_ABANDON_TIMEOUT {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_abandon_timeout(env, timeout);
+            }
+            AdminAction::SetFeeOverride(fee_override) => {
+                if fee_override.bps > shares::BPS_DENOMINATOR as u32 {
+                    return Err(Error::InvalidConfig);
+                }
+                // Fees need somewhere to go
+                if fee_override.bps > 0 && fees::fee_recipient(env).is_none() {
+                    return Err(Error::InvalidConfig);
+                }
+                // A split's fee can only change while there's a release left
+                if let FeeTarget::Split(split_id) = fee_override.target {
+                    if !storage::has_split(env, split_id) {
+                        return Err(Error::SplitNotFound);
+                    }
+                    if storage::get_split(env, split_id).status == SplitStatus::Released {
+                        return Err(Error::SplitReleased);
+                    }
+                }
+                storage::set_fee_override(env, &fee_override.target, Some(fee_override.bps));
+                events::emit_fee_override(env, &fee_override.target, Some(fee_override.bps));
+            }
+            AdminAction::RemoveFeeOverride(target) => {
+                storage::set_fee_override(env, &target, None);
+                events::emit_fee_override(env, &target, None);
+            }
+            AdminAction::SetCancelQuorum(bps) => {
+                if bps > shares::BPS_DENOMINATOR as u32 {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_cancel_quorum(env, bps);
+            }
+            AdminAction::AddFeeExemption(exemption) => {
+                storage::set_fee_exempt(env, &exemption, true);
+                events::emit_fee_exemption(env, &exemption, true);
+            }
+            AdminAction::RemoveFeeExemption(exemption) => {
+                storage::set_fee_exempt(env, &exemption, false);
+                events::emit_fee_exemption(env, &exemption, false);
+            }
+            AdminAction::SetTimelockDelay(delay) => {
+                // Otherwise one scheduled change could switch the timelock off
+                if delay < MIN_TIMELOCK_DELAY {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_timelock_delay(env, delay);
+            }
+            AdminAction::SetAdmin(new_admin) => {
+                storage::set_admin(env, &new_admin);
+            }
+            AdminAction::SetRoundingPolicy(policy) => {
+                storage::set_rounding_policy(env, policy);
+            }
+            AdminAction::SetOracle(oracle) => {
+                storage::set_oracle(env, &oracle);
+            }
+            AdminAction::AcceptToken(token) => {
+                storage::set_token_accepted(env, &token, true);
+            }
+            AdminAction::RemoveToken(token) => {
+                storage::set_token_accepted(env, &token, false);
+            }
+            AdminAction::SetSwapRouter(router) => {
+                storage::set_swap_router(env, &router);
+            }
+            AdminAction::SetArbiter(arbiter) => {
+                storage::set_arbiter(env, &arbiter);
+            }
+            AdminAction::SetInsurance(config) => {
+                if config.premium_bps as i128 > shares::BPS_DENOMINATOR || config.max_claim < 0 {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_insurance_config(env, &config);
+            }
+            AdminAction::SetMaxOpenSplits(max) => {
+                storage::set_max_open_splits(env, max);
+            }
+            AdminAction::SetActivationWindow(window) => {
+                storage::set_activation_window(env, window);
+            }
+            AdminAction::AddOperator(operator) => {
+                storage::set_operator(env, &operator, true);
+            }
+            AdminAction::RemoveOperator(operator) => {
+                storage::set_operator(env, &operator, false);
+            }
+            AdminAction::AddDepositor(invoker) => {
+                storage::set_depositor(env, &invoker, true);
+            }
+            AdminAction::RemoveDepositor(invoker) => {
+                storage::set_depositor(env, &invoker, false);
+            }
+            AdminAction::SetShareToken(companion) => {
+                storage::set_share_token(env, &companion);
+            }
+            AdminAction::SetRentConfig(config) => {
+                if config.keeper_fee <= 0 {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_rent_config(env, &config);
+            }
+            AdminAction::SetBreaker(config) => {
+                if config.max_volume < 0 || config.large_release < 0 {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_breaker_config(env, &config);
+            }
+            AdminAction::SetMinDeposit(min) => {
+                let valid = match min {
+                    MinDeposit::None => true,
+                    MinDeposit::Absolute(amount) => amount > 0,
+                    MinDeposit::ShareBps(bps) => bps > 0 && bps <= 10_000,
+                };
+                if !valid {
+                    return Err(Error::InvalidConfig);
+                }
+                storage::set_min_deposit(env, &min);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn drop_action(env: &Env, action_id: u64) -> Result<(), Error> {
+        if storage::get_scheduled_action(env, action_id).is_none() {
+            return Err(Error::ActionNotFound);
+        }
+
+        storage::remove_scheduled_action(env, action_id);
+        events::emit_action_cancelled(env, action_id);
+
+        Ok(())
+    }
+
+    /// The role that may queue, execute or cancel an action besides the admin
+    fn action_role(action: &AdminAction) -> Option<Role> {
+        match action {
+            AdminAction::SetFeeModule(_)
+            | AdminAction::RemoveFeeModule
+            | AdminAction::SetFeeSchedule(_)
+            | AdminAction::RemoveFeeSchedule
+            | AdminAction::AddFeeExemption(_)
+            | AdminAction::RemoveFeeExemption(_)
+            | AdminAction::SetFeeOverride(_)
+            | AdminAction::RemoveFeeOverride(_) => Some(Role::FeeManager),
+            _ => None,
+        }
+    }
+
+    fn require_action_role(env: &Env, caller: &Address, action: &AdminAction) -> Result<(), Error> {
+        caller.require_auth();
+        if *caller == storage::get_admin(env) {
+            return Ok(());
+        }
+        match Self::action_role(action) {
+            Some(role) if storage::has_role(env, role, caller) => Ok(()),
+            _ => Err(Error::AccessDenied),
+        }
+    }
+
+    /// Require `caller` to be allowed to apply, or when `lifting` take off,
+    /// a brake
+    ///
+    /// A pauser key may live in a hot wallet, so it can only stop things;
+    /// starting them again stays with the admin.
+    fn require_brake_role(env: &Env, caller: &Address, lifting: bool) -> Result<(), Error> {
+        if !lifting {
+            return Self::require_role(env, caller, Role::Pauser);
+        }
+        caller.require_auth();
+        if *caller != storage::get_admin(env) {
+            return Err(Error::AccessDenied);
+        }
+        Ok(())
+    }
+
+    /// Require `caller` to be the admin or to hold `role`
+    fn require_role(env: &Env, caller: &Address, role: Role) -> Result<(), Error> {
+        caller.require_auth();
+        if *caller == storage::get_admin(env) || storage::has_role(env, role, caller) {
+            Ok(())
+        } else {
+            Err(Error::AccessDenied)
+        }
+    }
+
+    /// Get a queued admin action
+    pub fn get_scheduled_action(env: Env, action_id: u64) -> Option<ScheduledAction> {
+        storage::get_scheduled_action(&env, action_id)
+    }
+
+    /// Get the current timelock delay in seconds
+    pub fn get_timelock_delay(env: Env) -> u64 {
+        storage::get_timelock_delay(&env)
+    }
+
+    /// Get how much of a token the contract holds on behalf of splits
+    ///
+    /// I'm maintaining this on every deposit and payout rather than
+    /// summing splits, so operators can reconcile it against the contract's
+    /// token balance in one call.
+    pub fn get_total_escrowed(env: Env, token: Address) -> i128 {
+        storage::get_total_escrowed(&env, &token)
+    }
+
+    /// Get the registered fee module and recipient, if any
+    pub fn get_fee_module(env: Env) -> Option<FeeConfig> {
+        storage::get_fee_config(&env)
+    }
+
+    /// Get the tiered fee schedule, if any
+    pub fn get_fee_schedule(env: Env) -> Option<FeeSchedule> {
+        storage::get_fee_schedule(&env)
+    }
+
+    /// Whether a creator or token is exempt from release fees
+    pub fn is_fee_exempt(env: Env, exemption: FeeExemption) -> bool {
+        storage::is_fee_exempt(&env, &exemption)
+    }
+
+    /// Get the fee rate overriding the usual fees for a split or creator
+    pub fn get_fee_override(env: Env, target: FeeTarget) -> Option<u32> {
+        storage::get_fee_override(&env, &target)
+    }
+
+    /// Get the rate, in basis points, the schedule charges a split of `total`
+    ///
+    /// A registered fee module overrides the schedule, so this is only what
+    /// releases pay while none is registered.
+    pub fn get_fee_bps(env: Env, total: i128) -> u32 {
+        storage::get_fee_schedule(&env).map_or(0, |s| fees::tier_bps(&s, total))
+    }
+
+    /// Pause or unpause new splits and deposits
+    ///
+    /// I'm applying this immediately rather than through the timelock, since
+    /// a pause is an emergency brake. Releases and cancels keep working so
+    /// funds already in escrow are never stuck. `caller` is the admin or a
+    /// pauser, but only the admin can unpause.
+    pub fn set_paused(env: Env, caller: Address, paused: bool) -> Result<(), Error> {
+        Self::require_brake_role(&env, &caller, !paused)?;
+        storage::set_paused(&env, paused);
+        events::emit_paused(&env, paused);
+        Ok(())
+    }
+
+    /// Pause or resume creation, deposits, releases and refunds separately
+    ///
+    /// Applied immediately, like `set_paused`, and that includes pausing
+    /// releases: skipping the timelock is deliberate, since a brake that
+    /// waits out the delay can't stop an incident. Refunding can be stopped
+    /// here too, so an incident response can freeze funds in place or leave
+    /// only the exits open. A pauser can only set flags; clearing any of
+    /// them takes the admin.
+    pub fn set_pause_flags(env: Env, caller: Address, flags: PauseFlags) -> Result<(), Error> {
+        let current = storage::get_stored_pause_flags(&env);
+        let lifting = (current.create && !flags.create)
+            || (current.deposit && !flags.deposit)
+            || (current.release && !flags.release)
+            || (current.refund && !flags.refund);
+        Self::require_brake_role(&env, &caller, lifting)?;
+        storage::set_pause_flags(&env, &flags);
+        events::emit_pause_flags(&env, &flags);
+        Ok(())
+    }
+
+    /// Get the pause switches in effect, including the blanket pause
+    pub fn get_pause_flags(env: Env) -> PauseFlags {
+        storage::get_pause_flags(&env)
+    }
+
+    /// Clear a tripped circuit breaker and start counting again
+    ///
+    /// Applied immediately, so the admin can reopen as soon as they've
+    /// looked at what tripped it.
+    pub fn reset_breaker(env: Env) {
+        storage::get_admin(&env).require_auth();
+        breaker::reset(&env);
+        events::emit_breaker_reset(&env);
+    }
+
+    /// Get the circuit breaker's caps
+    pub fn get_breaker_config(env: Env) -> Option<BreakerConfig> {
+        storage::get_breaker_config(&env)
+    }
+
+    /// Get what the circuit breaker has counted and whether it's tripped
+    pub fn get_breaker_state(env: Env) -> BreakerState {
+        storage::get_breaker_state(&env)
+    }
+
+    /// Declare or lift an emergency
+    ///
+    /// This is for a critical bug. While the emergency lasts every pause
+    /// switch is on, bonds, insurance and dispute rulings are frozen too, no
+    /// split changes shape, and the only way to move money is
+    /// `emergency_withdraw`.
+    pub fn set_emergency(env: Env, active: bool) {
+        storage::get_admin(&env).require_auth();
+        storage::set_emergency(&env, active);
+        events::emit_emergency(&env, active);
+    }
+
+    /// Check whether an emergency is in effect
+    pub fn is_emergency(env: Env) -> bool {
+        storage::is_emergency(&env)
+    }
+
+    /// Halt one suspicious split while it's investigated
+    ///
+    /// A frozen split takes no deposits and pays nothing out, but nothing
+    /// about it changes, so `unfreeze_split` picks up exactly where it
+    /// stopped. Deposits, releases and anything that would change the split
+    /// return `ContractPaused`. `caller` is the admin or a pauser.
+    pub fn freeze_split(env: Env, caller: Address, split_id: u64) -> Result<(), Error> {
+        Self::set_split_frozen(&env, &caller, split_id, true)
+    }
+
+    /// Let a frozen split take deposits and pay out again
+    ///
+    /// Admin only, like every other way of lifting a brake.
+    pub fn unfreeze_split(env: Env, caller: Address, split_id: u64) -> Result<(), Error> {
+        Self::set_split_frozen(&env, &caller, split_id, false)
+    }
+
+    /// Check whether the admin froze a split
+    pub fn is_frozen(env: Env, split_id: u64) -> bool {
+        storage::is_frozen(&env, split_id)
+    }
+
+    /// Hand a participant back everything they have in escrow on a split
+    ///
+    /// Only works during an emergency, and works whatever state the split
+    /// is in. Anyone can call it, since the money only ever goes to the
+    /// participant who deposited it. I'm paying out the recorded held
+    /// balance, the same amount `claim_refund` would pay. A split that wasn't
+    /// finished yet is cancelled, so once the emergency is lifted the usual
+    /// refunds take over.
+    pub fn emergency_withdraw(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<i128, Error> {
+        if !storage::is_emergency(&env) {
+            return Err(Error::ActionNotReady);
+        }
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let mut split = storage::get_split(&env, split_id);
+        if matches!(
+            split.status,
+            SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed
+        ) {
+            lifecycle::transition(&env, &mut split, SplitStatus::Cancelled, Action::Cancel);
+            Self::record_cancel(&env, split_id, &String::from_str(&env, "emergency"));
+        }
+        Self::refund_held(&env, split, &participant)
+    }
+
+    /// Refund everyone on a completed split its creator never released
+    ///
+    /// This is the dead-man switch for a creator who lost their keys or
+    /// disappeared. Once a split has sat at Completed for the admin's
+    /// abandon timeout, anyone can call this. The split is only cancelled
+    /// here: participants get their held balances back through
+    /// `claim_refund` or the `process_refunds` cursor, so a large split
+    /// never has to pay everyone in one transaction. Returns the total held
+    /// in the escrow token, which is now refundable.
+    pub fn refund_abandoned(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if storage::get_pause_flags(&env).refund {
+            return Err(Error::ContractPaused);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        // A settled IOU split ends at Completed too, but never held a token
+        if split.mode == SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+        if split.status != SplitStatus::Completed {
+            return Err(Error::NotRefundable);
+        }
+        let mut held = 0;
+        let mut holds_funds = false;
+        for p in split.participants.iter() {
+            let balance = storage::get_held_balance(&env, split_id, &p.address);
+            held += balance;
+            holds_funds |=
+                balance > 0 || !storage::get_foreign_held(&env, split_id, &p.address).is_empty();
+        }
+        if !holds_funds {
+            return Err(Error::NoFundsAvailable);
+        }
+        if storage::get_dispute(&env, split_id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+        let timeout = storage::get_abandon_timeout(&env);
+        // A split waiting on its schedule isn't abandoned until it's due
+        let completed_at = storage::get_completed_at(&env, split_id)
+            .map(|at| at.max(storage::get_scheduled_release(&env, split_id).unwrap_or(0)));
+        match completed_at {
+            Some(at) if timeout > 0 && env.ledger().timestamp() >= at + timeout => {}
+            _ => return Err(Error::ActionNotReady),
+        }
+
+        lifecycle::transition(&env, &mut split, SplitStatus::Cancelled, Action::Cancel);
+        storage::set_split(&env, split_id, &split);
+        Self::record_cancel(&env, split_id, &String::from_str(&env, "abandoned"));
+
+        events::emit_split_abandoned(&env, split_id, held);
+
+        Ok(held)
+    }
+
+    /// Bring a split over from an older deployment, keeping its ID
+    ///
+    /// Only the admin can import, and only into an unused ID, so live data
+    /// can't be overwritten. The record has to add up: shares sum to the
+    /// total and payments to `amount_collected`. Money still in escrow is
+    /// taken from the admin and held for each participant as they paid it,
+    /// so a split that has released part of its funds can't be imported
+    /// with a balance. New split IDs continue after the imported one.
+    pub fn import_split(env: Env, split: Split) -> Result<(), Error> {
+        let admin = storage::get_admin(&env);
+        admin.require_auth();
+
+        if split.id == 0 || storage::has_split(&env, split.id) {
+            return Err(Error::InvalidConfig);
+        }
+        Self::check_imported(&split)?;
+        let held = split.amount_collected - split.amount_released;
+        if held > 0 && (split.mode != SplitMode::Escrow || split.amount_released > 0) {
+            return Err(Error::UnsupportedMode);
+        }
+
+        // Payment flags and the edit counter describe this deployment's
+        // record, so they're derived rather than taken from the input
+        let mut split = split;
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            p.has_paid = p.amount_paid >= p.share_amount;
+            split.participants.set(i, p);
+        }
+        split.nonce = 0;
+
+        let split_id = split.id;
+        storage::reserve_split_id(&env, split_id);
+        storage::set_split(&env, split_id, &split);
+        let (_, extend_to) = storage::get_ttl(&env);
+        storage::set_live_until(&env, split_id, env.ledger().sequence() + extend_to);
+        storage::set_ref_code(&env, split_id, &refcode::encode(&env, split_id));
+        storage::set_split_display(
+            &env,
+            split_id,
+            &SplitDisplay {
+                decimals: token::Client::new(&env, &storage::get_token(&env)).decimals(),
+                symbol: None,
+            },
+        );
+        storage::add_to_creator_index(&env, &split.creator, split_id);
+        storage::add_to_day_index(&env, &split.creator, split.created_at, split_id);
+        storage::add_to_category_index(&env, &split.creator, split.category, split_id);
+        for p in split.participants.iter() {
+            storage::add_to_participant_index(&env, &p.address, split_id);
+        }
+        for tag in split.tags.iter() {
+            storage::add_to_tag_index(&env, &split.creator, &tag, split_id);
+        }
+
+        if held > 0 {
+            let token_address = storage::get_token(&env);
+            for p in split.participants.iter() {
+                if p.amount_paid > 0 {
+                    storage::add_held_balance(&env, split_id, &p.address, p.amount_paid);
+                }
+            }
+            storage::add_total_escrowed(&env, &token_address, held);
+            token::Client::new(&env, &token_address).transfer(
+                &admin,
+                &env.current_contract_address(),
+                &held,
+            );
+        }
+        events::emit_split_imported(&env, split_id, &split.creator, held);
+        let terms_hash = Self::commit_terms(&env, &split);
+        events::emit_terms_hash(&env, split_id, &terms_hash);
+
+        Ok(())
+    }
+
+    /// Tune how long storage entries live, in ledgers
+    ///
+    /// Every entry the contract touches is extended to `extend_to` once its
+    /// remaining TTL drops below `threshold`. Shorter TTLs cost less rent
+    /// but need more traffic (or manual bumps) to keep splits alive. The
+    /// threshold must be at least `MIN_TTL_THRESHOLD` and below `extend_to`,
+    /// which can't exceed `MAX_TTL_EXTEND_TO`. Entries keep the TTL they
+    /// have until they're next touched.
+    pub fn set_ttl(env: Env, threshold: u32, extend_to: u32) -> Result<(), Error> {
+        storage::get_admin(&env).require_auth();
+
+        if threshold < MIN_TTL_THRESHOLD || threshold >= extend_to || extend_to > MAX_TTL_EXTEND_TO
+        {
+            return Err(Error::InvalidConfig);
+        }
+        storage::set_ttl(&env, threshold, extend_to);
+        events::emit_ttl_set(&env, threshold, extend_to);
+
+        Ok(())
+    }
+
+    /// Get the admin, pause flag and storage TTL parameters
+    pub fn get_config(env: Env) -> ContractConfig {
+        let (ttl_threshold, ttl_extend_to) = storage::get_ttl(&env);
+        ContractConfig {
+            admin: storage::get_admin(&env),
+            is_paused: storage::is_paused(&env),
+            ttl_threshold,
+            ttl_extend_to,
+        }
+    }
+
+    /// Describe this deployment in a single call
+    pub fn get_info(env: Env) -> ContractInfo {
+        let fee_config = storage::get_fee_config(&env);
+        ContractInfo {
+            version: String::from_str(&env, CONTRACT_VERSION),
+            admin: storage::get_admin(&env),
+            token: storage::get_token(&env),
+            paused: storage::is_paused(&env),
+            fee_module: fee_config.clone().map(|c| c.module),
+            fee_recipient: fee_config.map(|c| c.recipient),
+            rounding_policy: storage::get_rounding_policy(&env),
+            timelock_delay: storage::get_timelock_delay(&env),
+        }
+    }
+
+    /// Preview what a deposit would do without making it
+    ///
+    /// I'm running the same checks and state changes as `deposit` against
+    /// an in-memory copy, including the auto-release a completing deposit
+    /// triggers, so wallets can show the outcome before asking for a
+    /// signature.
+    pub fn quote_deposit(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        amount: i128,
+    ) -> Result<DepositQuote, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        let index = Self::check_deposit(&env, &split, &participant, amount)?;
+        Self::apply_deposit(&env, &mut split, index, amount);
+
+        let p = split.participants.get(index).unwrap();
+        let mut quote = DepositQuote {
+            accepted: amount,
+            round_up: Self::round_up_extra(&env, split_id, amount),
+            remaining_share: p.share_amount - p.amount_paid,
+            status: split.status.clone(),
+            released: 0,
+            fee: 0,
+        };
+
+        if split.mode == SplitMode::PassThrough {
+            quote.released = amount;
+        } else if !Self::is_fully_funded_internal(&split) {
+            if Self::drips(&env, &split) {
+                let available = split.amount_collected - split.amount_released;
+                let native = available - Self::foreign_holdings(&env, &split).1;
+                quote.released = available;
+                if native > 0 {
+                    let token_address = storage::get_token(&env);
+                    quote.fee =
+                        fees::compute_release_fee(&env, &split, &token_address, native)?.part;
+                }
+            }
+        } else if Self::holds_at_completion(&env, &split) {
+            quote.status = SplitStatus::Completed;
+        } else {
+            // A failed auto-release leaves the deposit in place, just unreleased
+            if let Ok(release) = Self::quote_release_internal(&env, &split) {
+                quote.status = release.status;
+                quote.released = release.amount;
+                quote.fee = release.fee;
+            }
+        }
+
+        Ok(quote)
+    }
+
+    /// Preview what `release_funds` would pay out without releasing
+    pub fn quote_release(env: Env, split_id: u64) -> Result<ReleaseQuote, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        Self::quote_release_internal(&env, &split)
+    }
+
+    /// List every transfer a release of the split would make
+    ///
+    /// Runs the same checks as `release_funds` and returns the same errors.
+    /// On success the transfers follow `pay_out` step by step: the fee, the
+    /// payout plan or the creator's payout, then deposits in other tokens.
+    /// On a delayed or confirmed split they're what the release pays once
+    /// it goes through.
+    pub fn preflight_release(env: Env, split_id: u64) -> Result<ReleasePreflight, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        let amount = Self::check_release(&env, &split)?;
+
+        let now = env.ledger().timestamp();
+        let mut transfers = Vec::new(&env);
+        let mut push = |kind: TransferKind, to: &Address, token: &Address, amount: i128| {
+            if amount > 0 {
+                transfers.push_back(TransferRecord {
+                    kind,
+                    to: to.clone(),
+                    token: token.clone(),
+                    amount,
+                    timestamp: now,
+                });
+            }
+        };
+
+        let token_address = storage::get_token(&env);
+        let (foreign, foreign_credit) = Self::foreign_holdings(&env, &split);
+        let native = amount - foreign_credit;
+        let penalties = Self::pending_penalties(&env, &split, native);
+        let mut penalty = 0;
+        for (participant, back) in penalties.iter() {
+            push(TransferKind::Penalty, &participant, &token_address, back);
+            penalty += back;
+        }
+        let fee_cut = if native - penalty > 0 {
+            fees::compute_release_fee(&env, &split, &token_address, native - penalty)?
+        } else {
+            Apportioned::default()
+        };
+        let net = fee_cut.rest;
+        let fee_recipient = fees::fee_recipient(&env);
+        if let Some(recipient) = &fee_recipient {
+            push(TransferKind::Fee, recipient, &token_address, fee_cut.part);
+        }
+        push(
+            TransferKind::Release,
+            &split.creator,
+            &token_address,
+            fee_cut.dust,
+        );
+
+        let mut retained = 0;
+        if let Some(proposal) = storage::get_release_proposal(&env, split_id) {
+            let count = proposal.plan.len() as usize;
+            let mut bps = [0u32; MAX_PAYOUT_RECIPIENTS as usize];
+            for (i, share) in proposal.plan.iter().enumerate() {
+                bps[i] = share.bps;
+            }
+            let mut amounts = [0i128; MAX_PAYOUT_RECIPIENTS as usize];
+            shares::bps_shares(net, &bps[..count], &mut amounts[..count])?;
+            for (i, share) in proposal.plan.iter().enumerate() {
+                push(
+                    TransferKind::Release,
+                    &share.recipient,
+                    &token_address,
+                    amounts[i],
+                );
+            }
+        } else if storage::get_payout_asset(&env, split_id).is_none()
+            && storage::get_claimable_payout(&env, split_id).is_some()
+        {
+            retained = net;
+        } else {
+            push(TransferKind::Release, &split.creator, &token_address, net);
+        }
+
+        for (token, held) in foreign.iter() {
+            let token_fee = fees::compute_release_fee(&env, &split, &token, held)?.part;
+            if let Some(recipient) = &fee_recipient {
+                push(TransferKind::Fee, recipient, &token, token_fee);
+            }
+            push(
+                TransferKind::Release,
+                &split.creator,
+                &token,
+                held - token_fee,
+            );
+        }
+        let bond = storage::get_bond(&env, split_id);
+        push(
+            TransferKind::BondReturn,
+            &split.creator,
+            &token_address,
+            bond,
+        );
+
+        Ok(ReleasePreflight {
+            transfers,
+            retained,
+            status: SplitStatus::Released,
+        })
+    }
+
+    /// Withdraw a participant's escrowed deposits from a dead split
+    ///
+    /// I'm refunding from the participant's own held balance rather than
+    /// recomputing it from `amount_paid`, so a refund can never pay out more
+    /// than that participant actually put in.
+    pub fn claim_refund(env: Env, split_id: u64, participant: Address) -> Result<i128, Error> {
+        participant.require_auth();
+        Self::claim_refund_internal(&env, split_id, &participant)
+    }
+
+    /// Push refunds to the next `max_n` participants of a dead split
+    ///
+    /// Claiming one by one is fine for a dinner, but a 200-person split
+    /// can't be refunded in a single transaction. I'm keeping a cursor per
+    /// split, so anyone can call this again and again until every
+    /// participant has been visited; those with nothing held (because they
+    /// already claimed) are skipped. Returns how many participants are
+    /// still left, so 0 means done.
+    pub fn process_refunds(env: Env, split_id: u64, max_n: u32) -> Result<u32, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if max_n == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if storage::get_pause_flags(&env).refund {
+            return Err(Error::ContractPaused);
+        }
+        let split = storage::get_split(&env, split_id);
+        if split.status != SplitStatus::Cancelled && split.status != SplitStatus::Expired {
+            return Err(Error::NotRefundable);
+        }
+
+        let count = split.participants.len();
+        let start = storage::get_refund_cursor(&env, split_id);
+        if start >= count {
+            return Ok(0);
+        }
+        let end = start.saturating_add(max_n).min(count);
+
+        let mut refunded = 0;
+        for i in start..end {
+            let participant = split.participants.get(i).unwrap().address;
+            let current = storage::get_split(&env, split_id);
+            if let Ok(amount) = Self::refund_held(&env, current, &participant) {
+                refunded += amount;
+            }
+        }
+        storage::set_refund_cursor(&env, split_id, end);
+        events::emit_refunds_processed(&env, split_id, end, refunded);
+
+        Ok(count - end)
+    }
+
+    /// Get how many participants `process_refunds` has visited on a split
+    pub fn get_refund_cursor(env: Env, split_id: u64) -> u32 {
+        storage::get_refund_cursor(&env, split_id)
+    }
+
+    /// Pay a participant's held balances on a split back to them
+    fn refund_held(env: &Env, mut split: Split, participant: &Address) -> Result<i128, Error> {
+        let split_id = split.id;
+        let amount = storage::take_held_balance(env, split_id, participant);
+        let foreign = storage::take_foreign_held(env, split_id, participant);
+        if amount <= 0 && foreign.is_empty() {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let credited = amount + foreign.iter().map(|d| d.credited).sum::<i128>();
+        split.amount_collected -= credited;
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            if p.address == *participant {
+                p.amount_paid -= credited;
+                p.has_paid = false;
+                split.participants.set(i, p);
+            }
+        }
+        storage::set_split(env, split_id, &split);
+        // Round-ups only go to charity on release, so they come back too
+        let refunded = amount + storage::take_round_up_held(env, split_id, participant);
+        let token_address = storage::get_token(env);
+        if refunded > 0 {
+            storage::add_total_escrowed(env, &token_address, -refunded);
+        }
+        for d in foreign.iter() {
+            storage::add_total_escrowed(env, &d.token, -d.amount);
+        }
+
+        // The books are settled; only now do the tokens move
+        let contract_address = env.current_contract_address();
+        let refund_to = Self::refund_recipient(env, participant);
+        if refunded > 0 {
+            token::Client::new(env, &token_address).transfer(
+                &contract_address,
+                &refund_to,
+                &refunded,
+            );
+            Self::log_transfer(
+                env,
+                split_id,
+                TransferKind::Refund,
+                &refund_to,
+                &token_address,
+                refunded,
+            );
+            events::emit_refund_processed(env, split_id, participant, refunded);
+        }
+        for d in foreign.iter() {
+            token::Client::new(env, &d.token).transfer(&contract_address, &refund_to, &d.amount);
+            Self::log_transfer(
+                env,
+                split_id,
+                TransferKind::Refund,
+                &refund_to,
+                &d.token,
+                d.amount,
+            );
+            events::emit_foreign_payout(env, split_id, participant, &d.token, d.amount, 0);
+        }
+        Self::recheck_split(env, &split);
+
+        Ok(refunded)
+    }
+
+    /// Pay a split's retainage to its creator once the window has passed
+    ///
+    /// Each release restarts the window. An open dispute holds the
+    /// retainage until the arbiter rules. Returns the amount claimed.
+    pub fn claim_retainage(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        if storage::get_pause_flags(&env).release {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(&env, split_id)?;
+
+        let mut retainage =
+            storage::get_retainage(&env, split_id).ok_or(Error::NoFundsAvailable)?;
+        if retainage.held <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+        if storage::get_dispute(&env, split_id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+        if env.ledger().timestamp() < retainage.claimable_at {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        let amount = retainage.held;
+        retainage.held = 0;
+        storage::set_retainage(&env, split_id, &retainage);
+        let token_address = storage::get_token(&env);
+        storage::add_total_escrowed(&env, &token_address, -amount);
+
+        token::Client::new(&env, &token_address).transfer(
+            &env.current_contract_address(),
+            &split.creator,
+            &amount,
+        );
+        Self::log_transfer(
+            &env,
+            split_id,
+            TransferKind::Claim,
+            &split.creator,
+            &token_address,
+            amount,
+        );
+        events::emit_retainage_claimed(&env, split_id, &split.creator, amount);
+        Ok(amount)
+    }
+
+    /// Get a split's retainage settings and what it's holding back
+    pub fn get_retainage(env: Env, split_id: u64) -> Option<Retainage> {
+        storage::get_retainage(&env, split_id)
+    }
+
+    /// Give a creator their bond back from a cancelled or expired split
+    ///
+    /// Released splits return the bond automatically; this covers splits
+    /// that never got that far. An open dispute keeps the bond locked.
+    pub fn reclaim_bond(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+
+        if split.status != SplitStatus::Cancelled && split.status != SplitStatus::Expired {
+            return Err(Error::NotRefundable);
+        }
+        if storage::get_dispute(&env, split_id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+        if storage::get_bond(&env, split_id) <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        Ok(Self::return_bond(&env, &split))
+    }
+
+    /// Report how close a split's storage is to expiring
+    ///
+    /// Apps can warn "this split's storage expires in 5 days" and call
+    /// `bump_split`. Since TTLs can't be read on-chain, `live_until` is a
+    /// lower bound kept as the split is written and bumped. The split's
+    /// record, bond, rent budget and held balances share it.
+    pub fn get_split_health(env: Env, split_id: u64) -> Result<SplitHealth, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let now = env.ledger().sequence();
+        let (threshold, extend_to) = storage::get_ttl(&env);
+        let live_until = storage::get_live_until(&env, split_id).unwrap_or(now);
+        let ledgers_left = live_until.saturating_sub(now);
+
+        let can_bump = storage::get_rent_budget(&env, split_id).is_some_and(|budget| {
+            budget.remaining > 0
+                && now >= budget.last_bump.saturating_add(extend_to - threshold)
+                && settlement::is_open(&storage::get_split(&env, split_id))
+        });
+
+        Ok(SplitHealth {
+            live_until,
+            ledgers_left,
+            bump_recommended: ledgers_left < threshold,
+            can_bump,
+        })
+    }
+
+    /// Keep a long-lived split's entries alive, paid from its rent budget
+    ///
+    /// Anyone can act as the keeper. The split's record, bond, rent budget
+    /// and held balances are extended to the full TTL, and the keeper gets
+    /// the configured fee from the budget (or whatever's left of it). A bump
+    /// is due once the last one could have decayed to the TTL threshold;
+    /// earlier calls return `ActionNotReady`, so a keeper can't drain the
+    /// budget.
+    pub fn bump_split(env: Env, split_id: u64, keeper: Address) -> Result<i128, Error> {
+        keeper.require_auth();
+        Self::check_no_emergency(&env)?;
+
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed => {}
+        }
+        let mut budget = storage::get_rent_budget(&env, split_id).ok_or(Error::NoFundsAvailable)?;
+        if budget.remaining <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+        let (threshold, extend_to) = storage::get_ttl(&env);
+        let now = env.ledger().sequence();
+        if now < budget.last_bump.saturating_add(extend_to - threshold) {
+            return Err(Error::ActionNotReady);
+        }
+
+        let config = storage::get_rent_config(&env).ok_or(Error::InvalidConfig)?;
+        let fee = config.keeper_fee.min(budget.remaining);
+        budget.remaining -= fee;
+        budget.last_bump = now;
+        storage::set_rent_budget(&env, split_id, &budget);
+        storage::keep_split_alive(&env, &split);
+
+        storage::add_total_escrowed(&env, &budget.token, -fee);
+        token::Client::new(&env, &budget.token).transfer(
+            &env.current_contract_address(),
+            &keeper,
+            &fee,
+        );
+        Self::log_transfer(
+            &env,
+            split_id,
+            TransferKind::Rent,
+            &keeper,
+            &budget.token,
+            fee,
+        );
+        events::emit_rent_bumped(&env, split_id, &keeper, fee);
+
+        Ok(fee)
+    }
+
+    /// Give a creator the unused rent budget of a cancelled or expired split
+    ///
+    /// Released splits return it automatically along with the bond.
+    pub fn reclaim_rent(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+
+        if !settlement::is_void(&split) {
+            return Err(Error::NotRefundable);
+        }
+        match Self::return_rent(&env, &split) {
+            0 => Err(Error::NoFundsAvailable),
+            returned => Ok(returned),
+        }
+    }
+
+    /// Hand a participant's whole position on a tokenized split to someone else
+    ///
+    /// Only the registered share token contract calls this, when `from`
+    /// transfers their share tokens to `to`, and both `from` and `to` must
+    /// authorize it too. The share, what's been paid, held deposits, refund
+    /// rights and any pull schedule all move; `to` takes over the obligation
+    /// as if they'd been listed from the start. Not while a dispute or a
+    /// total increase is open.
+    pub fn transfer_share(
+        env: Env,
+        split_id: u64,
+        from: Address,
+        to: Address,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        if !storage::is_tokenized(&env, split_id) {
+            return Err(Error::UnsupportedMode);
+        }
+        let companion = storage::get_share_token(&env).ok_or(Error::InvalidConfig)?;
+        companion.require_auth();
+        from.require_auth();
+        to.require_auth();
+
+        let mut split = storage::get_split(&env, split_id);
+        if split.status == SplitStatus::Released {
+            return Err(Error::SplitReleased);
+        }
+        if storage::get_dispute(&env, split_id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+        if storage::get_total_increase(&env, split_id).is_some() {
+            return Err(Error::ActionNotReady);
+        }
+        if from == to || split.participants.iter().any(|p| p.address == to) {
+            return Err(Error::InvalidParticipants);
+        }
+        let index = split
+            .participants
+            .iter()
+            .position(|p| p.address == from)
+            .ok_or(Error::ParticipantNotFound)? as u32;
+
+        let mut participant = split.participants.get(index).unwrap();
+        Self::check_spending_cap(
+            &env,
+            &to,
+            participant.share_amount - participant.amount_paid,
+        )?;
+        participant.address = to.clone();
+        split.participants.set(index, participant);
+        storage::set_split(&env, split_id, &split);
+        storage::move_participant_state(&env, &split, &from, &to);
+        stats::record_joined(&env, &to);
+
+        events::emit_share_transferred(&env, split_id, &from, &to);
+        Ok(())
+    }
+
+    /// Whether a split's obligations are share tokens
+    pub fn is_tokenized(env: Env, split_id: u64) -> bool {
+        storage::is_tokenized(&env, split_id)
+    }
+
+    /// Get what's left of a split's rent budget, if it has one
+    pub fn get_rent_budget(env: Env, split_id: u64) -> Option<RentBudget> {
+        storage::get_rent_budget(&env, split_id)
+    }
+
+    /// Get the creator bond still locked for a split (0 if none)
+    pub fn get_bond(env: Env, split_id: u64) -> i128 {
+        storage::get_bond(&env, split_id)
+    }
+
+    /// Check if a split is fully funded
+    pub fn is_fully_funded(env: Env, split_id: u64) -> Result<bool, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        Ok(Self::is_fully_funded_internal(&split))
+    }
+
+    /// Cancel a split and mark for refunds
+    ///
+    /// I'm allowing only the creator to cancel, and only if not fully completed.
+    /// The creator has to say why; the reason is kept on the split and sent
+    /// with the cancel event so participants know why they're being refunded.
+    pub fn cancel_split(
+        env: Env,
+        split_id: u64,
+        reason: String,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+
+        // Only the creator can cancel
+        split.creator.require_auth();
+
+        Self::check_nonce(&split, expected_nonce)?;
+        Self::check_cancel_reason(&reason)?;
+
+        if storage::get_pause_flags(&env).refund {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(&env, split_id)?;
+
+        match split.status {
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed => {}
+        }
+
+        // Once people have paid in, enough of them have to agree
+        if split.status != SplitStatus::Pending && !Self::cancel_approved(&env, &split) {
+            return Err(Error::ActionNotReady);
+        }
+
+        // Mark as cancelled
+        lifecycle::transition(&env, &mut split, SplitStatus::Cancelled, Action::Cancel);
+        storage::set_split(&env, split_id, &split);
+
+        // Record the reason and emit the cancellation event
+        Self::record_cancel(&env, split_id, &reason);
+
+        Ok(())
+    }
+
+    /// Agree to the creator cancelling a split you've paid into
+    ///
+    /// When the admin sets a cancel quorum, a creator can't cancel an
+    /// Active or Completed split until that share of its paid participants
+    /// have called this. Approving twice is harmless. Returns how many paid
+    /// participants have approved so far.
+    pub fn approve_cancel(env: Env, split_id: u64, participant: Address) -> Result<u32, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        participant.require_auth();
+
+        let split = storage::get_split(&env, split_id);
+        match split.status {
+            SplitStatus::Active | SplitStatus::Completed => {}
+            SplitStatus::Pending => return Err(Error::SplitNotFunded),
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+        }
+        let entry = split
+            .participants
+            .iter()
+            .find(|p| p.address == participant)
+            .ok_or(Error::ParticipantNotFound)?;
+        if entry.amount_paid == 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        let mut approvals = storage::get_cancel_approvals(&env, split_id);
+        if !approvals.contains(&participant) {
+            approvals.push_back(participant.clone());
+            storage::set_cancel_approvals(&env, split_id, &approvals);
+            events::emit_cancel_approved(&env, split_id, &participant);
+        }
+        Ok(Self::cancel_votes(&split, &approvals).0)
+    }
+
+    /// Get the participants who have approved cancelling a split
+    pub fn get_cancel_approvals(env: Env, split_id: u64) -> Vec<Address> {
+        storage::get_cancel_approvals(&env, split_id)
+    }
+
+    /// Get why a split was cancelled, if it was
+    pub fn get_cancel_reason(env: Env, split_id: u64) -> Option<String> {
+        storage::get_cancel_reason(&env, split_id)
+    }
+
+    /// Cancel many of a creator's Pending splits in one transaction
+    ///
+    /// Each ID gets its own result, like `expire_batch`, so one bad ID
+    /// doesn't undo the rest. Only splits nobody has paid into yet qualify;
+    /// others are skipped with `HasDeposits` or their final status's error,
+    /// and someone else's split with `AccessDenied`.
+    pub fn batch_cancel(
+        env: Env,
+        creator: Address,
+        split_ids: Vec<u64>,
+        reason: String,
+    ) -> Vec<BatchResult> {
+        creator.require_auth();
+        if let Err(err) = Self::check_cancel_reason(&reason) {
+            panic_with_error!(&env, err);
+        }
+
+        let mut results = Vec::new(&env);
+        for split_id in split_ids.iter() {
+            let outcome = Self::cancel_pending(&env, &creator, split_id, &reason);
+            results.push_back(Self::batch_result(split_id, outcome));
+        }
+        results
+    }
+
+    /// Lower a split's total when the final bill comes in smaller
+    ///
+    /// I'm scaling every share down in proportion, with the same rounding
+    /// as `shares::weighted_shares`, and refunding anyone who already paid
+    /// more than their new share straight from their escrowed balance. Only
+    /// escrow splits that haven't paid anything out (and hold no deposits in
+    /// other tokens) qualify, so every excess is still sitting in escrow.
+    /// Returns the total refunded.
+    pub fn reduce_total(
+        env: Env,
+        split_id: u64,
+        new_total: i128,
+        expected_nonce: Option<u64>,
+    ) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_nonce(&split, expected_nonce)?;
+        // Lowering the total refunds overpayments, so it waits out an
+        // emergency or a refund pause like any other refund
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        if storage::get_pause_flags(&env).refund {
+            return Err(Error::ContractPaused);
+        }
+
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed => {}
+        }
+        if split.mode != SplitMode::Escrow
+            || split.amount_released > 0
+            || Self::foreign_holdings(&env, &split).1 > 0
+        {
+            return Err(Error::UnsupportedMode);
+        }
+        if storage::is_joinable(&env, split_id) {
+            return Err(Error::ActionNotReady);
+        }
+        let old_total = split.total_amount;
+        if new_total <= 0 || new_total >= old_total {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Floor every scaled share, then hand the leftover stroops to the
+        // first-listed participants with a non-zero share
+        let mut new_shares = Vec::new(&env);
+        let mut assigned = 0;
+        for p in split.participants.iter() {
+            let share = new_total * p.share_amount / old_total;
+            assigned += share;
+            new_shares.push_back(share);
+        }
+        let mut left_over = new_total - assigned;
+        for (i, p) in split.participants.iter().enumerate() {
+            if left_over == 0 {
+                break;
+            }
+            if p.share_amount > 0 {
+                new_shares.set(i as u32, new_shares.get(i as u32).unwrap() + 1);
+                left_over -= 1;
+            }
+        }
+        // Slots already at zero (a credited creator, a written-off or
+        // rebalanced participant) stay there; every other share has to stay
+        // positive, as at creation
+        for (i, p) in split.participants.iter().enumerate() {
+            if p.share_amount > 0 && new_shares.get(i as u32).unwrap() <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+        }
+        if new_shares.iter().sum::<i128>() != new_total {
+            return Err(Error::SharesMismatch);
+        }
+
+        let mut refunds = Vec::new(&env);
+        for (i, mut p) in split.participants.iter().enumerate() {
+            if p.share_amount == 0 {
+                continue;
+            }
+            p.share_amount = new_shares.get(i as u32).unwrap();
+            let excess = p.amount_paid - p.share_amount;
+            if excess > 0 {
+                p.amount_paid = p.share_amount;
+                split.amount_collected -= excess;
+                refunds.push_back((p.address.clone(), excess));
+            }
+            p.has_paid = p.amount_paid >= p.share_amount;
+            split.participants.set(i as u32, p);
+        }
+        split.total_amount = new_total;
+
+        storage::set_split(&env, split_id, &split);
+        events::emit_total_reduced(&env, split_id, old_total, new_total);
+
+        let token_address = storage::get_token(&env);
+        let token_client = token::Client::new(&env, &token_address);
+        let mut refunded = 0;
+        for (participant, excess) in refunds.iter() {
+            storage::add_held_balance(&env, split_id, &participant, -excess);
+            storage::add_total_escrowed(&env, &token_address, -excess);
+            let refund_to = Self::refund_recipient(&env, &participant);
+            token_client.transfer(&env.current_contract_address(), &refund_to, &excess);
+            Self::log_transfer(
+                &env,
+                split_id,
+                TransferKind::Refund,
+                &refund_to,
+                &token_address,
+                excess,
+            );
+            events::emit_refund_processed(&env, split_id, &participant, excess);
+            refunded += excess;
+        }
+
+        // Lowering the total can finish a split that was still collecting
+        if split.status != SplitStatus::Completed && Self::is_fully_funded_internal(&split) {
+            Self::complete_or_release(&env, split_id, split);
+        }
+
+        Ok(refunded)
+    }
+
+    /// Propose a higher total with new shares
+    ///
+    /// No share may shrink (use `reduce_total` for that). The increase only
+    /// takes effect once every participant whose share grows has accepted
+    /// with `accept_increase`; until then deposits count against the
+    /// current shares. A new proposal replaces the previous one and its
+    /// acceptances.
+    pub fn propose_increase(
+        env: Env,
+        split_id: u64,
+        new_total: i128,
+        new_shares: Vec<i128>,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        Self::check_nonce(&split, expected_nonce)?;
+        Self::check_increasable(&split)?;
+        if storage::is_joinable(&env, split_id) {
+            return Err(Error::ActionNotReady);
+        }
+
+        if new_total <= split.total_amount {
+            return Err(Error::InvalidAmount);
+        }
+        if new_shares.len() != split.participants.len()
+            || new_shares.iter().sum::<i128>() != new_total
+        {
+            return Err(Error::SharesMismatch);
+        }
+
+        let mut pending = Vec::new(&env);
+        let mut shares = Vec::new(&env);
+        for (p, share) in split.participants.iter().zip(new_shares.iter()) {
+            if share < p.share_amount {
+                return Err(Error::InvalidAmount);
+            }
+            if share > p.share_amount {
+                pending.push_back(p.address.clone());
+            }
+            shares.push_back(IncreasedShare {
+                participant: p.address,
+                share,
+            });
+        }
+
+        storage::set_total_increase(
+            &env,
+            split_id,
+            &TotalIncrease {
+                new_total,
+                shares,
+                pending: pending.clone(),
+                proposed_at: env.ledger().timestamp(),
+            },
+        );
+        // The proposal lives beside the split, so save it just to bump the nonce
+        storage::set_split(&env, split_id, &split);
+        events::emit_increase_proposed(&env, split_id, new_total, &pending);
+
+        Ok(())
+    }
+
+    /// Accept a proposed total increase, applying it once everyone has
+    ///
+    /// Returns true if this acceptance applied the increase.
+    pub fn accept_increase(env: Env, split_id: u64, participant: Address) -> Result<bool, Error> {
+        participant.require_auth();
+        Self::accept_increase_internal(&env, split_id, &participant)
+    }
+
+    /// Get a split's proposed total increase, if any
+    pub fn get_total_increase(env: Env, split_id: u64) -> Option<TotalIncrease> {
+        storage::get_total_increase(&env, split_id)
+    }
+
+    /// Push back a split's deadlines so a nearly-funded split isn't expired
+    ///
+    /// I only let deadlines move later: each one given must already exist
+    /// on the split and be strictly later than both its current value and
+    /// now. Passing `None` leaves that deadline alone. Each call counts
+    /// once against the split's `max_extensions`, if it has one.
+    pub fn extend_deadline(
+        env: Env,
+        split_id: u64,
+        deadline: Option<u64>,
+        deadline_ledger: Option<u32>,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+        Self::check_not_frozen(&env, split_id)?;
+        Self::check_nonce(&split, expected_nonce)?;
+
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active => {}
+        }
+
+        let current_ledger = storage::get_deadline_ledger(&env, split_id);
+        if deadline.is_none() && deadline_ledger.is_none() {
+            return Err(Error::InvalidDeadline);
+        }
+        if let Some(new) = deadline {
+            match split.deadline {
+                Some(old) if new > old && new > env.ledger().timestamp() => {}
+                _ => return Err(Error::InvalidDeadline),
+            }
+        }
+        if let Some(new) = deadline_ledger {
+            match current_ledger {
+                Some(old) if new > old && new > env.ledger().sequence() => {}
+                _ => return Err(Error::InvalidDeadline),
+            }
+        }
+
+        let extensions = storage::get_extension_count(&env, split_id) + 1;
+        if let Some(max) = storage::get_max_extensions(&env, split_id) {
+            if extensions > max {
+                return Err(Error::ExtensionLimitReached);
+            }
+        }
+
+        if deadline.is_some() {
+            split.deadline = deadline;
+            storage::set_split(&env, split_id, &split);
+        }
+        if let Some(new) = deadline_ledger {
+            storage::set_deadline_ledger(&env, split_id, new);
+        }
+        storage::set_extension_count(&env, split_id, extensions);
+
+        events::emit_deadline_extended(
+            &env,
+            split_id,
+            split.deadline,
+            deadline_ledger.or(current_ledger),
+            extensions,
+        );
+
+        Ok(())
+    }
+
+    /// Emit a payment reminder for an underfunded split
+    ///
+    /// I'm leaving this permissionless so off-chain bots can act as keepers.
+    /// A reminder lists every participant who still owes money and is only
+    /// emitted once per reminder interval, counted from the last reminder
+    /// (or from creation for the first one).
+    pub fn poke(env: Env, split_id: u64) -> Result<Vec<Address>, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active => {}
+        }
+
+        let now = env.ledger().timestamp();
+        let since = storage::get_last_reminder(&env, split_id).unwrap_or(split.created_at);
+        if now < since.saturating_add(storage::get_reminder_interval(&env)) {
+            return Err(Error::ReminderTooSoon);
+        }
+
+        let mut unpaid = Vec::new(&env);
+        for p in split.participants.iter() {
+            if p.amount_paid < p.share_amount {
+                unpaid.push_back(p.address);
+            }
+        }
+
+        storage::set_last_reminder(&env, split_id, now);
+        events::emit_payment_reminder(&env, split_id, &unpaid);
+
+        Ok(unpaid)
+    }
+
+    /// Expire a split whose deadline passed before it was fully funded
+    ///
+    /// I'm leaving this permissionless so keepers can tidy up; eligibility
+    /// is fully determined by the split's own deadline and status.
+    pub fn expire_split(env: Env, split_id: u64) -> Result<(), Error> {
+        Self::expire_split_internal(&env, split_id)
+    }
+
+    /// Expire a split and collect the cleanup bounty for it
+    ///
+    /// Eligibility is exactly that of `expire_split`, so a keeper can only
+    /// collect for a split that really is past its deadline and unfunded.
+    /// The bounty is the stake the creator put up when the split was
+    /// created, never the split's deposits, so a split can't pay out more
+    /// than it put in. Returns what the keeper was paid.
+    ///
+    /// A split only earns its bounty if it took deposits, lived at least
+    /// `MIN_BOUNTY_AGE` and isn't being expired by its own creator.
+    /// Anything else still expires, and the stake goes back to the creator.
+    pub fn expire_with_bounty(env: Env, split_id: u64, keeper: Address) -> Result<i128, Error> {
+        keeper.require_auth();
+        Self::expire_split_internal(&env, split_id)?;
+
+        let split = storage::get_split(&env, split_id);
+        let earned = split.amount_collected > 0
+            && split.creator != keeper
+            && env.ledger().timestamp() >= split.created_at + MIN_BOUNTY_AGE;
+        if !earned {
+            Self::return_bounty_stake(&env, &split);
+            return Ok(0);
+        }
+
+        let bounty = storage::take_bounty_stake(&env, split_id);
+        if bounty > 0 {
+            let token_address = storage::get_token(&env);
+            storage::add_total_escrowed(&env, &token_address, -bounty);
+            token::Client::new(&env, &token_address).transfer(
+                &env.current_contract_address(),
+                &keeper,
+                &bounty,
+            );
+            events::emit_bounty_paid(&env, split_id, &keeper, bounty);
+        }
+        Ok(bounty)
+    }
+
+    /// Give a creator the expiry bounty back from a cancelled or expired split
+    ///
+    /// Released splits return it automatically, and so does
+    /// `expire_with_bounty` when the keeper didn't earn it.
+    pub fn reclaim_bounty_stake(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_no_emergency(&env)?;
+
+        if split.status != SplitStatus::Cancelled && split.status != SplitStatus::Expired {
+            return Err(Error::NotRefundable);
+        }
+        if storage::get_bounty_stake(&env, split_id) <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+
+        Ok(Self::return_bounty_stake(&env, &split))
+    }
+
+    /// Get the sequence number of the latest event, overall or for a split
+    ///
+    /// An indexer that has seen fewer events than this has missed some.
+    pub fn get_event_seq(env: Env, split_id: Option<u64>) -> u64 {
+        storage::get_event_seq(&env, split_id)
+    }
+
+    /// Get the expiry bounty a split's creator put up (0 if none)
+    pub fn get_bounty_stake(env: Env, split_id: u64) -> i128 {
+        storage::get_bounty_stake(&env, split_id)
+    }
+
+    /// Cancel a split nobody paid into within the activation window
+    ///
+    /// I'm leaving this permissionless like `expire_split`, so keepers can
+    /// clear abandoned splits out of the active set. Only Pending splits
+    /// qualify, and only once the window has passed since creation.
+    pub fn cancel_inactive(env: Env, split_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        if storage::get_pause_flags(&env).refund {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(&env, split_id)?;
+        let window = storage::get_activation_window(&env);
+        if window == 0 {
+            return Err(Error::InvalidConfig);
+        }
+
+        let mut split = storage::get_split(&env, split_id);
+        match split.status {
+            SplitStatus::Pending => {}
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Active | SplitStatus::Completed => return Err(Error::HasDeposits),
+        }
+        if env.ledger().timestamp() < split.created_at + window {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        lifecycle::transition(&env, &mut split, SplitStatus::Cancelled, Action::Cancel);
+        storage::set_split(&env, split_id, &split);
+        Self::record_cancel(&env, split_id, &String::from_str(&env, "inactive"));
+
+        Ok(())
+    }
+
+    /// Expire many past-deadline splits in one transaction
+    ///
+    /// Each ID gets its own result; ineligible or unknown splits are
+    /// skipped with the error they would have returned individually.
+    pub fn expire_batch(env: Env, split_ids: Vec<u64>) -> Vec<BatchResult> {
+        let mut results = Vec::new(&env);
+        for split_id in split_ids.iter() {
+            let outcome = Self::expire_split_internal(&env, split_id);
+            results.push_back(Self::batch_result(split_id, outcome));
+        }
+        results
+    }
+
+    /// Set the minimum time between payment reminders (admin only)
+    pub fn set_reminder_interval(env: Env, interval: u64) -> Result<(), Error> {
+        storage::get_admin(&env).require_auth();
+
+        if interval == 0 {
+            return Err(Error::InvalidConfig);
+        }
+
+        storage::set_reminder_interval(&env, interval);
+        Ok(())
+    }
+
+    /// Rewrite a split entry in the current storage layout
+    ///
+    /// I'm leaving this permissionless: it never changes what a split means,
+    /// only how it's encoded. Returns whether anything was rewritten, so
+    /// an operator can sweep old entries after an upgrade.
+    pub fn migrate_split(env: Env, split_id: u64) -> Result<bool, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let (split, outdated) = storage::load_split(&env, split_id);
+        if !outdated {
+            return Ok(false);
+        }
+
+        storage::set_split(&env, split_id, &split);
+        events::emit_split_migrated(&env, split_id);
+        Ok(true)
+    }
+
+    /// Pin an off-chain receipt to a split
+    ///
+    /// I'm letting the creator set or replace the reference at any point;
+    /// each change is emitted so a dispute can see which receipt was pinned
+    /// when.
+    pub fn set_receipt_ref(
+        env: Env,
+        split_id: u64,
+        receipt_ref: Bytes,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_nonce(&split, expected_nonce)?;
+
+        if receipt_ref.is_empty() || receipt_ref.len() > MAX_RECEIPT_REF_LEN {
+            return Err(Error::InvalidReceiptRef);
+        }
+
+        split.receipt_ref = Some(receipt_ref.clone());
+        storage::set_split(&env, split_id, &split);
+
+        events::emit_receipt_set(&env, split_id, &receipt_ref);
+
+        Ok(())
+    }
+
+    /// Point a split at rich off-chain data (ipfs://, https://)
+    ///
+    /// Only the creator can set it, and an empty URI clears it. I don't
+    /// check the scheme; clients decide which links they're willing to open.
+    pub fn set_metadata_uri(
+        env: Env,
+        split_id: u64,
+        uri: String,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let mut split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_nonce(&split, expected_nonce)?;
+
+        if uri.len() > MAX_METADATA_URI_LEN {
+            return Err(Error::UriTooLong);
+        }
+
+        split.metadata_uri = if uri.is_empty() {
+            None
+        } else {
+            Some(uri.clone())
+        };
+        storage::set_split(&env, split_id, &split);
+
+        events::emit_metadata_uri_set(&env, split_id, &uri);
+
+        Ok(())
+    }
+
+    /// Give a participant a readable label ("Mom", "Flat 3B") on a split
+    ///
+    /// Only the creator can label, and an empty label clears it. Labels are
+    /// public on-chain, so clients should treat them as display hints.
+    pub fn set_participant_label(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        label: String,
+        expected_nonce: Option<u64>,
+    ) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+        Self::check_nonce(&split, expected_nonce)?;
+
+        if !split.participants.iter().any(|p| p.address == participant) {
+            return Err(Error::ParticipantNotFound);
+        }
+        if label.len() > MAX_LABEL_LEN {
+            return Err(Error::LabelTooLong);
+        }
+
+        if label.is_empty() {
+            storage::remove_participant_label(&env, split_id, &participant);
+        } else {
+            storage::set_participant_label(&env, split_id, &participant, &label);
+        }
+        // Labels live beside the split, so save it just to bump the nonce
+        storage::set_split(&env, split_id, &split);
+        events::emit_label_set(&env, split_id, &participant, &label);
+
+        Ok(())
+    }
+
+    /// Get a participant's label on a split, if the creator set one
+    ///
+    /// Restricted splits return `AccessDenied`, like `get_split`.
+    pub fn get_participant_label(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<Option<String>, Error> {
+        Self::viewable_split(&env, split_id, None)?;
+        Ok(storage::get_participant_label(&env, split_id, &participant))
+    }
+
+    /// `get_participant_label` for a member of a restricted split
+    pub fn get_participant_label_as(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        viewer: Address,
+    ) -> Result<Option<String>, Error> {
+        Self::viewable_split(&env, split_id, Some(&viewer))?;
+        Ok(storage::get_participant_label(&env, split_id, &participant))
+    }
+
+    /// Append a short note to a split ("waiting on Dan")
+    ///
+    /// Only the creator can add notes, at any point in the split's life.
+    /// Notes can't be edited or removed, and a split holds at most
+    /// `MAX_NOTES` of them (`TooManyNotes` after that).
+    pub fn add_note(env: Env, split_id: u64, text: String) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+
+        if text.is_empty() {
+            return Err(Error::EmptyNote);
+        }
+        if text.len() > MAX_NOTE_LEN {
+            return Err(Error::NoteTooLong);
+        }
+        let mut notes = storage::get_notes(&env, split_id);
+        if notes.len() >= MAX_NOTES {
+            return Err(Error::TooManyNotes);
+        }
+
+        notes.push_back(SplitNote {
+            text: text.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        storage::set_notes(&env, split_id, &notes);
+        events::emit_note_added(&env, split_id, &text);
+
+        Ok(())
+    }
+
+    /// Get the creator's notes on a split, oldest first
+    ///
+    /// Restricted splits return `AccessDenied`, like `get_split`.
+    pub fn get_notes(env: Env, split_id: u64) -> Result<Vec<SplitNote>, Error> {
+        Self::viewable_split(&env, split_id, None)?;
+        Ok(storage::get_notes(&env, split_id))
+    }
+
+    /// `get_notes` for a member of a restricted split
+    pub fn get_notes_as(env: Env, split_id: u64, viewer: Address) -> Result<Vec<SplitNote>, Error> {
+        Self::viewable_split(&env, split_id, Some(&viewer))?;
+        Ok(storage::get_notes(&env, split_id))
+    }
+
+    /// Get a split's charity round-up settings, if it has them
+    pub fn get_round_up(env: Env, split_id: u64) -> Option<RoundUp> {
+        storage::get_round_up(&env, split_id)
+    }
+
+    /// Get the round-ups a split is holding for its charity
+    ///
+    /// These are tracked apart from the bill: they never count toward
+    /// `amount_collected` or anyone's share.
+    pub fn get_round_up_held(env: Env, split_id: u64) -> Result<i128, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(&env, split_id);
+        Ok(split
+            .participants
+            .iter()
+            .map(|p| storage::get_round_up_held(&env, split_id, &p.address))
+            .sum())
+    }
+
+    /// Get the salted contact hash a participant was invited with, if any
+    pub fn get_contact_hash(env: Env, split_id: u64, participant: Address) -> Option<Bytes> {
+        storage::get_contact_hash(&env, split_id, &participant)
+    }
+
+    /// Get the hash of the terms a split was created with
+    ///
+    /// Splits created before terms hashes existed have none.
+    pub fn get_terms_hash(env: Env, split_id: u64) -> Option<BytesN<32>> {
+        storage::get_terms_hash(&env, split_id)
+    }
+
+    /// Get a split's terms as they stand now
+    ///
+    /// Their hash matches `get_terms_hash` until something like
+    /// `reduce_total` changes the shares.
+    pub fn get_split_terms(env: Env, split_id: u64) -> Result<SplitTerms, Error> {
+        let split = Self::viewable_split(&env, split_id, None)?;
+        Ok(Self::split_terms(&env, &split))
+    }
+
+    /// Check a revealed salt and contact against a participant's stored hash
+    ///
+    /// `preimage` is the salt followed by the email or phone, exactly as
+    /// the app hashed it at creation. Run this as a simulation so the
+    /// contact never lands in a transaction.
+    pub fn verify_contact(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+        preimage: Bytes,
+    ) -> Result<bool, Error> {
+        let expected = storage::get_contact_hash(&env, split_id, &participant)
+            .ok_or(Error::ParticipantNotFound)?;
+        let actual: BytesN<32> = env.crypto().sha256(&preimage).into();
+
+        Ok(Bytes::from(actual) == expected)
+    }
+
+    /// Check a plaintext description against a private split's stored hash
+    ///
+    /// I'm hashing the UTF-8 bytes with SHA-256, the same thing clients do
+    /// before creating the split. Run this as a simulation so the plaintext
+    /// never lands in a transaction.
+    pub fn verify_description(env: Env, split_id: u64, description: String) -> Result<bool, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        let expected = split.description_hash.get().ok_or(Error::NotPrivate)?;
+        let actual = Self::hash_string(&env, &description).ok_or(Error::DescriptionTooLong)?;
+
+        Ok(actual == *expected)
+    }
+
+    /// Get one page of a creator's splits in a category
+    ///
+    /// I'm paging through the per-creator category index so budgeting apps
+    /// can group spending without an off-chain indexer.
+    pub fn get_splits_by_category(
+        env: Env,
+        creator: Address,
+        category: SplitCategory,
+        page: u32,
+    ) -> Vec<Split> {
+        let ids = storage::get_category_page(&env, &creator, category, page);
+        Self::load_splits(&env, &ids, None)
+    }
+
+    /// Get one page of the IDs of every split currently in a status
+    ///
+    /// Lets keepers find, say, every Completed split awaiting release
+    /// without scanning the whole ID space. IDs are listed in the order
+    /// they entered the status.
+    pub fn get_split_ids_by_status(env: Env, status: SplitStatus, page: u32) -> Vec<u64> {
+        storage::get_status_page(&env, status, page)
+    }
+
+    /// Get one page of the splits created between two timestamps
+    ///
+    /// Both ends are inclusive, and `creator` narrows the query to one
+    /// creator's splits. I'm walking the per-day buckets, loading splits
+    /// only on the first and last day to trim them to the exact range, and
+    /// stopping as soon as the page is full. Restricted splits are skipped
+    /// before they count towards a page, so pages stay full. A query spans
+    /// at most `MAX_QUERY_DAYS` days.
+    pub fn get_splits_created_between(
+        env: Env,
+        creator: Option<Address>,
+        from_ts: u64,
+        to_ts: u64,
+        page: u32,
+    ) -> Result<Vec<Split>, Error> {
+        if from_ts > to_ts {
+            return Err(Error::InvalidDeadline);
+        }
+        let first_day = from_ts / storage::DAY_SECONDS;
+        let last_day = to_ts / storage::DAY_SECONDS;
+        if last_day - first_day >= MAX_QUERY_DAYS {
+            return Err(Error::InvalidDeadline);
+        }
+
+        let skip = page.saturating_mul(storage::PAGE_SIZE);
+        let mut seen = 0;
+        let mut ids = Vec::new(&env);
+        'days: for day in first_day..=last_day {
+            let edge = day == first_day || day == last_day;
+            let list = storage::day_list(&creator, day);
+            let len = storage::list_len(&env, &list);
+            for split_id in (0..len).map(|i| storage::list_get(&env, &list, i)) {
+                if storage::get_visibility(&env, split_id) != Visibility::Public {
+                    continue;
+                }
+                if edge {
+                    let created_at = storage::get_split(&env, split_id).created_at;
+                    if created_at < from_ts || created_at > to_ts {
+                        continue;
+                    }
+                }
+                if seen >= skip {
+                    ids.push_back(split_id);
+                    if ids.len() == storage::PAGE_SIZE {
+                        break 'days;
+                    }
+                }
+                seen += 1;
+            }
+        }
+
+        Ok(Self::load_splits(&env, &ids, None))
+    }
+
+    /// Get one page of every split a creator has created
+    ///
+    /// Restricted splits are left out; `get_splits_by_creator_as` shows
+    /// them to their members.
+    pub fn get_splits_by_creator(env: Env, creator: Address, page: u32) -> Vec<Split> {
+        let ids = storage::get_creator_page(&env, &creator, page);
+        Self::load_splits(&env, &ids, None)
+    }
+
+    /// `get_splits_by_creator` including the restricted splits `viewer` is
+    /// the creator or a listed participant of
+    pub fn get_splits_by_creator_as(
+        env: Env,
+        creator: Address,
+        page: u32,
+        viewer: Address,
+    ) -> Vec<Split> {
+        viewer.require_auth();
+        let ids = storage::get_creator_page(&env, &creator, page);
+        Self::load_splits(&env, &ids, Some(&viewer))
+    }
+
+    /// Get one page of a creator's splits carrying a tag
+    pub fn get_splits_by_tag(env: Env, creator: Address, tag: Symbol, page: u32) -> Vec<Split> {
+        let ids = storage::get_tag_page(&env, &creator, &tag, page);
+        Self::load_splits(&env, &ids, None)
+    }
+
+    /// Get several splits in one call
+    ///
+    /// I'm skipping IDs that don't exist or are restricted to their
+    /// participants instead of failing the whole read, and reading at most
+    /// `PAGE_SIZE` IDs so a dashboard call stays within budget.
+    pub fn get_splits(env: Env, split_ids: Vec<u64>) -> Vec<Split> {
+        Self::load_splits(&env, &Self::existing_ids(&env, &split_ids), None)
+    }
+
+    /// Get the headline numbers of several splits in one call
+    ///
+    /// Skips the same IDs as `get_splits`.
+    pub fn get_summaries(env: Env, split_ids: Vec<u64>) -> Vec<SplitSummary> {
+        let mut summaries = Vec::new(&env);
+        for split in Self::load_splits(&env, &Self::existing_ids(&env, &split_ids), None).iter() {
+            summaries.push_back(Self::summarize(&env, &split));
+        }
+        summaries
+    }
+
+    /// Get one page of the open splits an address created or takes part in
+    ///
+    /// This is the one read a wallet home screen needs: a summary of each
+    /// split that isn't Released, Cancelled or Expired, with what the
+    /// address still owes. I'm walking the creator's open splits and then
+    /// the participant index, both stored one entry per split, and stopping
+    /// as soon as the page is full, so closed splits never cost anything.
+    /// `address` must authorize the call, since the list includes its
+    /// restricted splits.
+    pub fn get_active_splits_for(env: Env, address: Address, page: u32) -> Vec<ActiveSplit> {
+        address.require_auth();
+        let created = storage::creator_open_list(&address);
+        let joined = storage::participant_list(&address);
+        let created_len = storage::list_len(&env, &created);
+        let joined_len = storage::list_len(&env, &joined);
+        let ids = (0..created_len)
+            .map(|i| storage::list_get(&env, &created, i))
+            .chain(
+                (0..joined_len)
+                    .map(|i| storage::list_get(&env, &joined, i))
+                    .filter(|split_id| !storage::list_contains(&env, &created, *split_id)),
+            );
+
+        let skip = page.saturating_mul(storage::PAGE_SIZE);
+        let mut seen = 0;
+        let mut active = Vec::new(&env);
+        for split_id in ids {
+            let split = storage::get_split(&env, split_id);
+            if !settlement::is_open(&split) {
+                continue;
+            }
+            if seen >= skip {
+                let remaining: i128 = split
+                    .participants
+                    .iter()
+                    .filter(|p| p.address == address)
+                    .map(|p| p.share_amount - p.amount_paid)
+                    .sum();
+                active.push_back(ActiveSplit {
+                    summary: Self::summarize(&env, &split),
+                    is_creator: split.creator == address,
+                    remaining,
+                });
+                if active.len() == storage::PAGE_SIZE {
+                    break;
+                }
+            }
+            seen += 1;
+        }
+        active
+    }
+
+    /// Get an address's lifetime counters, for profile pages
+    ///
+    /// Counts splits created and joined, everything paid toward shares,
+    /// escrow-token payouts received, and the average days from a split's
+    /// creation to paying a share in full.
+    pub fn get_address_stats(env: Env, address: Address) -> AddressStats {
+        storage::get_address_stats(&env, &address)
+    }
+
+    /// Sum what an address still owes across one page of its open splits
+    ///
+    /// I'm reading one page of the participant index, which only holds
+    /// open splits, so a call costs the same however many splits name the
+    /// address. Callers add the pages up until one comes back empty.
+    pub fn get_total_owed(env: Env, participant: Address, page: u32) -> OwedSummary {
+        let ids = storage::get_participant_page(&env, &participant, page);
+        Self::owed_across(&env, &participant, &ids)
+    }
+
+    /// Create a group of members who split bills together
+    ///
+    /// I'm always adding the creator as a member so they can attach their
+    /// own splits without listing themselves.
+    pub fn create_group(env: Env, creator: Address, members: Vec<Address>) -> u64 {
+        creator.require_auth();
+
+        let mut group_members = Vec::new(&env);
+        group_members.push_back(creator.clone());
+        for member in members.iter() {
+            if !group_members.contains(&member) {
+                group_members.push_back(member);
+            }
+        }
+
+        let group_id = storage::get_next_group_id(&env);
+        let group = Group {
+            id: group_id,
+            creator: creator.clone(),
+            members: group_members,
+            split_ids: Vec::new(&env),
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_group(&env, &group);
+
+        events::emit_group_created(&env, group_id, &creator);
+
+        group_id
+    }
+
+    /// Attach an existing split to a group
+    ///
+    /// Only the split creator can do this, and everyone on the split must
+    /// already be a member so the group's balances stay self-contained. A
+    /// group holds at most `MAX_GROUP_SPLITS`, since its views walk them all.
+    pub fn add_split_to_group(env: Env, group_id: u64, split_id: u64) -> Result<(), Error> {
+        let mut group = storage::get_group(&env, group_id).ok_or(Error::GroupNotFound)?;
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        split.creator.require_auth();
+
+        if storage::get_split_group(&env, split_id).is_some() {
+            return Err(Error::SplitAlreadyGrouped);
+        }
+
+        if !group.members.contains(&split.creator) {
+            return Err(Error::NotGroupMember);
+        }
+        for p in split.participants.iter() {
+            if !group.members.contains(&p.address) {
+                return Err(Error::NotGroupMember);
+            }
+        }
+        if group.split_ids.len() >= MAX_GROUP_SPLITS {
+            return Err(Error::TooManyGroupSplits);
+        }
+
+        group.split_ids.push_back(split_id);
+        storage::set_group(&env, &group);
+        storage::set_split_group(&env, split_id, group_id);
+
+        Ok(())
+    }
+
+    /// Propose a short payment plan for a group's open IOU obligations
+    ///
+    /// I'm netting every member's unpaid shares and credits across the
+    /// group's open IOU splits, then greedily pairing the largest debtor with
+    /// the largest creditor, so members can settle with at most
+    /// `members - 1` direct payments. Escrow and pass-through splits are left
+    /// out, since paying a member directly doesn't settle what they owe the
+    /// contract. The plan is only stored on the group and emitted: no split
+    /// changes, and members still record their payments on each split.
+    pub fn simplify_group_debts(env: Env, group_id: u64) -> Result<Vec<Settlement>, Error> {
+        let group = storage::get_group(&env, group_id).ok_or(Error::GroupNotFound)?;
+
+        let mut balances: Map<Address, i128> = Map::new(&env);
+        for split_id in group.split_ids.iter() {
+            let split = storage::get_split(&env, split_id);
+            if split.mode == SplitMode::Iou && settlement::is_open(&split) {
+                settlement::add_split_balances(&mut balances, &split);
+            }
+        }
+
+        let plan = settlement::simplify(&env, &balances);
+        storage::set_group_settlement(&env, group_id, &plan);
+
+        events::emit_debts_simplified(&env, group_id, &plan);
+
+        Ok(plan)
+    }
+
+    /// Get every member's running totals across a group's splits
+    ///
+    /// Answers "who is carrying the group" in one read. Unlike
+    /// `simplify_group_debts`, released splits count too, so the totals are
+    /// the group's whole history; cancelled and expired splits drop out.
+    /// Members come back in the group's order.
+    pub fn get_group_standings(env: Env, group_id: u64) -> Result<Vec<MemberStanding>, Error> {
+        let group = storage::get_group(&env, group_id).ok_or(Error::GroupNotFound)?;
+
+        let mut splits = Vec::new(&env);
+        for split_id in group.split_ids.iter() {
+            let split = storage::get_split(&env, split_id);
+            if !settlement::is_void(&split) {
+                splits.push_back(split);
+            }
+        }
+
+        let mut standings = Vec::new(&env);
+        for member in group.members.iter() {
+            let mut standing = MemberStanding {
+                address: member.clone(),
+                owed: 0,
+                paid: 0,
+                fronted: 0,
+                received: 0,
+                net: 0,
+            };
+            for split in splits.iter() {
+                if split.creator == member {
+                    standing.fronted += split.total_amount;
+                    standing.received += split.amount_collected;
+                }
+                for p in split.participants.iter().filter(|p| p.address == member) {
+                    standing.owed += p.share_amount;
+                    standing.paid += p.amount_paid;
+                }
+            }
+            standing.net = standing.paid + standing.fronted - standing.owed - standing.received;
+            standings.push_back(standing);
+        }
+
+        Ok(standings)
+    }
+
+    /// Get group details by ID
+    pub fn get_group(env: Env, group_id: u64) -> Result<Group, Error> {
+        storage::get_group(&env, group_id).ok_or(Error::GroupNotFound)
+    }
+
+    /// Get the most recent settlement plan computed for a group
+    pub fn get_group_settlement(env: Env, group_id: u64) -> Vec<Settlement> {
+        storage::get_group_settlement(&env, group_id)
+    }
+
+    /// Work out who owes whom across a group or an event
+    ///
+    /// I'm computing this on the fly from the open splits, without storing
+    /// anything, so a client can show a "settle up" screen at any time.
+    /// `simplify_group_debts` records the same plan for a group.
+    pub fn get_settlement_report(
+        env: Env,
+        scope: SettlementScope,
+    ) -> Result<SettlementReport, Error> {
+        let split_ids = match scope {
+            SettlementScope::Group(group_id) => {
+                storage::get_group(&env, group_id)
+                    .ok_or(Error::GroupNotFound)?
+                    .split_ids
+            }
+            SettlementScope::Event(event_id) => {
+                storage::get_event(&env, event_id)
+                    .ok_or(Error::EventNotFound)?
+                    .split_ids
+            }
+        };
+
+        let mut balances: Map<Address, i128> = Map::new(&env);
+        for split_id in split_ids.iter() {
+            let split = storage::get_split(&env, split_id);
+            if settlement::is_open(&split) {
+                settlement::add_split_balances(&mut balances, &split);
+            }
+        }
+
+        let mut positions = Vec::new(&env);
+        for (address, net) in balances.iter() {
+            if net != 0 {
+                positions.push_back(NetPosition { address, net });
+            }
+        }
+
+        Ok(SettlementReport {
+            positions,
+            payments: settlement::simplify(&env, &balances),
+        })
+    }
+
+    /// Link a split under a parent split, e.g. one vendor bill of a wedding
+    ///
+    /// I'm allowing a single level only: a parent can't have a parent and a
+    /// child can't have children, so no chain or cycle can form. Both
+    /// creators sign. The parent must be an escrow split, since it's held
+    /// at release until every child is funded.
+    pub fn link_child_split(env: Env, parent_id: u64, child_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, parent_id) || !storage::has_split(&env, child_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if parent_id == child_id {
+            return Err(Error::InvalidConfig);
+        }
+
+        let parent = storage::get_split(&env, parent_id);
+        let child = storage::get_split(&env, child_id);
+        parent.creator.require_auth();
+        if child.creator != parent.creator {
+            child.creator.require_auth();
+        }
+
+        if parent.mode != SplitMode::Escrow {
+            return Err(Error::UnsupportedMode);
+        }
+        match parent.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            _ => {}
+        }
+        if storage::get_parent(&env, child_id).is_some() {
+            return Err(Error::SplitAlreadyGrouped);
+        }
+        if storage::get_parent(&env, parent_id).is_some()
+            || !storage::get_children(&env, child_id).is_empty()
+        {
+            return Err(Error::UnsupportedMode);
+        }
+        if storage::get_children(&env, parent_id).len() >= MAX_CHILD_SPLITS {
+            return Err(Error::TooManyChildSplits);
+        }
+
+        storage::add_child(&env, parent_id, child_id);
+        events::emit_split_linked(&env, parent_id, child_id);
+
+        Ok(())
+    }
+
+    /// Detach a child split from its parent
+    ///
+    /// A child with no deadline could otherwise hold its parent at
+    /// Completed forever. Only the parent's creator signs, since all this
+    /// changes is what the parent waits for. Not once the parent is
+    /// released.
+    pub fn unlink_child_split(env: Env, parent_id: u64, child_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, parent_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let parent = storage::get_split(&env, parent_id);
+        parent.creator.require_auth();
+
+        if storage::get_parent(&env, child_id) != Some(parent_id) {
+            return Err(Error::InvalidConfig);
+        }
+        if parent.status == SplitStatus::Released {
+            return Err(Error::SplitReleased);
+        }
+
+        storage::remove_child(&env, parent_id, child_id);
+        events::emit_split_unlinked(&env, parent_id, child_id);
+
+        Ok(())
+    }
+
+    /// Get the parent a split rolls up into, if any
+    pub fn get_parent_split(env: Env, split_id: u64) -> Option<u64> {
+        storage::get_parent(&env, split_id)
+    }
+
+    /// Get the child splits linked under a parent
+    pub fn get_child_splits(env: Env, split_id: u64) -> Vec<u64> {
+        storage::get_children(&env, split_id)
+    }
+
+    /// Get a split's funding progress in one read
+    ///
+    /// I'm returning only what a progress bar needs, so clients don't have
+    /// to fetch the whole split and its participants to draw one.
+    pub fn get_progress(env: Env, split_id: u64) -> Result<SplitProgress, Error> {
+        let split = Self::viewable_split(&env, split_id, None)?;
+
+        let funded_bps = if split.total_amount > 0 {
+            core::cmp::min(
+                split.amount_collected * shares::BPS_DENOMINATOR / split.total_amount,
+                shares::BPS_DENOMINATOR,
+            ) as u32
+        } else {
+            10_000
+        };
+        let paid_count = split.participants.iter().filter(|p| p.has_paid).count() as u32;
+        let now = env.ledger().timestamp();
+        let sequence = env.ledger().sequence();
+
+        Ok(SplitProgress {
+            collected: split.amount_collected,
+            total: split.total_amount,
+            funded_bps,
+            paid_count,
+            unpaid_count: split.participants.len() - paid_count,
+            seconds_left: split.deadline.map(|d| d.saturating_sub(now)),
+            ledgers_left: storage::get_deadline_ledger(&env, split_id)
+                .map(|d| d.saturating_sub(sequence)),
+        })
+    }
+
+    /// Roll a parent split's children up into its progress
+    pub fn get_family_progress(env: Env, split_id: u64) -> Result<FamilyProgress, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let parent = storage::get_split(&env, split_id);
+        let mut progress = FamilyProgress {
+            total: parent.total_amount,
+            collected: parent.amount_collected,
+            children: 0,
+            children_done: 0,
+        };
+        for child_id in storage::get_children(&env, split_id).iter() {
+            let child = storage::get_split(&env, child_id);
+            if settlement::is_void(&child) {
+                continue;
+            }
+            progress.total += child.total_amount;
+            progress.collected += child.amount_collected;
+            progress.children += 1;
+            if matches!(child.status, SplitStatus::Completed | SplitStatus::Released) {
+                progress.children_done += 1;
+            }
+        }
+
+        Ok(progress)
+    }
+
+    /// Create an event, such as a trip, to collect related splits under
+    pub fn create_event(env: Env, creator: Address, name: String) -> Result<u64, Error> {
+        creator.require_auth();
+
+        if name.len() > MAX_EVENT_NAME_LEN {
+            return Err(Error::EventNameTooLong);
+        }
+
+        let event_id = storage::get_next_event_id(&env);
+        let event = SplitEvent {
+            id: event_id,
+            creator: creator.clone(),
+            name,
+            split_ids: Vec::new(&env),
+            created_at: env.ledger().timestamp(),
+        };
+        storage::set_event(&env, &event);
+
+        events::emit_event_created(&env, event_id, &creator);
+
+        Ok(event_id)
+    }
+
+    /// Attach an existing split to an event
+    ///
+    /// I'm asking both the event creator and the split creator to sign, so
+    /// nobody can pad someone else's trip or pull in someone else's bill.
+    /// A split belongs to at most one event.
+    pub fn add_split_to_event(env: Env, event_id: u64, split_id: u64) -> Result<(), Error> {
+        let mut event = storage::get_event(&env, event_id).ok_or(Error::EventNotFound)?;
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        event.creator.require_auth();
+        if split.creator != event.creator {
+            split.creator.require_auth();
+        }
+
+        if storage::get_split_event(&env, split_id).is_some() {
+            return Err(Error::SplitAlreadyGrouped);
+        }
+        if event.split_ids.len() >= MAX_EVENT_SPLITS {
+            return Err(Error::TooManyEventSplits);
+        }
+
+        event.split_ids.push_back(split_id);
+        storage::set_event(&env, &event);
+        storage::set_split_event(&env, split_id, event_id);
+
+        events::emit_event_split_added(&env, event_id, split_id);
+
+        Ok(())
+    }
+
+    /// Get event details by ID
+    pub fn get_event(env: Env, event_id: u64) -> Result<SplitEvent, Error> {
+        storage::get_event(&env, event_id).ok_or(Error::EventNotFound)
+    }
+
+    /// Get the event a split is attached to, if any
+    pub fn get_split_event(env: Env, split_id: u64) -> Option<u64> {
+        storage::get_split_event(&env, split_id)
+    }
+
+    /// Sum totals and completion progress across an event's splits
+    pub fn get_event_summary(env: Env, event_id: u64) -> Result<EventSummary, Error> {
+        let event = storage::get_event(&env, event_id).ok_or(Error::EventNotFound)?;
+
+        let mut summary = EventSummary {
+            total: 0,
+            collected: 0,
+            split_count: 0,
+            completed_count: 0,
+            progress_bps: 0,
+        };
+        for split_id in event.split_ids.iter() {
+            let split = storage::get_split(&env, split_id);
+            if settlement::is_void(&split) {
+                continue;
+            }
+
+            summary.total += split.total_amount;
+            summary.collected += split
+                .participants
+                .iter()
+                .map(|p| p.amount_paid)
+                .sum::<i128>();
+            summary.split_count += 1;
+            if matches!(split.status, SplitStatus::Completed | SplitStatus::Released) {
+                summary.completed_count += 1;
+            }
+        }
+        if summary.total > 0 {
+            summary.progress_bps =
+                (summary.collected * shares::BPS_DENOMINATOR / summary.total) as u32;
+        }
+
+        Ok(summary)
+    }
+
+    /// Get what each person owes and has paid across an event's splits
+    ///
+    /// Members are listed in the order they first appear on the event's
+    /// splits.
+    pub fn get_event_members(env: Env, event_id: u64) -> Result<Vec<EventMember>, Error> {
+        let event = storage::get_event(&env, event_id).ok_or(Error::EventNotFound)?;
+
+        let mut members: Vec<EventMember> = Vec::new(&env);
+        for split_id in event.split_ids.iter() {
+            let split = storage::get_split(&env, split_id);
+            if settlement::is_void(&split) {
+                continue;
+            }
+
+            for p in split.participants.iter() {
+                match members.iter().position(|m| m.address == p.address) {
+                    Some(i) => {
+                        let mut member = members.get(i as u32).unwrap();
+                        member.owed += p.share_amount;
+                        member.paid += p.amount_paid;
+                        members.set(i as u32, member);
+                    }
+                    None => members.push_back(EventMember {
+                        address: p.address.clone(),
+                        owed: p.share_amount,
+                        paid: p.amount_paid,
+                    }),
+                }
+            }
+        }
+
+        Ok(members)
+    }
+
+    /// Get split details by ID
+    ///
+    /// Splits restricted to their participants return `AccessDenied` here;
+    /// members read them through `get_split_as`.
+    pub fn get_split(env: Env, split_id: u64) -> Result<Split, Error> {
+        Self::viewable_split(&env, split_id, None)
+    }
+
+    /// Get split details as the creator or a listed participant
+    ///
+    /// `viewer` must authorize the call, which is what keeps restricted
+    /// splits out of reach of strangers simulating the view.
+    pub fn get_split_as(env: Env, split_id: u64, viewer: Address) -> Result<Split, Error> {
+        Self::viewable_split(&env, split_id, Some(&viewer))
+    }
+
+    /// Get split details, or `None` if there's no split to show
+    ///
+    /// This is `get_split` for callers probing IDs: unknown splits and
+    /// splits restricted to their participants both come back as `None`
+    /// instead of an error. (The generated client already has a
+    /// `try_get_split`, so this one goes by another name.)
+    pub fn find_split(env: Env, split_id: u64) -> Option<Split> {
+        Self::viewable_split(&env, split_id, None).ok()
+    }
+
+    /// Whether a split with this ID has been created
+    ///
+    /// Restricted splits count too; only their details are hidden.
+    pub fn split_exists(env: Env, split_id: u64) -> bool {
+        storage::has_split(&env, split_id)
+    }
+
+    /// Get a split's short reference code (e.g. `XK7-29Q`)
+    ///
+    /// Splits created before codes existed get theirs derived on the fly;
+    /// it's the same code they would have been given.
+    pub fn get_ref_code(env: Env, split_id: u64) -> Result<String, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        Ok(
+            storage::get_ref_code(&env, split_id)
+                .unwrap_or_else(|| refcode::encode(&env, split_id)),
+        )
+    }
+
+    /// Look a split up by its reference code
+    ///
+    /// Case and hyphens are ignored. A mistyped code fails its check digit
+    /// and returns `SplitNotFound` rather than someone else's split.
+    pub fn get_split_by_code(env: Env, code: String) -> Result<Split, Error> {
+        let split_id = refcode::decode(&env, &code).ok_or(Error::SplitNotFound)?;
+        Self::viewable_split(&env, split_id, None)
+    }
+
+    /// Get the ledger sequence deadline of a split, if it has one
+    pub fn get_deadline_ledger(env: Env, split_id: u64) -> Option<u32> {
+        storage::get_deadline_ledger(&env, split_id)
+    }
+
+    /// Get the smallest deposit accepted per transaction
+    pub fn get_min_deposit(env: Env) -> MinDeposit {
+        storage::get_min_deposit(&env)
+    }
+
+    /// Whether a split only accepts deposits of a whole remaining share
+    pub fn requires_full_payment(env: Env, split_id: u64) -> bool {
+        storage::requires_full_payment(&env, split_id)
+    }
+
+    /// Get the decimals and ticker to render a split's amounts with
+    ///
+    /// Splits created before this was recorded report the token's current
+    /// decimals and no ticker.
+    pub fn get_split_display(env: Env, split_id: u64) -> Result<SplitDisplay, Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        Ok(
+            storage::get_split_display(&env, split_id).unwrap_or_else(|| SplitDisplay {
+                decimals: token::Client::new(&env, &storage::get_token(&env)).decimals(),
+                symbol: None,
+            }),
+        )
+    }
+
+    /// Get a participant's status in a split
+    ///
+    /// Required for DRIP escrow queries.
+    pub fn get_participant_status(
+        env: Env,
+        split_id: u64,
+        participant: Address,
+    ) -> Result<Participant, Error> {
+        // On a restricted split only the participant themselves may look
+        let restricted = storage::get_visibility(&env, split_id) == Visibility::Participants;
+        let split = Self::viewable_split(&env, split_id, restricted.then_some(&participant))?;
+
+        for i in 0..split.participants.len() {
+            let p = split.participants.get(i).unwrap();
+            if p.address == participant {
+                return Ok(p);
+            }
+        }
+
+        Err(Error::ParticipantNotFound)
+    }
+
+    /// Check that a split's accounting and status are self-consistent
+    ///
+    /// I'm exposing this for monitoring and fuzzing: shares sum to the
+    /// total, paid amounts add up to what was collected, escrowed balances
+    /// back what hasn't been released, and the status fits the payments.
+    pub fn check_invariants(env: Env, split_id: u64) -> Result<(), Error> {
+        if !storage::has_split(&env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        let split = storage::get_split(&env, split_id);
+        if !invariants::holds(&env, &split) {
+            return Err(Error::InvariantViolated);
+        }
+        Ok(())
+    }
+
+    /// Get what a participant currently has in escrow for a split
+    pub fn get_held_balance(env: Env, split_id: u64, participant: Address) -> i128 {
+        storage::get_held_balance(&env, split_id, &participant)
+    }
+
+    /// Get every participant of a split with computed progress fields
+    pub fn get_participants(env: Env, split_id: u64) -> Result<Vec<ParticipantView>, Error> {
+        let split = Self::viewable_split(&env, split_id, None)?;
+        Ok(Self::participant_views(&env, &split))
+    }
+
+    /// `get_participants` for the creator or a listed participant of a
+    /// restricted split
+    pub fn get_participants_as(
+        env: Env,
+        split_id: u64,
+        viewer: Address,
+    ) -> Result<Vec<ParticipantView>, Error> {
+        let split = Self::viewable_split(&env, split_id, Some(&viewer))?;
+        Ok(Self::participant_views(&env, &split))
+    }
+
+    /// Who can read a split through the views
+    pub fn get_visibility(env: Env, split_id: u64) -> Visibility {
+        storage::get_visibility(&env, split_id)
+    }
+
+    /// Get the contract admin
+    pub fn get_admin(env: Env) -> Address {
+        storage::get_admin(&env)
+    }
+
+    /// Get the token contract address
+    pub fn get_token(env: Env) -> Address {
+        storage::get_token(&env)
+    }
+}
+
+impl SplitEscrowContract {
+    fn create_split_internal(
+        env: &Env,
+        creator: Address,
+        description: String,
+        total_amount: i128,
+        participant_addresses: Vec<Address>,
+        participant_shares: Vec<i128>,
+        options: SplitOptions,
+    ) -> Result<u64, Error> {
+        // Verify the creator is authorizing this call
+        creator.require_auth();
+
+        Self::store_new_split(
+            env,
+            creator,
+            description,
+            total_amount,
+            participant_addresses,
+            participant_shares,
+            options,
+            false,
+        )
+    }
+
+    /// Validate and store a new split; callers handle authorization
+    ///
+    /// A `staged` split puts up its expiry bounty when the creator accepts
+    /// it, since they haven't signed yet.
+    #[allow(clippy::too_many_arguments)]
+    fn store_new_split(
+        env: &Env,
+        creator: Address,
+        description: String,
+        total_amount: i128,
+        participant_addresses: Vec<Address>,
+        participant_shares: Vec<i128>,
+        options: SplitOptions,
+        staged: bool,
+    ) -> Result<u64, Error> {
+        // A retried call gets the split the first attempt made
+        if let Some(key) = options.idempotency_key.get() {
+            if let Some(split_id) = storage::get_idempotent_split(env, &creator, key) {
+                return Ok(split_id);
+            }
+        }
+
+        if storage::get_pause_flags(env).create || breaker::blocks_creation(env) {
+            return Err(Error::ContractPaused);
+        }
+
+        let max_open = storage::get_max_open_splits(env);
+        if max_open > 0 && storage::get_open_count(env, &creator) >= max_open {
+            return Err(Error::TooManyOpenSplits);
+        }
+
+        // Validate inputs
+        if participant_addresses.len() != participant_shares.len() {
+            return Err(Error::InvalidParticipants);
+        }
+
+        // Open splits start empty and fill up through `join_split`; every
+        // joiner needs at least one stroop of the total
+        if options.max_headcount > 0 {
+            if !participant_addresses.is_empty() || options.max_headcount > MAX_HEADCOUNT {
+                return Err(Error::InvalidParticipants);
+            }
+            if total_amount < options.max_headcount as i128 {
+                return Err(Error::InvalidAmount);
+            }
+        } else if participant_addresses.is_empty() {
+            return Err(Error::InvalidParticipants);
+        }
+
+        // Deposits are attributed by address, so each one may appear once
+        for (i, address) in participant_addresses.iter().enumerate() {
+            if participant_addresses.first_index_of(&address) != Some(i as u32) {
+                return Err(Error::InvalidParticipants);
+            }
+        }
+
+        // Every amount must be positive: a zero or negative share would let
+        // a participant count as paid without paying, or inflate the others
+        if total_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let mut shares_sum: i128 = 0;
+        for share in participant_shares.iter() {
+            if share <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            shares_sum = shares_sum.checked_add(share).ok_or(Error::InvalidAmount)?;
+        }
+
+        // Validate shares sum to total
+        if options.max_headcount == 0 && shares_sum != total_amount {
+            return Err(Error::SharesMismatch);
+        }
+
+        // Private splits must never carry the plaintext on-chain
+        if options.description_hash.get().is_some() && !description.is_empty() {
+            return Err(Error::PlaintextDescription);
+        }
+
+        // Contact hashes line up with the participants; an empty one skips
+        if let Some(hashes) = &options.contact_hashes {
+            if hashes.len() != participant_addresses.len() {
+                return Err(Error::InvalidParticipants);
+            }
+            if hashes.iter().any(|h| !h.is_empty() && h.len() != 32) {
+                return Err(Error::InvalidHash);
+            }
+        }
+
+        if let Some(deadline) = options.deadline {
+            if deadline <= env.ledger().timestamp() {
+                return Err(Error::InvalidDeadline);
+            }
+        }
+        if let Some(sequence) = options.deadline_ledger {
+            if sequence <= env.ledger().sequence() {
+                return Err(Error::InvalidDeadline);
+            }
+        }
+
+        // Only escrow splits have a single release to return the bond at
+        if options.bond < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if options.bond > 0 && options.mode != SplitMode::Escrow {
+            return Err(Error::UnsupportedMode);
+        }
+        if options.rent_budget < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if options.rent_budget > 0 && storage::get_rent_config(env).is_none() {
+            return Err(Error::InvalidConfig);
+        }
+        // Round-ups wait in escrow for the release, which only escrow
+        // splits have
+        if options.round_up_unit < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if options.round_up_unit > 0 {
+            if options.round_up_charity.is_none() {
+                return Err(Error::InvalidConfig);
+            }
+            if options.mode != SplitMode::Escrow {
+                return Err(Error::UnsupportedMode);
+            }
+        }
+        // Share tokens are minted once, for a fixed participant list
+        if options.tokenized {
+            if options.max_headcount > 0 || options.crowdfund {
+                return Err(Error::UnsupportedMode);
+            }
+            if storage::get_share_token(env).is_none() {
+                return Err(Error::InvalidConfig);
+            }
+        }
+        // Contributions are deposits, which IOU splits never take
+        if options.crowdfund && options.mode == SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+        // Pass-through already forwards deposits; IOU never receives any
+        if options.reimburse && options.mode != SplitMode::Escrow {
+            return Err(Error::UnsupportedMode);
+        }
+        // Delayed and confirmed releases can't coexist with paying out every
+        // deposit, or with each other
+        let gated = options.release_delay > 0 || options.confirmer.is_some();
+        if gated && (options.mode != SplitMode::Escrow || options.reimburse) {
+            return Err(Error::UnsupportedMode);
+        }
+        if options.release_delay > 0 && options.confirmer.is_some() {
+            return Err(Error::UnsupportedMode);
+        }
+        // Invitations lapse for want of a deposit, and their shares have to
+        // be free to move to someone else
+        if options.invite_window > 0
+            && (options.mode == SplitMode::Iou || options.max_headcount > 0 || options.tokenized)
+        {
+            return Err(Error::UnsupportedMode);
+        }
+        // Retainage is held back from a release, so it needs money in escrow
+        if options.retainage_bps > 0 {
+            if options.retainage_bps > shares::BPS_DENOMINATOR as u32
+                || options.retainage_window == 0
+            {
+                return Err(Error::InvalidConfig);
+            }
+            if options.mode != SplitMode::Escrow || options.reimburse {
+                return Err(Error::UnsupportedMode);
+            }
+        }
+        // Settling the creator's own share up front needs them on the list,
+        // and collecting it needs a split that takes deposits
+        if options.creator_share != CreatorShare::Owed {
+            if !participant_addresses.contains(&creator) {
+                return Err(Error::InvalidParticipants);
+            }
+            if options.creator_share == CreatorShare::Collected && options.mode == SplitMode::Iou {
+                return Err(Error::UnsupportedMode);
+            }
+        }
+        // A credited creator share leaves the split like a write-off, so
+        // only what the others owe is ever collected (or insured)
+        let credited = match options.creator_share {
+            CreatorShare::Credited => {
+                let index = participant_addresses.first_index_of(&creator).unwrap();
+                participant_shares.get(index).unwrap()
+            }
+            _ => 0,
+        };
+        if total_amount - credited <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let total_amount = total_amount - credited;
+        // Nobody gets listed past their own spending cap; the creator
+        // listing themselves is their own call
+        for (address, share) in participant_addresses.iter().zip(participant_shares.iter()) {
+            if address != creator {
+                Self::check_spending_cap(env, &address, share)?;
+            }
+        }
+
+        // IOU splits never hold money, so there's nothing to insure
+        let premium = if options.insured {
+            if options.mode == SplitMode::Iou {
+                return Err(Error::UnsupportedMode);
+            }
+            let config = storage::get_insurance_config(env).ok_or(Error::InvalidConfig)?;
+            shares::apportion(
+                total_amount,
+                config.premium_bps as i128,
+                shares::BPS_DENOMINATOR,
+                storage::get_rounding_policy(env),
+            )
+            .part
+        } else {
+            0
+        };
+
+        if let Some(symbol) = &options.display_symbol {
+            if symbol.is_empty() || symbol.len() > MAX_DISPLAY_SYMBOL_LEN {
+                return Err(Error::InvalidSymbol);
+            }
+        }
+
+        let tags = options.tags.clone().unwrap_or(Vec::new(env));
+        if tags.len() > MAX_TAGS {
+            return Err(Error::TooManyTags);
+        }
+        for i in 0..tags.len() {
+            if tags.slice(i + 1..).contains(tags.get(i).unwrap()) {
+                return Err(Error::DuplicateTag);
+            }
+        }
+
+        // Get the next split ID
+        let split_id = storage::get_next_split_id(env);
+
+        // Create participant entries
+        let mut participants = Vec::new(env);
+        for i in 0..participant_addresses.len() {
+            let address = participant_addresses.get(i).unwrap();
+            let credit = credited > 0 && address == creator;
+            let participant = Participant {
+                address,
+                share_amount: if credit {
+                    0
+                } else {
+                    participant_shares.get(i).unwrap()
+                },
+                amount_paid: 0,
+                has_paid: credit,
+            };
+            participants.push_back(participant);
+        }
+
+        // Create the split
+        let split = Split {
+            id: split_id,
+            creator: creator.clone(),
+            description,
+            description_hash: options.description_hash.clone(),
+            total_amount,
+            amount_collected: 0,
+            amount_released: 0,
+            fees_paid: 0,
+            participants,
+            status: SplitStatus::Pending,
+            mode: options.mode,
+            category: options.category,
+            receipt_ref: None,
+            tags: tags.clone(),
+            deadline: options.deadline,
+            completion_hook: options.completion_hook.clone(),
+            created_at: env.ledger().timestamp(),
+            metadata_uri: None,
+            nonce: 0,
+        };
+
+        // Store the split; a fresh entry gets the full TTL
+        storage::set_split(env, split_id, &split);
+        let (_, extend_to) = storage::get_ttl(env);
+        storage::set_live_until(env, split_id, env.ledger().sequence() + extend_to);
+        storage::set_ref_code(env, split_id, &refcode::encode(env, split_id));
+        if let Some(sequence) = options.deadline_ledger {
+            storage::set_deadline_ledger(env, split_id, sequence);
+        }
+        if let Some(max) = options.max_extensions {
+            storage::set_max_extensions(env, split_id, max);
+        }
+        if options.bond > 0 {
+            let token_address = storage::get_token(env);
+            token::Client::new(env, &token_address).transfer(
+                &creator,
+                &env.current_contract_address(),
+                &options.bond,
+            );
+            storage::set_bond(env, split_id, options.bond);
+            storage::add_total_escrowed(env, &token_address, options.bond);
+            events::emit_bond_locked(env, split_id, &creator, options.bond);
+        }
+        if !staged {
+            Self::lock_bounty_stake(env, &split);
+        }
+        if options.rent_budget > 0 {
+            let rent_token = storage::get_rent_config(env).unwrap().token;
+            token::Client::new(env, &rent_token).transfer(
+                &creator,
+                &env.current_contract_address(),
+                &options.rent_budget,
+            );
+            storage::set_rent_budget(
+                env,
+                split_id,
+                &RentBudget {
+                    remaining: options.rent_budget,
+                    token: rent_token.clone(),
+                    last_bump: env.ledger().sequence(),
+                },
+            );
+            storage::add_total_escrowed(env, &rent_token, options.rent_budget);
+            events::emit_rent_locked(env, split_id, options.rent_budget);
+        }
+        if options.round_up_unit > 0 {
+            storage::set_round_up(
+                env,
+                split_id,
+                &RoundUp {
+                    unit: options.round_up_unit,
+                    charity: options.round_up_charity.clone().unwrap(),
+                },
+            );
+        }
+        if options.tokenized {
+            storage::set_tokenized(env, split_id);
+            share_token::mint_shares(env, &split)?;
+        }
+        if let Some(hashes) = &options.contact_hashes {
+            for (address, hash) in participant_addresses.iter().zip(hashes.iter()) {
+                if !hash.is_empty() {
+                    storage::set_contact_hash(env, split_id, &address, &hash);
+                }
+            }
+        }
+        if options.require_full_payment {
+            storage::set_require_full_payment(env, split_id);
+        }
+        if options.max_headcount > 0 {
+            storage::set_join_cap(env, split_id, options.max_headcount);
+        }
+        if options.crowdfund {
+            storage::set_crowdfund(env, split_id);
+        }
+        if options.reimburse {
+            storage::set_reimburses_creator(env, split_id);
+        }
+        if options.release_delay > 0 {
+            storage::set_release_delay(env, split_id, options.release_delay);
+        }
+        if options.invite_window > 0 {
+            storage::set_invite_window(env, split_id, options.invite_window);
+        }
+        if options.retainage_bps > 0 {
+            storage::set_retainage(
+                env,
+                split_id,
+                &Retainage {
+                    bps: options.retainage_bps,
+                    window: options.retainage_window,
+                    held: 0,
+                    claimable_at: 0,
+                },
+            );
+        }
+        if let Some(confirmer) = &options.confirmer {
+            storage::set_confirmer(env, split_id, confirmer);
+        }
+        if options.confirm_offchain {
+            storage::set_requires_offchain_confirmation(env, split_id);
+        }
+        if options.visibility != Visibility::Public {
+            storage::set_visibility(env, split_id, options.visibility);
+        }
+        if options.insured {
+            storage::set_insured(env, split_id);
+            if premium > 0 {
+                let token_address = storage::get_token(env);
+                token::Client::new(env, &token_address).transfer(
+                    &creator,
+                    &env.current_contract_address(),
+                    &premium,
+                );
+                storage::add_insurance_pool(env, premium);
+                storage::add_total_escrowed(env, &token_address, premium);
+                events::emit_premium_paid(env, split_id, premium);
+            }
+        }
+        storage::set_split_display(
+            env,
+            split_id,
+            &SplitDisplay {
+                decimals: token::Client::new(env, &storage::get_token(env)).decimals(),
+                symbol: options.display_symbol.clone(),
+            },
+        );
+        storage::add_to_creator_index(env, &creator, split_id);
+        stats::record_created(env, &creator);
+        if let Some(key) = options.idempotency_key.get() {
+            storage::set_idempotent_split(env, &creator, key, split_id);
+        }
+        storage::add_to_day_index(env, &creator, split.created_at, split_id);
+        breaker::record_creation(env);
+        storage::add_to_category_index(env, &creator, options.category, split_id);
+        for i in 0..participant_addresses.len() {
+            let participant = participant_addresses.get(i).unwrap();
+            if !participant_addresses.slice(..i).contains(&participant) {
+                storage::add_to_participant_index(env, &participant, split_id);
+                stats::record_joined(env, &participant);
+            }
+        }
+        for tag in tags.iter() {
+            storage::add_to_tag_index(env, &creator, &tag, split_id);
+        }
+
+        let terms_hash = Self::commit_terms(env, &split);
+
+        // Emit creation event
+        events::emit_split_created(env, split_id, &creator, total_amount);
+        events::emit_terms_hash(env, split_id, &terms_hash);
+        if let Some(hash) = options.description_hash.get() {
+            events::emit_description_hash(env, split_id, hash);
+        }
+
+        match options.creator_share {
+            CreatorShare::Owed => {}
+            CreatorShare::Credited => {
+                storage::set_forgiven(env, split_id, &creator, credited);
+                events::emit_written_off(env, split_id, &creator, credited);
+            }
+            CreatorShare::Collected => {
+                let index = participant_addresses.first_index_of(&creator).unwrap();
+                let share = participant_shares.get(index).unwrap();
+                Self::deposit_internal(env, split_id, &creator, &creator, share)?;
+            }
+        }
+
+        Ok(split_id)
+    }
+
+    /// Build the terms a split commits to from its stored record
+    fn split_terms(env: &Env, split: &Split) -> SplitTerms {
+        let mut participants = Vec::new(env);
+        let mut shares = Vec::new(env);
+        for p in split.participants.iter() {
+            participants.push_back(p.address);
+            shares.push_back(p.share_amount);
+        }
+        let description_hash = match split.description_hash.get() {
+            Some(hash) => hash.clone(),
+            None => env
+                .crypto()
+                .sha256(&split.description.clone().to_xdr(env))
+                .into(),
+        };
+        SplitTerms {
+            contract: env.current_contract_address(),
+            split_id: split.id,
+            creator: split.creator.clone(),
+            token: storage::get_token(env),
+            total_amount: split.total_amount,
+            participants,
+            shares,
+            deadline: split.deadline,
+            deadline_ledger: storage::get_deadline_ledger(env, split.id),
+            description_hash,
+        }
+    }
+
+    /// Hash a new split's terms and store the hash for good
+    fn commit_terms(env: &Env, split: &Split) -> BytesN<32> {
+        let terms = Self::split_terms(env, split);
+        let hash: BytesN<32> = env.crypto().sha256(&terms.to_xdr(env)).into();
+        storage::set_terms_hash(env, split.id, &hash);
+        hash
+    }
+
+    /// SHA-256 of a string's UTF-8 bytes, or None if it exceeds the buffer
+    fn hash_string(env: &Env, value: &String) -> Option<BytesN<32>> {
+        let len = value.len() as usize;
+        if len > MAX_HASHED_STRING_LEN {
+            return None;
+        }
+
+        let mut buf = [0u8; MAX_HASHED_STRING_LEN];
+        value.copy_into_slice(&mut buf[..len]);
+        let bytes = Bytes::from_slice(env, &buf[..len]);
+        Some(env.crypto().sha256(&bytes).into())
+    }
+
+    fn expire_split_internal(env: &Env, split_id: u64) -> Result<(), Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        Self::check_no_emergency(env)?;
+        Self::check_not_frozen(env, split_id)?;
+
+        let mut split = storage::get_split(env, split_id);
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active => {}
+        }
+
+        if !Self::is_past_deadline(env, &split) {
+            return Err(Error::DeadlineNotReached);
+        }
+
+        lifecycle::transition(env, &mut split, SplitStatus::Expired, Action::Expire);
+        storage::set_split(env, split_id, &split);
+
+        events::emit_split_expired(env, split_id);
+
+        Ok(())
+    }
+
+    /// Whether the split has a deadline and it has passed
+    ///
+    /// A split can carry a timestamp deadline, a ledger sequence deadline,
+    /// or both; passing either one is enough.
+    fn is_past_deadline(env: &Env, split: &Split) -> bool {
+        let past_timestamp = match split.deadline {
+            Some(deadline) => env.ledger().timestamp() > deadline,
+            None => false,
+        };
+        let past_sequence = match storage::get_deadline_ledger(env, split.id) {
+            Some(sequence) => env.ledger().sequence() > sequence,
+            None => false,
+        };
+        past_timestamp || past_sequence
+    }
+
+    /// Turn an operation outcome into a batch result entry
+    fn batch_result(split_id: u64, outcome: Result<(), Error>) -> BatchResult {
+        match outcome {
+            Ok(()) => BatchResult {
+                split_id,
+                success: true,
+                error: 0,
+            },
+            Err(err) => BatchResult {
+                split_id,
+                success: false,
+                error: err as u32,
+            },
+        }
+    }
+
+    /// Build the participant read models of a split
+    fn participant_views(env: &Env, split: &Split) -> Vec<ParticipantView> {
+        let split_id = split.id;
+        let past_deadline = settlement::is_open(split) && Self::is_past_deadline(env, split);
+
+        let mut views = Vec::new(env);
+        for p in split.participants.iter() {
+            let remaining = core::cmp::max(p.share_amount - p.amount_paid, 0);
+            let pct_paid = if p.share_amount > 0 {
+                core::cmp::min(p.amount_paid * 10_000 / p.share_amount, 10_000) as u32
+            } else {
+                10_000
+            };
+
+            views.push_back(ParticipantView {
+                label: storage::get_participant_label(env, split_id, &p.address),
+                settled_offchain: storage::get_offchain_settled(env, split_id, &p.address),
+                forgiven: storage::get_forgiven(env, split_id, &p.address),
+                address: p.address,
+                share: p.share_amount,
+                paid: p.amount_paid,
+                remaining,
+                pct_paid,
+                overdue: past_deadline && remaining > 0,
+            });
+        }
+
+        views
+    }
+
+    /// Check that `actor` is the participant or their registered delegate
+    fn require_actor(env: &Env, actor: &Address, participant: &Address) -> Result<(), Error> {
+        actor.require_auth();
+        if actor != participant && storage::get_delegate(env, participant).as_ref() != Some(actor) {
+            return Err(Error::AccessDenied);
+        }
+        Ok(())
+    }
+
+    /// Deposit toward a participant's share, paid by `payer`
+    fn deposit_internal(
+        env: &Env,
+        split_id: u64,
+        participant: &Address,
+        payer: &Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(env, split_id);
+        let index = Self::check_deposit(env, &split, participant, amount)?;
+
+        Self::take_deposit(env, split, index, participant, payer, amount, false)
+    }
+
+    /// Record a participant's acceptance of a proposed total increase
+    fn accept_increase_internal(
+        env: &Env,
+        split_id: u64,
+        participant: &Address,
+    ) -> Result<bool, Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        Self::check_no_emergency(env)?;
+        Self::check_not_frozen(env, split_id)?;
+        let mut increase =
+            storage::get_total_increase(env, split_id).ok_or(Error::ActionNotFound)?;
+        let index = increase
+            .pending
+            .first_index_of(participant)
+            .ok_or(Error::ParticipantNotFound)?;
+        let mut split = storage::get_split(env, split_id);
+        Self::check_increasable(&split)?;
+
+        increase.pending.remove(index);
+        events::emit_increase_accepted(env, split_id, participant);
+        if !increase.pending.is_empty() {
+            storage::set_total_increase(env, split_id, &increase);
+            return Ok(false);
+        }
+
+        // The shares were agreed per address, so they only apply to the
+        // participants they were proposed for
+        if increase.shares.len() != split.participants.len() {
+            return Err(Error::SharesMismatch);
+        }
+        for i in 0..split.participants.len() {
+            let mut p = split.participants.get(i).unwrap();
+            let share = increase
+                .shares
+                .iter()
+                .find(|s| s.participant == p.address)
+                .map(|s| s.share)
+                .ok_or(Error::SharesMismatch)?;
+            if share < p.share_amount {
+                return Err(Error::SharesMismatch);
+            }
+            p.share_amount = share;
+            p.has_paid = p.amount_paid >= share;
+            split.participants.set(i, p);
+        }
+        let old_total = split.total_amount;
+        split.total_amount = increase.new_total;
+        // A split held at Completed is collecting again
+        if split.status == SplitStatus::Completed {
+            lifecycle::transition(env, &mut split, SplitStatus::Active, Action::Reopen);
+        }
+        storage::set_split(env, split_id, &split);
+        storage::remove_total_increase(env, split_id);
+        events::emit_total_increased(env, split_id, old_total, increase.new_total);
+
+        Ok(true)
+    }
+
+    /// Refund a participant's held balances on a dead split
+    fn claim_refund_internal(
+        env: &Env,
+        split_id: u64,
+        participant: &Address,
+    ) -> Result<i128, Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        if storage::get_pause_flags(env).refund {
+            return Err(Error::ContractPaused);
+        }
+        let split = storage::get_split(env, split_id);
+        if split.status != SplitStatus::Cancelled && split.status != SplitStatus::Expired {
+            return Err(Error::NotRefundable);
+        }
+
+        Self::refund_held(env, split, participant)
+    }
+
+    /// Load a split for a view, enforcing its visibility
+    ///
+    /// Restricted splits need `viewer` to be the creator or a listed
+    /// participant and to have authorized the call.
+    fn viewable_split(env: &Env, split_id: u64, viewer: Option<&Address>) -> Result<Split, Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(env, split_id);
+        if let Some(viewer) = viewer {
+            viewer.require_auth();
+        }
+        if storage::get_visibility(env, split_id) == Visibility::Public {
+            return Ok(split);
+        }
+
+        let viewer = viewer.ok_or(Error::AccessDenied)?;
+        let member =
+            split.creator == *viewer || split.participants.iter().any(|p| p.address == *viewer);
+        if !member {
+            return Err(Error::AccessDenied);
+        }
+        Ok(split)
+    }
+
+    /// Cancel one of `creator`'s splits that nobody has paid into
+    fn cancel_pending(
+        env: &Env,
+        creator: &Address,
+        split_id: u64,
+        reason: &String,
+    ) -> Result<(), Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if storage::get_pause_flags(env).refund {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(env, split_id)?;
+        let mut split = storage::get_split(env, split_id);
+        if split.creator != *creator {
+            return Err(Error::AccessDenied);
+        }
+        match split.status {
+            SplitStatus::Pending => {}
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Active | SplitStatus::Completed => return Err(Error::HasDeposits),
+        }
+
+        lifecycle::transition(env, &mut split, SplitStatus::Cancelled, Action::Cancel);
+        storage::set_split(env, split_id, &split);
+        Self::record_cancel(env, split_id, reason);
+
+        Ok(())
+    }
+
+    /// Count approvals from participants who still have money in, and
+    /// how many participants have money in
+    fn cancel_votes(split: &Split, approvals: &Vec<Address>) -> (u32, u32) {
+        let mut approved = 0;
+        let mut paid = 0;
+        for p in split.participants.iter() {
+            if p.amount_paid > 0 {
+                paid += 1;
+                if approvals.contains(&p.address) {
+                    approved += 1;
+                }
+            }
+        }
+        (approved, paid)
+    }
+
+    /// Whether enough paid participants agreed for the creator to cancel
+    fn cancel_approved(env: &Env, split: &Split) -> bool {
+        let quorum = storage::get_cancel_quorum(env) as u64;
+        if quorum == 0 {
+            return true;
+        }
+        let approvals = storage::get_cancel_approvals(env, split.id);
+        let (approved, paid) = Self::cancel_votes(split, &approvals);
+        let needed = (paid as u64 * quorum).div_ceil(shares::BPS_DENOMINATOR as u64);
+        approved as u64 >= needed
+    }
+
+    /// Where a participant's refunds should be sent
+    fn refund_recipient(env: &Env, participant: &Address) -> Address {
+        storage::get_refund_address(env, participant).unwrap_or(participant.clone())
+    }
+
+    /// Check that a split's participant slots can still change hands
+    fn check_slot_change(env: &Env, split: &Split) -> Result<(), Error> {
+        Self::check_no_emergency(env)?;
+        Self::check_not_frozen(env, split.id)?;
+        match split.status {
+            SplitStatus::Pending | SplitStatus::Active => {}
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+        }
+        if storage::get_dispute(env, split.id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+        if storage::get_total_increase(env, split.id).is_some() {
+            return Err(Error::ActionNotReady);
+        }
+        Ok(())
+    }
+
+    /// Load a split for its creator to deal with a lapsed participant
+    fn lapsed_slot(env: &Env, split_id: u64, participant: &Address) -> Result<(Split, u32), Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        let split = storage::get_split(env, split_id);
+        split.creator.require_auth();
+        Self::check_slot_change(env, &split)?;
+
+        let index = split
+            .participants
+            .iter()
+            .position(|p| p.address == *participant)
+            .ok_or(Error::ParticipantNotFound)? as u32;
+        if !storage::is_lapsed(env, split_id, participant) {
+            return Err(Error::ActionNotReady);
+        }
+        Ok((split, index))
+    }
+
+    /// Refuse to put `added` more on an address than its spending cap allows
+    fn check_spending_cap(env: &Env, address: &Address, added: i128) -> Result<(), Error> {
+        let Some(cap) = storage::get_spending_cap(env, address) else {
+            return Ok(());
+        };
+        // Stop at the first page that passes the cap, so an address named in
+        // many open splits costs no more to check than it has to
+        let mut owed = added;
+        let mut page = 0;
+        loop {
+            let ids = storage::get_participant_page(env, address, page);
+            if ids.is_empty() {
+                return Ok(());
+            }
+            owed = owed.saturating_add(Self::owed_across(env, address, &ids).total);
+            if owed > cap {
+                return Err(Error::SpendingCapExceeded);
+            }
+            page += 1;
+        }
+    }
+
+    /// Sum what a participant still owes across some splits, skipping any
+    /// that have closed
+    fn owed_across(env: &Env, participant: &Address, split_ids: &Vec<u64>) -> OwedSummary {
+        let mut summary = OwedSummary {
+            total: 0,
+            split_count: 0,
+        };
+
+        for split_id in split_ids.iter() {
+            let split = storage::get_split(env, split_id);
+            if !settlement::is_open(&split) {
+                continue;
+            }
+
+            let owed: i128 = split
+                .participants
+                .iter()
+                .filter(|p| p.address == *participant)
+                .map(|p| p.share_amount - p.amount_paid)
+                .sum();
+            if owed > 0 {
+                summary.total += owed;
+                summary.split_count += 1;
+            }
+        }
+
+        summary
+    }
+
+    /// Fail fast when the caller edited an older version of the split
+    ///
+    /// Clients pass the `nonce` they last read; `None` skips the check for
+    /// callers that don't care about concurrent edits.
+    fn check_nonce(split: &Split, expected: Option<u64>) -> Result<(), Error> {
+        match expected {
+            Some(nonce) if nonce != split.nonce => Err(Error::StaleNonce),
+            _ => Ok(()),
+        }
+    }
+
+    /// Reject a missing or oversized cancellation reason
+    fn check_cancel_reason(reason: &String) -> Result<(), Error> {
+        if reason.is_empty() {
+            return Err(Error::CancelReasonRequired);
+        }
+        if reason.len() > MAX_CANCEL_REASON_LEN {
+            return Err(Error::DescriptionTooLong);
+        }
+        Ok(())
+    }
+
+    /// Keep why a split was cancelled and tell everyone watching it
+    fn record_cancel(env: &Env, split_id: u64, reason: &String) {
+        storage::set_cancel_reason(env, split_id, reason);
+        events::emit_split_cancelled(env, split_id, reason);
+    }
+
+    /// Load a split `batch_release` can pay out right away
+    ///
+    /// Quoting runs the fee module too, so a failing one skips the split
+    /// instead of aborting the batch.
+    fn check_batch_release(env: &Env, split_id: u64) -> Result<Split, Error> {
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+        if storage::get_confirmer(env, split_id).is_some()
+            || storage::get_release_delay(env, split_id) > 0
+        {
+            return Err(Error::UnsupportedMode);
+        }
+        let split = storage::get_split(env, split_id);
+        Self::quote_release_internal(env, &split)?;
+        Ok(split)
+    }
+
+    /// The first `PAGE_SIZE` of `ids` that name a stored split
+    fn existing_ids(env: &Env, ids: &Vec<u64>) -> Vec<u64> {
+        let mut existing = Vec::new(env);
+        for split_id in ids.iter().take(PAGE_SIZE as usize) {
+            if storage::has_split(env, split_id) {
+                existing.push_back(split_id);
+            }
+        }
+        existing
+    }
+
+    /// Load the splits for a list of IDs, in order
+    ///
+    /// Splits restricted to their participants are left out, unless
+    /// `viewer`, who the caller has already authenticated, is one of them.
+    fn load_splits(env: &Env, ids: &Vec<u64>, viewer: Option<&Address>) -> Vec<Split> {
+        let mut splits = Vec::new(env);
+        for split_id in ids.iter() {
+            let split = storage::get_split(env, split_id);
+            let shown = storage::get_visibility(env, split_id) == Visibility::Public
+                || viewer.is_some_and(|viewer| {
+                    split.creator == *viewer
+                        || split.participants.iter().any(|p| p.address == *viewer)
+                });
+            if shown {
+                splits.push_back(split);
+            }
+        }
+        splits
+    }
+
+    /// Build the headline numbers of a split
+    fn summarize(env: &Env, split: &Split) -> SplitSummary {
+        SplitSummary {
+            id: split.id,
+            creator: split.creator.clone(),
+            status: split.status.clone(),
+            total_amount: split.total_amount,
+            amount_collected: split.amount_collected,
+            participant_count: split.participants.len(),
+            paid_count: split.participants.iter().filter(|p| p.has_paid).count() as u32,
+            deadline: split.deadline,
+            frozen: storage::is_frozen(env, split.id),
+        }
+    }
+
+    /// Check that an imported split record is self-consistent
+    fn check_imported(split: &Split) -> Result<(), Error> {
+        if split.participants.is_empty() || split.total_amount <= 0 {
+            return Err(Error::InvalidParticipants);
+        }
+        let mut shares = 0;
+        let mut paid = 0;
+        for (i, p) in split.participants.iter().enumerate() {
+            if split
+                .participants
+                .slice(..i as u32)
+                .iter()
+                .any(|q| q.address == p.address)
+            {
+                return Err(Error::InvalidParticipants);
+            }
+            if p.share_amount < 0 || p.amount_paid < 0 || p.amount_paid > p.share_amount {
+                return Err(Error::InvalidAmount);
+            }
+            shares += p.share_amount;
+            paid += p.amount_paid;
+        }
+        if shares != split.total_amount {
+            return Err(Error::SharesMismatch);
+        }
+        if paid != split.amount_collected
+            || split.amount_released < 0
+            || split.amount_released > split.amount_collected
+            || split.fees_paid < 0
+            || split.fees_paid > split.amount_released
+        {
+            return Err(Error::InvalidAmount);
+        }
+
+        // Later transitions trust the status, so it has to match the money
+        let funded = paid == split.total_amount;
+        let consistent = match split.status {
+            SplitStatus::Pending => paid == 0,
+            SplitStatus::Active => !funded,
+            SplitStatus::Completed => funded,
+            SplitStatus::Released => funded && split.amount_released == paid,
+            SplitStatus::Cancelled | SplitStatus::Expired => true,
+        };
+        if !consistent {
+            return Err(Error::InvalidConfig);
+        }
+        Ok(())
+    }
+
+    /// Divide `total` by units, returning the shares and the unit price
+    fn unit_amounts(env: &Env, total: i128, units: &Vec<u32>) -> Result<(Vec<i128>, i128), Error> {
+        let count = units.len() as usize;
+        if count > shares::MAX_UNIT_HOLDERS {
+            return Err(Error::InvalidParticipants);
+        }
+        let mut held = [0u32; shares::MAX_UNIT_HOLDERS];
+        for (i, u) in units.iter().enumerate() {
+            held[i] = u;
+        }
+        let mut amounts = [0i128; shares::MAX_UNIT_HOLDERS];
+        let price = shares::unit_shares(total, &held[..count], &mut amounts[..count])?;
+
+        let mut out = Vec::new(env);
+        for amount in amounts[..count].iter() {
+            out.push_back(*amount);
+        }
+        Ok((out, price))
+    }
+
+    /// Re-derive every share of a unit-priced split from its total
+    fn apply_units(env: &Env, split: &mut Split, units: &mut UnitShares) -> Result<(), Error> {
+        let (amounts, price) = Self::unit_amounts(env, split.total_amount, &units.units)?;
+        for (i, amount) in amounts.iter().enumerate() {
+            let mut p = split.participants.get(i as u32).unwrap();
+            p.share_amount = amount;
+            split.participants.set(i as u32, p);
+        }
+        units.unit_price = price;
+        Ok(())
+    }
+
+    fn is_fully_funded_internal(split: &Split) -> bool {
+        let mut total_paid: i128 = 0;
+        for i in 0..split.participants.len() {
+            total_paid += split.participants.get(i).unwrap().amount_paid;
+        }
+        total_paid >= split.total_amount
+    }
+
+    /// Whether a fully funded split waits at Completed for an explicit release
+    ///
+    /// Splits paying out in another asset, under dispute, with a release
+    /// delay or with a confirmer are held; everything else is released
+    /// straight away.
+    fn holds_at_completion(env: &Env, split: &Split) -> bool {
+        let split_id = split.id;
+        storage::get_payout_asset(env, split_id).is_some()
+            || storage::get_dispute(env, split_id).is_some()
+            || storage::get_release_delay(env, split_id) > 0
+            || storage::get_confirmer(env, split_id).is_some()
+            || Self::awaits_schedule(env, split_id)
+            || Self::has_unfunded_children(env, split_id)
+            || storage::get_pause_flags(env).release
+            || storage::is_frozen(env, split_id)
+            || breaker::blocks_release(env, split.amount_collected - split.amount_released)
+    }
+
+    /// The late-release penalty a completed split has run up, in basis points
+    ///
+    /// The clock starts when the split was completed and held, plus any
+    /// release delay it was created with or its scheduled release time, so
+    /// waiting periods the creator can't skip don't count against them.
+    fn late_penalty_bps(env: &Env, split: &Split) -> u32 {
+        let (Some(penalty), Some(completed_at)) = (
+            storage::get_late_penalty(env),
+            storage::get_completed_at(env, split.id),
+        ) else {
+            return 0;
+        };
+        let held_until = (completed_at + storage::get_release_delay(env, split.id))
+            .max(storage::get_scheduled_release(env, split.id).unwrap_or(0));
+        let due = held_until + penalty.grace;
+        let now = env.ledger().timestamp();
+        if now <= due {
+            return 0;
+        }
+        let days = (now - due).div_ceil(storage::DAY_SECONDS);
+        (days.saturating_mul(penalty.bps_per_day as u64)).min(penalty.max_bps as u64) as u32
+    }
+
+    /// Split a late-release penalty of `bps` on `native` between the
+    /// participants, in proportion to what they paid
+    ///
+    /// The pot itself rounds per the rounding policy. Dividing it floors,
+    /// leaving any stroops of dust with the creator.
+    fn late_penalties(env: &Env, split: &Split, native: i128, bps: u32) -> Vec<(Address, i128)> {
+        let mut penalties = Vec::new(env);
+        if bps == 0 || native <= 0 || split.amount_collected <= 0 {
+            return penalties;
+        }
+        let pot = shares::apportion(
+            native,
+            bps as i128,
+            shares::BPS_DENOMINATOR,
+            storage::get_rounding_policy(env),
+        )
+        .part;
+        for p in split.participants.iter() {
+            let back = pot * p.amount_paid / split.amount_collected;
+            if back > 0 {
+                penalties.push_back((p.address.clone(), back));
+            }
+        }
+        penalties
+    }
+
+    /// The late-release penalty a full release of `split` would pay now
+    fn pending_penalties(env: &Env, split: &Split, native: i128) -> Vec<(Address, i128)> {
+        if split.status != SplitStatus::Completed {
+            return Vec::new(env);
+        }
+        Self::late_penalties(env, split, native, Self::late_penalty_bps(env, split))
+    }
+
+    /// Whether a parent split still has a child that isn't fully funded
+    ///
+    /// Cancelled and expired children no longer block the parent.
+    fn has_unfunded_children(env: &Env, split_id: u64) -> bool {
+        storage::get_children(env, split_id).iter().any(|child_id| {
+            matches!(
+                storage::get_split(env, child_id).status,
+                SplitStatus::Pending | SplitStatus::Active
+            )
+        })
+    }
+
+    /// Finish a deposit that fully funded a split
+    ///
+    /// The split is marked Completed before any release is tried, so a
+    /// release that's refused (a broken fee module, say) leaves it waiting
+    /// at Completed, with its completion time recorded, for `release_funds`.
+    fn complete_or_release(env: &Env, split_id: u64, mut split: Split) {
+        lifecycle::transition(env, &mut split, SplitStatus::Completed, Action::Complete);
+        storage::set_split(env, split_id, &split);
+        storage::set_completed_at(env, split_id, env.ledger().timestamp());
+        events::emit_escrow_completed(env, split_id, split.total_amount);
+
+        if !Self::holds_at_completion(env, &split) {
+            if let Err(error) = Self::release_funds_internal(env, split_id, split.clone()) {
+                events::emit_release_failed(env, split_id, error as u32);
+            }
+        }
+        hooks::notify_completed(env, &split);
+    }
+
+    fn release_funds_internal(env: &Env, split_id: u64, mut split: Split) -> Result<i128, Error> {
+        let available = Self::check_release(env, &split)?;
+
+        let newly_completed = split.status != SplitStatus::Completed;
+        let penalty_bps = if newly_completed {
+            0
+        } else {
+            Self::late_penalty_bps(env, &split)
+        };
+        if newly_completed {
+            lifecycle::transition(env, &mut split, SplitStatus::Completed, Action::Complete);
+            events::emit_escrow_completed(env, split_id, split.total_amount);
+        }
+
+        // The split is marked released before any token moves
+        lifecycle::transition(env, &mut split, SplitStatus::Released, Action::Release);
+        let paid = Self::pay_out(env, &mut split, available, penalty_bps)?;
+        Self::return_bond(env, &split);
+        Self::return_bounty_stake(env, &split);
+        Self::return_rent(env, &split);
+        Self::donate_round_ups(env, &split);
+
+        events::emit_funds_released(
+            env,
+            split_id,
+            &split.creator,
+            paid,
+            env.ledger().timestamp(),
+        );
+
+        // Call out only after our own state is final
+        if newly_completed {
+            hooks::notify_completed(env, &split);
+        }
+
+        Ok(paid)
+    }
+
+    /// Whether a partly funded split forwards its deposits right away
+    ///
+    /// An open dispute holds them in escrow; the creator can draw them on
+    /// demand with `release_partial` once it's resolved.
+    fn drips(env: &Env, split: &Split) -> bool {
+        storage::reimburses_creator(env, split.id)
+            && storage::get_dispute(env, split.id).is_none()
+            && !storage::get_pause_flags(env).release
+            && !storage::is_frozen(env, split.id)
+            && !breaker::blocks_release(env, split.amount_collected - split.amount_released)
+    }
+
+    /// Pay everything collected but not yet released to the creator,
+    /// leaving the split's status as it is
+    fn release_available(env: &Env, split: &mut Split) -> Result<i128, Error> {
+        let available = split.amount_collected - split.amount_released;
+        let paid = Self::pay_out(env, split, available, 0)?;
+
+        events::emit_funds_released(
+            env,
+            split.id,
+            &split.creator,
+            paid,
+            env.ledger().timestamp(),
+        );
+
+        Ok(paid)
+    }
+
+    /// Save a split whose shares just shrank or were settled off-chain,
+    /// finishing it if that funded it
+    fn finish_if_funded(env: &Env, mut split: Split) {
+        if !Self::is_fully_funded_internal(&split) {
+            storage::set_split(env, split.id, &split);
+            return;
+        }
+
+        match split.mode {
+            SplitMode::Iou => {
+                lifecycle::transition(env, &mut split, SplitStatus::Completed, Action::Complete);
+                storage::set_split(env, split.id, &split);
+                events::emit_escrow_completed(env, split.id, split.total_amount);
+                hooks::notify_completed(env, &split);
+            }
+            _ if split.amount_collected == split.amount_released => {
+                Self::finish_without_escrow(env, split);
+            }
+            _ => Self::complete_or_release(env, split.id, split),
+        }
+    }
+
+    fn set_split_frozen(
+        env: &Env,
+        caller: &Address,
+        split_id: u64,
+        frozen: bool,
+    ) -> Result<(), Error> {
+        Self::require_brake_role(env, caller, !frozen)?;
+        if !storage::has_split(env, split_id) {
+            return Err(Error::SplitNotFound);
+        }
+
+        storage::set_frozen(env, split_id, frozen);
+        events::emit_split_frozen(env, split_id, frozen);
+        Ok(())
+    }
+
+    /// Refuse calls that would move money while an emergency is declared
+    fn check_no_emergency(env: &Env) -> Result<(), Error> {
+        if storage::is_emergency(env) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Fail with `ContractPaused` if the admin froze the split
+    fn check_not_frozen(env: &Env, split_id: u64) -> Result<(), Error> {
+        if storage::is_frozen(env, split_id) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+
+    /// Close a fully funded split whose money never passed through escrow
+    fn finish_without_escrow(env: &Env, mut split: Split) {
+        let newly_completed = split.status != SplitStatus::Completed;
+        if newly_completed {
+            events::emit_escrow_completed(env, split.id, split.total_amount);
+        }
+        lifecycle::transition(env, &mut split, SplitStatus::Released, Action::Release);
+        storage::set_split(env, split.id, &split);
+        Self::return_bond(env, &split);
+        Self::return_bounty_stake(env, &split);
+        Self::return_rent(env, &split);
+
+        if newly_completed {
+            hooks::notify_completed(env, &split);
+        }
     }
-}
 
-impl SplitEscrowContract {
-    fn is_fully_funded_internal(split: &Split) -> bool {
-        let mut total_paid: i128 = 0;
-        for i in 0..split.participants.len() {
-            total_paid += split.participants.get(i).unwrap().amount_paid;
+    /// Check that a split can still take on a higher total
+    fn check_increasable(split: &Split) -> Result<(), Error> {
+        match split.status {
+            SplitStatus::Cancelled => Err(Error::SplitCancelled),
+            SplitStatus::Released => Err(Error::SplitReleased),
+            SplitStatus::Expired => Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active | SplitStatus::Completed => Ok(()),
         }
-        total_paid >= split.total_amount
     }
 
-    fn release_funds_internal(env: &Env, split_id: u64, mut split: Split) -> Result<i128, Error> {
+    /// Send the unused part of a split's rent budget back to its creator
+    fn return_rent(env: &Env, split: &Split) -> i128 {
+        let Some(budget) = storage::take_rent_budget(env, split.id) else {
+            return 0;
+        };
+        let (remaining, rent_token) = (budget.remaining, budget.token);
+        if remaining <= 0 {
+            return 0;
+        }
+
+        storage::add_total_escrowed(env, &rent_token, -remaining);
+        token::Client::new(env, &rent_token).transfer(
+            &env.current_contract_address(),
+            &split.creator,
+            &remaining,
+        );
+        Self::log_transfer(
+            env,
+            split.id,
+            TransferKind::Rent,
+            &split.creator,
+            &rent_token,
+            remaining,
+        );
+        events::emit_rent_returned(env, split.id, remaining);
+        remaining
+    }
+
+    /// Take the expiry bounty from a new split's creator, if one applies
+    ///
+    /// Only splits with a deadline can be expired, so only they pay it.
+    fn lock_bounty_stake(env: &Env, split: &Split) {
+        let bounty = storage::get_expiry_bounty(env);
+        let expires =
+            split.deadline.is_some() || storage::get_deadline_ledger(env, split.id).is_some();
+        if bounty <= 0 || !expires {
+            return;
+        }
+
+        let token_address = storage::get_token(env);
+        token::Client::new(env, &token_address).transfer(
+            &split.creator,
+            &env.current_contract_address(),
+            &bounty,
+        );
+        storage::set_bounty_stake(env, split.id, bounty);
+        storage::add_total_escrowed(env, &token_address, bounty);
+        events::emit_bounty_staked(env, split.id, &split.creator, bounty);
+    }
+
+    /// Send a split's unclaimed bounty stake back to its creator
+    fn return_bounty_stake(env: &Env, split: &Split) -> i128 {
+        let stake = storage::take_bounty_stake(env, split.id);
+        if stake <= 0 {
+            return 0;
+        }
+
+        let token_address = storage::get_token(env);
+        storage::add_total_escrowed(env, &token_address, -stake);
+        token::Client::new(env, &token_address).transfer(
+            &env.current_contract_address(),
+            &split.creator,
+            &stake,
+        );
+        events::emit_bounty_returned(env, split.id, &split.creator, stake);
+        stake
+    }
+
+    /// Send a split's locked bond back to its creator, returning the amount
+    fn return_bond(env: &Env, split: &Split) -> i128 {
+        let bond = storage::take_bond(env, split.id);
+        if bond <= 0 {
+            return 0;
+        }
+
+        let token_address = storage::get_token(env);
+        storage::add_total_escrowed(env, &token_address, -bond);
+        token::Client::new(env, &token_address).transfer(
+            &env.current_contract_address(),
+            &split.creator,
+            &bond,
+        );
+        Self::log_transfer(
+            env,
+            split.id,
+            TransferKind::BondReturn,
+            &split.creator,
+            &token_address,
+            bond,
+        );
+        events::emit_bond_returned(env, split.id, &split.creator, bond);
+        bond
+    }
+
+    /// What a deposit of `amount` is rounded up by for the split's charity
+    fn round_up_extra(env: &Env, split_id: u64, amount: i128) -> i128 {
+        storage::get_round_up(env, split_id).map_or(0, |r| (r.unit - amount % r.unit) % r.unit)
+    }
+
+    /// Give everything participants rounded up to the split's charity
+    fn donate_round_ups(env: &Env, split: &Split) -> i128 {
+        let Some(round_up) = storage::get_round_up(env, split.id) else {
+            return 0;
+        };
+        let mut total = 0;
+        for p in split.participants.iter() {
+            total += storage::take_round_up_held(env, split.id, &p.address);
+        }
+        if total <= 0 {
+            return 0;
+        }
+
+        let token_address = storage::get_token(env);
+        storage::add_total_escrowed(env, &token_address, -total);
+        token::Client::new(env, &token_address).transfer(
+            &env.current_contract_address(),
+            &round_up.charity,
+            &total,
+        );
+        Self::log_transfer(
+            env,
+            split.id,
+            TransferKind::Donation,
+            &round_up.charity,
+            &token_address,
+            total,
+        );
+        events::emit_donated(env, split.id, &round_up.charity, total);
+        total
+    }
+
+    /// Validate a deposit, returning the depositing participant's index
+    fn check_deposit(
+        env: &Env,
+        split: &Split,
+        participant: &Address,
+        amount: i128,
+    ) -> Result<u32, Error> {
+        if storage::get_pause_flags(env).deposit {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(env, split.id)?;
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        // IOU splits never hold tokens; payments are confirmed via mark_paid
+        if split.mode == SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+
+        // Staged splits only count money once their creator has signed off,
+        // and open or unit-priced splits once their shares stop moving
+        if storage::is_awaiting_creator(env, split.id)
+            || storage::is_joinable(env, split.id)
+            || storage::has_unlocked_units(env, split.id)
+        {
+            return Err(Error::ActionNotReady);
+        }
+
+        // Verify the split is still accepting deposits
+        match split.status {
+            SplitStatus::Cancelled => return Err(Error::SplitCancelled),
+            SplitStatus::Released => return Err(Error::SplitReleased),
+            SplitStatus::Completed => return Err(Error::SplitFullyFunded),
+            SplitStatus::Expired => return Err(Error::SplitExpired),
+            SplitStatus::Pending | SplitStatus::Active => {}
+        }
+
+        if Self::is_past_deadline(env, split) {
+            return Err(Error::SplitExpired);
+        }
+
+        let index = split
+            .participants
+            .iter()
+            .position(|p| p.address == *participant)
+            .ok_or(Error::ParticipantNotFound)? as u32;
+        if storage::is_lapsed(env, split.id, participant) {
+            return Err(Error::ParticipantLapsed);
+        }
+
+        let p = split.participants.get(index).unwrap();
+        let remaining = p.share_amount - p.amount_paid;
+        if amount > remaining {
+            return Err(Error::ExceedsRemaining);
+        }
+        if amount < remaining && storage::requires_full_payment(env, split.id) {
+            return Err(Error::PartialPaymentNotAllowed);
+        }
+
+        // A final payment smaller than the minimum is always allowed
+        let minimum = match storage::get_min_deposit(env) {
+            MinDeposit::None => 0,
+            MinDeposit::Absolute(minimum) => minimum,
+            MinDeposit::ShareBps(bps) => p.share_amount * bps as i128 / shares::BPS_DENOMINATOR,
+        };
+        if amount < minimum && amount < remaining {
+            return Err(Error::DepositTooSmall);
+        }
+
+        Ok(index)
+    }
+
+    /// Record a validated deposit on the split (state only, no transfers)
+    fn apply_deposit(env: &Env, split: &mut Split, index: u32, amount: i128) {
+        let mut p = split.participants.get(index).unwrap();
+        p.amount_paid += amount;
+        p.has_paid = p.amount_paid >= p.share_amount;
+        split.participants.set(index, p);
+
+        let before = split.amount_collected;
+        split.amount_collected += amount;
+        Self::emit_milestones(env, split, before);
+        let pass_through = split.mode == SplitMode::PassThrough;
+        if pass_through {
+            split.amount_released += amount;
+        }
+
+        if split.status == SplitStatus::Pending {
+            lifecycle::transition(env, split, SplitStatus::Active, Action::Deposit);
+        }
+        if pass_through && Self::is_fully_funded_internal(split) {
+            lifecycle::transition(env, split, SplitStatus::Released, Action::Deposit);
+        }
+    }
+
+    /// Announce each funding milestone the split just crossed
+    ///
+    /// A split that drops back below one (after a refund, say) announces
+    /// it again when it's crossed again.
+    fn emit_milestones(env: &Env, split: &Split, before: i128) {
+        if split.total_amount <= 0 {
+            return;
+        }
+        let reached =
+            |collected: i128, pct: u32| collected * 100 >= split.total_amount * pct as i128;
+        for pct in FUNDING_MILESTONES {
+            if !reached(before, pct) && reached(split.amount_collected, pct) {
+                events::emit_funding_milestone(env, split.id, pct, split.amount_collected);
+            }
+        }
+    }
+
+    /// Move a checked deposit into escrow (or on to the creator) and record it
+    ///
+    /// Shared by `deposit`, `deposit_via` and `contribute` once the
+    /// depositor has a participant entry at `index`. Tokens come from
+    /// `payer`; everything else is credited to `participant`.
+    fn take_deposit(
+        env: &Env,
+        mut split: Split,
+        index: u32,
+        participant: &Address,
+        payer: &Address,
+        amount: i128,
+        pulled: bool,
+    ) -> Result<(), Error> {
+        let split_id = split.id;
+
+        // Tokens go from the participant to the escrow contract, or straight
+        // to the creator for pass-through splits. A scheduled pull spends
+        // the participant's allowance instead. It has to run first so a
+        // refused pull fails before anything is recorded.
+        let pass_through = split.mode == SplitMode::PassThrough;
+        let recipient = if pass_through {
+            split.creator.clone()
+        } else {
+            env.current_contract_address()
+        };
+        let token_address = storage::get_token(env);
+        let token_client = token::Client::new(env, &token_address);
+        let extra = Self::round_up_extra(env, split_id, amount);
+        let charged = amount + extra;
+        if pulled {
+            let pull = token_client.try_transfer_from(
+                &env.current_contract_address(),
+                payer,
+                &recipient,
+                &charged,
+            );
+            if !matches!(pull, Ok(Ok(()))) {
+                return Err(Error::NoFundsAvailable);
+            }
+        }
+        if !pass_through {
+            storage::add_total_escrowed(env, &token_address, charged);
+            storage::add_held_balance(env, split_id, participant, amount);
+        }
+        if extra > 0 {
+            storage::add_round_up_held(env, split_id, participant, extra);
+            events::emit_rounded_up(env, split_id, participant, extra);
+        }
+
+        Self::apply_deposit(env, &mut split, index, amount);
+
+        // Save the updated split before the token is called
+        storage::set_split(env, split_id, &split);
+        if !pulled {
+            token_client.transfer(payer, &recipient, &charged);
+            Self::recheck_split(env, &split);
+        }
+        stats::record_payment(env, &split, participant, amount);
+        if pass_through {
+            stats::record_received(env, &split.creator, amount);
+        }
+
+        // Emit deposit event
+        events::emit_deposit_received(env, split_id, participant, amount);
+
+        // Pass-through deposits are already with the creator
+        if pass_through {
+            events::emit_funds_released(
+                env,
+                split_id,
+                &split.creator,
+                amount,
+                env.ledger().timestamp(),
+            );
+            if split.status == SplitStatus::Released {
+                events::emit_escrow_completed(env, split_id, split.total_amount);
+                hooks::notify_completed(env, &split);
+            }
+            return Ok(());
+        }
+
+        // Auto-release funds if fully funded
+        if Self::is_fully_funded_internal(&split) {
+            Self::complete_or_release(env, split_id, split);
+        } else if Self::drips(env, &split) {
+            Self::release_available(env, &mut split)?;
+        }
+
+        Ok(())
+    }
+
+    /// Whether a split's scheduled release time is still ahead
+    fn awaits_schedule(env: &Env, split_id: u64) -> bool {
+        storage::get_scheduled_release(env, split_id)
+            .is_some_and(|release_at| env.ledger().timestamp() < release_at)
+    }
+
+    /// Validate a partial release, returning the gross amount it would move
+    fn check_partial_release(env: &Env, split: &Split) -> Result<i128, Error> {
+        if storage::get_pause_flags(env).release {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(env, split.id)?;
+
+        if split.mode == SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+
         if split.status == SplitStatus::Cancelled {
             return Err(Error::SplitCancelled);
         }
@@ -318,61 +6526,452 @@ impl SplitEscrowContract {
             return Err(Error::SplitReleased);
         }
 
-        if !Self::is_fully_funded_internal(&split) {
+        if split.status == SplitStatus::Expired {
+            return Err(Error::SplitExpired);
+        }
+
+        if storage::get_dispute(env, split.id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+
+        if Self::awaits_schedule(env, split.id) {
+            return Err(Error::ActionNotReady);
+        }
+
+        if Self::is_fully_funded_internal(split) {
+            return Err(Error::SplitFullyFunded);
+        }
+
+        // Every payout of a delayed or confirmed split goes through its
+        // own release path
+        if storage::get_release_delay(env, split.id) > 0
+            || storage::get_confirmer(env, split.id).is_some()
+        {
+            return Err(Error::UnsupportedMode);
+        }
+
+        let available = split.amount_collected - split.amount_released;
+        if available <= 0 {
+            return Err(Error::NoFundsAvailable);
+        }
+        if breaker::blocks_release(env, available) {
+            return Err(Error::ContractPaused);
+        }
+
+        Ok(available)
+    }
+
+    /// Validate a full release, returning the gross amount it would move
+    fn check_release(env: &Env, split: &Split) -> Result<i128, Error> {
+        if split.mode == SplitMode::Iou {
+            return Err(Error::UnsupportedMode);
+        }
+
+        if storage::get_pause_flags(env).release {
+            return Err(Error::ContractPaused);
+        }
+        Self::check_not_frozen(env, split.id)?;
+
+        if storage::get_dispute(env, split.id).is_some() {
+            return Err(Error::DisputeOpen);
+        }
+
+        if split.status == SplitStatus::Cancelled {
+            return Err(Error::SplitCancelled);
+        }
+
+        if split.status == SplitStatus::Released {
+            return Err(Error::SplitReleased);
+        }
+
+        if !Self::is_fully_funded_internal(split) {
             return Err(Error::SplitNotFunded);
         }
 
+        // A parent pays out only once all of its children are funded, and a
+        // scheduled split only once its time has come
+        if Self::has_unfunded_children(env, split.id) || Self::awaits_schedule(env, split.id) {
+            return Err(Error::ActionNotReady);
+        }
+
         let available = split.amount_collected - split.amount_released;
         if available <= 0 {
             return Err(Error::NoFundsAvailable);
         }
 
-        if split.status != SplitStatus::Completed {
-            split.status = SplitStatus::Completed;
-            events::emit_escrow_completed(env, split_id, split.total_amount);
+        if breaker::blocks_release(env, available) {
+            return Err(Error::ContractPaused);
         }
 
+        Ok(available)
+    }
+
+    /// What a full release of `split` would pay out, without paying it
+    fn quote_release_internal(env: &Env, split: &Split) -> Result<ReleaseQuote, Error> {
+        let amount = Self::check_release(env, split)?;
         let token_address = storage::get_token(env);
-        let token_client = token::Client::new(env, &token_address);
+
+        // Deposits in other tokens are forwarded separately; this quote
+        // covers the escrow token part
+        let native = amount - Self::foreign_holdings(env, split).1;
+        let penalty: i128 = Self::pending_penalties(env, split, native)
+            .iter()
+            .map(|(_, back)| back)
+            .sum();
+        let fee = if native - penalty > 0 {
+            fees::compute_release_fee(env, split, &token_address, native - penalty)?.part
+        } else {
+            0
+        };
+
+        Ok(ReleaseQuote {
+            amount,
+            fee,
+            net: native - penalty - fee,
+            status: SplitStatus::Released,
+        })
+    }
+
+    /// Move `amount` out of escrow: the release fee to the fee recipient and
+    /// the rest to the creator, swapped first if the split has a payout
+    /// asset. Returns what the creator received of the escrow (or payout)
+    /// token.
+    fn pay_out(
+        env: &Env,
+        split: &mut Split,
+        amount: i128,
+        penalty_bps: u32,
+    ) -> Result<i128, Error> {
+        let token_address = storage::get_token(env);
+        let payout_asset = storage::get_payout_asset(env, split.id);
+        let (foreign, foreign_credit) = Self::foreign_holdings(env, split);
+        let native = amount - foreign_credit;
+
+        let penalties = Self::late_penalties(env, split, native, penalty_bps);
+        let penalty: i128 = penalties.iter().map(|(_, back)| back).sum();
+
+        // Price every fee before moving anything, so a failing fee module
+        // can't leave a release half done
+        let fee_cut = if native - penalty > 0 {
+            fees::compute_release_fee(env, split, &token_address, native - penalty)?
+        } else {
+            Apportioned::default()
+        };
+        let fee = fee_cut.part;
+        let retain_cut = Self::retainage_cut(env, split.id, fee_cut.rest);
+        let retained = retain_cut.part;
+        let net = retain_cut.rest;
+        // Only `AssignToCreator` leaves dust, and it's the creator's
+        // whoever the payout goes to
+        let dust = fee_cut.dust + retain_cut.dust;
+        let mut foreign_fees = Map::new(env);
+        for (token, held) in foreign.iter() {
+            foreign_fees.set(
+                token.clone(),
+                fees::compute_release_fee(env, split, &token, held)?.part,
+            );
+        }
+        // An override can charge with no module or schedule to name a
+        // recipient; refuse rather than fail halfway through paying out
+        let fee_recipient = fees::fee_recipient(env);
+        let charges = fee > 0 || foreign_fees.values().iter().any(|f| f > 0);
+        if charges && fee_recipient.is_none() {
+            return Err(Error::InvalidConfig);
+        }
+
+        // Settle the books and save the split before calling any token.
+        // A release always drains everything collected so far, so every
+        // participant's escrowed balance leaves the contract.
+        breaker::record_release(env, native);
+        for p in split.participants.iter() {
+            storage::take_held_balance(env, split.id, &p.address);
+            storage::take_foreign_held(env, split.id, &p.address);
+        }
+        storage::add_total_escrowed(env, &token_address, retained - native);
+        for (token, held) in foreign.iter() {
+            storage::add_total_escrowed(env, &token, -held);
+        }
+        if retained > 0 {
+            Self::hold_retainage(env, split.id, retained);
+        }
+        split.amount_released += amount;
+        split.fees_paid += fee;
+        storage::set_split(env, split.id, split);
+
         let contract_address = env.current_contract_address();
-        token_client.transfer(&contract_address, &split.creator, &available);
 
-        split.amount_released += available;
-        split.status = SplitStatus::Released;
-        storage::set_split(env, split_id, &split);
+        // Only a completed split pays out in full
+        let kind = match split.status {
+            SplitStatus::Completed | SplitStatus::Released => TransferKind::Release,
+            _ => TransferKind::PartialRelease,
+        };
 
-        events::emit_funds_released(
-            env,
-            split_id,
-            &split.creator,
-            available,
-            env.ledger().timestamp(),
-        );
+        let token_client = token::Client::new(env, &token_address);
+        for (participant, back) in penalties.iter() {
+            let refund_to = Self::refund_recipient(env, &participant);
+            token_client.transfer(&contract_address, &refund_to, &back);
+            Self::log_transfer(
+                env,
+                split.id,
+                TransferKind::Penalty,
+                &refund_to,
+                &token_address,
+                back,
+            );
+        }
+        if penalty > 0 {
+            events::emit_late_penalty(env, split.id, penalty_bps, penalty);
+        }
+        if fee > 0 {
+            let recipient = fee_recipient.clone().unwrap();
+            token_client.transfer(&contract_address, &recipient, &fee);
+            Self::log_transfer(
+                env,
+                split.id,
+                TransferKind::Fee,
+                &recipient,
+                &token_address,
+                fee,
+            );
+            events::emit_fee_collected(env, split.id, &recipient, fee);
+        }
+        if dust > 0 {
+            token_client.transfer(&contract_address, &split.creator, &dust);
+            Self::log_transfer(env, split.id, kind, &split.creator, &token_address, dust);
+        }
+        let mut paid = net;
+        if let Some(proposal) = storage::get_release_proposal(env, split.id) {
+            // A confirmed plan replaces every creator payout preference
+            Self::pay_plan(env, split.id, &token_client, &proposal.plan, net, kind)?;
+        } else if net > 0 {
+            match &payout_asset {
+                None => match storage::get_claimable_payout(env, split.id) {
+                    None => {
+                        token_client.transfer(&contract_address, &split.creator, &net);
+                        Self::log_transfer(
+                            env,
+                            split.id,
+                            kind,
+                            &split.creator,
+                            &token_address,
+                            net,
+                        );
+                    }
+                    Some(claimable) => Self::credit_payout_bucket(env, split.id, &claimable, net),
+                },
+                Some(asset) => {
+                    // `min_out` covers the whole split; a partial release
+                    // owes its share of it, rounded up
+                    let whole = split.total_amount.max(1);
+                    let min_out = (asset.min_out * amount + whole - 1) / whole;
+                    paid = router::swap_to(
+                        env,
+                        &token_address,
+                        &asset.token,
+                        net,
+                        min_out,
+                        &split.creator,
+                    )?;
+                    Self::log_transfer(env, split.id, kind, &split.creator, &asset.token, paid);
+                    events::emit_payout_swapped(
+                        env,
+                        split.id,
+                        &token_address,
+                        &asset.token,
+                        net,
+                        paid,
+                    );
+                }
+            }
+        }
 
-        Ok(available)
+        // Deposits in other tokens are forwarded as-is, minus their own fee
+        for (token, held) in foreign.iter() {
+            let token_fee = foreign_fees.get(token.clone()).unwrap();
+            let client = token::Client::new(env, &token);
+            if token_fee > 0 {
+                let recipient = fee_recipient.clone().unwrap();
+                client.transfer(&contract_address, &recipient, &token_fee);
+                Self::log_transfer(
+                    env,
+                    split.id,
+                    TransferKind::Fee,
+                    &recipient,
+                    &token,
+                    token_fee,
+                );
+            }
+            client.transfer(&contract_address, &split.creator, &(held - token_fee));
+            Self::log_transfer(
+                env,
+                split.id,
+                kind,
+                &split.creator,
+                &token,
+                held - token_fee,
+            );
+            events::emit_foreign_payout(env, split.id, &split.creator, &token, held, token_fee);
+        }
+        Self::recheck_split(env, split);
+
+        Ok(paid)
     }
-}
 
-    /// Get a participant's status in a split
+    /// How much of a release's net payout the split keeps back, as `part`
+    fn retainage_cut(env: &Env, split_id: u64, net: i128) -> Apportioned {
+        match storage::get_retainage(env, split_id) {
+            Some(retainage) if net > 0 => shares::apportion(
+                net,
+                retainage.bps as i128,
+                shares::BPS_DENOMINATOR,
+                storage::get_rounding_policy(env),
+            ),
+            _ => Apportioned {
+                rest: net,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Add to a split's retainage and restart its confirmation window
+    fn hold_retainage(env: &Env, split_id: u64, amount: i128) {
+        let mut retainage = storage::get_retainage(env, split_id).unwrap();
+        retainage.held += amount;
+        retainage.claimable_at = env.ledger().timestamp() + retainage.window;
+        storage::set_retainage(env, split_id, &retainage);
+        events::emit_retainage_held(env, split_id, amount, retainage.claimable_at);
+    }
+
+    /// Make sure the token calls just made left the split as I saved it
     ///
-    /// Required for DRIP escrow queries.
-    pub fn get_participant_status(
-        env: Env,
-        split_id: u64,
-        participant: Address,
-    ) -> Result<Participant, Error> {
-        if !storage::has_split(&env, split_id) {
-            return Err(Error::SplitNotFound);
+    /// Soroban refuses to let a token re-enter this contract, so this
+    /// should never fire. If it does, the whole call is rolled back rather
+    /// than finishing on state that moved underneath it.
+    fn recheck_split(env: &Env, split: &Split) {
+        let stored = storage::get_split(env, split.id);
+        if stored.status != split.status
+            || stored.amount_collected != split.amount_collected
+            || stored.amount_released != split.amount_released
+        {
+            panic_with_error!(env, Error::InvariantViolated);
         }
+    }
 
-        let split = storage::get_split(&env, split_id);
+    /// Divide `net` between the recipients of a confirmed payout plan
+    fn pay_plan(
+        env: &Env,
+        split_id: u64,
+        token_client: &token::Client,
+        plan: &Vec<PayoutShare>,
+        net: i128,
+        kind: TransferKind,
+    ) -> Result<(), Error> {
+        let count = plan.len() as usize;
+        let mut bps = [0u32; MAX_PAYOUT_RECIPIENTS as usize];
+        for (i, share) in plan.iter().enumerate() {
+            bps[i] = share.bps;
+        }
+        let mut amounts = [0i128; MAX_PAYOUT_RECIPIENTS as usize];
+        shares::bps_shares(net, &bps[..count], &mut amounts[..count])?;
 
-        for i in 0..split.participants.len() {
-            let p = split.participants.get(i).unwrap();
-            if p.address == participant {
-                return Ok(p);
+        let contract_address = env.current_contract_address();
+        for (i, share) in plan.iter().enumerate() {
+            if amounts[i] > 0 {
+                token_client.transfer(&contract_address, &share.recipient, &amounts[i]);
+                Self::log_transfer(
+                    env,
+                    split_id,
+                    kind,
+                    &share.recipient,
+                    &token_client.address,
+                    amounts[i],
+                );
+                events::emit_plan_paid(env, split_id, &share.recipient, amounts[i]);
             }
         }
+        Ok(())
+    }
 
-        Err(Error::ParticipantNotFound)
+    /// Park a release in the split's payout bucket
+    ///
+    /// Each new release restarts the claim window for the whole bucket.
+    fn credit_payout_bucket(env: &Env, split_id: u64, claimable: &ClaimablePayout, amount: i128) {
+        let waiting = storage::get_payout_bucket(env, split_id).map_or(0, |b| b.amount);
+        let bucket = PayoutBucket {
+            recipient: claimable.recipient.clone(),
+            amount: waiting + amount,
+            expires_at: env.ledger().timestamp() + claimable.window,
+        };
+        storage::set_payout_bucket(env, split_id, &bucket);
+        // The money hasn't left the contract, so it stays counted as
+        // escrowed until it's claimed or reclaimed
+        storage::add_total_escrowed(env, &storage::get_token(env), amount);
+        events::emit_payout_claimable(env, split_id, &bucket.recipient, amount, bucket.expires_at);
+    }
+
+    /// Append an outbound transfer to a split's history
+    fn log_transfer(
+        env: &Env,
+        split_id: u64,
+        kind: TransferKind,
+        to: &Address,
+        token: &Address,
+        amount: i128,
+    ) {
+        let payout = matches!(
+            kind,
+            TransferKind::Release | TransferKind::PartialRelease | TransferKind::Claim
+        );
+        if payout && *token == storage::get_token(env) {
+            stats::record_received(env, to, amount);
+        }
+        storage::push_transfer(
+            env,
+            split_id,
+            &TransferRecord {
+                kind,
+                to: to.clone(),
+                token: token.clone(),
+                amount,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+    }
+
+    /// Pay out and clear a split's payout bucket
+    fn empty_payout_bucket(env: &Env, split_id: u64, bucket: &PayoutBucket, to: &Address) {
+        storage::remove_payout_bucket(env, split_id);
+        let token_address = storage::get_token(env);
+        storage::add_total_escrowed(env, &token_address, -bucket.amount);
+        token::Client::new(env, &token_address).transfer(
+            &env.current_contract_address(),
+            to,
+            &bucket.amount,
+        );
+        Self::log_transfer(
+            env,
+            split_id,
+            TransferKind::Claim,
+            to,
+            &token_address,
+            bucket.amount,
+        );
+        events::emit_payout_claimed(env, split_id, to, bucket.amount);
+    }
+
+    /// Sum a split's escrowed deposits in other tokens
+    ///
+    /// Returns the native amount held per token and the total reference
+    /// credit those deposits earned.
+    fn foreign_holdings(env: &Env, split: &Split) -> (Map<Address, i128>, i128) {
+        let mut held = Map::new(env);
+        let mut credit = 0;
+        for p in split.participants.iter() {
+            for d in storage::get_foreign_held(env, split.id, &p.address).iter() {
+                held.set(d.token.clone(), held.get(d.token).unwrap_or(0) + d.amount);
+                credit += d.credited;
+            }
+        }
+        (held, credit)
     }
+}
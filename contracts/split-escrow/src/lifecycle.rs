@@ -0,0 +1,84 @@
+//! # Lifecycle Module for Split Escrow Contract
+//!
+//! I'm keeping every status change a split can make in one table, so a new
+//! function can't quietly move a split somewhere it shouldn't go. Functions
+//! still check status up front for their own, friendlier errors; the table
+//! is the backstop that every status change goes through.
+
+use soroban_sdk::{panic_with_error, Env};
+
+use crate::types::{Error, Split, SplitStatus};
+
+/// What's causing a split to change status
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Action {
+    /// A deposit or payment confirmation came in
+    Deposit,
+
+    /// The split is fully funded and held in escrow
+    Complete,
+
+    /// The money left escrow (or never needed to be there)
+    Release,
+
+    /// A raised total has the split collecting again
+    Reopen,
+
+    /// The creator, the arbiter or an emergency called the split off
+    Cancel,
+
+    /// The deadline passed before the split was funded
+    Expire,
+}
+
+use Action::*;
+use SplitStatus::*;
+
+/// Every allowed (from, to, action) triple
+///
+/// Released, Cancelled and Expired are final: nothing leaves them.
+pub const TRANSITIONS: [(SplitStatus, SplitStatus, Action); 15] = [
+    (Pending, Active, Deposit),
+    (Pending, Completed, Deposit),
+    (Active, Completed, Deposit),
+    // Pass-through splits are done as soon as the last deposit forwards
+    (Pending, Released, Deposit),
+    (Active, Released, Deposit),
+    (Pending, Completed, Complete),
+    (Active, Completed, Complete),
+    (Active, Released, Release),
+    (Completed, Released, Release),
+    (Completed, Active, Reopen),
+    (Pending, Cancelled, Cancel),
+    (Active, Cancelled, Cancel),
+    (Completed, Cancelled, Cancel),
+    (Pending, Expired, Expire),
+    (Active, Expired, Expire),
+];
+
+/// Whether `action` may move a split from `from` to `to`
+///
+/// Staying in the same status isn't a transition, so it's always allowed.
+pub fn allowed(from: &SplitStatus, to: &SplitStatus, action: Action) -> bool {
+    from == to
+        || TRANSITIONS
+            .iter()
+            .any(|(f, t, a)| f == from && t == to && *a == action)
+}
+
+/// Move a split to `to`, or abort if the table doesn't allow it
+///
+/// A split stuck in a final status aborts with that status's own error;
+/// anything else the table rejects is an `InvariantViolated`.
+pub fn transition(env: &Env, split: &mut Split, to: SplitStatus, action: Action) {
+    if !allowed(&split.status, &to, action) {
+        let error = match split.status {
+            Cancelled => Error::SplitCancelled,
+            Released => Error::SplitReleased,
+            Expired => Error::SplitExpired,
+            _ => Error::InvariantViolated,
+        };
+        panic_with_error!(env, error);
+    }
+    split.status = to;
+}
@@ -0,0 +1,53 @@
+//! # Migration Module for Split Escrow Contract
+//!
+//! I'm keeping every split layout conversion here so storage only has to
+//! ask for "the current split" and never cares how old the entry is.
+//!
+//! Entries written before versioning are a bare `SplitV1`; everything
+//! written since is wrapped in `StoredSplit`, so the tag tells us which
+//! layout we're looking at.
+
+use soroban_sdk::{Env, TryFromVal, Val, Vec};
+
+use crate::types::{MaybeHash, Split, SplitCategory, SplitMode, SplitV1, StoredSplit};
+
+/// Decode a raw split entry of any known layout
+///
+/// Returns the current `Split` and whether the entry needs rewriting.
+pub fn decode(env: &Env, raw: &Val) -> (Split, bool) {
+    if let Ok(StoredSplit::V2(split)) = StoredSplit::try_from_val(env, raw) {
+        return (split, false);
+    }
+
+    let legacy = SplitV1::try_from_val(env, raw).expect("Unknown split layout");
+    (upgrade_v1(env, legacy), true)
+}
+
+/// Lift a pre-versioning split into the current layout
+///
+/// Old splits were always plain escrow splits with public descriptions
+/// and no fees, so every new field gets its default. The nonce starts at
+/// zero; the rewrite that follows bumps it like any other save.
+fn upgrade_v1(env: &Env, legacy: SplitV1) -> Split {
+    Split {
+        id: legacy.id,
+        creator: legacy.creator,
+        description: legacy.description,
+        description_hash: MaybeHash::None,
+        total_amount: legacy.total_amount,
+        amount_collected: legacy.amount_collected,
+        amount_released: legacy.amount_released,
+        fees_paid: 0,
+        participants: legacy.participants,
+        status: legacy.status,
+        mode: SplitMode::Escrow,
+        category: SplitCategory::Other,
+        receipt_ref: None,
+        tags: Vec::new(env),
+        deadline: None,
+        completion_hook: None,
+        created_at: legacy.created_at,
+        metadata_uri: None,
+        nonce: 0,
+    }
+}
@@ -0,0 +1,38 @@
+//! # Oracle Module for Split Escrow Contract
+//!
+//! I'm isolating price conversion here so a split can stay denominated in
+//! the escrow token while participants pay in other approved tokens.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::storage;
+use crate::types::Error;
+
+/// Interface a price oracle contract must implement
+///
+/// `to_reference` returns what `amount` of `token` is worth in the escrow
+/// (reference) token, in the reference token's smallest unit.
+#[allow(dead_code)]
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracle {
+    fn to_reference(env: Env, token: Address, amount: i128) -> i128;
+}
+
+/// Convert a deposit in `token` into reference units
+///
+/// A missing oracle, a trapping oracle or a non-positive quote all fail the
+/// deposit rather than crediting a guess.
+pub fn to_reference(env: &Env, token: &Address, amount: i128) -> Result<i128, Error> {
+    let oracle = storage::get_oracle(env).ok_or(Error::OracleFailed)?;
+
+    let credited = match PriceOracleClient::new(env, &oracle).try_to_reference(token, &amount) {
+        Ok(Ok(credited)) => credited,
+        _ => return Err(Error::OracleFailed),
+    };
+
+    if credited <= 0 {
+        return Err(Error::OracleFailed);
+    }
+
+    Ok(credited)
+}
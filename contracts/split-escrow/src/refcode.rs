@@ -0,0 +1,112 @@
+//! # Reference Codes for Split Escrow Contract
+//!
+//! I'm giving every split a short code like `XK7-29Q` that people can read
+//! out loud or paste in a chat instead of a numeric ID plus a contract
+//! address.
+//!
+//! The code is the split ID mixed with a salt taken from the contract's own
+//! address, written in Crockford base32 and followed by one check digit.
+//! Mixing in the address means the same ID gets a different code on every
+//! deployment; the mixing is reversible, so a code decodes straight back to
+//! its split ID without an index.
+
+use soroban_sdk::{xdr::ToXdr, Env, String};
+
+/// Crockford base32: no I, L, O or U, so codes survive being read aloud
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Minimum number of data digits (IDs up to 2^25 fit, codes grow after)
+const MIN_DIGITS: usize = 5;
+
+/// Most digits a code can have: 13 cover any u64, plus the check digit
+const MAX_DIGITS: usize = 14;
+
+/// Longest code accepted, hyphens included
+const MAX_CODE_LEN: usize = 32;
+
+/// Salt for this deployment, from the hash of the contract's address
+fn salt(env: &Env) -> u64 {
+    let address = env.current_contract_address().to_xdr(env);
+    let hash = env.crypto().sha256(&address).to_array();
+    // 25 bits, so small IDs keep five-digit codes
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) as u64 & 0x1ff_ffff
+}
+
+/// Check digit over the data digits (weighted sum, mod the prime 31)
+fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (i as u32 + 1) * *d as u32)
+        .sum();
+    (sum % 31) as u8
+}
+
+/// The reference code of `split_id` on this deployment
+pub fn encode(env: &Env, split_id: u64) -> String {
+    let mut value = split_id ^ salt(env);
+
+    // Least significant digit first, then reversed into reading order
+    let mut digits = [0u8; MAX_DIGITS];
+    let mut count = 0;
+    while count < MIN_DIGITS || value > 0 {
+        digits[count] = (value % 32) as u8;
+        value /= 32;
+        count += 1;
+    }
+    digits[..count].reverse();
+    digits[count] = check_digit(&digits[..count]);
+    count += 1;
+
+    // Group in threes from the left: XK7-29Q
+    let mut out = [0u8; MAX_CODE_LEN];
+    let mut len = 0;
+    for (i, d) in digits[..count].iter().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            out[len] = b'-';
+            len += 1;
+        }
+        out[len] = ALPHABET[*d as usize];
+        len += 1;
+    }
+    String::from_bytes(env, &out[..len])
+}
+
+/// The split ID a reference code stands for, if the code is well formed
+///
+/// Case and hyphens don't matter, and the letters people confuse with
+/// digits (O, I, L) are read as 0 and 1.
+pub fn decode(env: &Env, code: &String) -> Option<u64> {
+    let len = code.len() as usize;
+    if len > MAX_CODE_LEN {
+        return None;
+    }
+    let mut raw = [0u8; MAX_CODE_LEN];
+    code.copy_into_slice(&mut raw[..len]);
+
+    let mut digits = [0u8; MAX_CODE_LEN];
+    let mut count = 0;
+    for c in raw[..len].iter() {
+        let c = match c.to_ascii_uppercase() {
+            b'-' | b' ' => continue,
+            b'O' => b'0',
+            b'I' | b'L' => b'1',
+            c => c,
+        };
+        digits[count] = ALPHABET.iter().position(|a| *a == c)? as u8;
+        count += 1;
+    }
+    if !(MIN_DIGITS + 1..=MAX_DIGITS).contains(&count) {
+        return None;
+    }
+
+    let data = &digits[..count - 1];
+    if check_digit(data) != digits[count - 1] {
+        return None;
+    }
+    let mut value: u64 = 0;
+    for d in data {
+        value = value.checked_mul(32)?.checked_add(*d as u64)?;
+    }
+    Some(value ^ salt(env))
+}
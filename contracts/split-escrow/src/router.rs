@@ -0,0 +1,63 @@
+//! # Router Module for Split Escrow Contract
+//!
+//! I'm putting the swap integration behind one function so a creator can
+//! be paid in a different asset without the escrow knowing how the swap is
+//! routed.
+
+use soroban_sdk::{contractclient, token, Address, Env};
+
+use crate::storage;
+use crate::types::Error;
+
+/// Interface a swap router contract must implement
+///
+/// `swap` pulls `amount_in` of `token_in` from `from` (the escrow approves
+/// it first), sends at least `min_out` of `token_out` to `to`, and returns
+/// the amount sent.
+#[allow(dead_code)]
+#[contractclient(name = "SwapRouterClient")]
+pub trait SwapRouter {
+    fn swap(
+        env: Env,
+        from: Address,
+        token_in: Address,
+        token_out: Address,
+        amount_in: i128,
+        min_out: i128,
+        to: Address,
+    ) -> i128;
+}
+
+/// Swap escrowed `amount` of `token_in` into `token_out` for `to`
+///
+/// I'm approving exactly `amount` for this ledger only, and I'm measuring
+/// what `to` actually received rather than trusting the router's return
+/// value or its handling of `min_out`.
+pub fn swap_to(
+    env: &Env,
+    token_in: &Address,
+    token_out: &Address,
+    amount: i128,
+    min_out: i128,
+    to: &Address,
+) -> Result<i128, Error> {
+    let router = storage::get_swap_router(env).ok_or(Error::InvalidConfig)?;
+    let escrow = env.current_contract_address();
+
+    token::Client::new(env, token_in).approve(&escrow, &router, &amount, &env.ledger().sequence());
+
+    let out = token::Client::new(env, token_out);
+    let before = out.balance(to);
+    let swapped = SwapRouterClient::new(env, &router)
+        .try_swap(&escrow, token_in, token_out, &amount, &min_out, to);
+    if !matches!(swapped, Ok(Ok(_))) {
+        return Err(Error::SwapFailed);
+    }
+    let amount_out = out.balance(to) - before;
+
+    if amount_out < min_out {
+        return Err(Error::SlippageExceeded);
+    }
+
+    Ok(amount_out)
+}
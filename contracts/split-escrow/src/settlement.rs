@@ -0,0 +1,110 @@
+//! # Settlement Module for Split Escrow Contract
+//!
+//! I'm keeping the debt-netting math here so every view that needs a
+//! "who pays whom" answer computes it the same way.
+
+use soroban_sdk::{Address, Env, Map, Vec};
+
+use crate::types::{Settlement, Split, SplitStatus};
+
+/// Whether a split still carries outstanding obligations
+///
+/// Released, cancelled and expired splits are settled (or void), so they
+/// never contribute to a group's net balances.
+pub fn is_open(split: &Split) -> bool {
+    !matches!(
+        split.status,
+        SplitStatus::Released | SplitStatus::Cancelled | SplitStatus::Expired
+    )
+}
+
+/// Whether a split was called off, so nobody owes anything on it
+///
+/// Released splits still count toward totals; only cancelled and expired
+/// ones drop out.
+pub fn is_void(split: &Split) -> bool {
+    matches!(split.status, SplitStatus::Cancelled | SplitStatus::Expired)
+}
+
+/// Accumulate each member's net position from a split's unpaid shares
+///
+/// Every participant owes their unpaid remainder to the creator, so the
+/// creator's balance goes up by the same amount the participant's goes down.
+/// Positive balances are owed money, negative balances owe money.
+pub fn add_split_balances(balances: &mut Map<Address, i128>, split: &Split) {
+    for p in split.participants.iter() {
+        let remaining = p.share_amount - p.amount_paid;
+        if remaining <= 0 || p.address == split.creator {
+            continue;
+        }
+
+        let debtor = balances.get(p.address.clone()).unwrap_or(0);
+        balances.set(p.address.clone(), debtor - remaining);
+
+        let creditor = balances.get(split.creator.clone()).unwrap_or(0);
+        balances.set(split.creator.clone(), creditor + remaining);
+    }
+}
+
+/// Reduce a set of net balances to a short list of direct payments
+///
+/// I'm using the greedy largest-debtor-pays-largest-creditor approach: each
+/// step fully settles at least one member, so a group of `n` members never
+/// needs more than `n - 1` payments. Ties are broken by map order, which keeps
+/// the plan deterministic for a given state.
+pub fn simplify(env: &Env, balances: &Map<Address, i128>) -> Vec<Settlement> {
+    let mut creditors: Vec<(Address, i128)> = Vec::new(env);
+    let mut debtors: Vec<(Address, i128)> = Vec::new(env);
+
+    for (address, balance) in balances.iter() {
+        if balance > 0 {
+            creditors.push_back((address, balance));
+        } else if balance < 0 {
+            debtors.push_back((address, -balance));
+        }
+    }
+
+    let mut plan = Vec::new(env);
+
+    loop {
+        let (ci, (creditor, credit)) = match largest(&creditors) {
+            Some(entry) => entry,
+            None => break,
+        };
+        let (di, (debtor, debt)) = match largest(&debtors) {
+            Some(entry) => entry,
+            None => break,
+        };
+
+        let amount = if credit < debt { credit } else { debt };
+        plan.push_back(Settlement {
+            from: debtor.clone(),
+            to: creditor.clone(),
+            amount,
+        });
+
+        creditors.set(ci, (creditor, credit - amount));
+        debtors.set(di, (debtor, debt - amount));
+    }
+
+    plan
+}
+
+/// Find the entry with the largest positive amount
+fn largest(entries: &Vec<(Address, i128)>) -> Option<(u32, (Address, i128))> {
+    let mut best: Option<(u32, (Address, i128))> = None;
+    for i in 0..entries.len() {
+        let entry = entries.get(i).unwrap();
+        if entry.1 <= 0 {
+            continue;
+        }
+        let better = match &best {
+            Some((_, (_, amount))) => entry.1 > *amount,
+            None => true,
+        };
+        if better {
+            best = Some((i, entry));
+        }
+    }
+    best
+}
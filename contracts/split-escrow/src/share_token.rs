@@ -0,0 +1,33 @@
+//! # Share Token Module for Split Escrow Contract
+//!
+//! I'm letting a companion contract represent each participant's obligation
+//! as a transferable token. The escrow mints the tokens when a tokenized
+//! split is created and trusts the companion to tell it when one changes
+//! hands, so the whole position follows the token.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::storage;
+use crate::types::{Error, Split};
+
+/// Interface a share token contract must implement
+///
+/// `mint` issues `amount` of the split's share token to `to`. When a holder
+/// transfers their tokens, the companion calls the escrow's `transfer_share`
+/// so the obligation moves with them. A position can't be split: the
+/// companion should only allow whole-balance transfers.
+#[allow(dead_code)]
+#[contractclient(name = "ShareTokenClient")]
+pub trait ShareToken {
+    fn mint(env: Env, split_id: u64, to: Address, amount: i128);
+}
+
+/// Mint every participant's share token for a newly created split
+pub fn mint_shares(env: &Env, split: &Split) -> Result<(), Error> {
+    let companion = storage::get_share_token(env).ok_or(Error::InvalidConfig)?;
+    let client = ShareTokenClient::new(env, &companion);
+    for p in split.participants.iter() {
+        client.mint(&split.id, &p.address, &p.share_amount);
+    }
+    Ok(())
+}
@@ -0,0 +1,214 @@
+//! # Share Math Module for Split Escrow Contract
+//!
+//! I'm keeping how a total gets divided between participants in one place,
+//! so every mode that splits evenly rounds the same way. Proportional
+//! payouts take their rounding from the deployment's `RoundingPolicy`.
+//!
+//! Nothing in here touches `Env`: every function is plain integer math over
+//! slices, so frontends and off-chain services can link this module and
+//! preview exactly the shares the contract will enforce.
+
+use crate::types::{Error, RoundingPolicy};
+
+/// Basis points in one whole (100%)
+pub const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Most participants a unit-priced split can have
+pub const MAX_UNIT_HOLDERS: usize = 50;
+
+/// The share of participant `index` when `total` is divided `count` ways
+///
+/// Shares differ by at most one stroop. The remainder goes to the
+/// first-listed participants, one stroop each, so the shares always sum
+/// exactly to `total` and only depend on the order participants are listed.
+pub fn equal_share(total: i128, count: u32, index: u32) -> i128 {
+    if count == 0 {
+        return 0;
+    }
+    let base = total / count as i128;
+    let remainder = (total % count as i128) as u32;
+    if index < remainder {
+        base + 1
+    } else {
+        base
+    }
+}
+
+/// Fill `out` with `total` divided equally between `out.len()` participants
+pub fn equal_shares(total: i128, out: &mut [i128]) {
+    let count = out.len() as u32;
+    for (i, share) in out.iter_mut().enumerate() {
+        *share = equal_share(total, count, i as u32);
+    }
+}
+
+/// Fill `out` with `total` divided in proportion to `weights`
+///
+/// Each share is first floored; the stroops that flooring leaves over go
+/// one each to the first-listed participants with a non-zero weight, so the
+/// result is deterministic and sums exactly to `total`.
+pub fn weighted_shares(total: i128, weights: &[i128], out: &mut [i128]) -> Result<(), Error> {
+    if weights.len() != out.len() {
+        return Err(Error::SharesMismatch);
+    }
+    if weights.iter().any(|w| *w < 0) {
+        return Err(Error::InvalidAmount);
+    }
+    proportional(total, |i| weights[i], out)
+}
+
+/// Fill `out` with `total` divided by basis-point allocations
+///
+/// The allocations must add up to exactly 10 000 (100%). Rounding follows
+/// `weighted_shares`.
+pub fn bps_shares(total: i128, bps: &[u32], out: &mut [i128]) -> Result<(), Error> {
+    if bps.len() != out.len() {
+        return Err(Error::SharesMismatch);
+    }
+    if bps.iter().map(|b| *b as i128).sum::<i128>() != BPS_DENOMINATOR {
+        return Err(Error::SharesMismatch);
+    }
+    proportional(total, |i| bps[i] as i128, out)
+}
+
+/// Fill `out` with `total` divided by whole units (nights, seats, items)
+///
+/// Every unit costs the floored `total / sum(units)`, and what that leaves
+/// over is spread like `weighted_shares`, so the result is deterministic
+/// and sums exactly to `total`. Returns the per-unit price.
+pub fn unit_shares(total: i128, units: &[u32], out: &mut [i128]) -> Result<i128, Error> {
+    if units.len() != out.len() {
+        return Err(Error::SharesMismatch);
+    }
+    if units.len() > MAX_UNIT_HOLDERS {
+        return Err(Error::InvalidParticipants);
+    }
+    let unit_count: i128 = units.iter().map(|u| *u as i128).sum();
+    if total < 0 || unit_count == 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let price = total / unit_count;
+    let mut extra = [0i128; MAX_UNIT_HOLDERS];
+    let extra = &mut extra[..out.len()];
+    proportional(total - price * unit_count, |i| units[i] as i128, extra)?;
+    for (i, share) in out.iter_mut().enumerate() {
+        *share = price * units[i] as i128 + extra[i];
+    }
+    Ok(price)
+}
+
+/// A subtotal grown by tax and tip, each in basis points
+///
+/// Tax and tip are each floored separately on the subtotal, matching how
+/// receipts print them line by line.
+pub fn with_tax_and_tip(subtotal: i128, tax_bps: u32, tip_bps: u32) -> i128 {
+    let tax = subtotal * tax_bps as i128 / BPS_DENOMINATOR;
+    let tip = subtotal * tip_bps as i128 / BPS_DENOMINATOR;
+    subtotal + tax + tip
+}
+
+/// Fill `out` with each participant's share of an itemized bill
+///
+/// `item_totals[i]` is the sum of the items participant `i` ordered. Tax and
+/// tip are added to the whole bill and spread in proportion to those
+/// subtotals. Returns the grand total the shares add up to.
+pub fn item_shares(
+    item_totals: &[i128],
+    tax_bps: u32,
+    tip_bps: u32,
+    out: &mut [i128],
+) -> Result<i128, Error> {
+    let subtotal: i128 = item_totals.iter().sum();
+    let total = with_tax_and_tip(subtotal, tax_bps, tip_bps);
+    weighted_shares(total, item_totals, out)?;
+    Ok(total)
+}
+
+/// Floor each weighted share, then hand the leftover stroops out in order
+fn proportional(
+    total: i128,
+    weight: impl Fn(usize) -> i128,
+    out: &mut [i128],
+) -> Result<(), Error> {
+    let weight_sum: i128 = (0..out.len()).map(&weight).sum();
+    if total < 0 || weight_sum <= 0 {
+        return Err(Error::InvalidAmount);
+    }
+
+    let mut assigned = 0;
+    for (i, share) in out.iter_mut().enumerate() {
+        *share = total * weight(i) / weight_sum;
+        assigned += *share;
+    }
+
+    // Flooring loses less than one stroop per weighted share, so a single
+    // pass over the weighted participants always places the whole remainder
+    let mut left_over = total - assigned;
+    for (i, share) in out.iter_mut().enumerate() {
+        if left_over == 0 {
+            break;
+        }
+        if weight(i) > 0 {
+            *share += 1;
+            left_over -= 1;
+        }
+    }
+    Ok(())
+}
+
+/// An amount carved into a proportional part, the rest, and rounding dust
+///
+/// `part + rest + dust` always equals the amount that was divided.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Apportioned {
+    /// The proportional part (e.g. a fee or a tranche)
+    pub part: i128,
+
+    /// What's left for the payee
+    pub rest: i128,
+
+    /// Remainder owed to the split creator under `AssignToCreator`
+    pub dust: i128,
+}
+
+/// Carve `numerator / denominator` of `amount` out, rounding per `policy`
+///
+/// Every proportional payout goes through here so dust is handled the same
+/// way everywhere and no stroop is ever created or lost.
+pub fn apportion(
+    amount: i128,
+    numerator: i128,
+    denominator: i128,
+    policy: RoundingPolicy,
+) -> Apportioned {
+    let exact = amount * numerator;
+    let floor = exact / denominator;
+    let has_dust = exact % denominator != 0;
+
+    match policy {
+        RoundingPolicy::TruncateToPayee => Apportioned {
+            part: floor,
+            rest: amount - floor,
+            dust: 0,
+        },
+        RoundingPolicy::RoundToFeePool => {
+            let part = if has_dust { floor + 1 } else { floor };
+            Apportioned {
+                part,
+                rest: amount - part,
+                dust: 0,
+            }
+        }
+        RoundingPolicy::AssignToCreator => {
+            // The payee gets the mirror-image floor; what neither floor
+            // covers goes to the creator
+            let rest = amount * (denominator - numerator) / denominator;
+            Apportioned {
+                part: floor,
+                rest,
+                dust: amount - floor - rest,
+            }
+        }
+    }
+}
@@ -0,0 +1,55 @@
+//! # Stats Module for Split Escrow Contract
+//!
+//! I'm keeping running counters per address so apps can show a profile
+//! page from one read instead of indexing every event off-chain. Counters
+//! only ever grow; refunds don't take payments back out of them.
+
+use soroban_sdk::{Address, Env};
+
+use crate::storage;
+use crate::types::Split;
+
+/// Seconds in a day, for days-to-pay
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Count a split an address created
+pub fn record_created(env: &Env, creator: &Address) {
+    let mut stats = storage::get_address_stats(env, creator);
+    stats.splits_created += 1;
+    storage::set_address_stats(env, creator, &stats);
+}
+
+/// Count a split an address became a participant in
+pub fn record_joined(env: &Env, participant: &Address) {
+    let mut stats = storage::get_address_stats(env, participant);
+    stats.splits_joined += 1;
+    storage::set_address_stats(env, participant, &stats);
+}
+
+/// Count a payment toward a participant's share
+///
+/// `split` must already include the payment. If it finished the share, the
+/// days since the split was created go into the average days-to-pay.
+pub fn record_payment(env: &Env, split: &Split, participant: &Address, amount: i128) {
+    let mut stats = storage::get_address_stats(env, participant);
+    stats.total_paid += amount;
+
+    let finished = split
+        .participants
+        .iter()
+        .any(|p| p.address == *participant && p.has_paid);
+    if finished {
+        let days = env.ledger().timestamp().saturating_sub(split.created_at) / SECONDS_PER_DAY;
+        stats.shares_paid += 1;
+        stats.days_to_pay_total += days;
+        stats.avg_days_to_pay = stats.days_to_pay_total / stats.shares_paid as u64;
+    }
+    storage::set_address_stats(env, participant, &stats);
+}
+
+/// Count escrow-token money an address received from a split
+pub fn record_received(env: &Env, recipient: &Address, amount: i128) {
+    let mut stats = storage::get_address_stats(env, recipient);
+    stats.total_received += amount;
+    storage::set_address_stats(env, recipient, &stats);
+}
@@ -6,151 +6,2357 @@
 //! This module includes both original storage patterns and the enhanced
 //! escrow storage keys as specified in issue #59.
 
-use soroban_sdk::{contracttype, Address, Env, String};
+use soroban_sdk::{contracttype, Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Val, Vec};
 
-use crate::types::{Split, SplitEscrow};
-use soroban_sdk::{Env, Symbol};
+use crate::migration;
+use crate::types::{
+    AddressStats, BondSlash, BreakerConfig, BreakerState, BridgeCredit, ClaimablePayout,
+    Contribution, Dispute, FeeConfig, FeeExemption, FeeSchedule, FeeTarget, ForeignDeposit, Group,
+    InsuranceConfig, LatePenalty, MinDeposit, PauseFlags, PayoutAsset, PayoutBucket, PullSchedule,
+    ReleaseProposal, ReleaseRequest, RentBudget, RentConfig, Retainage, Role, RoundUp,
+    RoundingPolicy, ScheduledAction, Settlement, Split, SplitCategory, SplitDisplay, SplitEscrow,
+    SplitEvent, SplitNote, SplitStatus, StoredSplit, TotalIncrease, TransferRecord, UnitShares,
+    Visibility,
+};
 
-use crate::types::SplitEscrow;
+// ============================================
+// Original Storage Keys
+// ============================================
+
+/// Original storage keys for the contract (preserved for compatibility)
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// The contract administrator
+    Admin,
+
+    /// Counter for generating unique split IDs
+    SplitCounter,
+
+    /// A split record, indexed by ID
+    Split(u64),
+
+    /// The token contract address used for escrow
+    Token,
+
+    /// Whether the contract is initialized
+    Initialized,
+
+    /// Counter for generating unique group IDs
+    GroupCounter,
+
+    /// A group record, indexed by ID
+    Group(u64),
+
+    /// The group a split belongs to, indexed by split ID
+    SplitGroup(u64),
+
+    /// The latest simplified settlement plan for a group
+    GroupSettlement(u64),
+
+    /// Cap on a creator's open splits (absent or 0 = no cap)
+    MaxOpenSplits,
+
+    /// Display label the creator gave a participant on a split
+    ParticipantLabel(u64, Address),
+
+    /// Decimals and ticker to render a split's amounts with
+    SplitDisplay(u64),
+
+    /// Split only accepts deposits of a participant's whole remaining share
+    RequireFullPayment(u64),
+
+    /// Who can read a split through the views
+    Visibility(u64),
+
+    /// Off-chain settlements need the participant's authorization too
+    ConfirmOffchain(u64),
+
+    /// Amount of a participant's share settled outside the contract
+    OffchainSettled(u64, Address),
+
+    /// Smallest deposit accepted per transaction
+    MinDeposit,
+
+    /// Seconds a Pending split may go without deposits (0 = no limit)
+    ActivationWindow,
+
+    /// Minimum seconds between payment reminders for a split
+    ReminderInterval,
+
+    /// Timestamp of the last payment reminder, indexed by split ID
+    LastReminder(u64),
+
+    /// The registered fee module and fee recipient
+    FeeConfig,
+
+    /// Seconds an admin action must wait between scheduling and execution
+    TimelockDelay,
+
+    /// Counter for generating unique scheduled action IDs
+    ActionCounter,
+
+    /// A queued admin action, indexed by ID
+    ScheduledAction(u64),
+
+    /// Whether new splits and deposits are blocked
+    Paused,
+
+    /// Collected-but-unreleased funds held for splits, per token
+    TotalEscrowed(Address),
+
+    /// Funds a participant has in escrow for a split
+    HeldBalance(u64, Address),
+
+    /// Where rounding dust goes in proportional payouts
+    RoundingPolicy,
+
+    /// Price oracle used to value deposits in other tokens
+    Oracle,
+
+    /// Whether deposits are accepted in a token other than the escrow token
+    AcceptedToken(Address),
+
+    /// Deposits a participant has in escrow in other tokens, per split
+    ForeignHeld(u64, Address),
+
+    /// Swap router used to pay creators in another asset
+    SwapRouter,
+
+    /// The asset a split's creator asked to be paid in
+    PayoutAsset(u64),
+
+    /// Claim-based payout settings for a split
+    ClaimablePayout(u64),
+
+    /// Released funds waiting to be claimed for a split
+    PayoutBucket(u64),
+
+    /// Ledger sequence after which a split can be expired
+    DeadlineLedger(u64),
+
+    /// Cap on deadline extensions for a split
+    MaxExtensions(u64),
+
+    /// Deadline extensions used so far for a split
+    ExtensionCount(u64),
+
+    /// Creator bond still locked for a split
+    Bond(u64),
+
+    /// Address that rules on disputes
+    Arbiter,
+
+    /// Open dispute on a split
+    Dispute(u64),
+
+    /// Audit record of a split's bond slash
+    BondSlash(u64),
+
+    /// Insurance pool parameters
+    InsuranceConfig,
+
+    /// Escrow-token balance of the insurance pool
+    InsurancePool,
+
+    /// Whether a split paid the insurance premium
+    Insured(u64),
+
+    /// Loss a participant can still claim from the pool for a split
+    InsuranceClaim(u64, Address),
+}
+
+// ============================================
+// Split Option Keys
+// ============================================
+
+/// Per-split settings added after `DataKey` reached the 50-variant limit
+/// of a contract type
+#[contracttype]
+#[derive(Clone)]
+pub enum OptionKey {
+    /// Expiry bounty the creator put up for a split
+    BountyStake(u64),
+
+    /// Deposits are forwarded to the creator as they arrive
+    Reimburse(u64),
+
+    /// Seconds a release request waits before it can execute
+    ReleaseDelay(u64),
+
+    /// Pending release request for a split
+    ReleaseRequest(u64),
+
+    /// Address that confirms a split's payout plan
+    Confirmer(u64),
+
+    /// Payout plan waiting for confirmation
+    ReleaseProposal(u64),
+
+    /// Hash of the reason a participant gave when objecting to a release
+    Objection(u64),
+
+    /// Higher total waiting for participants to accept
+    TotalIncrease(u64),
+
+    /// Split staged by an operator that its creator hasn't accepted yet
+    AwaitingCreator(u64),
+
+    /// Short human-readable reference code of a split
+    RefCode(u64),
+
+    /// Headcount cap of an open split that's still taking members
+    JoinCap(u64),
+
+    /// Anyone may contribute toward the split
+    Crowdfund(u64),
+
+    /// Who has contributed to a crowdfunded split, and how much
+    Contributions(u64),
+
+    /// The parent a child split rolls up into
+    Parent(u64),
+
+    /// Child splits of a parent split
+    Children(u64),
+
+    /// Unpaid amount the creator forgave a participant
+    Forgiven(u64, Address),
+
+    /// How many per-entry transfer records a split has
+    TransferCount(u64),
+
+    /// One transfer out of escrow, by its position in the split's log
+    Transfer(u64, u32),
+
+    /// What's left of the creator's rent budget for a split
+    Rent(u64),
+
+    /// Participants' obligations are share tokens
+    Tokenized(u64),
+
+    /// An insured split's losses have been recorded against the pool
+    LossesRecorded(u64),
+
+    /// A participant's scheduled pulls from their allowance
+    PullSchedule(u64, Address),
+
+    /// The admin froze the split pending an investigation
+    Frozen(u64),
+
+    /// Sequence number of the split's latest event
+    EventSeq(u64),
+
+    /// Notes the creator appended to the split, oldest first
+    Notes(u64),
+
+    /// The split's charity round-up settings
+    RoundUp(u64),
+
+    /// Round-ups a participant has paid on top of their deposits
+    RoundUpHeld(u64, Address),
+
+    /// When the split was last completed and held for release
+    CompletedAt(u64),
+
+    /// Salted hash of the contact a participant was invited through
+    ContactHash(u64, Address),
+
+    /// Whether the creator lets a contract deposit for participants
+    Depositor(u64, Address),
+
+    /// Units each participant holds on a unit-priced split
+    Units(u64),
+
+    /// Ledger the split's entries are known to live until, at the least
+    LiveUntil(u64),
+
+    /// Why the split was cancelled
+    CancelReason(u64),
+
+    /// Participants who agreed to the creator cancelling the split
+    CancelApprovals(u64),
+
+    /// The split's retainage settings and what it holds back
+    Retainage(u64),
+
+    /// SHA-256 of the terms the split was created with
+    TermsHash(u64),
+
+    /// Seconds after creation before unpaid invitations lapse
+    InviteWindow(u64),
+
+    /// Whether a participant's invitation lapsed before they paid
+    Lapsed(u64, Address),
+
+    /// Timestamp before which the split's escrow stays put
+    ScheduledRelease(u64),
+
+    /// Index of the next participant `process_refunds` will visit
+    RefundCursor(u64),
+}
+
+/// Deployment-wide settings added after `DataKey` filled up
+#[contracttype]
+#[derive(Clone)]
+pub enum ConfigKey {
+    /// Whether an address may stage splits on behalf of creators
+    Operator(Address),
+
+    /// Which parts of the contract are paused
+    PauseFlags,
+
+    /// Whether the admin has declared an emergency
+    Emergency,
+
+    /// Circuit breaker caps
+    Breaker,
+
+    /// Circuit breaker counts for the current window
+    BreakerState,
+
+    /// TTL threshold and extension, in ledgers
+    Ttl,
+
+    /// Token and keeper fee of rent budgets
+    Rent,
+
+    /// Companion contract that issues share tokens
+    ShareToken,
+
+    /// Tiered fee schedule
+    FeeSchedule,
+
+    /// A creator or token releases are free for
+    FeeExempt(FeeExemption),
+
+    /// Bounty paid to whoever expires an eligible split
+    ExpiryBounty,
+
+    /// Sequence number of the latest event the contract emitted
+    EventSeq,
+
+    /// Whether an address holds a role
+    Role(Role, Address),
+
+    /// Penalty for parking a completed split
+    LatePenalty,
+
+    /// Whether a contract may deposit for participants on every split
+    Depositor(Address),
+
+    /// Address a participant lets act for them on any split
+    Delegate(Address),
+
+    /// Seconds before an unreleased completed split counts as abandoned
+    AbandonTimeout,
+
+    /// Share of paid participants who must approve a cancel, in bps
+    CancelQuorum,
+
+    /// Address a participant wants their refunds sent to
+    RefundAddress(Address),
+
+    /// Fixed fee rate, in bps, for one split or creator
+    FeeOverride(FeeTarget),
+
+    /// Credit already made for a classic payment reference
+    BridgeRef(BytesN<32>),
+
+    /// Most an address lets others put on it across open splits
+    SpendingCap(Address),
+}
+
+/// Keys for events, which collect splits under a name like a trip
+#[contracttype]
+#[derive(Clone)]
+pub enum EventKey {
+    /// Counter for generating unique event IDs
+    Counter,
+
+    /// An event record, indexed by ID
+    Event(u64),
+
+    /// The event a split belongs to, indexed by split ID
+    SplitEvent(u64),
+}
+
+/// Keys for per-address lifetime counters
+#[contracttype]
+#[derive(Clone)]
+pub enum StatsKey {
+    /// Counters for an address
+    Address(Address),
+}
+
+/// Keys for the per-status split ID indexes
+#[contracttype]
+#[derive(Clone)]
+pub enum IndexKey {
+    /// IDs of every split currently in a status
+    Status(SplitStatus),
+
+    /// The status a split is currently filed under
+    Filed(u64),
+
+    /// IDs of every split created on a day (days since the Unix epoch)
+    Day(u64),
+
+    /// IDs of a creator's splits created on a day
+    CreatorDay(Address, u64),
+
+    /// The split a creator made with an idempotency key
+    Idempotency(Address, BytesN<32>),
+
+    /// How many of a creator's splits are filed under an open status
+    OpenCount(Address),
+
+    /// IDs of a creator's splits filed under a category
+    Category(Address, SplitCategory),
+
+    /// IDs of a creator's splits carrying a tag
+    Tag(Address, Symbol),
+
+    /// IDs of the open splits an address participates in
+    Participant(Address),
+
+    /// IDs of every split a creator has created
+    Creator(Address),
+
+    /// IDs of a creator's splits filed under an open status
+    CreatorOpen(Address),
+}
+
+/// Keys for split ID lists stored one entry per ID
+///
+/// A list that anyone can grow (every split created on a day, every split
+/// in a status) can't live in a single `Vec`: it would be rewritten on each
+/// push and eventually outgrow the entry size. Each ID gets its own slot
+/// instead, so a push or removal touches a fixed number of entries.
+#[contracttype]
+#[derive(Clone)]
+pub enum ListKey {
+    /// How many IDs a list holds
+    Len(IndexKey),
+
+    /// The ID at a position in a list
+    Item(IndexKey, u32),
+
+    /// The position of an ID in a list that supports removal
+    Pos(IndexKey, u64),
+}
+
+// ============================================
+// Enhanced Storage Keys (Issue #59)
+// ============================================
+
+/// Enhanced storage keys matching issue #59 specification
+///
+/// I'm using a separate enum to clearly distinguish the new
+/// escrow storage pattern from the original split storage.
+#[contracttype]
+#[derive(Clone)]
+pub enum StorageKey {
+    /// Escrow record indexed by split_id string
+    /// Maps: split_id -> SplitEscrow
+    Escrow(String),
+
+    /// Individual participant payment tracking
+    /// Maps: (split_id, participant_address) -> i128 amount
+    ParticipantPayment(String, Address),
+
+    /// Total number of escrows created
+    EscrowCount,
+
+    /// Admin address (shared with original)
+    Admin,
+}
+
+/// Default TTL, in ledgers, that a touched entry is extended to
+pub const LEDGER_TTL_PERSISTENT: u32 = 31_536_000;
+
+/// Default remaining TTL, in ledgers, below which a touched entry is extended
+pub const LEDGER_TTL_THRESHOLD: u32 = 86_400;
+
+/// Smallest TTL threshold the admin can set (about a day of ledgers)
+pub const MIN_TTL_THRESHOLD: u32 = 17_280;
+
+/// Largest TTL the admin can have entries extended to
+pub const MAX_TTL_EXTEND_TO: u32 = LEDGER_TTL_PERSISTENT;
+
+/// Default minimum time between payment reminders (1 day)
+const DEFAULT_REMINDER_INTERVAL: u64 = 86_400;
+
+/// Default delay before a scheduled admin action can run (2 days)
+const DEFAULT_TIMELOCK_DELAY: u64 = 172_800;
+
+/// Number of entries returned per page by the paginated index views
+pub const PAGE_SIZE: u32 = 20;
+
+// ============================================
+// TTL Functions
+// ============================================
+
+/// Get the TTL threshold and extension the admin set, or the defaults
+pub fn get_ttl(env: &Env) -> (u32, u32) {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::Ttl)
+        .unwrap_or((LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT))
+}
+
+/// Set the TTL threshold and extension used from now on
+pub fn set_ttl(env: &Env, threshold: u32, extend_to: u32) {
+    env.storage()
+        .persistent()
+        .set(&ConfigKey::Ttl, &(threshold, extend_to));
+    extend(env, &ConfigKey::Ttl);
+}
+
+/// Extend a split's core entries to the full configured TTL right away
+///
+/// Covers the split record, its bond, bounty stake and rent budget, and each
+/// participant's held balance.
+pub fn keep_split_alive(env: &Env, split: &Split) {
+    let (_, extend_to) = get_ttl(env);
+    let persistent = env.storage().persistent();
+    let split_key = DataKey::Split(split.id);
+    persistent.extend_ttl(&split_key, extend_to, extend_to);
+    let bond_key = DataKey::Bond(split.id);
+    if persistent.has(&bond_key) {
+        persistent.extend_ttl(&bond_key, extend_to, extend_to);
+    }
+    let stake_key = OptionKey::BountyStake(split.id);
+    if persistent.has(&stake_key) {
+        persistent.extend_ttl(&stake_key, extend_to, extend_to);
+    }
+    let rent_key = OptionKey::Rent(split.id);
+    if persistent.has(&rent_key) {
+        persistent.extend_ttl(&rent_key, extend_to, extend_to);
+    }
+    for p in split.participants.iter() {
+        let held_key = DataKey::HeldBalance(split.id, p.address);
+        if persistent.has(&held_key) {
+            persistent.extend_ttl(&held_key, extend_to, extend_to);
+        }
+    }
+    set_live_until(env, split.id, env.ledger().sequence() + extend_to);
+}
+
+/// Get the ledger a split's entries are known to live until, if recorded
+pub fn get_live_until(env: &Env, split_id: u64) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::LiveUntil(split_id))
+}
+
+/// Record the ledger a split's entries are known to live until
+pub fn set_live_until(env: &Env, split_id: u64, live_until: u32) {
+    let key = OptionKey::LiveUntil(split_id);
+    env.storage().persistent().set(&key, &live_until);
+    extend(env, &key);
+}
+
+/// Extend a persistent entry's TTL by the configured amounts
+fn extend<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let (threshold, extend_to) = get_ttl(env);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, threshold, extend_to);
+}
+
+// ============================================
+// Admin Storage Functions
+// ============================================
+
+/// Check if the admin has been set
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().persistent().has(&DataKey::Admin)
+}
+
+/// Get the contract admin address
+pub fn get_admin(env: &Env) -> Address {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Admin)
+        .expect("Admin not set")
+}
+
+/// Set the contract admin address
+pub fn set_admin(env: &Env, admin: &Address) {
+    env.storage().persistent().set(&DataKey::Admin, admin);
+    extend(env, &DataKey::Admin);
+}
+
+// ============================================
+// Token Storage Functions
+// ============================================
+
+/// Check if the token has been set
+pub fn has_token(env: &Env) -> bool {
+    env.storage().persistent().has(&DataKey::Token)
+}
+
+/// Get the token contract address
+pub fn get_token(env: &Env) -> Address {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Token)
+        .expect("Token not set")
+}
+
+/// Set the token contract address
+pub fn set_token(env: &Env, token: &Address) {
+    env.storage().persistent().set(&DataKey::Token, token);
+    extend(env, &DataKey::Token);
+}
+
+// ============================================
+// Fee Storage Functions
+// ============================================
+
+/// Get the registered fee module, if any
+pub fn get_fee_config(env: &Env) -> Option<FeeConfig> {
+    env.storage().persistent().get(&DataKey::FeeConfig)
+}
+
+/// Register a fee module
+pub fn set_fee_config(env: &Env, config: &FeeConfig) {
+    let key = DataKey::FeeConfig;
+    env.storage().persistent().set(&key, config);
+    extend(env, &key);
+}
+
+/// Unregister the fee module, making releases free
+pub fn remove_fee_config(env: &Env) {
+    env.storage().persistent().remove(&DataKey::FeeConfig);
+}
+
+/// Get the tiered fee schedule, if any
+pub fn get_fee_schedule(env: &Env) -> Option<FeeSchedule> {
+    env.storage().persistent().get(&ConfigKey::FeeSchedule)
+}
+
+/// Set the tiered fee schedule
+pub fn set_fee_schedule(env: &Env, schedule: &FeeSchedule) {
+    let key = ConfigKey::FeeSchedule;
+    env.storage().persistent().set(&key, schedule);
+    extend(env, &key);
+}
+
+/// Remove the tiered fee schedule
+pub fn remove_fee_schedule(env: &Env) {
+    env.storage().persistent().remove(&ConfigKey::FeeSchedule);
+}
+
+/// Whether a creator or token is exempt from fees
+pub fn is_fee_exempt(env: &Env, exemption: &FeeExemption) -> bool {
+    env.storage()
+        .persistent()
+        .has(&ConfigKey::FeeExempt(exemption.clone()))
+}
+
+/// Exempt a creator or token from fees, or charge it again
+pub fn set_fee_exempt(env: &Env, exemption: &FeeExemption, exempt: bool) {
+    let key = ConfigKey::FeeExempt(exemption.clone());
+    if exempt {
+        env.storage().persistent().set(&key, &true);
+        extend(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+/// Get the credit made for a classic payment reference, if any
+pub fn get_bridge_credit(env: &Env, payment_ref: &BytesN<32>) -> Option<BridgeCredit> {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::BridgeRef(payment_ref.clone()))
+}
+
+/// Record the credit made for a classic payment reference
+pub fn set_bridge_credit(env: &Env, payment_ref: &BytesN<32>, credit: &BridgeCredit) {
+    let key = ConfigKey::BridgeRef(payment_ref.clone());
+    env.storage().persistent().set(&key, credit);
+    extend(env, &key);
+}
+
+/// Get the fee rate overriding the usual fees for a split or creator
+pub fn get_fee_override(env: &Env, target: &FeeTarget) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::FeeOverride(target.clone()))
+}
+
+/// Set (`Some`) or remove (`None`) a split's or creator's fee override
+pub fn set_fee_override(env: &Env, target: &FeeTarget, bps: Option<u32>) {
+    let key = ConfigKey::FeeOverride(target.clone());
+    match bps {
+        Some(bps) => {
+            env.storage().persistent().set(&key, &bps);
+            extend(env, &key);
+        }
+        None => env.storage().persistent().remove(&key),
+    }
+}
+
+/// Get the deployment's rounding policy
+pub fn get_rounding_policy(env: &Env) -> RoundingPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RoundingPolicy)
+        .unwrap_or_default()
+}
+
+/// Get the smallest deposit accepted per transaction
+pub fn get_min_deposit(env: &Env) -> MinDeposit {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MinDeposit)
+        .unwrap_or_default()
+}
+
+/// Set the smallest deposit accepted per transaction
+pub fn set_min_deposit(env: &Env, min: &MinDeposit) {
+    let key = DataKey::MinDeposit;
+    env.storage().persistent().set(&key, min);
+    extend(env, &key);
+}
+
+/// Get the activation window in seconds (0 = no limit)
+pub fn get_activation_window(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ActivationWindow)
+        .unwrap_or(0)
+}
+
+/// Set the activation window in seconds
+pub fn set_activation_window(env: &Env, window: u64) {
+    let key = DataKey::ActivationWindow;
+    env.storage().persistent().set(&key, &window);
+    extend(env, &key);
+}
+
+/// Whether an address may stage splits on behalf of creators
+pub fn is_operator(env: &Env, operator: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::Operator(operator.clone()))
+        .unwrap_or(false)
+}
+
+/// Grant or revoke the operator role
+pub fn set_operator(env: &Env, operator: &Address, enabled: bool) {
+    let key = ConfigKey::Operator(operator.clone());
+    if !enabled {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Whether an address was granted a role
+pub fn has_role(env: &Env, role: Role, account: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::Role(role, account.clone()))
+        .unwrap_or(false)
+}
+
+/// Grant or revoke a role
+pub fn set_role(env: &Env, role: Role, account: &Address, granted: bool) {
+    let key = ConfigKey::Role(role, account.clone());
+    if !granted {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Whether a staged split still waits for its creator to accept it
+pub fn is_awaiting_creator(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::AwaitingCreator(split_id))
+        .unwrap_or(false)
+}
+
+/// Mark a split as staged (`true`) or accepted by its creator (`false`)
+pub fn set_awaiting_creator(env: &Env, split_id: u64, awaiting: bool) {
+    let key = OptionKey::AwaitingCreator(split_id);
+    if !awaiting {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Headcount cap of a split still open to joiners (0 once locked or fixed)
+pub fn get_join_cap(env: &Env, split_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::JoinCap(split_id))
+        .unwrap_or(0)
+}
+
+/// Whether a split is still open to joiners
+pub fn is_joinable(env: &Env, split_id: u64) -> bool {
+    get_join_cap(env, split_id) > 0
+}
+
+/// Open a split to joiners up to `cap` people, or lock it with 0
+pub fn set_join_cap(env: &Env, split_id: u64, cap: u32) {
+    let key = OptionKey::JoinCap(split_id);
+    if cap == 0 {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &cap);
+    extend(env, &key);
+}
+
+/// Whether anyone may contribute toward a split
+pub fn is_crowdfund(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Crowdfund(split_id))
+        .unwrap_or(false)
+}
+
+/// Open a split to contributions from any address
+pub fn set_crowdfund(env: &Env, split_id: u64) {
+    let key = OptionKey::Crowdfund(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Get the contributors to a crowdfunded split, in order of first contribution
+pub fn get_contributions(env: &Env, split_id: u64) -> Vec<Contribution> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Contributions(split_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store the contributors to a crowdfunded split
+pub fn set_contributions(env: &Env, split_id: u64, contributions: &Vec<Contribution>) {
+    let key = OptionKey::Contributions(split_id);
+    env.storage().persistent().set(&key, contributions);
+    extend(env, &key);
+}
+
+/// Get the parent a split rolls up into, if any
+pub fn get_parent(env: &Env, split_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&OptionKey::Parent(split_id))
+}
+
+/// Get the child splits of a parent split
+pub fn get_children(env: &Env, split_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Children(split_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Link a child split under a parent split
+pub fn add_child(env: &Env, parent_id: u64, child_id: u64) {
+    let mut children = get_children(env, parent_id);
+    children.push_back(child_id);
+
+    let key = OptionKey::Children(parent_id);
+    env.storage().persistent().set(&key, &children);
+    extend(env, &key);
+
+    let key = OptionKey::Parent(child_id);
+    env.storage().persistent().set(&key, &parent_id);
+    extend(env, &key);
+}
+
+/// Detach a child split from its parent
+pub fn remove_child(env: &Env, parent_id: u64, child_id: u64) {
+    let mut children = get_children(env, parent_id);
+    if let Some(index) = children.first_index_of(child_id) {
+        children.remove(index);
+    }
+
+    let key = OptionKey::Children(parent_id);
+    if children.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &children);
+        extend(env, &key);
+    }
+
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::Parent(child_id));
+}
+
+/// Get how much of a participant's share the creator forgave
+pub fn get_forgiven(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Forgiven(split_id, participant.clone()))
+        .unwrap_or(0)
+}
+
+/// Record the amount the creator forgave a participant
+pub fn set_forgiven(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    let key = OptionKey::Forgiven(split_id, participant.clone());
+    env.storage().persistent().set(&key, &amount);
+    extend(env, &key);
+}
+
+/// Get one page of the transfers out of escrow recorded for a split,
+/// oldest first
+pub fn get_transfer_page(env: &Env, split_id: u64, page: u32) -> Vec<TransferRecord> {
+    let persistent = env.storage().persistent();
+    let count: u32 = persistent
+        .get(&OptionKey::TransferCount(split_id))
+        .unwrap_or(0);
+
+    let mut records = Vec::new(env);
+    let start = page.saturating_mul(PAGE_SIZE);
+    let end = core::cmp::min(start.saturating_add(PAGE_SIZE), count);
+    for position in start..end {
+        records.push_back(
+            persistent
+                .get(&OptionKey::Transfer(split_id, position))
+                .unwrap(),
+        );
+    }
+    records
+}
+
+/// Append a transfer to a split's log
+///
+/// Each record gets its own entry, so a long history never has to be
+/// read back and rewritten.
+pub fn push_transfer(env: &Env, split_id: u64, record: &TransferRecord) {
+    let count_key = OptionKey::TransferCount(split_id);
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let key = OptionKey::Transfer(split_id, count);
+    env.storage().persistent().set(&key, record);
+    extend(env, &key);
+    env.storage().persistent().set(&count_key, &(count + 1));
+    extend(env, &count_key);
+}
+
+/// Get the notes appended to a split, oldest first
+pub fn get_notes(env: &Env, split_id: u64) -> Vec<SplitNote> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Notes(split_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Save a split's notes
+pub fn set_notes(env: &Env, split_id: u64, notes: &Vec<SplitNote>) {
+    let key = OptionKey::Notes(split_id);
+    env.storage().persistent().set(&key, notes);
+    extend(env, &key);
+}
+
+/// Set the deployment's rounding policy
+pub fn set_rounding_policy(env: &Env, policy: RoundingPolicy) {
+    let key = DataKey::RoundingPolicy;
+    env.storage().persistent().set(&key, &policy);
+    extend(env, &key);
+}
+
+// ============================================
+// Escrow Total Storage Functions
+// ============================================
+
+/// Get how much of a token is currently held in escrow for splits
+pub fn get_total_escrowed(env: &Env, token: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TotalEscrowed(token.clone()))
+        .unwrap_or(0)
+}
+
+/// Adjust the escrowed total for a token by `delta` (negative on payout)
+pub fn add_total_escrowed(env: &Env, token: &Address, delta: i128) {
+    let key = DataKey::TotalEscrowed(token.clone());
+    let total = get_total_escrowed(env, token) + delta;
+    env.storage().persistent().set(&key, &total);
+    extend(env, &key);
+}
+
+/// Get what a participant currently has in escrow for a split
+pub fn get_held_balance(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::HeldBalance(split_id, participant.clone()))
+        .unwrap_or(0)
+}
+
+/// Add a deposit to a participant's escrowed balance for a split
+pub fn add_held_balance(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    let key = DataKey::HeldBalance(split_id, participant.clone());
+    let balance = get_held_balance(env, split_id, participant) + amount;
+    env.storage().persistent().set(&key, &balance);
+    extend(env, &key);
+}
+
+/// Remove and return a participant's escrowed balance for a split
+pub fn take_held_balance(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    let balance = get_held_balance(env, split_id, participant);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::HeldBalance(split_id, participant.clone()));
+    balance
+}
+
+/// Get a participant's escrowed deposits in other tokens for a split
+pub fn get_foreign_held(env: &Env, split_id: u64, participant: &Address) -> Vec<ForeignDeposit> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ForeignHeld(split_id, participant.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Record a deposit in another token against a participant's balance
+pub fn add_foreign_held(env: &Env, split_id: u64, participant: &Address, deposit: ForeignDeposit) {
+    let key = DataKey::ForeignHeld(split_id, participant.clone());
+    let mut held = get_foreign_held(env, split_id, participant);
+    held.push_back(deposit);
+    env.storage().persistent().set(&key, &held);
+    extend(env, &key);
+}
+
+/// Remove and return a participant's escrowed deposits in other tokens
+pub fn take_foreign_held(env: &Env, split_id: u64, participant: &Address) -> Vec<ForeignDeposit> {
+    let held = get_foreign_held(env, split_id, participant);
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ForeignHeld(split_id, participant.clone()));
+    held
+}
+
+// ============================================
+// Multi-Token Storage Functions
+// ============================================
+
+/// Get the price oracle, if one is configured
+pub fn get_oracle(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Oracle)
+}
+
+/// Set the price oracle
+pub fn set_oracle(env: &Env, oracle: &Address) {
+    let key = DataKey::Oracle;
+    env.storage().persistent().set(&key, oracle);
+    extend(env, &key);
+}
+
+/// Get the swap router, if one is configured
+pub fn get_swap_router(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::SwapRouter)
+}
+
+/// Set the swap router
+pub fn set_swap_router(env: &Env, router: &Address) {
+    let key = DataKey::SwapRouter;
+    env.storage().persistent().set(&key, router);
+    extend(env, &key);
+}
+
+/// Get the asset a split's creator asked to be paid in, if any
+pub fn get_payout_asset(env: &Env, split_id: u64) -> Option<PayoutAsset> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PayoutAsset(split_id))
+}
+
+/// Pay a split's creator in another asset
+pub fn set_payout_asset(env: &Env, split_id: u64, asset: &PayoutAsset) {
+    let key = DataKey::PayoutAsset(split_id);
+    env.storage().persistent().set(&key, asset);
+    extend(env, &key);
+}
+
+/// Pay a split's creator in the escrow token again
+pub fn remove_payout_asset(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PayoutAsset(split_id));
+}
+
+/// Get a split's ledger sequence deadline, if any
+pub fn get_deadline_ledger(env: &Env, split_id: u64) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DeadlineLedger(split_id))
+}
+
+/// Set a split's ledger sequence deadline
+pub fn set_deadline_ledger(env: &Env, split_id: u64, sequence: u32) {
+    let key = DataKey::DeadlineLedger(split_id);
+    env.storage().persistent().set(&key, &sequence);
+    extend(env, &key);
+}
+
+/// Get the cap on a split's deadline extensions, if any
+pub fn get_max_extensions(env: &Env, split_id: u64) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MaxExtensions(split_id))
+}
+
+/// Cap a split's deadline extensions
+pub fn set_max_extensions(env: &Env, split_id: u64, max: u32) {
+    let key = DataKey::MaxExtensions(split_id);
+    env.storage().persistent().set(&key, &max);
+    extend(env, &key);
+}
+
+/// Get how many times a split's deadline was extended
+pub fn get_extension_count(env: &Env, split_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ExtensionCount(split_id))
+        .unwrap_or(0)
+}
+
+/// Record how many times a split's deadline was extended
+pub fn set_extension_count(env: &Env, split_id: u64, count: u32) {
+    let key = DataKey::ExtensionCount(split_id);
+    env.storage().persistent().set(&key, &count);
+    extend(env, &key);
+}
+
+/// Get the creator bond locked for a split (0 if none)
+pub fn get_bond(env: &Env, split_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Bond(split_id))
+        .unwrap_or(0)
+}
+
+/// Record the creator bond locked for a split
+pub fn set_bond(env: &Env, split_id: u64, amount: i128) {
+    let key = DataKey::Bond(split_id);
+    env.storage().persistent().set(&key, &amount);
+    extend(env, &key);
+}
+
+/// Remove and return a split's creator bond (0 if none)
+pub fn take_bond(env: &Env, split_id: u64) -> i128 {
+    let key = DataKey::Bond(split_id);
+    let amount = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().remove(&key);
+    amount
+}
+
+/// Get the expiry bounty a split's creator put up (0 if none)
+pub fn get_bounty_stake(env: &Env, split_id: u64) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::BountyStake(split_id))
+        .unwrap_or(0)
+}
+
+/// Record the expiry bounty a split's creator put up
+pub fn set_bounty_stake(env: &Env, split_id: u64, amount: i128) {
+    let key = OptionKey::BountyStake(split_id);
+    env.storage().persistent().set(&key, &amount);
+    extend(env, &key);
+}
+
+/// Remove and return a split's bounty stake (0 if none)
+pub fn take_bounty_stake(env: &Env, split_id: u64) -> i128 {
+    let key = OptionKey::BountyStake(split_id);
+    let amount = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().remove(&key);
+    amount
+}
+
+/// Get the rent token and keeper fee, if the admin set them
+pub fn get_rent_config(env: &Env) -> Option<RentConfig> {
+    env.storage().persistent().get(&ConfigKey::Rent)
+}
+
+/// Set the rent token and keeper fee
+pub fn set_rent_config(env: &Env, config: &RentConfig) {
+    let key = ConfigKey::Rent;
+    env.storage().persistent().set(&key, config);
+    extend(env, &key);
+}
+
+/// Get a participant's pull schedule for a split, if they set one
+pub fn get_pull_schedule(env: &Env, split_id: u64, participant: &Address) -> Option<PullSchedule> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::PullSchedule(split_id, participant.clone()))
+}
+
+/// Store a participant's pull schedule for a split
+pub fn set_pull_schedule(env: &Env, split_id: u64, participant: &Address, schedule: &PullSchedule) {
+    let key = OptionKey::PullSchedule(split_id, participant.clone());
+    env.storage().persistent().set(&key, schedule);
+    extend(env, &key);
+}
+
+/// Remove a participant's pull schedule for a split
+pub fn remove_pull_schedule(env: &Env, split_id: u64, participant: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::PullSchedule(split_id, participant.clone()));
+}
+
+/// Get an address's lifetime counters (all zero if it never took part)
+pub fn get_address_stats(env: &Env, address: &Address) -> AddressStats {
+    env.storage()
+        .persistent()
+        .get(&StatsKey::Address(address.clone()))
+        .unwrap_or_default()
+}
+
+/// Store an address's lifetime counters
+pub fn set_address_stats(env: &Env, address: &Address, stats: &AddressStats) {
+    let key = StatsKey::Address(address.clone());
+    env.storage().persistent().set(&key, stats);
+    extend(env, &key);
+}
+
+/// Get the companion contract that issues share tokens, if registered
+pub fn get_share_token(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&ConfigKey::ShareToken)
+}
+
+/// Register the companion contract that issues share tokens
+pub fn set_share_token(env: &Env, companion: &Address) {
+    let key = ConfigKey::ShareToken;
+    env.storage().persistent().set(&key, companion);
+    extend(env, &key);
+}
+
+/// Whether a split's obligations are share tokens
+pub fn is_tokenized(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Tokenized(split_id))
+        .unwrap_or(false)
+}
+
+/// Mark a split's obligations as share tokens
+pub fn set_tokenized(env: &Env, split_id: u64) {
+    let key = OptionKey::Tokenized(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Move a participant's escrowed balances and settlement records for a
+/// split to another address
+///
+/// The new holder is only indexed while the split is open, like every
+/// participant in `set_split`.
+pub fn move_participant_state(env: &Env, split: &Split, from: &Address, to: &Address) {
+    let split_id = split.id;
+    let persistent = env.storage().persistent();
+    let keys: [Val; 8] = [
+        DataKey::HeldBalance(split_id, from.clone()).into_val(env),
+        DataKey::ForeignHeld(split_id, from.clone()).into_val(env),
+        DataKey::OffchainSettled(split_id, from.clone()).into_val(env),
+        DataKey::InsuranceClaim(split_id, from.clone()).into_val(env),
+        OptionKey::Forgiven(split_id, from.clone()).into_val(env),
+        OptionKey::RoundUpHeld(split_id, from.clone()).into_val(env),
+        OptionKey::PullSchedule(split_id, from.clone()).into_val(env),
+        OptionKey::Lapsed(split_id, from.clone()).into_val(env),
+    ];
+    let new_keys: [Val; 8] = [
+        DataKey::HeldBalance(split_id, to.clone()).into_val(env),
+        DataKey::ForeignHeld(split_id, to.clone()).into_val(env),
+        DataKey::OffchainSettled(split_id, to.clone()).into_val(env),
+        DataKey::InsuranceClaim(split_id, to.clone()).into_val(env),
+        OptionKey::Forgiven(split_id, to.clone()).into_val(env),
+        OptionKey::RoundUpHeld(split_id, to.clone()).into_val(env),
+        OptionKey::PullSchedule(split_id, to.clone()).into_val(env),
+        OptionKey::Lapsed(split_id, to.clone()).into_val(env),
+    ];
+    for (old_key, new_key) in keys.iter().zip(new_keys.iter()) {
+        if let Some(value) = persistent.get::<Val, Val>(old_key) {
+            persistent.remove(old_key);
+            persistent.set(new_key, &value);
+            extend(env, new_key);
+        }
+    }
+
+    // The label and contact described the old holder, not the position
+    remove_participant_label(env, split_id, from);
+    remove_contact_hash(env, split_id, from);
+    remove_from_participant_index(env, from, split_id);
+    if !is_closed(&split.status) {
+        add_to_participant_index(env, to, split_id);
+    }
+}
+
+/// Get what's left of a split's rent budget, if it has one
+pub fn get_rent_budget(env: &Env, split_id: u64) -> Option<RentBudget> {
+    env.storage().persistent().get(&OptionKey::Rent(split_id))
+}
+
+/// Record what's left of a split's rent budget
+pub fn set_rent_budget(env: &Env, split_id: u64, budget: &RentBudget) {
+    let key = OptionKey::Rent(split_id);
+    env.storage().persistent().set(&key, budget);
+    extend(env, &key);
+}
+
+/// Remove and return a split's rent budget
+pub fn take_rent_budget(env: &Env, split_id: u64) -> Option<RentBudget> {
+    let key = OptionKey::Rent(split_id);
+    let budget = env.storage().persistent().get(&key);
+    env.storage().persistent().remove(&key);
+    budget
+}
+
+/// Get a split's charity round-up settings, if it has them
+pub fn get_round_up(env: &Env, split_id: u64) -> Option<RoundUp> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::RoundUp(split_id))
+}
+
+/// Give a split a charity round-up
+pub fn set_round_up(env: &Env, split_id: u64, round_up: &RoundUp) {
+    let key = OptionKey::RoundUp(split_id);
+    env.storage().persistent().set(&key, round_up);
+    extend(env, &key);
+}
+
+/// Get the round-ups a participant has paid into a split
+pub fn get_round_up_held(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::RoundUpHeld(split_id, participant.clone()))
+        .unwrap_or(0)
+}
+
+/// Add to the round-ups a participant has paid into a split
+pub fn add_round_up_held(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    let key = OptionKey::RoundUpHeld(split_id, participant.clone());
+    let held = get_round_up_held(env, split_id, participant) + amount;
+    env.storage().persistent().set(&key, &held);
+    extend(env, &key);
+}
+
+/// Remove and return the round-ups a participant has paid into a split
+pub fn take_round_up_held(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    let held = get_round_up_held(env, split_id, participant);
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::RoundUpHeld(split_id, participant.clone()));
+    held
+}
+
+/// Get the record of a split's bond slash, if any
+pub fn get_bond_slash(env: &Env, split_id: u64) -> Option<BondSlash> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::BondSlash(split_id))
+}
+
+/// Record a split's bond slash
+pub fn set_bond_slash(env: &Env, split_id: u64, slash: &BondSlash) {
+    let key = DataKey::BondSlash(split_id);
+    env.storage().persistent().set(&key, slash);
+    extend(env, &key);
+}
+
+/// Get how a split's amounts should be rendered, if recorded
+pub fn get_split_display(env: &Env, split_id: u64) -> Option<SplitDisplay> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SplitDisplay(split_id))
+}
+
+/// Record how a split's amounts should be rendered
+pub fn set_split_display(env: &Env, split_id: u64, display: &SplitDisplay) {
+    let key = DataKey::SplitDisplay(split_id);
+    env.storage().persistent().set(&key, display);
+    extend(env, &key);
+}
+
+/// Get who can read a split through the views
+pub fn get_visibility(env: &Env, split_id: u64) -> Visibility {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Visibility(split_id))
+        .unwrap_or_default()
+}
+
+/// Set who can read a split through the views
+pub fn set_visibility(env: &Env, split_id: u64, visibility: Visibility) {
+    let key = DataKey::Visibility(split_id);
+    env.storage().persistent().set(&key, &visibility);
+    extend(env, &key);
+}
+
+/// Whether a split's off-chain settlements need the participant's auth
+pub fn requires_offchain_confirmation(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ConfirmOffchain(split_id))
+        .unwrap_or(false)
+}
+
+/// Make a split's off-chain settlements need the participant's auth
+pub fn set_requires_offchain_confirmation(env: &Env, split_id: u64) {
+    let key = DataKey::ConfirmOffchain(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Get how much of a participant's share was settled off-chain
+pub fn get_offchain_settled(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OffchainSettled(split_id, participant.clone()))
+        .unwrap_or(0)
+}
+
+/// Record an off-chain settlement of a participant's share
+pub fn add_offchain_settled(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    let key = DataKey::OffchainSettled(split_id, participant.clone());
+    let settled: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(settled + amount));
+    extend(env, &key);
+}
+
+/// Whether a split only accepts full-share deposits
+pub fn requires_full_payment(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::RequireFullPayment(split_id))
+        .unwrap_or(false)
+}
+
+/// Make a split only accept full-share deposits
+pub fn set_require_full_payment(env: &Env, split_id: u64) {
+    let key = DataKey::RequireFullPayment(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Whether a split forwards deposits to its creator as they arrive
+pub fn reimburses_creator(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Reimburse(split_id))
+        .unwrap_or(false)
+}
+
+/// Make a split forward deposits to its creator as they arrive
+pub fn set_reimburses_creator(env: &Env, split_id: u64) {
+    let key = OptionKey::Reimburse(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Get how long a split's release requests wait (0 = no delay)
+pub fn get_release_delay(env: &Env, split_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::ReleaseDelay(split_id))
+        .unwrap_or(0)
+}
+
+/// Set how long a split's release requests wait
+pub fn set_release_delay(env: &Env, split_id: u64, delay: u64) {
+    let key = OptionKey::ReleaseDelay(split_id);
+    env.storage().persistent().set(&key, &delay);
+    extend(env, &key);
+}
+
+/// Get a split's pending release request, if any
+pub fn get_release_request(env: &Env, split_id: u64) -> Option<ReleaseRequest> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::ReleaseRequest(split_id))
+}
+
+/// Record a split's release request
+pub fn set_release_request(env: &Env, split_id: u64, request: &ReleaseRequest) {
+    let key = OptionKey::ReleaseRequest(split_id);
+    env.storage().persistent().set(&key, request);
+    extend(env, &key);
+}
+
+/// Drop a split's release request once it executes
+pub fn remove_release_request(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::ReleaseRequest(split_id));
+}
+
+/// Get when a split is scheduled to release, if it is
+pub fn get_scheduled_release(env: &Env, split_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::ScheduledRelease(split_id))
+}
+
+/// Schedule a split's release, replacing any earlier time
+pub fn set_scheduled_release(env: &Env, split_id: u64, release_at: u64) {
+    let key = OptionKey::ScheduledRelease(split_id);
+    env.storage().persistent().set(&key, &release_at);
+    extend(env, &key);
+}
+
+/// Drop a split's release schedule once it has paid out
+pub fn remove_scheduled_release(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::ScheduledRelease(split_id));
+}
+
+/// Get how many of a split's participants `process_refunds` has visited
+pub fn get_refund_cursor(env: &Env, split_id: u64) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::RefundCursor(split_id))
+        .unwrap_or(0)
+}
+
+/// Move a split's refund cursor past the participants just visited
+pub fn set_refund_cursor(env: &Env, split_id: u64, cursor: u32) {
+    let key = OptionKey::RefundCursor(split_id);
+    env.storage().persistent().set(&key, &cursor);
+    extend(env, &key);
+}
+
+/// Get the address that confirms a split's payout plan, if any
+pub fn get_confirmer(env: &Env, split_id: u64) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Confirmer(split_id))
+}
+
+/// Require a split's payouts to be confirmed by `confirmer`
+pub fn set_confirmer(env: &Env, split_id: u64, confirmer: &Address) {
+    let key = OptionKey::Confirmer(split_id);
+    env.storage().persistent().set(&key, confirmer);
+    extend(env, &key);
+}
+
+/// Get a split's payout plan waiting for confirmation, if any
+pub fn get_release_proposal(env: &Env, split_id: u64) -> Option<ReleaseProposal> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::ReleaseProposal(split_id))
+}
+
+/// Store a split's proposed payout plan
+pub fn set_release_proposal(env: &Env, split_id: u64, proposal: &ReleaseProposal) {
+    let key = OptionKey::ReleaseProposal(split_id);
+    env.storage().persistent().set(&key, proposal);
+    extend(env, &key);
+}
+
+/// Drop a split's payout plan once it has been paid
+pub fn remove_release_proposal(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::ReleaseProposal(split_id));
+}
+
+/// Get a split's proposed total increase, if any
+pub fn get_total_increase(env: &Env, split_id: u64) -> Option<TotalIncrease> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::TotalIncrease(split_id))
+}
+
+/// Store a split's proposed total increase
+pub fn set_total_increase(env: &Env, split_id: u64, increase: &TotalIncrease) {
+    let key = OptionKey::TotalIncrease(split_id);
+    env.storage().persistent().set(&key, increase);
+    extend(env, &key);
+}
+
+/// Drop a split's proposed total increase
+pub fn remove_total_increase(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::TotalIncrease(split_id));
+}
+
+/// Get a split's stored reference code
+pub fn get_ref_code(env: &Env, split_id: u64) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::RefCode(split_id))
+}
+
+/// Store a split's reference code
+pub fn set_ref_code(env: &Env, split_id: u64, code: &String) {
+    let key = OptionKey::RefCode(split_id);
+    env.storage().persistent().set(&key, code);
+    extend(env, &key);
+}
+
+/// Get a participant's label on a split, if any
+pub fn get_participant_label(env: &Env, split_id: u64, participant: &Address) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ParticipantLabel(split_id, participant.clone()))
+}
+
+/// Label a participant on a split
+pub fn set_participant_label(env: &Env, split_id: u64, participant: &Address, label: &String) {
+    let key = DataKey::ParticipantLabel(split_id, participant.clone());
+    env.storage().persistent().set(&key, label);
+    extend(env, &key);
+}
+
+/// Clear a participant's label on a split
+pub fn remove_participant_label(env: &Env, split_id: u64, participant: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ParticipantLabel(split_id, participant.clone()));
+}
+
+/// Get the salted contact hash stored for a participant, if any
+pub fn get_contact_hash(env: &Env, split_id: u64, participant: &Address) -> Option<Bytes> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::ContactHash(split_id, participant.clone()))
+}
+
+/// Store the salted contact hash a participant was invited through
+pub fn set_contact_hash(env: &Env, split_id: u64, participant: &Address, hash: &Bytes) {
+    let key = OptionKey::ContactHash(split_id, participant.clone());
+    env.storage().persistent().set(&key, hash);
+    extend(env, &key);
+}
+
+/// Forget a participant's contact hash
+pub fn remove_contact_hash(env: &Env, split_id: u64, participant: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::ContactHash(split_id, participant.clone()));
+}
+
+/// Get a unit-priced split's units, if it has them
+pub fn get_unit_shares(env: &Env, split_id: u64) -> Option<UnitShares> {
+    env.storage().persistent().get(&OptionKey::Units(split_id))
+}
+
+/// Store a unit-priced split's units
+pub fn set_unit_shares(env: &Env, split_id: u64, units: &UnitShares) {
+    let key = OptionKey::Units(split_id);
+    env.storage().persistent().set(&key, units);
+    extend(env, &key);
+}
+
+/// Whether a split's units are still being decided
+pub fn has_unlocked_units(env: &Env, split_id: u64) -> bool {
+    get_unit_shares(env, split_id).is_some_and(|u| !u.locked)
+}
+
+/// Whether the admin lets an invoker deposit on every split
+pub fn is_depositor(env: &Env, invoker: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::Depositor(invoker.clone()))
+        .unwrap_or(false)
+}
+
+/// Add an invoker to, or drop it from, the deployment-wide allowlist
+pub fn set_depositor(env: &Env, invoker: &Address, enabled: bool) {
+    let key = ConfigKey::Depositor(invoker.clone());
+    if !enabled {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Get the delegate a participant registered, if any
+pub fn get_delegate(env: &Env, participant: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::Delegate(participant.clone()))
+}
+
+/// Register a participant's delegate, replacing any earlier one
+pub fn set_delegate(env: &Env, participant: &Address, delegate: &Address) {
+    let key = ConfigKey::Delegate(participant.clone());
+    env.storage().persistent().set(&key, delegate);
+    extend(env, &key);
+}
+
+/// Get where a participant wants refunds sent, if they said
+pub fn get_refund_address(env: &Env, participant: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::RefundAddress(participant.clone()))
+}
+
+/// Register a participant's refund address, replacing any earlier one
+pub fn set_refund_address(env: &Env, participant: &Address, refund_to: &Address) {
+    let key = ConfigKey::RefundAddress(participant.clone());
+    env.storage().persistent().set(&key, refund_to);
+    extend(env, &key);
+}
+
+/// Get the cap an address put on what others can make it owe, if any
+pub fn get_spending_cap(env: &Env, address: &Address) -> Option<i128> {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::SpendingCap(address.clone()))
+}
+
+/// Set an address's spending cap, replacing any earlier one
+pub fn set_spending_cap(env: &Env, address: &Address, cap: i128) {
+    let key = ConfigKey::SpendingCap(address.clone());
+    env.storage().persistent().set(&key, &cap);
+    extend(env, &key);
+}
+
+/// Lift an address's spending cap
+pub fn remove_spending_cap(env: &Env, address: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&ConfigKey::SpendingCap(address.clone()));
+}
+
+/// Forget a participant's refund address
+pub fn remove_refund_address(env: &Env, participant: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&ConfigKey::RefundAddress(participant.clone()));
+}
+
+/// Forget a participant's delegate
+pub fn remove_delegate(env: &Env, participant: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&ConfigKey::Delegate(participant.clone()));
+}
+
+/// Whether a split's creator lets an invoker deposit on it
+pub fn is_split_depositor(env: &Env, split_id: u64, invoker: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Depositor(split_id, invoker.clone()))
+        .unwrap_or(false)
+}
+
+/// Add an invoker to, or drop it from, a split's allowlist
+pub fn set_split_depositor(env: &Env, split_id: u64, invoker: &Address, enabled: bool) {
+    let key = OptionKey::Depositor(split_id, invoker.clone());
+    if !enabled {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Get why a split was cancelled, if it was
+pub fn get_cancel_reason(env: &Env, split_id: u64) -> Option<String> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::CancelReason(split_id))
+}
+
+/// Record why a split was cancelled
+pub fn set_cancel_reason(env: &Env, split_id: u64, reason: &String) {
+    let key = OptionKey::CancelReason(split_id);
+    env.storage().persistent().set(&key, reason);
+    extend(env, &key);
+}
+
+/// Get the cap on each creator's open splits (0 = no cap)
+pub fn get_max_open_splits(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MaxOpenSplits)
+        .unwrap_or(0)
+}
+
+/// Set the cap on each creator's open splits
+pub fn set_max_open_splits(env: &Env, max: u32) {
+    let key = DataKey::MaxOpenSplits;
+    env.storage().persistent().set(&key, &max);
+    extend(env, &key);
+}
+
+/// Get the dispute arbiter, if one is set
+pub fn get_arbiter(env: &Env) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::Arbiter)
+}
+
+/// Set the dispute arbiter
+pub fn set_arbiter(env: &Env, arbiter: &Address) {
+    let key = DataKey::Arbiter;
+    env.storage().persistent().set(&key, arbiter);
+    extend(env, &key);
+}
+
+/// Get the open dispute on a split, if any
+pub fn get_dispute(env: &Env, split_id: u64) -> Option<Dispute> {
+    env.storage().persistent().get(&DataKey::Dispute(split_id))
+}
+
+/// Open a dispute on a split
+pub fn set_dispute(env: &Env, split_id: u64, dispute: &Dispute) {
+    let key = DataKey::Dispute(split_id);
+    env.storage().persistent().set(&key, dispute);
+    extend(env, &key);
+}
+
+/// Close a split's dispute
+pub fn remove_dispute(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Dispute(split_id));
+    env.storage()
+        .persistent()
+        .remove(&OptionKey::Objection(split_id));
+}
+
+/// Get the reason hash of the objection behind a split's open dispute
+pub fn get_objection(env: &Env, split_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Objection(split_id))
+}
+
+/// Record the reason hash of an objection to a split's release
+pub fn set_objection(env: &Env, split_id: u64, reason_hash: &BytesN<32>) {
+    let key = OptionKey::Objection(split_id);
+    env.storage().persistent().set(&key, reason_hash);
+    extend(env, &key);
+}
+
+/// Get the insurance pool parameters, if the admin has set them
+pub fn get_insurance_config(env: &Env) -> Option<InsuranceConfig> {
+    env.storage().persistent().get(&DataKey::InsuranceConfig)
+}
+
+/// Set the insurance pool parameters
+pub fn set_insurance_config(env: &Env, config: &InsuranceConfig) {
+    let key = DataKey::InsuranceConfig;
+    env.storage().persistent().set(&key, config);
+    extend(env, &key);
+}
+
+/// Get the insurance pool's balance
+pub fn get_insurance_pool(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InsurancePool)
+        .unwrap_or(0)
+}
+
+/// Adjust the insurance pool's balance by `delta`
+pub fn add_insurance_pool(env: &Env, delta: i128) {
+    let key = DataKey::InsurancePool;
+    let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(balance + delta));
+    extend(env, &key);
+}
+
+/// Whether a split paid the insurance premium
+pub fn is_insured(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Insured(split_id))
+        .unwrap_or(false)
+}
+
+/// Mark a split as insured
+pub fn set_insured(env: &Env, split_id: u64) {
+    let key = DataKey::Insured(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Whether an insured split's losses were already recorded
+pub fn has_recorded_losses(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::LossesRecorded(split_id))
+        .unwrap_or(false)
+}
+
+/// Mark an insured split's losses as recorded, so they're never recorded
+/// twice
+pub fn set_losses_recorded(env: &Env, split_id: u64) {
+    let key = OptionKey::LossesRecorded(split_id);
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
+}
+
+/// Get the loss a participant can claim from the pool for a split
+pub fn get_insurance_claim(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::InsuranceClaim(split_id, participant.clone()))
+        .unwrap_or(0)
+}
+
+/// Record the loss a participant can claim from the pool for a split
+pub fn set_insurance_claim(env: &Env, split_id: u64, participant: &Address, amount: i128) {
+    let key = DataKey::InsuranceClaim(split_id, participant.clone());
+    env.storage().persistent().set(&key, &amount);
+    extend(env, &key);
+}
+
+/// Remove and return a participant's claimable loss for a split
+pub fn take_insurance_claim(env: &Env, split_id: u64, participant: &Address) -> i128 {
+    let key = DataKey::InsuranceClaim(split_id, participant.clone());
+    let amount = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().remove(&key);
+    amount
+}
+
+/// Get a split's claim-based payout settings, if any
+pub fn get_claimable_payout(env: &Env, split_id: u64) -> Option<ClaimablePayout> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ClaimablePayout(split_id))
+}
+
+/// Send a split's releases to a claimable bucket
+pub fn set_claimable_payout(env: &Env, split_id: u64, payout: &ClaimablePayout) {
+    let key = DataKey::ClaimablePayout(split_id);
+    env.storage().persistent().set(&key, payout);
+    extend(env, &key);
+}
+
+/// Get a split's unclaimed released funds, if any
+pub fn get_payout_bucket(env: &Env, split_id: u64) -> Option<PayoutBucket> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PayoutBucket(split_id))
+}
+
+/// Store a split's unclaimed released funds
+pub fn set_payout_bucket(env: &Env, split_id: u64, bucket: &PayoutBucket) {
+    let key = DataKey::PayoutBucket(split_id);
+    env.storage().persistent().set(&key, bucket);
+    extend(env, &key);
+}
+
+/// Remove a split's payout bucket once it has been paid out
+pub fn remove_payout_bucket(env: &Env, split_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::PayoutBucket(split_id));
+}
+
+/// Check whether deposits are accepted in a token
+pub fn is_token_accepted(env: &Env, token: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::AcceptedToken(token.clone()))
+        .unwrap_or(false)
+}
+
+/// Accept or stop accepting deposits in a token
+pub fn set_token_accepted(env: &Env, token: &Address, accepted: bool) {
+    let key = DataKey::AcceptedToken(token.clone());
+    if accepted {
+        env.storage().persistent().set(&key, &true);
+        extend(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
+
+// ============================================
+// Pause Storage Functions
+// ============================================
+
+/// Check whether the contract is paused
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+/// Pause or unpause the contract
+pub fn set_paused(env: &Env, paused: bool) {
+    let key = DataKey::Paused;
+    env.storage().persistent().set(&key, &paused);
+    extend(env, &key);
+}
+
+/// Get the pause switches in effect, folding in the blanket pause flag
+pub fn get_pause_flags(env: &Env) -> PauseFlags {
+    let mut flags: PauseFlags = env
+        .storage()
+        .persistent()
+        .get(&ConfigKey::PauseFlags)
+        .unwrap_or_default();
+    if is_paused(env) {
+        flags.create = true;
+        flags.deposit = true;
+    }
+    if is_emergency(env) {
+        flags = PauseFlags {
+            create: true,
+            deposit: true,
+            release: true,
+            refund: true,
+        };
+    }
+    flags
+}
+
+/// Get the circuit breaker's caps, if the admin set any
+pub fn get_breaker_config(env: &Env) -> Option<BreakerConfig> {
+    env.storage().persistent().get(&ConfigKey::Breaker)
+}
+
+/// Set the circuit breaker's caps
+pub fn set_breaker_config(env: &Env, config: &BreakerConfig) {
+    let key = ConfigKey::Breaker;
+    env.storage().persistent().set(&key, config);
+    extend(env, &key);
+}
+
+/// Get the circuit breaker's counts
+pub fn get_breaker_state(env: &Env) -> BreakerState {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::BreakerState)
+        .unwrap_or_default()
+}
+
+/// Set the circuit breaker's counts
+pub fn set_breaker_state(env: &Env, state: &BreakerState) {
+    let key = ConfigKey::BreakerState;
+    env.storage().persistent().set(&key, state);
+    extend(env, &key);
+}
+
+/// Check whether the admin has declared an emergency
+pub fn is_emergency(env: &Env) -> bool {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::Emergency)
+        .unwrap_or(false)
+}
+
+/// Get the sequence number of the latest event, overall or for a split
+/// (0 if there hasn't been one)
+pub fn get_event_seq(env: &Env, split_id: Option<u64>) -> u64 {
+    let persistent = env.storage().persistent();
+    match split_id {
+        Some(split_id) => persistent.get(&OptionKey::EventSeq(split_id)),
+        None => persistent.get(&ConfigKey::EventSeq),
+    }
+    .unwrap_or(0)
+}
+
+/// Advance the event sequence, overall or for a split, returning the new
+/// number
+pub fn next_event_seq(env: &Env, split_id: Option<u64>) -> u64 {
+    let next = get_event_seq(env, split_id) + 1;
+    match split_id {
+        Some(split_id) => {
+            let key = OptionKey::EventSeq(split_id);
+            env.storage().persistent().set(&key, &next);
+            extend(env, &key);
+        }
+        None => {
+            let key = ConfigKey::EventSeq;
+            env.storage().persistent().set(&key, &next);
+            extend(env, &key);
+        }
+    }
+    next
+}
+
+/// Get the bounty paid for expiring a split (0 if none)
+pub fn get_expiry_bounty(env: &Env) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::ExpiryBounty)
+        .unwrap_or(0)
+}
+
+/// Set the bounty paid for expiring a split
+pub fn set_expiry_bounty(env: &Env, bounty: i128) {
+    let key = ConfigKey::ExpiryBounty;
+    env.storage().persistent().set(&key, &bounty);
+    extend(env, &key);
+}
+
+/// Get the penalty for parking a completed split, if one is set
+pub fn get_late_penalty(env: &Env) -> Option<LatePenalty> {
+    env.storage().persistent().get(&ConfigKey::LatePenalty)
+}
+
+/// Set the penalty for parking a completed split
+pub fn set_late_penalty(env: &Env, penalty: &LatePenalty) {
+    let key = ConfigKey::LatePenalty;
+    env.storage().persistent().set(&key, penalty);
+    extend(env, &key);
+}
 
+/// Get the abandoned-split timeout (0 = off)
+pub fn get_abandon_timeout(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::AbandonTimeout)
+        .unwrap_or(0)
+}
 
-const ADMIN: Symbol = Symbol::new("ADMIN");
-const INIT: Symbol = Symbol::new("INIT");
+/// Set the abandoned-split timeout
+pub fn set_abandon_timeout(env: &Env, timeout: u64) {
+    let key = ConfigKey::AbandonTimeout;
+    env.storage().persistent().set(&key, &timeout);
+    extend(env, &key);
+}
 
-pub fn is_initialized(env: &Env) -> bool {
-    env.storage().instance().has(&INIT)
+/// Get the hash of a split's creation terms, if it has one
+pub fn get_terms_hash(env: &Env, split_id: u64) -> Option<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::TermsHash(split_id))
 }
 
-pub fn set_initialized(env: &Env) {
-    env.storage().instance().set(&INIT, &true);
+/// Store the hash of a split's creation terms
+pub fn set_terms_hash(env: &Env, split_id: u64, hash: &BytesN<32>) {
+    let key = OptionKey::TermsHash(split_id);
+    env.storage().persistent().set(&key, hash);
+    extend(env, &key);
 }
 
-pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().instance().set(&ADMIN, admin);
+/// Get a split's invitation window in seconds (0 = invitations never lapse)
+pub fn get_invite_window(env: &Env, split_id: u64) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::InviteWindow(split_id))
+        .unwrap_or(0)
 }
 
-pub fn get_admin(env: &Env) -> Address {
-    env.storage().instance().get(&ADMIN).unwrap()
+/// Set a split's invitation window
+pub fn set_invite_window(env: &Env, split_id: u64, window: u64) {
+    let key = OptionKey::InviteWindow(split_id);
+    env.storage().persistent().set(&key, &window);
+    extend(env, &key);
 }
 
-pub fn save_escrow(env: &Env, escrow: &SplitEscrow) {
+/// Whether a participant's invitation on a split has lapsed
+pub fn is_lapsed(env: &Env, split_id: u64, participant: &Address) -> bool {
     env.storage()
         .persistent()
-        .set(&escrow.split_id, escrow);
+        .get(&OptionKey::Lapsed(split_id, participant.clone()))
+        .unwrap_or(false)
 }
 
-pub fn get_escrow(env: &Env, split_id: &String) -> Option<SplitEscrow> {
-    env.storage().persistent().get(split_id)
+/// Mark a participant's invitation as lapsed, or clear the mark
+pub fn set_lapsed(env: &Env, split_id: u64, participant: &Address, lapsed: bool) {
+    let key = OptionKey::Lapsed(split_id, participant.clone());
+    if !lapsed {
+        env.storage().persistent().remove(&key);
+        return;
+    }
+    env.storage().persistent().set(&key, &true);
+    extend(env, &key);
 }
 
-// ============================================
-// Original Storage Keys
-// ============================================
+/// Get a split's retainage, if it keeps one
+pub fn get_retainage(env: &Env, split_id: u64) -> Option<Retainage> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Retainage(split_id))
+}
 
-/// Original storage keys for the contract (preserved for compatibility)
-#[contracttype]
-#[derive(Clone)]
-pub enum DataKey {
-    /// The contract administrator
-    Admin,
+/// Store a split's retainage
+pub fn set_retainage(env: &Env, split_id: u64, retainage: &Retainage) {
+    let key = OptionKey::Retainage(split_id);
+    env.storage().persistent().set(&key, retainage);
+    extend(env, &key);
+}
 
-    /// Counter for generating unique split IDs
-    SplitCounter,
+/// Get the cancel quorum in basis points (0 = off)
+pub fn get_cancel_quorum(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::CancelQuorum)
+        .unwrap_or(0)
+}
 
-    /// A split record, indexed by ID
-    Split(u64),
+/// Set the cancel quorum
+pub fn set_cancel_quorum(env: &Env, bps: u32) {
+    let key = ConfigKey::CancelQuorum;
+    env.storage().persistent().set(&key, &bps);
+    extend(env, &key);
+}
 
-    /// The token contract address used for escrow
-    Token,
+/// Get the participants who approved cancelling a split
+pub fn get_cancel_approvals(env: &Env, split_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::CancelApprovals(split_id))
+        .unwrap_or(Vec::new(env))
+}
 
-    /// Whether the contract is initialized
-    Initialized,
+/// Store the participants who approved cancelling a split
+pub fn set_cancel_approvals(env: &Env, split_id: u64, approvals: &Vec<Address>) {
+    let key = OptionKey::CancelApprovals(split_id);
+    env.storage().persistent().set(&key, approvals);
+    extend(env, &key);
 }
 
-// ============================================
-// Enhanced Storage Keys (Issue #59)
-// ============================================
+/// Get when a split was last completed and held for release
+pub fn get_completed_at(env: &Env, split_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::CompletedAt(split_id))
+}
 
-/// Enhanced storage keys matching issue #59 specification
-///
-/// I'm using a separate enum to clearly distinguish the new
-/// escrow storage pattern from the original split storage.
-#[contracttype]
-#[derive(Clone)]
-pub enum StorageKey {
-    /// Escrow record indexed by split_id string
-    /// Maps: split_id -> SplitEscrow
-    Escrow(String),
+/// Record that a split was completed and is held for release
+pub fn set_completed_at(env: &Env, split_id: u64, timestamp: u64) {
+    let key = OptionKey::CompletedAt(split_id);
+    env.storage().persistent().set(&key, &timestamp);
+    extend(env, &key);
+}
 
-    /// Individual participant payment tracking
-    /// Maps: (split_id, participant_address) -> i128 amount
-    ParticipantPayment(String, Address),
+/// Whether the admin froze a split
+pub fn is_frozen(env: &Env, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .get(&OptionKey::Frozen(split_id))
+        .unwrap_or(false)
+}
 
-    /// Total number of escrows created
-    EscrowCount,
+/// Freeze or unfreeze a split
+pub fn set_frozen(env: &Env, split_id: u64, frozen: bool) {
+    let key = OptionKey::Frozen(split_id);
+    if frozen {
+        env.storage().persistent().set(&key, &true);
+        extend(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
+}
 
-    /// Admin address (shared with original)
-    Admin,
+/// Declare or lift an emergency
+pub fn set_emergency(env: &Env, active: bool) {
+    let key = ConfigKey::Emergency;
+    if active {
+        env.storage().persistent().set(&key, &true);
+        extend(env, &key);
+    } else {
+        env.storage().persistent().remove(&key);
+    }
 }
 
-/// Time-to-live for persistent storage (about 1 year)
-const LEDGER_TTL_PERSISTENT: u32 = 31_536_000;
+/// Set the granular pause switches
+/// Get the switches last set through `set_pause_flags`, without the
+/// blanket pause or an emergency folded in
+pub fn get_stored_pause_flags(env: &Env) -> PauseFlags {
+    env.storage()
+        .persistent()
+        .get(&ConfigKey::PauseFlags)
+        .unwrap_or_default()
+}
 
-/// Time-to-live bump threshold (1 day)
-const LEDGER_TTL_THRESHOLD: u32 = 86_400;
+pub fn set_pause_flags(env: &Env, flags: &PauseFlags) {
+    let key = ConfigKey::PauseFlags;
+    env.storage().persistent().set(&key, flags);
+    extend(env, &key);
+}
 
 // ============================================
-// Admin Storage Functions
+// Timelock Storage Functions
 // ============================================
 
-/// Check if the admin has been set
-pub fn has_admin(env: &Env) -> bool {
-    env.storage().persistent().has(&DataKey::Admin)
+/// Get the delay between scheduling and executing an admin action
+pub fn get_timelock_delay(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TimelockDelay)
+        .unwrap_or(DEFAULT_TIMELOCK_DELAY)
 }
 
-/// Get the contract admin address
-pub fn get_admin(env: &Env) -> Address {
+/// Set the delay between scheduling and executing an admin action
+pub fn set_timelock_delay(env: &Env, delay: u64) {
+    let key = DataKey::TimelockDelay;
+    env.storage().persistent().set(&key, &delay);
+    extend(env, &key);
+}
+
+/// Get the next scheduled action ID and increment the counter
+pub fn get_next_action_id(env: &Env) -> u64 {
+    let key = DataKey::ActionCounter;
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().persistent().set(&key, &next);
+    extend(env, &key);
+    next
+}
+
+/// Get a queued admin action
+pub fn get_scheduled_action(env: &Env, action_id: u64) -> Option<ScheduledAction> {
     env.storage()
         .persistent()
-        .get(&DataKey::Admin)
-        .expect("Admin not set")
+        .get(&DataKey::ScheduledAction(action_id))
 }
 
-/// Set the contract admin address
-pub fn set_admin(env: &Env, admin: &Address) {
-    env.storage().persistent().set(&DataKey::Admin, admin);
-    env.storage().persistent().extend_ttl(
-        &DataKey::Admin,
-        LEDGER_TTL_THRESHOLD,
-        LEDGER_TTL_PERSISTENT,
-    );
+/// Queue an admin action
+pub fn set_scheduled_action(env: &Env, scheduled: &ScheduledAction) {
+    let key = DataKey::ScheduledAction(scheduled.id);
+    env.storage().persistent().set(&key, scheduled);
+    extend(env, &key);
+}
+
+/// Drop an admin action from the queue
+pub fn remove_scheduled_action(env: &Env, action_id: u64) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::ScheduledAction(action_id));
 }
 
 // ============================================
-// Token Storage Functions
+// Reminder Storage Functions
 // ============================================
 
-/// Check if the token has been set
-pub fn has_token(env: &Env) -> bool {
-    env.storage().persistent().has(&DataKey::Token)
+/// Get the minimum number of seconds between reminders for a split
+pub fn get_reminder_interval(env: &Env) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReminderInterval)
+        .unwrap_or(DEFAULT_REMINDER_INTERVAL)
 }
 
-/// Get the token contract address
-pub fn get_token(env: &Env) -> Address {
+/// Set the minimum number of seconds between reminders for a split
+pub fn set_reminder_interval(env: &Env, interval: u64) {
+    let key = DataKey::ReminderInterval;
+    env.storage().persistent().set(&key, &interval);
+    extend(env, &key);
+}
+
+/// Get when the last reminder was emitted for a split
+pub fn get_last_reminder(env: &Env, split_id: u64) -> Option<u64> {
     env.storage()
         .persistent()
-        .get(&DataKey::Token)
-        .expect("Token not set")
+        .get(&DataKey::LastReminder(split_id))
 }
 
-/// Set the token contract address
-pub fn set_token(env: &Env, token: &Address) {
-    env.storage().persistent().set(&DataKey::Token, token);
-    env.storage().persistent().extend_ttl(
-        &DataKey::Token,
-        LEDGER_TTL_THRESHOLD,
-        LEDGER_TTL_PERSISTENT,
-    );
+/// Record when a reminder was emitted for a split
+pub fn set_last_reminder(env: &Env, split_id: u64, timestamp: u64) {
+    let key = DataKey::LastReminder(split_id);
+    env.storage().persistent().set(&key, &timestamp);
+    extend(env, &key);
 }
 
 // ============================================
@@ -166,23 +2372,43 @@ pub fn get_next_split_id(env: &Env) -> u64 {
     let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
     let next = current + 1;
     env.storage().persistent().set(&key, &next);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+    extend(env, &key);
     next
 }
 
+/// Make sure future split IDs are issued after `split_id`
+pub fn reserve_split_id(env: &Env, split_id: u64) {
+    let key = DataKey::SplitCounter;
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    if split_id > current {
+        env.storage().persistent().set(&key, &split_id);
+        extend(env, &key);
+    }
+}
+
 // ============================================
 // Original Split Storage Functions
 // ============================================
 
 /// Get a split by ID
+///
+/// Older layouts are upgraded in memory; the entry itself is rewritten the
+/// next time the split is saved (or by an explicit `migrate_split`).
 pub fn get_split(env: &Env, split_id: u64) -> Split {
+    load_split(env, split_id).0
+}
+
+/// Get a split by ID along with whether its stored layout is outdated
+pub fn load_split(env: &Env, split_id: u64) -> (Split, bool) {
     let key = DataKey::Split(split_id);
-    env.storage()
+    let raw: Val = env
+        .storage()
         .persistent()
         .get(&key)
-        .expect("Split not found")
+        .expect("Split not found");
+    // A split that's being read is in use, so keep it alive
+    extend(env, &key);
+    migration::decode(env, &raw)
 }
 
 /// Check if a split exists
@@ -192,12 +2418,123 @@ pub fn has_split(env: &Env, split_id: u64) -> bool {
 }
 
 /// Store a split
+///
+/// Every status change goes through here, so this is also where a split
+/// moves between the per-status indexes and where its nonce is bumped.
 pub fn set_split(env: &Env, split_id: u64, split: &Split) {
     let key = DataKey::Split(split_id);
-    env.storage().persistent().set(&key, split);
+    let mut stored = split.clone();
+    stored.nonce = split.nonce + 1;
+    env.storage()
+        .persistent()
+        .set(&key, &StoredSplit::V2(stored));
+    extend(env, &key);
+
+    // The extension leaves at least the threshold, but we can't read how
+    // much more, so only a lower bound is recorded
+    let floor = env.ledger().sequence() + get_ttl(env).0;
+    if get_live_until(env, split_id).is_none_or(|known| known < floor) {
+        set_live_until(env, split_id, floor);
+    }
+
+    // Open splits written before the creator's open list existed join it
+    // on their next write
+    let open = IndexKey::CreatorOpen(split.creator.clone());
+    if !is_closed(&split.status) && !list_contains(env, &open, split_id) {
+        list_insert(env, &open, split_id);
+    }
+
+    let Some(previous) = file_under_status(env, split_id, &split.status) else {
+        return;
+    };
+    if previous.is_none() && !is_closed(&split.status) {
+        add_open_count(env, &split.creator, 1);
+    }
+    if is_closed(&split.status) && !previous.as_ref().is_some_and(is_closed) {
+        // Participant indexes only track open splits, so they stay as
+        // small as what each address is still involved in
+        for p in split.participants.iter() {
+            remove_from_participant_index(env, &p.address, split_id);
+        }
+        list_remove(env, &open, split_id);
+        if previous.is_some() {
+            add_open_count(env, &split.creator, -1);
+        }
+    }
+}
+
+/// How many of a creator's splits are open
+///
+/// Counted as splits are filed, so splits that haven't been written since
+/// the count was introduced aren't in it.
+pub fn get_open_count(env: &Env, creator: &Address) -> u32 {
     env.storage()
         .persistent()
-        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+        .get(&IndexKey::OpenCount(creator.clone()))
+        .unwrap_or(0)
+}
+
+fn add_open_count(env: &Env, creator: &Address, delta: i32) {
+    let key = IndexKey::OpenCount(creator.clone());
+    let count = get_open_count(env, creator).saturating_add_signed(delta);
+    env.storage().persistent().set(&key, &count);
+    extend(env, &key);
+}
+
+/// Whether a status is final, so the split never changes again
+fn is_closed(status: &SplitStatus) -> bool {
+    matches!(
+        status,
+        SplitStatus::Released | SplitStatus::Cancelled | SplitStatus::Expired
+    )
+}
+
+/// Move a split to the index of its current status, if it isn't there yet
+///
+/// Splits stored before the indexes existed are filed on their next
+/// write. Returns the status it was filed under before (`None` if it
+/// wasn't), or nothing when it was already filed in place.
+fn file_under_status(
+    env: &Env,
+    split_id: u64,
+    status: &SplitStatus,
+) -> Option<Option<SplitStatus>> {
+    let filed_key = IndexKey::Filed(split_id);
+    let filed: Option<SplitStatus> = env.storage().persistent().get(&filed_key);
+    if filed.as_ref() == Some(status) {
+        return None;
+    }
+
+    if let Some(previous) = &filed {
+        list_remove(env, &IndexKey::Status(previous.clone()), split_id);
+    }
+    list_insert(env, &IndexKey::Status(status.clone()), split_id);
+    env.storage().persistent().set(&filed_key, status);
+    extend(env, &filed_key);
+    Some(filed)
+}
+
+/// Seconds in one day bucket of the creation-time indexes
+pub const DAY_SECONDS: u64 = 86_400;
+
+/// File a new split under the day it was created, globally and per creator
+pub fn add_to_day_index(env: &Env, creator: &Address, created_at: u64, split_id: u64) {
+    let day = created_at / DAY_SECONDS;
+    list_push(env, &IndexKey::Day(day), split_id);
+    list_push(env, &IndexKey::CreatorDay(creator.clone(), day), split_id);
+}
+
+/// The list of splits created on a day, for one creator or everyone
+pub fn day_list(creator: &Option<Address>, day: u64) -> IndexKey {
+    match creator {
+        Some(creator) => IndexKey::CreatorDay(creator.clone(), day),
+        None => IndexKey::Day(day),
+    }
+}
+
+/// Get one page of the IDs of the splits currently in a status
+pub fn get_status_page(env: &Env, status: SplitStatus, page: u32) -> Vec<u64> {
+    list_page(env, &IndexKey::Status(status), page)
 }
 
 /// Remove a split (for cleanup if needed)
@@ -207,6 +2544,272 @@ pub fn remove_split(env: &Env, split_id: u64) {
     env.storage().persistent().remove(&key);
 }
 
+// ============================================
+// Index Storage Functions
+// ============================================
+
+/// How many IDs a per-entry list holds
+pub fn list_len(env: &Env, list: &IndexKey) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&ListKey::Len(list.clone()))
+        .unwrap_or(0)
+}
+
+/// Get the ID at a position in a per-entry list
+pub fn list_get(env: &Env, list: &IndexKey, position: u32) -> u64 {
+    let key = ListKey::Item(list.clone(), position);
+    let split_id = env.storage().persistent().get(&key).unwrap();
+    extend(env, &key);
+    split_id
+}
+
+/// Append an ID to a per-entry list
+fn list_push(env: &Env, list: &IndexKey, split_id: u64) {
+    let len = list_len(env, list);
+    let item = ListKey::Item(list.clone(), len);
+    env.storage().persistent().set(&item, &split_id);
+    extend(env, &item);
+    let len_key = ListKey::Len(list.clone());
+    env.storage().persistent().set(&len_key, &(len + 1));
+    extend(env, &len_key);
+}
+
+/// Append an ID to a removable per-entry list
+fn list_insert(env: &Env, list: &IndexKey, split_id: u64) {
+    let position = list_len(env, list);
+    list_push(env, list, split_id);
+    let pos_key = ListKey::Pos(list.clone(), split_id);
+    env.storage().persistent().set(&pos_key, &position);
+    extend(env, &pos_key);
+}
+
+/// Whether a removable per-entry list holds an ID
+pub fn list_contains(env: &Env, list: &IndexKey, split_id: u64) -> bool {
+    env.storage()
+        .persistent()
+        .has(&ListKey::Pos(list.clone(), split_id))
+}
+
+/// Drop an ID from a removable per-entry list, if it's there
+///
+/// The last ID takes the removed one's slot, so lists don't keep their
+/// order but never leave holes.
+fn list_remove(env: &Env, list: &IndexKey, split_id: u64) {
+    let persistent = env.storage().persistent();
+    let pos_key = ListKey::Pos(list.clone(), split_id);
+    let Some(position) = persistent.get::<_, u32>(&pos_key) else {
+        return;
+    };
+    persistent.remove(&pos_key);
+
+    let last = list_len(env, list) - 1;
+    let last_item = ListKey::Item(list.clone(), last);
+    if position != last {
+        let moved: u64 = persistent.get(&last_item).unwrap();
+        let slot = ListKey::Item(list.clone(), position);
+        persistent.set(&slot, &moved);
+        extend(env, &slot);
+        let moved_pos = ListKey::Pos(list.clone(), moved);
+        persistent.set(&moved_pos, &position);
+        extend(env, &moved_pos);
+    }
+    persistent.remove(&last_item);
+    let len_key = ListKey::Len(list.clone());
+    persistent.set(&len_key, &last);
+    extend(env, &len_key);
+}
+
+/// Read one page out of a per-entry list
+pub fn list_page(env: &Env, list: &IndexKey, page: u32) -> Vec<u64> {
+    let mut ids = Vec::new(env);
+    let len = list_len(env, list);
+    let start = page.saturating_mul(PAGE_SIZE);
+    let end = core::cmp::min(start.saturating_add(PAGE_SIZE), len);
+    for position in start..end {
+        ids.push_back(list_get(env, list, position));
+    }
+    ids
+}
+
+/// Get one page of the split IDs a creator has filed under a category
+pub fn get_category_page(
+    env: &Env,
+    creator: &Address,
+    category: SplitCategory,
+    page: u32,
+) -> Vec<u64> {
+    list_page(env, &IndexKey::Category(creator.clone(), category), page)
+}
+
+/// Record a split under its creator's category index
+pub fn add_to_category_index(env: &Env, creator: &Address, category: SplitCategory, split_id: u64) {
+    list_push(
+        env,
+        &IndexKey::Category(creator.clone(), category),
+        split_id,
+    );
+}
+
+/// Get the split a creator already made with an idempotency key, if any
+pub fn get_idempotent_split(env: &Env, creator: &Address, key: &BytesN<32>) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&IndexKey::Idempotency(creator.clone(), key.clone()))
+}
+
+/// Record the split a creator made with an idempotency key
+pub fn set_idempotent_split(env: &Env, creator: &Address, key: &BytesN<32>, split_id: u64) {
+    let key = IndexKey::Idempotency(creator.clone(), key.clone());
+    env.storage().persistent().set(&key, &split_id);
+    extend(env, &key);
+}
+
+/// Get one page of the split IDs a creator has created
+pub fn get_creator_page(env: &Env, creator: &Address, page: u32) -> Vec<u64> {
+    list_page(env, &IndexKey::Creator(creator.clone()), page)
+}
+
+/// Record a split under its creator's index
+pub fn add_to_creator_index(env: &Env, creator: &Address, split_id: u64) {
+    list_push(env, &IndexKey::Creator(creator.clone()), split_id);
+}
+
+/// Get one page of the split IDs a creator has tagged with a tag
+pub fn get_tag_page(env: &Env, creator: &Address, tag: &Symbol, page: u32) -> Vec<u64> {
+    list_page(env, &IndexKey::Tag(creator.clone(), tag.clone()), page)
+}
+
+/// Record a split under its creator's tag index
+pub fn add_to_tag_index(env: &Env, creator: &Address, tag: &Symbol, split_id: u64) {
+    list_push(env, &IndexKey::Tag(creator.clone(), tag.clone()), split_id);
+}
+
+/// Get one page of the IDs of the open splits an address participates in
+///
+/// Splits leave the index when they're released, cancelled or expire.
+pub fn get_participant_page(env: &Env, participant: &Address, page: u32) -> Vec<u64> {
+    list_page(env, &IndexKey::Participant(participant.clone()), page)
+}
+
+/// Record a split under a participant's index
+pub fn add_to_participant_index(env: &Env, participant: &Address, split_id: u64) {
+    let list = IndexKey::Participant(participant.clone());
+    if !list_contains(env, &list, split_id) {
+        list_insert(env, &list, split_id);
+    }
+}
+
+/// The list of a creator's open splits
+pub fn creator_open_list(creator: &Address) -> IndexKey {
+    IndexKey::CreatorOpen(creator.clone())
+}
+
+/// The per-entry list of the open splits an address participates in
+pub fn participant_list(participant: &Address) -> IndexKey {
+    IndexKey::Participant(participant.clone())
+}
+
+/// Drop a split from a participant's index
+fn remove_from_participant_index(env: &Env, participant: &Address, split_id: u64) {
+    list_remove(env, &IndexKey::Participant(participant.clone()), split_id);
+}
+
+// ============================================
+// Group Storage Functions
+// ============================================
+
+/// Get the next group ID and increment the counter
+pub fn get_next_group_id(env: &Env) -> u64 {
+    let key = DataKey::GroupCounter;
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().persistent().set(&key, &next);
+    extend(env, &key);
+    next
+}
+
+/// Get a group by ID
+pub fn get_group(env: &Env, group_id: u64) -> Option<Group> {
+    env.storage().persistent().get(&DataKey::Group(group_id))
+}
+
+/// Store a group
+pub fn set_group(env: &Env, group: &Group) {
+    let key = DataKey::Group(group.id);
+    env.storage().persistent().set(&key, group);
+    extend(env, &key);
+}
+
+/// Get the group a split has been attached to, if any
+pub fn get_split_group(env: &Env, split_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SplitGroup(split_id))
+}
+
+/// Record which group a split belongs to
+pub fn set_split_group(env: &Env, split_id: u64, group_id: u64) {
+    let key = DataKey::SplitGroup(split_id);
+    env.storage().persistent().set(&key, &group_id);
+    extend(env, &key);
+}
+
+/// Get the latest settlement plan computed for a group
+pub fn get_group_settlement(env: &Env, group_id: u64) -> Vec<Settlement> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GroupSettlement(group_id))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Store the settlement plan for a group
+pub fn set_group_settlement(env: &Env, group_id: u64, plan: &Vec<Settlement>) {
+    let key = DataKey::GroupSettlement(group_id);
+    env.storage().persistent().set(&key, plan);
+    extend(env, &key);
+}
+
+// ============================================
+// Event Storage Functions
+// ============================================
+
+/// Get the next event ID and increment the counter
+pub fn get_next_event_id(env: &Env) -> u64 {
+    let key = EventKey::Counter;
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().persistent().set(&key, &next);
+    extend(env, &key);
+    next
+}
+
+/// Get an event by ID
+pub fn get_event(env: &Env, event_id: u64) -> Option<SplitEvent> {
+    env.storage().persistent().get(&EventKey::Event(event_id))
+}
+
+/// Store an event
+pub fn set_event(env: &Env, event: &SplitEvent) {
+    let key = EventKey::Event(event.id);
+    env.storage().persistent().set(&key, event);
+    extend(env, &key);
+}
+
+/// Get the event a split has been attached to, if any
+pub fn get_split_event(env: &Env, split_id: u64) -> Option<u64> {
+    env.storage()
+        .persistent()
+        .get(&EventKey::SplitEvent(split_id))
+}
+
+/// Record which event a split belongs to
+pub fn set_split_event(env: &Env, split_id: u64, event_id: u64) {
+    let key = EventKey::SplitEvent(split_id);
+    env.storage().persistent().set(&key, &event_id);
+    extend(env, &key);
+}
+
 // ============================================
 // Enhanced Escrow Storage Functions (Issue #59)
 // ============================================
@@ -229,11 +2832,7 @@ pub fn increment_escrow_count(env: &Env) -> u64 {
     env.storage()
         .persistent()
         .set(&StorageKey::EscrowCount, &next);
-    env.storage().persistent().extend_ttl(
-        &StorageKey::EscrowCount,
-        LEDGER_TTL_THRESHOLD,
-        LEDGER_TTL_PERSISTENT,
-    );
+    extend(env, &StorageKey::EscrowCount);
     next
 }
 
@@ -256,9 +2855,7 @@ pub fn has_escrow(env: &Env, split_id: &String) -> bool {
 pub fn set_escrow(env: &Env, split_id: &String, escrow: &SplitEscrow) {
     let key = StorageKey::Escrow(split_id.clone());
     env.storage().persistent().set(&key, escrow);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+    extend(env, &key);
 }
 
 /// Remove an escrow
@@ -285,9 +2882,7 @@ pub fn get_participant_payment(env: &Env, split_id: &String, participant: &Addre
 pub fn set_participant_payment(env: &Env, split_id: &String, participant: &Address, amount: i128) {
     let key = StorageKey::ParticipantPayment(split_id.clone(), participant.clone());
     env.storage().persistent().set(&key, &amount);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+    extend(env, &key);
 }
 
 /// Add to the payment amount for a participant
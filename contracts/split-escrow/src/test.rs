@@ -9,8 +9,8 @@ extern crate std;
 
 use super::*;
 use soroban_sdk::{
-    symbol_short, testutils::Address as _, testutils::Events as _, token, Address, Env, String,
-    Symbol, TryIntoVal, Vec,
+    symbol_short, testutils::Address as _, testutils::Events as _, testutils::Ledger as _, token,
+    Address, Bytes, BytesN, Env, String, Symbol, TryIntoVal, Val, Vec,
 };
 use std::panic::{catch_unwind, AssertUnwindSafe};
 
@@ -27,7 +27,9 @@ fn setup_test() -> (
     env.mock_all_auths();
 
     let token_admin = Address::generate(&env);
-    let token_id = env.register_stellar_asset_contract(token_admin.clone());
+    let token_id = env
+        .register_stellar_asset_contract_v2(token_admin.clone())
+        .address();
     let token_client = token::Client::new(&env, &token_id);
     let token_admin_client = token::StellarAssetClient::new(&env, &token_id);
 
@@ -75,364 +77,7747 @@ fn test_double_initialize_fails() {
     initialize_contract(&client, &admin, &token_id);
 }
 
-// ============================================
-// Split Creation Tests
-// ============================================
-
 #[test]
-fn test_create_split() {
+fn test_get_info() {
     let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
-    let creator = Address::generate(&env);
-    let participant1 = Address::generate(&env);
-    let participant2 = Address::generate(&env);
+    let info = client.get_info();
+    assert_eq!(info.version, String::from_str(&env, CONTRACT_VERSION));
+    assert_eq!(info.admin, admin);
+    assert_eq!(info.token, token_id);
+    assert!(!info.paused);
+    assert_eq!(info.fee_module, None);
+    assert_eq!(info.fee_recipient, None);
+    assert_eq!(info.timelock_delay, client.get_timelock_delay());
 
-    let description = String::from_str(&env, "Dinner at Joe's");
-    let total_amount: i128 = 100_0000000; // 100 with 7 decimals
+    client.set_paused(&admin, &true);
+    assert!(client.get_info().paused);
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant1.clone());
-    addresses.push_back(participant2.clone());
+    assert_eq!(info.rounding_policy, RoundingPolicy::TruncateToPayee);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetRoundingPolicy(RoundingPolicy::RoundToFeePool),
+    );
+    assert_eq!(
+        client.get_info().rounding_policy,
+        RoundingPolicy::RoundToFeePool
+    );
+}
+
+#[test]
+fn test_paused_contract_rejects_new_splits() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    client.set_paused(&admin, &true);
 
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant);
     let mut shares = Vec::new(&env);
-    shares.push_back(50_0000000i128);
-    shares.push_back(50_0000000i128);
+    shares.push_back(100i128);
+
+    assert_eq!(
+        client.try_create_split(
+            &creator,
+            &String::from_str(&env, "Paused"),
+            &100,
+            &addresses,
+            &shares,
+        ),
+        Err(Ok(Error::ContractPaused))
+    );
+}
 
-    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares);
+#[test]
+fn test_ttl_parameters_are_bounded_and_applied() {
+    use soroban_sdk::testutils::storage::Persistent as _;
 
-    assert_eq!(split_id, 1);
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let config = client.get_config();
+    assert_eq!(config.ttl_threshold, LEDGER_TTL_THRESHOLD);
+    assert_eq!(config.ttl_extend_to, LEDGER_TTL_PERSISTENT);
+
+    for (threshold, extend_to) in [
+        (MIN_TTL_THRESHOLD - 1, 100_000),
+        (200_000, 200_000),
+        (MIN_TTL_THRESHOLD, MAX_TTL_EXTEND_TO + 1),
+    ] {
+        assert_eq!(
+            client.try_set_ttl(&threshold, &extend_to),
+            Err(Ok(Error::InvalidConfig))
+        );
+    }
+    client.set_ttl(&MIN_TTL_THRESHOLD, &1_000_000);
+    assert_eq!(client.get_config().ttl_extend_to, 1_000_000);
 
-    let split = client.get_split(&split_id);
-    assert_eq!(split.id, 1);
-    assert_eq!(split.creator, creator);
-    assert_eq!(split.total_amount, total_amount);
-    assert_eq!(split.status, SplitStatus::Pending);
-    assert_eq!(split.participants.len(), 2);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Split(split_id))
+    });
+    assert_eq!(ttl, 1_000_000);
 }
 
 #[test]
-#[should_panic(expected = "Participant shares must sum to total amount")]
-fn test_create_split_invalid_shares() {
+fn test_reads_keep_decaying_entries_alive() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
     let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
+    client.set_ttl(&MIN_TTL_THRESHOLD, &100_000);
+    env.deployer()
+        .extend_ttl(client.address.clone(), 500_000, 500_000);
 
     let creator = Address::generate(&env);
-    let participant = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let ttls = || {
+        env.as_contract(&client.address, || {
+            let persistent = env.storage().persistent();
+            (
+                persistent.get_ttl(&DataKey::Split(split_id)),
+                persistent.get_ttl(&ListKey::Item(IndexKey::Creator(creator.clone()), 0)),
+            )
+        })
+    };
+    assert_eq!(ttls(), (100_000, 100_000));
+
+    // Above the threshold nothing is extended
+    env.ledger().with_mut(|li| li.sequence_number += 50_000);
+    client.get_split(&split_id);
+    assert_eq!(ttls(), (50_000, 50_000));
+
+    // Below it, a plain read brings both entries back to full length
+    env.ledger().with_mut(|li| li.sequence_number += 40_000);
+    assert_eq!(ttls(), (10_000, 10_000));
+    client.get_split(&split_id);
+    client.get_splits_by_creator(&creator, &0);
+    assert_eq!(ttls(), (100_000, 100_000));
+}
 
-    let description = String::from_str(&env, "Bad split");
-    let total_amount: i128 = 100_0000000;
+#[test]
+fn test_rent_budget_pays_keepers_and_returns_the_rest() {
+    use soroban_sdk::testutils::storage::Persistent as _;
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant);
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    client.set_ttl(&MIN_TTL_THRESHOLD, &100_000);
+    env.deployer()
+        .extend_ttl(client.address.clone(), 500_000, 500_000);
 
-    // Share doesn't match total
-    let mut shares = Vec::new(&env);
-    shares.push_back(50_0000000i128);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    token_admin_client.mint(&creator, &30);
+    let options = SplitOptions {
+        rent_budget: 30,
+        ..Default::default()
+    };
+
+    // Without a rent config there's nothing to pay keepers in
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Options split"),
+            &100,
+            &Vec::from_array(&env, [alice.clone()]),
+            &Vec::from_array(&env, [100i128]),
+            &options,
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetRentConfig(RentConfig {
+            token: token_id.clone(),
+            keeper_fee: 10,
+        }),
+    );
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    assert_eq!(token_client.balance(&creator), 0);
+    assert_eq!(client.get_rent_budget(&split_id).unwrap().remaining, 30);
+
+    // Switching the rent token later doesn't change what this budget pays in
+    let other_token = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetRentConfig(RentConfig {
+            token: other_token,
+            keeper_fee: 10,
+        }),
+    );
 
-    client.create_split(&creator, &description, &total_amount, &addresses, &shares);
+    // Not due until the entries could have decayed to the threshold
+    assert_eq!(
+        client.try_bump_split(&split_id, &keeper),
+        Err(Ok(Error::ActionNotReady))
+    );
+    env.ledger()
+        .with_mut(|li| li.sequence_number += 100_000 - MIN_TTL_THRESHOLD);
+    assert_eq!(client.bump_split(&split_id, &keeper), 10);
+    assert_eq!(token_client.balance(&keeper), 10);
+    let ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Split(split_id))
+    });
+    assert_eq!(ttl, 100_000);
+
+    // Cancelling leaves the rest for the creator to reclaim
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(client.reclaim_rent(&split_id), 20);
+    assert_eq!(token_client.balance(&creator), 20);
+    assert_eq!(client.get_rent_budget(&split_id), None);
+    assert_eq!(
+        client.try_reclaim_rent(&split_id),
+        Err(Ok(Error::NoFundsAvailable))
+    );
 }
 
 #[test]
-#[should_panic(expected = "At least one participant is required")]
-fn test_create_split_no_participants() {
-    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+fn test_split_health_warns_before_storage_expires() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
+    client.set_ttl(&MIN_TTL_THRESHOLD, &100_000);
+    env.deployer()
+        .extend_ttl(client.address.clone(), 500_000, 500_000);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetRentConfig(RentConfig {
+            token: token_id.clone(),
+            keeper_fee: 10,
+        }),
+    );
 
     let creator = Address::generate(&env);
-    let description = String::from_str(&env, "Empty split");
-
-    let addresses: Vec<Address> = Vec::new(&env);
-    let shares: Vec<i128> = Vec::new(&env);
+    let alice = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    token_admin_client.mint(&creator, &30);
+    let options = SplitOptions {
+        rent_budget: 30,
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+
+    let start = env.ledger().sequence();
+    let health = client.get_split_health(&split_id);
+    assert_eq!(health.live_until, start + 100_000);
+    assert!(!health.bump_recommended);
+    assert!(!health.can_bump);
+
+    // Inside the threshold the split should be bumped, and a keeper can
+    env.ledger()
+        .with_mut(|li| li.sequence_number += 100_000 - MIN_TTL_THRESHOLD + 1);
+    let health = client.get_split_health(&split_id);
+    assert_eq!(health.ledgers_left, MIN_TTL_THRESHOLD - 1);
+    assert!(health.bump_recommended);
+    assert!(health.can_bump);
+
+    // The recorded bound never runs past the real TTL
+    let ttl = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&DataKey::Split(split_id))
+    });
+    assert!(ttl >= health.ledgers_left);
 
-    client.create_split(&creator, &description, &0, &addresses, &shares);
+    client.bump_split(&split_id, &keeper);
+    let health = client.get_split_health(&split_id);
+    assert_eq!(health.ledgers_left, 100_000);
+    assert!(!health.bump_recommended);
+    assert!(!health.can_bump);
 }
 
-// ============================================
-// Deposit Tests
-// ============================================
-
 #[test]
-fn test_deposit() {
+fn test_frozen_split_holds_deposits_and_releases() {
     let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
-    let participant = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let other = create_split_with_shares(&env, &client, &creator, &[(&alice, 1)]);
+    client.deposit(&split_id, &alice, &60);
+
+    client.freeze_split(&admin, &split_id);
+    assert!(client.is_frozen(&split_id));
+    assert!(
+        client
+            .get_summaries(&Vec::from_array(&env, [split_id]))
+            .get(0)
+            .unwrap()
+            .frozen
+    );
+    assert_eq!(
+        client.try_deposit(&split_id, &alice, &40),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_release_partial(&split_id),
+        Err(Ok(Error::ContractPaused))
+    );
+    // Other splits carry on
+    client.deposit(&other, &alice, &1);
 
-    let description = String::from_str(&env, "Test split");
-    let total_amount: i128 = 100_0000000;
+    client.unfreeze_split(&admin, &split_id);
+    client.deposit(&split_id, &alice, &39);
+    assert_eq!(client.get_split(&split_id).amount_collected, 99);
+    assert_eq!(token_client.balance(&creator), 1);
+}
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
+#[test]
+fn test_pause_flags_hold_releases_and_refunds() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
 
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+    let funded = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let refundable = create_split_with_shares(&env, &client, &creator, &[(&bob, 50)]);
+
+    client.set_pause_flags(
+        &admin,
+        &PauseFlags {
+            create: false,
+            deposit: false,
+            release: true,
+            refund: true,
+        },
+    );
 
-    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares);
+    // A fully funded split waits at Completed instead of paying out
+    client.deposit(&funded, &alice, &100);
+    assert_eq!(client.get_split(&funded).status, SplitStatus::Completed);
+    assert_eq!(token_client.balance(&creator), 0);
+    assert_eq!(
+        client.try_release_funds(&funded),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_cancel_split(&refundable, &String::from_str(&env, "plans changed"), &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_reduce_total(&refundable, &40, &None),
+        Err(Ok(Error::ContractPaused))
+    );
 
-    token_admin_client.mint(&participant, &100_0000000i128);
+    // The blanket pause still covers creation and deposits
+    client.set_paused(&admin, &true);
+    let flags = client.get_pause_flags();
+    assert!(flags.create && flags.deposit && flags.release && flags.refund);
+    client.set_paused(&admin, &false);
 
-    // Make a deposit
-    client.deposit(&split_id, &participant, &50_0000000);
+    client.set_pause_flags(&admin, &PauseFlags::default());
+    client.release_funds(&funded);
+    assert_eq!(token_client.balance(&creator), 100);
+    client.cancel_split(&refundable, &String::from_str(&env, "plans changed"), &None);
+}
 
-    let split = client.get_split(&split_id);
-    assert_eq!(split.status, SplitStatus::Active);
-    assert_eq!(split.amount_collected, 50_0000000);
+#[test]
+fn test_emergency_withdraw_returns_deposits() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
 
-    // Complete the deposit
-    client.deposit(&split_id, &participant, &50_0000000);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &60);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+    client.deposit(&split_id, &alice, &60);
+
+    assert_eq!(
+        client.try_emergency_withdraw(&split_id, &alice),
+        Err(Ok(Error::ActionNotReady))
+    );
 
-    let split = client.get_split(&split_id);
-    assert_eq!(split.status, SplitStatus::Released);
-    assert_eq!(split.amount_collected, 100_0000000);
-    assert_eq!(split.amount_released, 100_0000000);
+    client.set_emergency(&true);
+    assert!(client.is_emergency());
+    assert_eq!(
+        client.try_claim_refund(&split_id, &alice),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_reduce_total(&split_id, &50, &None),
+        Err(Ok(Error::ContractPaused))
+    );
 
-    let creator_balance = token_client.balance(&creator);
-    assert_eq!(creator_balance, 100_0000000);
+    assert_eq!(client.emergency_withdraw(&split_id, &alice), 60);
+    assert_eq!(token_client.balance(&alice), 60);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Cancelled);
+    assert_eq!(
+        client.try_emergency_withdraw(&split_id, &bob),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+    client.check_invariants(&split_id);
 }
 
 #[test]
-fn test_deposit_exceeds_share() {
-    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+fn test_emergency_blocks_lifecycle_changes() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
-    let participant = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+    client.set_emergency(&true);
+
+    assert_eq!(
+        client.try_set_units(&split_id, &alice, &1, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_lock_units(&split_id),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_join_split(&split_id, &bob),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_lock_split(&split_id),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_schedule_release(&split_id, &1_000),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_forgive_remaining(&split_id, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_write_off(&split_id, &alice, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_propose_increase(
+            &split_id,
+            &200,
+            &Vec::from_array(&env, [120i128, 80]),
+            &None
+        ),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_accept_increase(&split_id, &alice),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_extend_deadline(&split_id, &Some(1_000), &None, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_lapse_invitation(&split_id, &alice),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_reassign_slot(&split_id, &alice, &bob),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_rebalance_slot(&split_id, &alice),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_transfer_share(&split_id, &alice, &bob),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_expire_split(&split_id),
+        Err(Ok(Error::ContractPaused))
+    );
 
-    let description = String::from_str(&env, "Test split");
+    // Nothing about the split moved
+    client.set_emergency(&false);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Pending);
+    client.check_invariants(&split_id);
+}
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
+#[test]
+fn test_frozen_split_refuses_changes() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
 
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+    client.freeze_split(&admin, &split_id);
+
+    let reason = String::from_str(&env, "plans changed");
+    assert_eq!(
+        client.try_cancel_split(&split_id, &reason, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    let results = client.batch_cancel(&creator, &Vec::from_array(&env, [split_id]), &reason);
+    assert!(!results.get(0).unwrap().success);
+    assert_eq!(results.get(0).unwrap().error, Error::ContractPaused as u32);
+    assert_eq!(
+        client.try_forgive_remaining(&split_id, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_write_off(&split_id, &alice, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_reduce_total(&split_id, &50, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_extend_deadline(&split_id, &Some(1_000), &None, &None),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_transfer_share(&split_id, &alice, &bob),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_lapse_invitation(&split_id, &alice),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_reassign_slot(&split_id, &alice, &bob),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(
+        client.try_rebalance_slot(&split_id, &alice),
+        Err(Ok(Error::ContractPaused))
+    );
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+    client.unfreeze_split(&admin, &split_id);
+    client.cancel_split(&split_id, &reason, &None);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Cancelled);
+}
 
-    token_admin_client.mint(&participant, &200_0000000i128);
+#[test]
+fn test_circuit_breaker_trips_and_resets() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetBreaker(BreakerConfig {
+            window: BreakerWindow::Day,
+            max_creations: 3,
+            max_volume: 0,
+            large_release: 100,
+        }),
+    );
 
-    // Try to overpay
-    let result = catch_unwind(AssertUnwindSafe(|| {
-        client.deposit(&split_id, &participant, &150_0000000)
-    }));
-    assert!(result.is_err());
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &150);
+    let small = create_split_with_shares(&env, &client, &creator, &[(&alice, 50)]);
+    let large = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    assert!(!client.get_breaker_state().tripped);
+    create_split_with_shares(&env, &client, &creator, &[(&alice, 10)]);
+    assert!(client.get_breaker_state().tripped);
+
+    // New splits and large releases wait; small releases still go out
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(alice.clone());
+    let mut shares = Vec::new(&env);
+    shares.push_back(10i128);
+    assert_eq!(
+        client.try_create_split(
+            &creator,
+            &String::from_str(&env, "Blocked"),
+            &10,
+            &addresses,
+            &shares,
+        ),
+        Err(Ok(Error::ContractPaused))
+    );
+    client.deposit(&small, &alice, &50);
+    assert_eq!(token_client.balance(&creator), 50);
+    client.deposit(&large, &alice, &100);
+    assert_eq!(client.get_split(&large).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_release_funds(&large),
+        Err(Ok(Error::ContractPaused))
+    );
+
+    client.reset_breaker();
+    assert_eq!(client.get_breaker_state(), BreakerState::default());
+    client.release_funds(&large);
+    assert_eq!(token_client.balance(&creator), 150);
 }
 
 // ============================================
-// Cancel Tests
+// Split Creation Tests
 // ============================================
 
 #[test]
-fn test_cancel_split() {
+fn test_create_split() {
     let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
-    let participant = Address::generate(&env);
+    let participant1 = Address::generate(&env);
+    let participant2 = Address::generate(&env);
 
-    let description = String::from_str(&env, "Test split");
+    let description = String::from_str(&env, "Dinner at Joe's");
+    let total_amount: i128 = 100_0000000; // 100 with 7 decimals
 
     let mut addresses = Vec::new(&env);
-    addresses.push_back(participant);
+    addresses.push_back(participant1.clone());
+    addresses.push_back(participant2.clone());
 
     let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+    shares.push_back(50_0000000i128);
+    shares.push_back(50_0000000i128);
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares);
 
-    client.cancel_split(&split_id);
+    assert_eq!(split_id, 1);
 
     let split = client.get_split(&split_id);
-    assert_eq!(split.status, SplitStatus::Cancelled);
+    assert_eq!(split.id, 1);
+    assert_eq!(split.creator, creator);
+    assert_eq!(split.total_amount, total_amount);
+    assert_eq!(split.status, SplitStatus::Pending);
+    assert_eq!(split.participants.len(), 2);
 }
 
-// ============================================
-// Release Tests
-// ============================================
-
 #[test]
-fn test_release_funds() {
-    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+fn test_split_records_display_metadata() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
     let participant = Address::generate(&env);
 
-    let description = String::from_str(&env, "Test split");
-
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
-
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+    let plain = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(
+        client.get_split_display(&plain),
+        SplitDisplay {
+            decimals: 7,
+            symbol: None,
+        }
+    );
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+    let options = SplitOptions {
+        display_symbol: Some(String::from_str(&env, "USDC")),
+        ..Default::default()
+    };
+    let ticked = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    assert_eq!(
+        client.get_split_display(&ticked).symbol,
+        Some(String::from_str(&env, "USDC"))
+    );
 
-    // Complete the split (auto-release should occur)
-    token_admin_client.mint(&participant, &100_0000000i128);
-    client.deposit(&split_id, &participant, &100_0000000);
+    let bad = SplitOptions {
+        display_symbol: Some(String::from_str(&env, "")),
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "No ticker"),
+            &10,
+            &Vec::from_array(&env, [participant.clone()]),
+            &Vec::from_array(&env, [10i128]),
+            &bad,
+        ),
+        Err(Ok(Error::InvalidSymbol))
+    );
+    assert_eq!(
+        client.try_get_split_display(&99),
+        Err(Ok(Error::SplitNotFound))
+    );
+}
 
-    let split = client.get_split(&split_id);
-    assert_eq!(split.status, SplitStatus::Released);
+#[test]
+fn test_create_split_retries_with_idempotency_key() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
 
-    let creator_balance = token_client.balance(&creator);
-    assert_eq!(creator_balance, 100_0000000);
+    let creator = Address::generate(&env);
+    let other = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let keyed = |key: u8| SplitOptions {
+        idempotency_key: MaybeHash::Some(BytesN::from_array(&env, &[key; 32])),
+        ..Default::default()
+    };
 
-    // Manual release should be blocked after auto-release
-    let result = catch_unwind(AssertUnwindSafe(|| client.release_funds(&split_id)));
-    assert!(result.is_err());
+    // A retry after a timeout gets the split the first attempt made
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 10, &keyed(1));
+    let retried =
+        create_split_with_options_for(&env, &client, &creator, &participant, 10, &keyed(1));
+    assert_eq!(retried, split_id);
+    assert_eq!(client.get_splits_by_creator(&creator, &0).len(), 1);
+
+    // Keys are per creator, and a new key is a new split
+    let theirs = create_split_with_options_for(&env, &client, &other, &participant, 10, &keyed(1));
+    let fresh = create_split_with_options_for(&env, &client, &creator, &participant, 10, &keyed(2));
+    assert_ne!(theirs, split_id);
+    assert_ne!(fresh, split_id);
 }
 
 #[test]
-fn test_release_incomplete_split() {
+fn test_create_split_invalid_shares() {
     let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
     let participant = Address::generate(&env);
 
-    let description = String::from_str(&env, "Test split");
+    let description = String::from_str(&env, "Bad split");
+    let total_amount: i128 = 100_0000000;
 
     let mut addresses = Vec::new(&env);
     addresses.push_back(participant);
 
+    // Share doesn't match total
+    let mut shares = Vec::new(&env);
+    shares.push_back(50_0000000i128);
+
+    assert_eq!(
+        client.try_create_split(&creator, &description, &total_amount, &addresses, &shares),
+        Err(Ok(Error::SharesMismatch))
+    );
+}
+
+#[test]
+fn test_create_split_no_participants() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let description = String::from_str(&env, "Empty split");
+
+    let addresses: Vec<Address> = Vec::new(&env);
+    let shares: Vec<i128> = Vec::new(&env);
+
+    assert_eq!(
+        client.try_create_split(&creator, &description, &0, &addresses, &shares),
+        Err(Ok(Error::InvalidParticipants))
+    );
+}
+
+#[test]
+fn test_create_split_rejects_duplicate_participants() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let description = String::from_str(&env, "Dinner");
+
+    let addresses = Vec::from_array(&env, [alice.clone(), bob.clone(), alice.clone()]);
+    let shares = Vec::from_array(&env, [10i128, 10, 10]);
+    assert_eq!(
+        client.try_create_split(&creator, &description, &30, &addresses, &shares),
+        Err(Ok(Error::InvalidParticipants))
+    );
+    assert_eq!(
+        client.try_create_equal_split(&creator, &description, &30, &addresses),
+        Err(Ok(Error::InvalidParticipants))
+    );
+}
+
+#[test]
+fn test_amounts_must_be_positive() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let description = String::from_str(&env, "Boundaries");
+    let both = Vec::from_array(&env, [alice.clone(), bob.clone()]);
+    let create = |total: i128, shares: [i128; 2]| {
+        client.try_create_split(
+            &creator,
+            &description,
+            &total,
+            &both,
+            &Vec::from_array(&env, shares),
+        )
+    };
+
+    // A negative share can't be hidden behind a matching total
+    assert_eq!(create(100, [150, -50]), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(create(100, [100, 0]), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(create(0, [0, 0]), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(create(-2, [-1, -1]), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(create(1, [i128::MAX, 1]), Err(Ok(Error::InvalidAmount)));
+    assert_eq!(
+        client.try_create_equal_split(&creator, &description, &1, &both),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // One stroop each is the smallest valid split
+    let split_id = create(2, [1, 1]).unwrap().unwrap();
+    token_admin_client.mint(&alice, &1);
+    for amount in [0, -1, i128::MIN] {
+        assert_eq!(
+            client.try_deposit(&split_id, &alice, &amount),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_deposit_with_token(&split_id, &alice, &Address::generate(&env), &amount),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+    client.deposit(&split_id, &alice, &1);
+
+    // Lowering the total can't scale a share down to nothing
+    assert_eq!(
+        client.try_reduce_total(&split_id, &1, &None),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_create_equal_split_assigns_remainder_to_first_listed() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let mut addresses = Vec::new(&env);
+    for _ in 0..3 {
+        addresses.push_back(Address::generate(&env));
+    }
+
+    let split_id =
+        client.create_equal_split(&creator, &String::from_str(&env, "Pizza"), &100, &addresses);
+
+    let shares: std::vec::Vec<i128> = client
+        .get_split(&split_id)
+        .participants
+        .iter()
+        .map(|p| p.share_amount)
+        .collect();
+    assert_eq!(shares, [34, 33, 33]);
+}
+
+#[test]
+fn test_equal_shares_always_sum_to_total() {
+    for count in 1..=12usize {
+        for total in (0..200i128).chain([1_000_000_007, i64::MAX as i128]) {
+            let mut out = [0i128; 12];
+            let shares = &mut out[..count];
+            shares::equal_shares(total, shares);
+            assert_eq!(shares.iter().sum::<i128>(), total);
+
+            let max = *shares.iter().max().unwrap();
+            let min = *shares.iter().min().unwrap();
+            assert!(max - min <= 1);
+            // Larger shares always come first
+            assert_eq!(shares[0], max);
+        }
+    }
+}
+
+#[test]
+fn test_weighted_share_math_matches_totals() {
+    let mut out = [0i128; 3];
+
+    // 100 by 1:1:1 leaves one stroop, which goes to the first listed
+    shares::weighted_shares(100, &[1, 1, 1], &mut out).unwrap();
+    assert_eq!(out, [34, 33, 33]);
+
+    // Zero weights never pick up remainder stroops
+    shares::weighted_shares(100, &[0, 1, 2], &mut out).unwrap();
+    assert_eq!(out, [0, 34, 66]);
+
+    // 1_000 over 3 + 2 + 2 nights: 142 a night, 6 stroops spread by units
+    assert_eq!(shares::unit_shares(1_000, &[3, 2, 2], &mut out), Ok(142));
+    assert_eq!(out, [429, 286, 285]);
+    assert_eq!(
+        shares::unit_shares(1_000, &[0, 0, 0], &mut out),
+        Err(Error::InvalidAmount)
+    );
+
+    shares::bps_shares(1_001, &[5_000, 2_500, 2_500], &mut out).unwrap();
+    assert_eq!(out, [501, 250, 250]);
+    assert_eq!(
+        shares::bps_shares(1_000, &[5_000, 2_500, 2_499], &mut out),
+        Err(Error::SharesMismatch)
+    );
+    assert_eq!(
+        shares::weighted_shares(1_000, &[1, 1], &mut out),
+        Err(Error::SharesMismatch)
+    );
+
+    // 8% tax and 15% tip on a 2_000 bill ordered 1_200 / 500 / 300
+    let total = shares::item_shares(&[1_200, 500, 300], 800, 1_500, &mut out).unwrap();
+    assert_eq!(total, 2_460);
+    assert_eq!(out, [1_476, 615, 369]);
+    assert_eq!(out.iter().sum::<i128>(), total);
+}
+
+#[test]
+fn test_apportion_never_creates_or_loses_funds() {
+    let policies = [
+        RoundingPolicy::TruncateToPayee,
+        RoundingPolicy::RoundToFeePool,
+        RoundingPolicy::AssignToCreator,
+    ];
+
+    for policy in policies {
+        for amount in (0..300i128).chain([1_000_000_007]) {
+            for (numerator, denominator) in [(1, 3), (2, 3), (25, 10_000), (9_999, 10_000)] {
+                let cut = shares::apportion(amount, numerator, denominator, policy);
+                assert_eq!(cut.part + cut.rest + cut.dust, amount);
+                assert!(cut.part >= 0 && cut.rest >= 0 && (0..=1).contains(&cut.dust));
+            }
+        }
+    }
+
+    // 1% of 150 is 1.5 stroops: who gets the half depends on the policy
+    let fee = |policy| shares::apportion(150, 1, 100, policy);
+    assert_eq!(fee(RoundingPolicy::TruncateToPayee).part, 1);
+    assert_eq!(fee(RoundingPolicy::TruncateToPayee).rest, 149);
+    assert_eq!(fee(RoundingPolicy::RoundToFeePool).part, 2);
+    assert_eq!(fee(RoundingPolicy::RoundToFeePool).rest, 148);
+    assert_eq!(fee(RoundingPolicy::AssignToCreator).rest, 148);
+    assert_eq!(fee(RoundingPolicy::AssignToCreator).dust, 1);
+}
+
+#[test]
+fn test_rounding_policy_applies_to_release_fee_and_retainage() {
+    // A 1% fee and 10% retainage on 150: (creator now, treasury, retained)
+    let cases = [
+        (RoundingPolicy::TruncateToPayee, 135, 1, 14),
+        (RoundingPolicy::RoundToFeePool, 133, 2, 15),
+        (RoundingPolicy::AssignToCreator, 135, 1, 14),
+    ];
+
+    for (policy, paid, fee, retained) in cases {
+        let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+        initialize_contract(&client, &admin, &token_id);
+        let treasury = Address::generate(&env);
+        run_admin_action(
+            &env,
+            &client,
+            &AdminAction::SetFeeSchedule(FeeSchedule {
+                recipient: treasury.clone(),
+                tiers: Vec::from_array(
+                    &env,
+                    [FeeTier {
+                        min_total: 0,
+                        bps: 100,
+                    }],
+                ),
+            }),
+        );
+        run_admin_action(&env, &client, &AdminAction::SetRoundingPolicy(policy));
+
+        let creator = Address::generate(&env);
+        let participant = Address::generate(&env);
+        token_admin_client.mint(&participant, &150);
+        let options = SplitOptions {
+            retainage_bps: 1_000,
+            retainage_window: 100,
+            ..Default::default()
+        };
+        let split_id =
+            create_split_with_options_for(&env, &client, &creator, &participant, 150, &options);
+        client.deposit(&split_id, &participant, &150);
+
+        assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+        assert_eq!(token_client.balance(&creator), paid);
+        assert_eq!(token_client.balance(&treasury), fee);
+        assert_eq!(client.get_retainage(&split_id).unwrap().held, retained);
+
+        // Under AssignToCreator the two stroops of dust are their own payout
+        let dust = client
+            .get_transfer_history(&split_id, &0)
+            .iter()
+            .any(|t| t.to == creator && t.amount == 2);
+        assert_eq!(dust, policy == RoundingPolicy::AssignToCreator);
+    }
+}
+
+#[test]
+fn test_open_splits_per_creator_are_capped() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    run_admin_action(&env, &client, &AdminAction::SetMaxOpenSplits(2));
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &100);
+
+    let first = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    let second = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(
+        client.try_create_split(
+            &creator,
+            &String::from_str(&env, "Third"),
+            &10,
+            &Vec::from_array(&env, [participant.clone()]),
+            &Vec::from_array(&env, [10i128]),
+        ),
+        Err(Ok(Error::TooManyOpenSplits))
+    );
+
+    // Other creators have their own allowance
+    create_split_with_shares(&env, &client, &participant, &[(&creator, 10)]);
+
+    // Released and cancelled splits free up room
+    client.deposit(&first, &participant, &10);
+    client.cancel_split(&second, &String::from_str(&env, "plans changed"), &None);
+    create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(client.get_splits_by_creator(&creator, &0).len(), 4);
+}
+
+#[test]
+fn test_operator_stages_split_for_creator() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let operator = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &100);
+
+    let stage = |options: &SplitOptions| {
+        client.try_create_split_for(
+            &operator,
+            &creator,
+            &String::from_str(&env, "Team lunch"),
+            &Vec::from_array(&env, [participant.clone()]),
+            &Vec::from_array(&env, [100i128]),
+            options,
+        )
+    };
+    assert_eq!(
+        stage(&SplitOptions::default()),
+        Err(Ok(Error::AccessDenied))
+    );
+
+    run_admin_action(&env, &client, &AdminAction::AddOperator(operator.clone()));
+    assert!(client.is_operator(&operator));
+    let bonded = SplitOptions {
+        bond: 10,
+        ..Default::default()
+    };
+    assert_eq!(stage(&bonded), Err(Ok(Error::UnsupportedMode)));
+    let collected = SplitOptions {
+        creator_share: CreatorShare::Collected,
+        ..Default::default()
+    };
+    assert_eq!(stage(&collected), Err(Ok(Error::UnsupportedMode)));
+
+    // Shares that overflow when summed are an error, not a trap
+    assert_eq!(
+        client.try_create_split_for(
+            &operator,
+            &creator,
+            &String::from_str(&env, "Team lunch"),
+            &Vec::from_array(&env, [participant.clone(), Address::generate(&env)]),
+            &Vec::from_array(&env, [i128::MAX, 1]),
+            &SplitOptions::default(),
+        ),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // Only the operator signs; the creator hasn't been asked yet
+    let split_id = stage(&SplitOptions::default()).unwrap().unwrap();
+    assert_eq!(
+        env.auths().first().map(|(address, _)| address.clone()),
+        Some(operator.clone())
+    );
+    assert_eq!(client.get_split(&split_id).creator, creator);
+    assert!(client.is_awaiting_creator(&split_id));
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &100),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    client.accept_split(&split_id);
+    assert!(!client.is_awaiting_creator(&split_id));
+    assert_eq!(
+        client.try_accept_split(&split_id),
+        Err(Ok(Error::NotAwaitingAcceptance))
+    );
+    client.deposit(&split_id, &participant, &100);
+    assert_eq!(token_client.balance(&creator), 100);
+
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::RemoveOperator(operator.clone()),
+    );
+    assert!(!client.is_operator(&operator));
+}
+
+#[test]
+fn test_bridge_credits_classic_payments_once() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let bridge = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    token_admin_client.mint(&bridge, &100);
+    let payment_ref = BytesN::from_array(&env, &[7; 32]);
+
+    assert_eq!(
+        client.try_bridge_deposit(&bridge, &split_id, &alice, &60, &payment_ref),
+        Err(Ok(Error::AccessDenied))
+    );
+    client.grant_role(&Role::Bridge, &bridge);
+
+    // The credit has to name a listed participant
+    assert_eq!(
+        client.try_bridge_deposit(&bridge, &split_id, &stranger, &60, &payment_ref),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+    client.bridge_deposit(&bridge, &split_id, &alice, &60, &payment_ref);
+    assert_eq!(
+        client
+            .get_split(&split_id)
+            .participants
+            .get(0)
+            .unwrap()
+            .amount_paid,
+        60
+    );
+    assert_eq!(token_client.balance(&bridge), 40);
+    let credit = client.get_bridge_credit(&payment_ref).unwrap();
+    assert_eq!(
+        (
+            credit.bridge,
+            credit.split_id,
+            credit.participant,
+            credit.amount
+        ),
+        (bridge.clone(), split_id, alice.clone(), 60)
+    );
+
+    // The same classic payment can't be credited twice
+    assert_eq!(
+        client.try_bridge_deposit(&bridge, &split_id, &alice, &40, &payment_ref),
+        Err(Ok(Error::PaymentAlreadyCredited))
+    );
+    client.bridge_deposit(
+        &bridge,
+        &split_id,
+        &alice,
+        &40,
+        &BytesN::from_array(&env, &[8; 32]),
+    );
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+}
+
+#[test]
+fn test_refunds_go_to_the_registered_refund_address() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let new_wallet = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    token_admin_client.mint(&alice, &60);
+    client.deposit(&split_id, &alice, &60);
+
+    assert_eq!(
+        client.try_set_refund_address(&alice, &alice),
+        Err(Ok(Error::InvalidParticipants))
+    );
+    client.set_refund_address(&alice, &new_wallet);
+    assert_eq!(client.get_refund_address(&alice), Some(new_wallet.clone()));
+
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(client.claim_refund(&split_id, &alice), 60);
+    assert_eq!(token_client.balance(&alice), 0);
+    assert_eq!(token_client.balance(&new_wallet), 60);
+
+    client.clear_refund_address(&alice);
+    assert_eq!(client.get_refund_address(&alice), None);
+}
+
+#[test]
+fn test_delegate_acts_for_a_participant() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let household = Address::generate(&env);
+    token_admin_client.mint(&household, &100);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+    assert_eq!(
+        client.try_deposit_as(&household, &split_id, &alice, &30),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_set_delegate(&alice, &alice),
+        Err(Ok(Error::InvalidParticipants))
+    );
+
+    client.set_delegate(&alice, &household);
+    assert_eq!(client.get_delegate(&alice), Some(household.clone()));
+    client.deposit_as(&household, &split_id, &alice, &30);
+    assert_eq!(token_client.balance(&household), 70);
+    assert_eq!(
+        client
+            .get_split(&split_id)
+            .participants
+            .get(0)
+            .unwrap()
+            .amount_paid,
+        30
+    );
+
+    // The delegate can't act for anyone else
+    assert_eq!(
+        client.try_deposit_as(&household, &split_id, &bob, &10),
+        Err(Ok(Error::AccessDenied))
+    );
+
+    // A refund the delegate claims goes to the participant
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(client.claim_refund_as(&household, &split_id, &alice), 30);
+    assert_eq!(token_client.balance(&alice), 30);
+    assert_eq!(token_client.balance(&household), 70);
+
+    client.revoke_delegate(&alice);
+    assert_eq!(client.get_delegate(&alice), None);
+}
+
+#[test]
+fn test_allowlisted_contract_deposits_for_a_participant() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let payroll = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    token_admin_client.mint(&payroll, &100);
+    token_admin_client.mint(&treasury, &100);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60)]);
+    assert_eq!(
+        client.try_deposit_via(&payroll, &split_id, &alice, &20),
+        Err(Ok(Error::AccessDenied))
+    );
+
+    // The creator allows one contract on this split
+    client.set_split_depositor(&split_id, &payroll, &true);
+    assert!(client.can_deposit_via(&split_id, &payroll));
+    client.deposit_via(&payroll, &split_id, &alice, &20);
+    assert_eq!(token_client.balance(&payroll), 80);
+    assert_eq!(
+        client
+            .get_split(&split_id)
+            .participants
+            .get(0)
+            .unwrap()
+            .amount_paid,
+        20
+    );
+
+    let event = env.events().all().iter().find(|e| {
+        let topic: Symbol = e.1.get(0).unwrap().try_into_val(&env).unwrap();
+        topic == symbol_short!("dep_via")
+    });
+    let (_, _, data): (u64, u64, Val) = event.unwrap().2.try_into_val(&env).unwrap();
+    let data: (u64, Address, Address, i128) = data.try_into_val(&env).unwrap();
+    assert_eq!(data, (split_id, alice.clone(), payroll.clone(), 20));
+
+    // The admin allows another on every split
+    assert!(!client.can_deposit_via(&split_id, &treasury));
+    run_admin_action(&env, &client, &AdminAction::AddDepositor(treasury.clone()));
+    client.deposit_via(&treasury, &split_id, &alice, &40);
+    assert_eq!(token_client.balance(&treasury), 60);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+
+    client.set_split_depositor(&split_id, &payroll, &false);
+    assert!(!client.can_deposit_via(&split_id, &payroll));
+}
+
+// ============================================
+// Unit-Priced Split Tests
+// ============================================
+
+#[test]
+fn test_unit_split_prices_nights_at_lock() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &1_000);
+
+    let split_id = client.create_unit_split(
+        &creator,
+        &String::from_str(&env, "Beach house"),
+        &1_000,
+        &Vec::from_array(&env, [alice.clone(), bob.clone()]),
+        &Vec::from_array(&env, [3u32, 2]),
+    );
+    assert_eq!(
+        client.try_deposit(&split_id, &alice, &100),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    // Alice stayed an extra night, which makes Bob's nights cheaper
+    client.set_units(&split_id, &alice, &4, &None);
+    assert_eq!(client.lock_units(&split_id), 166);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 667);
+    assert_eq!(split.participants.get(1).unwrap().share_amount, 333);
+    assert_eq!(
+        client.get_unit_shares(&split_id),
+        Some(UnitShares {
+            units: Vec::from_array(&env, [4u32, 2]),
+            unit_price: 166,
+            locked: true,
+        })
+    );
+
+    assert_eq!(
+        client.try_set_units(&split_id, &bob, &3, &None),
+        Err(Ok(Error::UnitsLocked))
+    );
+    assert_eq!(
+        client.try_lock_units(&split_id),
+        Err(Ok(Error::UnitsLocked))
+    );
+    client.deposit(&split_id, &alice, &667);
+    assert_eq!(token_client.balance(&alice), 333);
+}
+
+// ============================================
+// Open Split Tests
+// ============================================
+
+#[test]
+fn test_open_split_fills_up_with_equal_shares() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let options = SplitOptions {
+        max_headcount: 3,
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Cabin"),
+        &100,
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &options,
+    );
+    assert_eq!(client.get_open_seats(&split_id), 3);
+    client.check_invariants(&split_id);
+
+    client.join_split(&split_id, &alice);
+    client.join_split(&split_id, &bob);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 50);
+    assert_eq!(split.participants.get(1).unwrap().share_amount, 50);
+    assert_eq!(
+        client.try_join_split(&split_id, &alice),
+        Err(Ok(Error::InvalidParticipants))
+    );
+
+    // A cap below the listed members leaves no seats rather than wrapping
+    env.as_contract(&client.address, || storage::set_join_cap(&env, split_id, 1));
+    assert_eq!(client.get_open_seats(&split_id), 0);
+    env.as_contract(&client.address, || storage::set_join_cap(&env, split_id, 3));
+
+    // Shares are still moving, so deposits wait
+    assert_eq!(
+        client.try_deposit(&split_id, &alice, &50),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    // The third joiner fills the split, which locks it
+    client.join_split(&split_id, &carol);
+    assert_eq!(client.get_open_seats(&split_id), 0);
+    let split = client.get_split(&split_id);
+    let shares: std::vec::Vec<i128> = split.participants.iter().map(|p| p.share_amount).collect();
+    assert_eq!(shares, [34, 33, 33]);
+    assert_eq!(
+        client.try_join_split(&split_id, &Address::generate(&env)),
+        Err(Ok(Error::UnsupportedMode))
+    );
+
+    client.deposit(&split_id, &alice, &34);
+    client.check_invariants(&split_id);
+}
+
+#[test]
+fn test_creator_locks_open_split_early() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+
+    let options = SplitOptions {
+        max_headcount: 5,
+        ..Default::default()
+    };
+    let empty = Vec::new(&env);
+    let no_shares = Vec::new(&env);
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Gift"),
+        &90,
+        &empty,
+        &no_shares,
+        &options,
+    );
+
+    // Nobody to owe the total yet
+    assert_eq!(
+        client.try_lock_split(&split_id),
+        Err(Ok(Error::InvalidParticipants))
+    );
+
+    client.join_split(&split_id, &alice);
+    client.lock_split(&split_id);
+    assert_eq!(client.get_open_seats(&split_id), 0);
+    assert_eq!(
+        client
+            .get_split(&split_id)
+            .participants
+            .get(0)
+            .unwrap()
+            .share_amount,
+        90
+    );
+    assert_eq!(
+        client.try_lock_split(&split_id),
+        Err(Ok(Error::UnsupportedMode))
+    );
+
+    // Open splits can't list people up front, or promise more seats than stroops
+    let listed = Vec::from_array(&env, [alice.clone()]);
+    let result = client.try_create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Gift"),
+        &90,
+        &listed,
+        &Vec::from_array(&env, [90i128]),
+        &options,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::InvalidParticipants)));
+    let result = client.try_create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Gift"),
+        &4,
+        &empty,
+        &no_shares,
+        &options,
+    );
+    assert_eq!(result.err(), Some(Ok(Error::InvalidAmount)));
+}
+
+// ============================================
+// Crowdfund Tests
+// ============================================
+
+#[test]
+fn test_crowdfund_contributions_cover_listed_shares() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let organizer = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let neighbour = Address::generate(&env);
+    token_admin_client.mint(&stranger, &100);
+    token_admin_client.mint(&neighbour, &100);
+
+    // The organizer backs the whole gift, and anyone can chip in
+    let options = SplitOptions {
+        crowdfund: true,
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &organizer, &friend, 100, &options);
+
+    client.contribute(&split_id, &stranger, &30);
+    client.contribute(&split_id, &stranger, &10);
+    client.contribute(&split_id, &neighbour, &20);
+
+    let contributions = client.get_contributions(&split_id);
+    assert_eq!(contributions.len(), 2);
+    assert_eq!(contributions.get(0).unwrap().amount, 40);
+    assert_eq!(contributions.get(1).unwrap().amount, 20);
+
+    // The friend's share shrank by what others put in
+    let split = client.get_split(&split_id);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 40);
+    assert_eq!(split.amount_collected, 60);
+    client.check_invariants(&split_id);
+
+    assert_eq!(
+        client.try_contribute(&split_id, &neighbour, &41),
+        Err(Ok(Error::ExceedsRemaining))
+    );
+    assert_eq!(
+        client.try_contribute(&split_id, &friend, &10),
+        Err(Ok(Error::InvalidParticipants))
+    );
+
+    // Contributions can't write the friend out; they keep one stroop
+    assert_eq!(
+        client.try_contribute(&split_id, &neighbour, &40),
+        Err(Ok(Error::ExceedsRemaining))
+    );
+    client.contribute(&split_id, &neighbour, &39);
+    assert_eq!(
+        client
+            .get_split(&split_id)
+            .participants
+            .get(0)
+            .unwrap()
+            .share_amount,
+        1
+    );
+
+    // Their last stroop funds the split and releases it to the organizer
+    token_admin_client.mint(&friend, &1);
+    client.deposit(&split_id, &friend, &1);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&organizer), 100);
+    assert_eq!(token_client.balance(&friend), 0);
+}
+
+#[test]
+fn test_crowdfund_caps_contributors() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    env.budget().reset_unlimited();
+
+    let organizer = Address::generate(&env);
+    let friend = Address::generate(&env);
+    let options = SplitOptions {
+        crowdfund: true,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &organizer, &friend, 1_000, &options);
+
+    // The friend plus MAX_HEADCOUNT - 1 contributors fill the split
+    for _ in 1..MAX_HEADCOUNT {
+        let contributor = Address::generate(&env);
+        token_admin_client.mint(&contributor, &1);
+        client.contribute(&split_id, &contributor, &1);
+    }
+    let latecomer = Address::generate(&env);
+    token_admin_client.mint(&latecomer, &1);
+    assert_eq!(
+        client.try_contribute(&split_id, &latecomer, &1),
+        Err(Ok(Error::InvalidParticipants))
+    );
+}
+
+#[test]
+fn test_contribute_requires_crowdfund_split() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    token_admin_client.mint(&stranger, &100);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 50)]);
+    assert_eq!(
+        client.try_contribute(&split_id, &stranger, &10),
+        Err(Ok(Error::UnsupportedMode))
+    );
+}
+
+// ============================================
+// Deposit Tests
+// ============================================
+
+#[test]
+fn test_deposit() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Test split");
+    let total_amount: i128 = 100_0000000;
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &total_amount, &addresses, &shares);
+
+    token_admin_client.mint(&participant, &100_0000000i128);
+
+    // Make a deposit
+    client.deposit(&split_id, &participant, &50_0000000);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Active);
+    assert_eq!(split.amount_collected, 50_0000000);
+
+    // Complete the deposit
+    client.deposit(&split_id, &participant, &50_0000000);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(split.amount_collected, 100_0000000);
+    assert_eq!(split.amount_released, 100_0000000);
+
+    let creator_balance = token_client.balance(&creator);
+    assert_eq!(creator_balance, 100_0000000);
+}
+
+#[test]
+fn test_deposit_exceeds_share() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Test split");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    token_admin_client.mint(&participant, &200_0000000i128);
+
+    // Try to overpay
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &150_0000000),
+        Err(Ok(Error::ExceedsRemaining))
+    );
+}
+
+/// Contract that deposits on a participant's behalf and reports failures
+/// instead of trapping
+mod depositor {
+    use crate::SplitEscrowContractClient;
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct Depositor;
+
+    #[contractimpl]
+    impl Depositor {
+        pub fn pay(env: Env, escrow: Address, split_id: u64, from: Address, amount: i128) -> u32 {
+            match SplitEscrowContractClient::new(&env, &escrow)
+                .try_deposit(&split_id, &from, &amount)
+            {
+                Ok(_) => 0,
+                Err(Ok(error)) => error as u32,
+                Err(Err(_)) => u32::MAX,
+            }
+        }
+    }
+}
+
+#[test]
+fn test_deposits_announce_funding_milestones() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 200)]);
+    token_admin_client.mint(&alice, &200);
+
+    let milestones = || {
+        let mut pcts = std::vec::Vec::new();
+        for event in env.events().all().iter() {
+            let topic: Symbol = event.1.get(0).unwrap().try_into_val(&env).unwrap();
+            if topic == symbol_short!("milestone") {
+                let (_, _, data): (u64, u64, Val) = event.2.try_into_val(&env).unwrap();
+                let (_, pct, _): (u64, u32, i128) = data.try_into_val(&env).unwrap();
+                pcts.push(pct);
+            }
+        }
+        pcts
+    };
+
+    client.deposit(&split_id, &alice, &40);
+    assert!(milestones().is_empty());
+    client.deposit(&split_id, &alice, &60);
+    assert_eq!(milestones(), [25, 50]);
+    client.deposit(&split_id, &alice, &100);
+    assert_eq!(milestones(), [25, 50, 75, 100]);
+}
+
+#[test]
+fn test_full_payment_split_rejects_partial_deposits() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &100);
+
+    let options = SplitOptions {
+        require_full_payment: true,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 40, &options);
+    assert!(client.requires_full_payment(&split_id));
+
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &39),
+        Err(Ok(Error::PartialPaymentNotAllowed))
+    );
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &41),
+        Err(Ok(Error::ExceedsRemaining))
+    );
+    client.deposit(&split_id, &participant, &40);
+
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 40);
+}
+
+#[test]
+fn test_minimum_deposit_blocks_dust_but_not_final_payments() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &1_000);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+
+    let action = client.schedule_action(&AdminAction::SetMinDeposit(MinDeposit::ShareBps(0)));
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_action(&action),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    // At least 30% of the share per deposit
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetMinDeposit(MinDeposit::ShareBps(3_000)),
+    );
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &299),
+        Err(Ok(Error::DepositTooSmall))
+    );
+    client.deposit(&split_id, &participant, &300);
+    client.deposit(&split_id, &participant, &500);
+
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetMinDeposit(MinDeposit::Absolute(250)),
+    );
+    assert_eq!(client.get_min_deposit(), MinDeposit::Absolute(250));
+
+    // 200 is below the minimum but settles the share
+    client.deposit(&split_id, &participant, &200);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+}
+
+#[test]
+fn test_deposit_errors_are_recoverable_by_callers() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 100)]);
+    token_admin_client.mint(&participant, &100);
+
+    // The participant signs for a deposit the depositor contract makes
+    env.mock_all_auths_allowing_non_root_auth();
+    let depositor_id = env.register_contract(None, depositor::Depositor);
+    let depositor = depositor::DepositorClient::new(&env, &depositor_id);
+
+    assert_eq!(
+        depositor.pay(&client.address, &split_id, &participant, &150),
+        Error::ExceedsRemaining as u32
+    );
+    assert_eq!(
+        depositor.pay(&client.address, &split_id, &participant, &100),
+        0
+    );
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+}
+
+#[test]
+fn test_reduce_total_refunds_overpayments() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &60);
+    token_admin_client.mint(&bob, &40);
+
+    let split_id = client.create_split(
+        &creator,
+        &String::from_str(&env, "Dinner"),
+        &100,
+        &Vec::from_array(&env, [alice.clone(), bob.clone()]),
+        &Vec::from_array(&env, [60i128, 40]),
+    );
+    client.deposit(&split_id, &alice, &60);
+    client.deposit(&split_id, &bob, &10);
+    assert_eq!(
+        client.try_reduce_total(&split_id, &100, &None),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // The bill came in at half: Alice gets her excess back, Bob still owes
+    assert_eq!(client.reduce_total(&split_id, &50, &None), 30);
+    assert_eq!(token_client.balance(&alice), 30);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.total_amount, 50);
+    assert_eq!(split.amount_collected, 40);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 30);
+    assert_eq!(split.participants.get(1).unwrap().share_amount, 20);
+    client.check_invariants(&split_id);
+
+    client.deposit(&split_id, &bob, &10);
+    assert_eq!(token_client.balance(&creator), 50);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+}
+
+#[test]
+fn test_reduce_total_can_complete_split() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &50);
+    token_admin_client.mint(&bob, &50);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+    client.deposit(&split_id, &alice, &45);
+    client.deposit(&split_id, &bob, &45);
+
+    // 89 splits 45/44: Bob gets a stroop back and the split is fully paid
+    assert_eq!(client.reduce_total(&split_id, &89, &None), 1);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 45);
+    assert_eq!(split.participants.get(1).unwrap().share_amount, 44);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&bob), 6);
+    assert_eq!(token_client.balance(&creator), 89);
+}
+
+#[test]
+fn test_reduce_total_leaves_credited_creator_share_alone() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &40);
+
+    let addresses = Vec::from_array(&env, [creator.clone(), alice.clone(), bob.clone()]);
+    let shares = Vec::from_array(&env, [40i128, 40, 40]);
+    let options = SplitOptions {
+        creator_share: CreatorShare::Credited,
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Cabin"),
+        &120,
+        &addresses,
+        &shares,
+        &options,
+    );
+    client.deposit(&split_id, &alice, &40);
+
+    assert_eq!(client.reduce_total(&split_id, &60, &None), 10);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.total_amount, 60);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 0);
+    assert!(split.participants.get(0).unwrap().has_paid);
+    assert_eq!(split.participants.get(1).unwrap().share_amount, 30);
+    assert_eq!(split.participants.get(2).unwrap().share_amount, 30);
+    assert_eq!(token_client.balance(&alice), 10);
+}
+
+#[test]
+fn test_total_increase_waits_for_every_affected_participant() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &100);
+    token_admin_client.mint(&carol, &100);
+
+    let split_id = create_split_with_shares(
+        &env,
+        &client,
+        &creator,
+        &[(&alice, 50), (&bob, 50), (&carol, 20)],
+    );
+    client.deposit(&split_id, &alice, &50);
+    assert_eq!(
+        client.try_propose_increase(
+            &split_id,
+            &150,
+            &Vec::from_array(&env, [40i128, 90, 20]),
+            &None,
+        ),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // Forgot the drinks: Alice and Bob each owe 10 more, Carol is unaffected
+    client.propose_increase(
+        &split_id,
+        &140,
+        &Vec::from_array(&env, [60i128, 60, 20]),
+        &None,
+    );
+    assert_eq!(
+        client.try_accept_increase(&split_id, &carol),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+
+    // Deposits against the old shares still count while consent is pending
+    client.deposit(&split_id, &bob, &20);
+    assert!(!client.accept_increase(&split_id, &alice));
+    assert_eq!(client.get_split(&split_id).total_amount, 120);
+    assert!(client.accept_increase(&split_id, &bob));
+    assert_eq!(client.get_total_increase(&split_id), None);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.total_amount, 140);
+    assert!(!split.participants.get(0).unwrap().has_paid);
+    assert_eq!(split.participants.get(1).unwrap().amount_paid, 20);
+
+    client.deposit(&split_id, &alice, &10);
+    client.deposit(&split_id, &bob, &40);
+    client.deposit(&split_id, &carol, &20);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 140);
+}
+
+#[test]
+fn test_total_increase_shares_follow_addresses() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+    client.propose_increase(&split_id, &130, &Vec::from_array(&env, [80i128, 50]), &None);
+    let proposed = client.get_total_increase(&split_id).unwrap();
+    assert_eq!(proposed.shares.get(0).unwrap().participant, alice);
+
+    // Reordering the participants doesn't hand Alice's increase to Bob
+    env.as_contract(&client.address, || {
+        let mut split = storage::get_split(&env, split_id);
+        let first = split.participants.pop_front().unwrap();
+        split.participants.push_back(first);
+        storage::set_split(&env, split_id, &split);
+    });
+    let kept = env.as_contract(&client.address, || storage::get_split(&env, split_id));
+
+    // A participant the shares weren't proposed for blocks the increase
+    env.as_contract(&client.address, || {
+        let mut split = storage::get_split(&env, split_id);
+        let mut p = split.participants.get(0).unwrap();
+        p.address = Address::generate(&env);
+        split.participants.set(0, p);
+        storage::set_split(&env, split_id, &split);
+    });
+    assert_eq!(
+        client.try_accept_increase(&split_id, &alice),
+        Err(Ok(Error::SharesMismatch))
+    );
+
+    env.as_contract(&client.address, || {
+        storage::set_split(&env, split_id, &kept)
+    });
+    assert!(client.accept_increase(&split_id, &alice));
+    let split = client.get_split(&split_id);
+    assert_eq!(split.participants.get(0).unwrap().address, bob);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 50);
+    assert_eq!(split.participants.get(1).unwrap().share_amount, 80);
+}
+
+// ============================================
+// Forgiveness Tests
+// ============================================
+
+#[test]
+fn test_forgive_remaining_completes_with_what_was_collected() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+
+    // Nothing collected yet, so there's nothing to complete with
+    assert_eq!(
+        client.try_forgive_remaining(&split_id, &None),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+
+    client.deposit(&split_id, &alice, &30);
+    assert_eq!(client.forgive_remaining(&split_id, &None), 70);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Completed);
+    assert_eq!(split.total_amount, 30);
+    let views = client.get_participants(&split_id);
+    assert_eq!(views.get(0).unwrap().forgiven, 20);
+    assert_eq!(views.get(1).unwrap().forgiven, 50);
+    assert_eq!(views.get(1).unwrap().remaining, 0);
+    assert_eq!(client.get_forgiven(&split_id, &bob), 50);
+    client.check_invariants(&split_id);
+
+    assert_eq!(
+        client.try_forgive_remaining(&split_id, &None),
+        Err(Ok(Error::SplitFullyFunded))
+    );
+    client.release_funds(&split_id);
+    assert_eq!(token_client.balance(&creator), 30);
+}
+
+#[test]
+fn test_write_off_lets_the_rest_complete() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &100);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 40), (&bob, 60)]);
+    client.deposit(&split_id, &alice, &40);
+    client.deposit(&split_id, &bob, &10);
+
+    // Bob defaults on the rest; with Alice paid up the split releases
+    assert_eq!(client.write_off(&split_id, &bob, &None), 50);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.total_amount, 50);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(
+        client.get_participants(&split_id).get(1).unwrap().forgiven,
+        50
+    );
+    assert_eq!(token_client.balance(&creator), 50);
+
+    let other = create_split_with_shares(&env, &client, &creator, &[(&alice, 40)]);
+    assert_eq!(
+        client.try_write_off(&other, &bob, &None),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+    // Writing off the only share would leave nothing to complete
+    assert_eq!(
+        client.try_write_off(&other, &alice, &None),
+        Err(Ok(Error::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_creator_share_is_credited_or_collected_at_creation() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(creator.clone());
+    addresses.push_back(alice.clone());
+    let shares = Vec::from_array(&env, [50i128, 50]);
+    let description = String::from_str(&env, "Cabin");
+
+    let mut options = SplitOptions {
+        creator_share: CreatorShare::Credited,
+        ..Default::default()
+    };
+    let only_alice = Vec::from_array(&env, [alice.clone()]);
+    let only_share = Vec::from_array(&env, [100i128]);
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &description,
+            &100,
+            &only_alice,
+            &only_share,
+            &options,
+        ),
+        Err(Ok(Error::InvalidParticipants))
+    );
+
+    // Credited: the creator's half never has to move
+    let credited = client.create_split_with_options(
+        &creator,
+        &description,
+        &100,
+        &addresses,
+        &shares,
+        &options,
+    );
+    let split = client.get_split(&credited);
+    assert_eq!(split.total_amount, 50);
+    assert!(split.participants.get(0).unwrap().has_paid);
+    assert_eq!(client.get_forgiven(&credited, &creator), 50);
+    token_admin_client.mint(&alice, &50);
+    client.deposit(&credited, &alice, &50);
+    assert_eq!(client.get_split(&credited).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 50);
+
+    // Collected: the creator pays in as part of creating
+    options.creator_share = CreatorShare::Collected;
+    let collected = client.create_split_with_options(
+        &creator,
+        &description,
+        &100,
+        &addresses,
+        &shares,
+        &options,
+    );
+    let split = client.get_split(&collected);
+    assert_eq!(split.amount_collected, 50);
+    assert_eq!(split.status, SplitStatus::Active);
+    assert_eq!(client.get_held_balance(&collected, &creator), 50);
+    assert_eq!(token_client.balance(&creator), 0);
+
+    options.mode = SplitMode::Iou;
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &description,
+            &100,
+            &addresses,
+            &shares,
+            &options,
+        ),
+        Err(Ok(Error::UnsupportedMode))
+    );
+}
+
+// ============================================
+// Cancel Tests
+// ============================================
+
+#[test]
+fn test_cancel_split() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Test split");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant);
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Cancelled);
+}
+
+#[test]
+fn test_cancel_after_deposits_needs_participant_approval() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    run_admin_action(&env, &client, &AdminAction::SetCancelQuorum(5_000));
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let split_id = create_split_with_shares(
+        &env,
+        &client,
+        &creator,
+        &[(&alice, 100), (&bob, 100), (&carol, 100)],
+    );
+    let reason = String::from_str(&env, "Venue fell through");
+
+    // Nobody has paid yet, so there is nothing to approve
+    assert_eq!(
+        client.try_approve_cancel(&split_id, &alice),
+        Err(Ok(Error::SplitNotFunded))
+    );
+
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &100);
+    client.deposit(&split_id, &alice, &100);
+    client.deposit(&split_id, &bob, &100);
+
+    // Two have paid, so one of them has to agree
+    assert_eq!(
+        client.try_cancel_split(&split_id, &reason, &None),
+        Err(Ok(Error::ActionNotReady))
+    );
+    assert_eq!(
+        client.try_approve_cancel(&split_id, &carol),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+    assert_eq!(client.approve_cancel(&split_id, &alice), 1);
+    assert_eq!(client.approve_cancel(&split_id, &alice), 1);
+    assert_eq!(client.get_cancel_approvals(&split_id).len(), 1);
+
+    client.cancel_split(&split_id, &reason, &None);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Cancelled);
+    client.claim_refund(&split_id, &bob);
+    assert_eq!(token_client.balance(&bob), 100);
+}
+
+#[test]
+fn test_cancel_reason_is_kept_and_announced() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 100)]);
+
+    // A reason is required, and it can't run on forever
+    assert_eq!(
+        client.try_cancel_split(&split_id, &String::from_str(&env, ""), &None),
+        Err(Ok(Error::CancelReasonRequired))
+    );
+    let rambling = "x".repeat(MAX_CANCEL_REASON_LEN as usize + 1);
+    assert_eq!(
+        client.try_cancel_split(&split_id, &String::from_str(&env, &rambling), &None),
+        Err(Ok(Error::DescriptionTooLong))
+    );
+    assert_eq!(client.get_cancel_reason(&split_id), None);
+
+    let reason = String::from_str(&env, "Restaurant closed");
+    client.cancel_split(&split_id, &reason, &None);
+    assert_eq!(client.get_cancel_reason(&split_id), Some(reason.clone()));
+
+    let event = env.events().all().iter().find(|e| {
+        let topic: Symbol = e.1.get(0).unwrap().try_into_val(&env).unwrap();
+        topic == symbol_short!("cancel")
+    });
+    let (_, _, data): (u64, u64, Val) = event.unwrap().2.try_into_val(&env).unwrap();
+    let data: (u64, String) = data.try_into_val(&env).unwrap();
+    assert_eq!(data, (split_id, reason.clone()));
+
+    assert_eq!(
+        client.try_cancel_split(&split_id, &reason, &None),
+        Err(Ok(Error::SplitCancelled))
+    );
+}
+
+#[test]
+fn test_refund_pays_back_each_held_balance() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+
+    token_admin_client.mint(&alice, &50);
+    token_admin_client.mint(&bob, &50);
+    client.deposit(&split_id, &alice, &30);
+    client.deposit(&split_id, &bob, &20);
+    assert_eq!(client.get_held_balance(&split_id, &alice), 30);
+    assert_eq!(client.get_held_balance(&split_id, &bob), 20);
+
+    // Nothing is refundable while the split is live
+    assert_eq!(
+        client.try_claim_refund(&split_id, &alice),
+        Err(Ok(Error::NotRefundable))
+    );
+
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(client.claim_refund(&split_id, &alice), 30);
+    assert_eq!(token_client.balance(&alice), 50);
+    assert_eq!(client.get_held_balance(&split_id, &alice), 0);
+    assert_eq!(
+        client.try_claim_refund(&split_id, &alice),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+
+    assert_eq!(client.claim_refund(&split_id, &bob), 20);
+    assert_eq!(client.get_split(&split_id).amount_collected, 0);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+#[test]
+fn test_process_refunds_resumes_across_calls() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let split_id = create_split_with_shares(
+        &env,
+        &client,
+        &creator,
+        &[(&alice, 40), (&bob, 40), (&carol, 40)],
+    );
+    for p in [&alice, &bob, &carol] {
+        token_admin_client.mint(p, &10);
+        client.deposit(&split_id, p, &10);
+    }
+
+    assert_eq!(
+        client.try_process_refunds(&split_id, &2),
+        Err(Ok(Error::NotRefundable))
+    );
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(
+        client.try_process_refunds(&split_id, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // Bob already claimed, so the sweep skips that slot
+    client.claim_refund(&split_id, &bob);
+    assert_eq!(client.process_refunds(&split_id, &2), 1);
+    assert_eq!(client.get_refund_cursor(&split_id), 2);
+    assert_eq!(token_client.balance(&alice), 10);
+    assert_eq!(token_client.balance(&carol), 0);
+
+    assert_eq!(client.process_refunds(&split_id, &2), 0);
+    assert_eq!(token_client.balance(&carol), 10);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+    assert_eq!(client.process_refunds(&split_id, &2), 0);
+}
+
+#[test]
+fn test_release_clears_held_balances() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+
+    token_admin_client.mint(&alice, &50);
+    client.deposit(&split_id, &alice, &50);
+    client.release_partial(&split_id);
+    assert_eq!(client.get_held_balance(&split_id, &alice), 0);
+
+    // Released funds are gone, so a later cancel has nothing to refund
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(
+        client.try_claim_refund(&split_id, &alice),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+}
+
+#[test]
+fn test_batch_cancel_reports_each_split() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &10);
+    let abandoned = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let paid_into = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    client.deposit(&paid_into, &alice, &10);
+    let not_mine = create_split_with_shares(&env, &client, &alice, &[(&creator, 100)]);
+
+    let mut ids = Vec::new(&env);
+    for id in [abandoned, paid_into, not_mine, 999] {
+        ids.push_back(id);
+    }
+    let results = client.batch_cancel(&creator, &ids, &String::from_str(&env, "trip called off"));
+    let errors: std::vec::Vec<u32> = results.iter().map(|r| r.error).collect();
+    assert_eq!(
+        errors,
+        [
+            0,
+            Error::HasDeposits as u32,
+            Error::AccessDenied as u32,
+            Error::SplitNotFound as u32
+        ]
+    );
+    assert!(results.get(0).unwrap().success);
+    assert_eq!(client.get_split(&abandoned).status, SplitStatus::Cancelled);
+    assert_eq!(client.get_split(&paid_into).status, SplitStatus::Active);
+}
+
+// ============================================
+// Release Tests
+// ============================================
+
+#[test]
+fn test_release_funds() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Test split");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    // Complete the split (auto-release should occur)
+    token_admin_client.mint(&participant, &100_0000000i128);
+    client.deposit(&split_id, &participant, &100_0000000);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+
+    let creator_balance = token_client.balance(&creator);
+    assert_eq!(creator_balance, 100_0000000);
+
+    // Manual release should be blocked after auto-release
+    let result = catch_unwind(AssertUnwindSafe(|| client.release_funds(&split_id)));
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_release_incomplete_split() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Test split");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant);
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    // Try to release without completing deposits
+    let result = catch_unwind(AssertUnwindSafe(|| client.release_funds(&split_id)));
+    assert!(result.is_err());
+}
+
+// ============================================
+// Partial Release and Funding Checks
+// ============================================
+
+#[test]
+fn test_is_fully_funded() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Funding check");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    token_admin_client.mint(&participant, &50_0000000i128);
+    client.deposit(&split_id, &participant, &50_0000000);
+
+    let funded = client.is_fully_funded(&split_id);
+    assert!(!funded);
+
+    token_admin_client.mint(&participant, &50_0000000i128);
+    client.deposit(&split_id, &participant, &50_0000000);
+
+    let funded = client.is_fully_funded(&split_id);
+    assert!(funded);
+}
+
+#[test]
+fn test_release_partial() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Partial release");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+
+    let mut shares = Vec::new(&env);
+    shares.push_back(100_0000000i128);
+
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    token_admin_client.mint(&participant, &60_0000000i128);
+    client.deposit(&split_id, &participant, &60_0000000);
+
+    let released = client.release_partial(&split_id);
+    assert_eq!(released, 60_0000000);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Active);
+    assert_eq!(split.amount_released, 60_0000000);
+
+    let creator_balance = token_client.balance(&creator);
+    assert_eq!(creator_balance, 60_0000000);
+
+    token_admin_client.mint(&participant, &40_0000000i128);
+    client.deposit(&split_id, &participant, &40_0000000);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(split.amount_released, 100_0000000);
+}
+
+#[test]
+fn test_quote_partial_release_nets_out_fees() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeSchedule(FeeSchedule {
+            recipient: Address::generate(&env),
+            tiers: Vec::from_array(
+                &env,
+                [FeeTier {
+                    min_total: 0,
+                    bps: 100,
+                }],
+            ),
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &1_000);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+    assert_eq!(
+        client.try_quote_partial_release(&split_id),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+
+    client.deposit(&split_id, &participant, &600);
+    let quote = client.quote_partial_release(&split_id);
+    assert_eq!((quote.amount, quote.fee, quote.net), (600, 6, 594));
+    assert_eq!(quote.status, SplitStatus::Active);
+
+    client.release_partial(&split_id);
+    assert_eq!(token_client.balance(&creator), quote.net);
+    assert_eq!(client.get_split(&split_id).amount_released, 600);
+}
+
+#[test]
+fn test_transfer_history_records_refunds_and_releases() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &100);
+
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+    client.deposit(&split_id, &alice, &50);
+    assert_eq!(client.get_transfer_history(&split_id, &0).len(), 0);
+
+    // Halving the total refunds Alice's excess
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    client.reduce_total(&split_id, &50, &None);
+    client.deposit(&split_id, &bob, &25);
+
+    let history = client.get_transfer_history(&split_id, &0);
+    assert_eq!(history.len(), 2);
+    assert_eq!(
+        history.get(0).unwrap(),
+        TransferRecord {
+            kind: TransferKind::Refund,
+            to: alice,
+            token: token_id.clone(),
+            amount: 25,
+            timestamp: 1_000,
+        }
+    );
+    let release = history.get(1).unwrap();
+    assert_eq!(release.kind, TransferKind::Release);
+    assert_eq!((release.to, release.amount), (creator, 50));
+}
+
+#[test]
+fn test_batch_release_skips_ineligible_splits() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &300);
+    let first = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let second = create_split_with_shares(&env, &client, &creator, &[(&alice, 150)]);
+    let unfunded = create_split_with_shares(&env, &client, &creator, &[(&alice, 50)]);
+
+    // Hold the funded splits at Completed
+    client.set_pause_flags(
+        &admin,
+        &PauseFlags {
+            release: true,
+            ..Default::default()
+        },
+    );
+    client.deposit(&first, &alice, &100);
+    client.deposit(&second, &alice, &150);
+    client.set_pause_flags(&admin, &PauseFlags::default());
+
+    let mut ids = Vec::new(&env);
+    for id in [first, unfunded, 999, second] {
+        ids.push_back(id);
+    }
+    let results = client.batch_release(&ids);
+    let errors: std::vec::Vec<u32> = results.iter().map(|r| r.error).collect();
+    assert_eq!(
+        errors,
+        [
+            0,
+            Error::SplitNotFunded as u32,
+            Error::SplitNotFound as u32,
+            0
+        ]
+    );
+    assert_eq!(token_client.balance(&creator), 250);
+    assert_eq!(client.get_split(&second).status, SplitStatus::Released);
+}
+
+// ============================================
+// Event Emission Tests
+// ============================================
+
+#[test]
+fn test_event_sequence_numbers_count_up() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let first = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    let second = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    token_admin_client.mint(&participant, &5);
+    client.deposit(&first, &participant, &5);
+
+    // Every event the contract emitted has the next global number
+    let seqs: std::vec::Vec<(u64, u64)> = env
+        .events()
+        .all()
+        .iter()
+        .filter(|event| event.0 == client.address)
+        .map(|event| {
+            let (seq, split_seq, _): (u64, u64, Val) = event.2.try_into_val(&env).unwrap();
+            (seq, split_seq)
+        })
+        .collect();
+    let total = client.get_event_seq(&None);
+    assert_eq!(
+        seqs.last().unwrap(),
+        &(total, client.get_event_seq(&Some(first)))
+    );
+    assert!(seqs.windows(2).all(|w| w[1].0 == w[0].0 + 1));
+    assert_eq!(seqs.len() as u64, total);
+    // Creation announces the split and its terms hash
+    assert_eq!(client.get_event_seq(&Some(second)), 2);
+}
+
+#[test]
+fn test_events_emitted_on_auto_release() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let description = String::from_str(&env, "Event check");
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+
     let mut shares = Vec::new(&env);
     shares.push_back(100_0000000i128);
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+
+    token_admin_client.mint(&participant, &100_0000000i128);
+    client.deposit(&split_id, &participant, &100_0000000);
+
+    let events = env.events().all();
+    let mut has_completed = false;
+    let mut has_released = false;
+
+    for i in 0..events.len() {
+        let event = events.get(i).unwrap();
+        let topics = &event.1;
+        let data = &event.2;
+
+        let topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
+        if topic == symbol_short!("completed") {
+            let (_, _, payload): (u64, u64, (u64, i128)) = data.try_into_val(&env).unwrap();
+            assert_eq!(payload.0, split_id);
+            assert_eq!(payload.1, 100_0000000);
+            has_completed = true;
+        }
+        if topic == symbol_short!("released") {
+            let (_, _, payload): (u64, u64, (u64, Address, i128, u64)) =
+                data.try_into_val(&env).unwrap();
+            assert_eq!(payload.0, split_id);
+            assert_eq!(payload.1, creator);
+            assert_eq!(payload.2, 100_0000000);
+            has_released = true;
+        }
+    }
+
+    assert!(has_completed);
+    assert!(has_released);
+}
+
+// ============================================
+// Group Debt Simplification Tests
+// ============================================
+
+/// Helper to create a split whose total is the sum of the given shares
+fn create_split_with_shares(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    creator: &Address,
+    shares: &[(&Address, i128)],
+) -> u64 {
+    let mut addresses = Vec::new(env);
+    let mut amounts = Vec::new(env);
+    let mut total: i128 = 0;
+    for (address, share) in shares.iter() {
+        addresses.push_back((*address).clone());
+        amounts.push_back(*share);
+        total += *share;
+    }
+
+    client.create_split(
+        creator,
+        &String::from_str(env, "Group bill"),
+        &total,
+        &addresses,
+        &amounts,
+    )
+}
+
+#[test]
+fn test_simplify_group_debts() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+
+    let mut members = Vec::new(&env);
+    members.push_back(bob.clone());
+    members.push_back(carol.clone());
+    let group_id = client.create_group(&alice, &members);
+
+    let iou = SplitOptions {
+        mode: SplitMode::Iou,
+        ..Default::default()
+    };
+    let create_iou = |creator: &Address, a: &Address, b: &Address, shares: [i128; 2]| {
+        client.create_split_with_options(
+            creator,
+            &String::from_str(&env, "Group bill"),
+            &(shares[0] + shares[1]),
+            &Vec::from_array(&env, [a.clone(), b.clone()]),
+            &Vec::from_array(&env, shares),
+            &iou,
+        )
+    };
+
+    // Alice paid dinner: Bob and Carol each owe her 30
+    let dinner = create_iou(&alice, &bob, &carol, [30, 30]);
+    // Bob paid the taxi: Alice owes him 20, Carol owes him 10
+    let taxi = create_iou(&bob, &alice, &carol, [20, 10]);
+    // An escrowed bill is owed to the contract, so it stays out of the plan
+    let tickets = create_split_with_shares(&env, &client, &alice, &[(&bob, 50)]);
+
+    client.add_split_to_group(&group_id, &dinner);
+    client.add_split_to_group(&group_id, &taxi);
+    client.add_split_to_group(&group_id, &tickets);
+
+    // Net: Alice +40, Bob 0, Carol -40, so one payment settles everything
+    let plan = client.simplify_group_debts(&group_id);
+    assert_eq!(plan.len(), 1);
+
+    let payment = plan.get(0).unwrap();
+    assert_eq!(payment.from, carol);
+    assert_eq!(payment.to, alice);
+    assert_eq!(payment.amount, 40);
+
+    assert_eq!(client.get_group_settlement(&group_id), plan);
+
+    // The plan is only a proposal: Carol still owes on each split
+    let dinner = client.get_split(&dinner);
+    assert_eq!(dinner.participants.get(1).unwrap().amount_paid, 0);
+}
+
+#[test]
+fn test_group_standings_show_who_carries_the_group() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token_admin_client.mint(&bob, &30);
+    let group_id =
+        client.create_group(&alice, &Vec::from_array(&env, [bob.clone(), carol.clone()]));
+
+    let dinner = create_split_with_shares(&env, &client, &alice, &[(&bob, 30), (&carol, 30)]);
+    let taxi = create_split_with_shares(&env, &client, &bob, &[(&alice, 20), (&carol, 10)]);
+    client.add_split_to_group(&group_id, &dinner);
+    client.add_split_to_group(&group_id, &taxi);
+    client.deposit(&dinner, &bob, &30);
+
+    let standings = client.get_group_standings(&group_id);
+    let row = |i: u32| {
+        let s = standings.get(i).unwrap();
+        (s.address, s.owed, s.paid, s.fronted, s.received, s.net)
+    };
+    assert_eq!(row(0), (alice.clone(), 20, 0, 60, 30, 10));
+    assert_eq!(row(1), (bob.clone(), 30, 30, 30, 0, 30));
+    assert_eq!(row(2), (carol.clone(), 40, 0, 0, 0, -40));
+    assert_eq!(standings.iter().map(|s| s.net).sum::<i128>(), 0);
+
+    assert_eq!(
+        client.try_get_group_standings(&99),
+        Err(Ok(Error::GroupNotFound))
+    );
+}
+
+#[test]
+fn test_add_split_to_group_requires_members() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    let mut members = Vec::new(&env);
+    members.push_back(bob.clone());
+    let group_id = client.create_group(&alice, &members);
+
+    let split_id = create_split_with_shares(&env, &client, &alice, &[(&bob, 10), (&outsider, 10)]);
+
+    let result = client.try_add_split_to_group(&group_id, &split_id);
+    assert_eq!(result, Err(Ok(Error::NotGroupMember)));
+    assert_eq!(client.get_group(&group_id).split_ids.len(), 0);
+}
+
+#[test]
+fn test_group_splits_are_capped() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    env.budget().reset_unlimited();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let group_id = client.create_group(&alice, &Vec::from_array(&env, [bob.clone()]));
+    for _ in 0..MAX_GROUP_SPLITS {
+        let split_id = create_split_with_shares(&env, &client, &alice, &[(&bob, 10)]);
+        client.add_split_to_group(&group_id, &split_id);
+    }
+
+    let extra = create_split_with_shares(&env, &client, &alice, &[(&bob, 10)]);
+    assert_eq!(
+        client.try_add_split_to_group(&group_id, &extra),
+        Err(Ok(Error::TooManyGroupSplits))
+    );
+    assert_eq!(
+        client.get_group(&group_id).split_ids.len(),
+        MAX_GROUP_SPLITS
+    );
+}
+
+#[test]
+fn test_group_members_are_deduplicated() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    // The creator is always a member, so listing them again is a duplicate too
+    let members = Vec::from_array(&env, [bob.clone(), alice.clone(), bob.clone()]);
+    let group_id = client.create_group(&alice, &members);
+    assert_eq!(
+        client.get_group(&group_id).members,
+        Vec::from_array(&env, [alice.clone(), bob.clone()])
+    );
+
+    let split_id = create_split_with_shares(&env, &client, &alice, &[(&bob, 10)]);
+    client.add_split_to_group(&group_id, &split_id);
+}
+
+// ============================================
+// Event Tests
+// ============================================
+
+#[test]
+fn test_event_aggregates_its_splits() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token_admin_client.mint(&bob, &100);
+
+    let event_id = client.create_event(&alice, &String::from_str(&env, "Lisbon trip"));
+
+    let hotel = create_split_with_shares(&env, &client, &alice, &[(&bob, 60), (&carol, 60)]);
+    let dinner = create_split_with_shares(&env, &client, &bob, &[(&alice, 30), (&carol, 30)]);
+    let museum = create_split_with_shares(&env, &client, &alice, &[(&bob, 10)]);
+    client.add_split_to_event(&event_id, &hotel);
+    client.add_split_to_event(&event_id, &dinner);
+    client.add_split_to_event(&event_id, &museum);
+    assert_eq!(client.get_split_event(&hotel), Some(event_id));
+
+    // A split joins at most one event
+    let second = client.create_event(&alice, &String::from_str(&env, "Porto"));
+    let result = client.try_add_split_to_event(&second, &hotel);
+    assert_eq!(result, Err(Ok(Error::SplitAlreadyGrouped)));
+    assert_eq!(
+        client.try_add_split_to_event(&99, &hotel),
+        Err(Ok(Error::EventNotFound))
+    );
+
+    client.deposit(&museum, &bob, &10);
+    client.deposit(&hotel, &bob, &60);
+    client.cancel_split(&dinner, &String::from_str(&env, "plans changed"), &None);
+
+    // The cancelled dinner drops out of the totals
+    let summary = client.get_event_summary(&event_id);
+    assert_eq!(summary.total, 130);
+    assert_eq!(summary.collected, 70);
+    assert_eq!(summary.split_count, 2);
+    assert_eq!(summary.completed_count, 1);
+    assert_eq!(summary.progress_bps, 5384);
+
+    let members = client.get_event_members(&event_id);
+    assert_eq!(members.len(), 2);
+    let bob_view = members.get(0).unwrap();
+    assert_eq!(
+        (bob_view.address, bob_view.owed, bob_view.paid),
+        (bob, 70, 70)
+    );
+    let carol_view = members.get(1).unwrap();
+    assert_eq!(
+        (carol_view.address, carol_view.owed, carol_view.paid),
+        (carol, 60, 0)
+    );
+
+    assert_eq!(
+        client.try_get_event_summary(&99),
+        Err(Ok(Error::EventNotFound))
+    );
+}
+
+#[test]
+fn test_settlement_report_for_group_and_event() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    token_admin_client.mint(&carol, &10);
+
+    let group_id =
+        client.create_group(&alice, &Vec::from_array(&env, [bob.clone(), carol.clone()]));
+    let event_id = client.create_event(&alice, &String::from_str(&env, "Ski weekend"));
+
+    let dinner = create_split_with_shares(&env, &client, &alice, &[(&bob, 30), (&carol, 30)]);
+    let taxi = create_split_with_shares(&env, &client, &bob, &[(&alice, 20), (&carol, 10)]);
+    for split_id in [dinner, taxi] {
+        client.add_split_to_group(&group_id, &split_id);
+        client.add_split_to_event(&event_id, &split_id);
+    }
+
+    // Carol already paid Bob for the taxi, so only her dinner share is open
+    client.deposit(&taxi, &carol, &10);
+
+    // Net: Alice +40, Bob -10, Carol -30
+    let report = client.get_settlement_report(&SettlementScope::Group(group_id));
+    assert_eq!(report.positions.len(), 3);
+    for position in report.positions.iter() {
+        let expected = if position.address == alice {
+            40
+        } else if position.address == bob {
+            -10
+        } else {
+            -30
+        };
+        assert_eq!(position.net, expected);
+    }
+    assert_eq!(report.payments.len(), 2);
+    let paid_to_alice: i128 = report
+        .payments
+        .iter()
+        .filter(|p| p.to == alice)
+        .map(|p| p.amount)
+        .sum();
+    assert_eq!(paid_to_alice, 40);
+
+    // The event holds the same splits, so it reports the same thing
+    assert_eq!(
+        client.get_settlement_report(&SettlementScope::Event(event_id)),
+        report
+    );
+
+    // Nothing is stored by the view
+    assert_eq!(client.get_group_settlement(&group_id).len(), 0);
+    assert_eq!(
+        client.try_get_settlement_report(&SettlementScope::Event(99)),
+        Err(Ok(Error::EventNotFound))
+    );
+}
+
+#[test]
+fn test_event_name_and_size_are_bounded() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    env.budget().reset_unlimited();
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let long = [b'a'; MAX_EVENT_NAME_LEN as usize + 1];
+    assert_eq!(
+        client.try_create_event(&alice, &String::from_bytes(&env, &long)),
+        Err(Ok(Error::EventNameTooLong))
+    );
+
+    let event_id = client.create_event(&alice, &String::from_str(&env, "Festival"));
+    for _ in 0..MAX_EVENT_SPLITS {
+        let split_id = create_split_with_shares(&env, &client, &alice, &[(&bob, 10)]);
+        client.add_split_to_event(&event_id, &split_id);
+    }
+    let extra = create_split_with_shares(&env, &client, &alice, &[(&bob, 10)]);
+    assert_eq!(
+        client.try_add_split_to_event(&event_id, &extra),
+        Err(Ok(Error::TooManyEventSplits))
+    );
+}
+
+// ============================================
+// Linked Split Tests
+// ============================================
+
+#[test]
+fn test_parent_split_waits_for_its_children() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let planner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &1_000);
+    token_admin_client.mint(&bob, &1_000);
+
+    let wedding = create_split_with_shares(&env, &client, &planner, &[(&alice, 100)]);
+    let catering = create_split_with_shares(&env, &client, &planner, &[(&alice, 50), (&bob, 50)]);
+    let flowers = create_split_with_shares(&env, &client, &planner, &[(&bob, 40)]);
+    client.link_child_split(&wedding, &catering);
+    client.link_child_split(&wedding, &flowers);
+    assert_eq!(client.get_parent_split(&catering), Some(wedding));
+    assert_eq!(
+        client.get_child_splits(&wedding),
+        Vec::from_array(&env, [catering, flowers])
+    );
+
+    // Only one level deep, and a child has a single parent
+    assert_eq!(
+        client.try_link_child_split(&catering, &flowers),
+        Err(Ok(Error::SplitAlreadyGrouped))
+    );
+    let other = create_split_with_shares(&env, &client, &planner, &[(&bob, 10)]);
+    assert_eq!(
+        client.try_link_child_split(&catering, &other),
+        Err(Ok(Error::UnsupportedMode))
+    );
+
+    // The parent is funded first, but holds while the children are open
+    client.deposit(&wedding, &alice, &100);
+    client.deposit(&catering, &alice, &50);
+    assert_eq!(client.get_split(&wedding).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_release_funds(&wedding),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    let progress = client.get_family_progress(&wedding);
+    assert_eq!(progress.total, 240);
+    assert_eq!(progress.collected, 150);
+    assert_eq!(progress.children, 2);
+    assert_eq!(progress.children_done, 0);
+
+    // A cancelled child stops counting, and a funded one no longer blocks
+    client.cancel_split(&flowers, &String::from_str(&env, "plans changed"), &None);
+    client.deposit(&catering, &bob, &50);
+    let progress = client.get_family_progress(&wedding);
+    assert_eq!(
+        (progress.total, progress.children, progress.children_done),
+        (200, 1, 1)
+    );
+
+    client.release_funds(&wedding);
+    assert_eq!(client.get_split(&wedding).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&planner), 200);
+}
+
+#[test]
+fn test_parent_split_children_are_capped() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    env.budget().reset_unlimited();
+
+    let planner = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let wedding = create_split_with_shares(&env, &client, &planner, &[(&bob, 100)]);
+    for _ in 0..MAX_CHILD_SPLITS {
+        let child = create_split_with_shares(&env, &client, &planner, &[(&bob, 10)]);
+        client.link_child_split(&wedding, &child);
+    }
+
+    let extra = create_split_with_shares(&env, &client, &planner, &[(&bob, 10)]);
+    assert_eq!(
+        client.try_link_child_split(&wedding, &extra),
+        Err(Ok(Error::TooManyChildSplits))
+    );
+}
+
+#[test]
+fn test_unlinking_a_stalled_child_frees_the_parent() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let planner = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let wedding = create_split_with_shares(&env, &client, &planner, &[(&alice, 100)]);
+    let band = create_split_with_shares(&env, &client, &planner, &[(&bob, 30)]);
+    let cake = create_split_with_shares(&env, &client, &planner, &[(&bob, 20)]);
+    client.link_child_split(&wedding, &band);
+    assert_eq!(
+        client.try_unlink_child_split(&wedding, &cake),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    // The band has no deadline and Bob never pays, so the wedding would wait forever
+    client.deposit(&wedding, &alice, &100);
+    assert_eq!(
+        client.try_release_funds(&wedding),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    client.unlink_child_split(&wedding, &band);
+    assert_eq!(client.get_parent_split(&band), None);
+    assert_eq!(client.get_child_splits(&wedding).len(), 0);
+    client.release_funds(&wedding);
+    assert_eq!(token_client.balance(&planner), 100);
+
+    // The child is free to go under another parent
+    let party = create_split_with_shares(&env, &client, &planner, &[(&alice, 10)]);
+    client.link_child_split(&party, &band);
+    assert_eq!(client.get_parent_split(&band), Some(party));
+}
+
+// ============================================
+// IOU Mode Tests
+// ============================================
+
+/// Helper to create a single-participant split with custom options
+fn create_split_with_options_for(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    creator: &Address,
+    participant: &Address,
+    share: i128,
+    options: &SplitOptions,
+) -> u64 {
+    let mut addresses = Vec::new(env);
+    addresses.push_back(participant.clone());
+    let mut shares = Vec::new(env);
+    shares.push_back(share);
+
+    client.create_split_with_options(
+        creator,
+        &String::from_str(env, "Options split"),
+        &share,
+        &addresses,
+        &shares,
+        options,
+    )
+}
+
+#[test]
+fn test_iou_mark_paid_completes_without_transfers() {
+    let (env, admin, token_id, client, token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let options = SplitOptions {
+        mode: SplitMode::Iou,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+
+    client.mark_paid(&split_id, &participant, &40);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.mode, SplitMode::Iou);
+    assert_eq!(split.status, SplitStatus::Active);
+    assert_eq!(split.participants.get(0).unwrap().amount_paid, 40);
+
+    client.mark_paid(&split_id, &participant, &60);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Completed);
+    assert!(split.participants.get(0).unwrap().has_paid);
+    assert_eq!(
+        client.try_mark_paid(&split_id, &participant, &1),
+        Err(Ok(Error::SplitFullyFunded))
+    );
+
+    // Nothing was ever escrowed or paid out
+    assert_eq!(token_client.balance(&client.address), 0);
+    assert_eq!(token_client.balance(&creator), 0);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::UnsupportedMode))
+    );
+}
+
+#[test]
+fn test_iou_rejects_deposit() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let options = SplitOptions {
+        mode: SplitMode::Iou,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+
+    token_admin_client.mint(&participant, &100);
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &100),
+        Err(Ok(Error::UnsupportedMode))
+    );
+}
+
+#[test]
+fn test_mark_paid_rejects_escrow_split() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 100)]);
+
+    assert_eq!(
+        client.try_mark_paid(&split_id, &participant, &100),
+        Err(Ok(Error::UnsupportedMode))
+    );
+}
+
+// ============================================
+// Pass-Through Mode Tests
+// ============================================
+
+#[test]
+fn test_pass_through_forwards_each_deposit() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let options = SplitOptions {
+        mode: SplitMode::PassThrough,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+
+    token_admin_client.mint(&participant, &100);
+
+    client.deposit(&split_id, &participant, &30);
+    assert_eq!(token_client.balance(&creator), 30);
+    assert_eq!(token_client.balance(&client.address), 0);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Active);
+    assert_eq!(split.amount_collected, 30);
+    assert_eq!(split.amount_released, 30);
+
+    client.deposit(&split_id, &participant, &70);
+    assert_eq!(token_client.balance(&creator), 100);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(split.amount_released, 100);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::SplitReleased))
+    );
+}
+
+#[test]
+fn test_reimbursement_split_drips_each_deposit() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let options = SplitOptions {
+        mode: SplitMode::PassThrough,
+        reimburse: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Dinner"),
+            &100,
+            &Vec::from_array(&env, [participant.clone()]),
+            &Vec::from_array(&env, [100i128]),
+            &options,
+        ),
+        Err(Ok(Error::UnsupportedMode))
+    );
+
+    let options = SplitOptions {
+        reimburse: true,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+    token_admin_client.mint(&participant, &100);
+
+    assert_eq!(
+        client.quote_deposit(&split_id, &participant, &30).released,
+        30
+    );
+    client.deposit(&split_id, &participant, &30);
+    assert_eq!(token_client.balance(&creator), 30);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Active);
+    assert_eq!(split.amount_released, 30);
+
+    client.deposit(&split_id, &participant, &70);
+    assert_eq!(token_client.balance(&creator), 100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    client.check_invariants(&split_id);
+}
+
+#[test]
+fn test_settled_offchain_share_is_never_escrowed() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+
+    // Bob paid the creator in cash
+    assert_eq!(client.mark_settled_offchain(&split_id, &bob), 40);
+    let topic: Symbol = env
+        .events()
+        .all()
+        .last()
+        .unwrap()
+        .1
+        .get(0)
+        .unwrap()
+        .try_into_val(&env)
+        .unwrap();
+    assert_eq!(topic, symbol_short!("offchain"));
+    assert_eq!(client.get_offchain_settled(&split_id, &bob), 40);
+    assert_eq!(
+        client
+            .get_participants(&split_id)
+            .get(1)
+            .unwrap()
+            .settled_offchain,
+        40
+    );
+    assert_eq!(
+        client.try_mark_settled_offchain(&split_id, &bob),
+        Err(Ok(Error::InvalidAmount))
+    );
+
+    // Only Alice's on-chain deposit is released
+    token_admin_client.mint(&alice, &60);
+    client.deposit(&split_id, &alice, &60);
+    assert_eq!(token_client.balance(&creator), 60);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(split.amount_released, 100);
+    client.check_invariants(&split_id);
+}
+
+#[test]
+fn test_settled_offchain_closes_split_without_escrow() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let options = SplitOptions {
+        confirm_offchain: true,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+
+    client.mark_settled_offchain(&split_id, &participant);
+    assert_eq!(
+        env.auths()
+            .iter()
+            .filter(|(address, _)| *address == participant)
+            .count(),
+        1
+    );
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 0);
+    client.check_invariants(&split_id);
+
+    // Settling the last share of a pass-through split completes it once
+    let alice = Address::generate(&env);
+    let options = SplitOptions {
+        mode: SplitMode::PassThrough,
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Cabin"),
+        &100,
+        &Vec::from_array(&env, [alice.clone(), participant.clone()]),
+        &Vec::from_array(&env, [60i128, 40]),
+        &options,
+    );
+    token_admin_client.mint(&alice, &60);
+    client.deposit(&split_id, &alice, &60);
+    let completed = || {
+        env.events()
+            .all()
+            .iter()
+            .filter(|event| {
+                let topic: Symbol = event.1.get(0).unwrap().try_into_val(&env).unwrap();
+                topic == symbol_short!("completed")
+            })
+            .count()
+    };
+    let before = completed();
+    client.mark_settled_offchain(&split_id, &participant);
+    assert_eq!(completed() - before, 1);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 60);
+    client.check_invariants(&split_id);
+}
+
+// ============================================
+// Description Privacy Tests
+// ============================================
+
+#[test]
+fn test_private_description_stores_only_hash() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let plaintext = "Rent for 12 Oak St";
+    let hash: BytesN<32> = env
+        .crypto()
+        .sha256(&Bytes::from_slice(&env, plaintext.as_bytes()))
+        .into();
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant.clone());
+    let mut shares = Vec::new(&env);
+    shares.push_back(100i128);
+
+    let options = SplitOptions {
+        description_hash: MaybeHash::Some(hash.clone()),
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, ""),
+        &100,
+        &addresses,
+        &shares,
+        &options,
+    );
+
+    let split = client.get_split(&split_id);
+    assert!(split.description.is_empty());
+    assert_eq!(split.description_hash, MaybeHash::Some(hash));
+
+    assert!(client.verify_description(&split_id, &String::from_str(&env, plaintext)));
+    assert!(!client.verify_description(&split_id, &String::from_str(&env, "Rent for 13 Oak St")));
+}
+
+#[test]
+fn test_contact_hash_proves_who_was_invited() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let preimage = Bytes::from_slice(&env, b"salt-123:alice@example.com");
+    let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+    let hash = Bytes::from(hash);
+
+    let options = SplitOptions {
+        contact_hashes: Some(Vec::from_array(&env, [hash.clone(), Bytes::new(&env)])),
+        ..Default::default()
+    };
+    let addresses = Vec::from_array(&env, [alice.clone(), bob.clone()]);
+    let shares = Vec::from_array(&env, [50i128, 50]);
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Dinner"),
+        &100,
+        &addresses,
+        &shares,
+        &options,
+    );
+
+    assert_eq!(client.get_contact_hash(&split_id, &alice), Some(hash));
+    assert_eq!(client.get_contact_hash(&split_id, &bob), None);
+    assert!(client.verify_contact(&split_id, &alice, &preimage));
+    assert!(!client.verify_contact(
+        &split_id,
+        &alice,
+        &Bytes::from_slice(&env, b"salt-123:mallory@example.com")
+    ));
+    assert_eq!(
+        client.try_verify_contact(&split_id, &bob, &preimage),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+
+    // One entry per participant, each a 32-byte hash
+    let short = SplitOptions {
+        contact_hashes: Some(Vec::from_array(&env, [Bytes::new(&env)])),
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Dinner"),
+            &100,
+            &addresses,
+            &shares,
+            &short,
+        ),
+        Err(Ok(Error::InvalidParticipants))
+    );
+}
+
+#[test]
+fn test_private_description_rejects_plaintext() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant);
+    let mut shares = Vec::new(&env);
+    shares.push_back(100i128);
+
+    let options = SplitOptions {
+        description_hash: MaybeHash::Some(BytesN::from_array(&env, &[7u8; 32])),
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Rent for 12 Oak St"),
+            &100,
+            &addresses,
+            &shares,
+            &options,
+        ),
+        Err(Ok(Error::PlaintextDescription))
+    );
+}
+
+#[test]
+fn test_restricted_split_is_only_readable_by_members() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let options = SplitOptions {
+        visibility: Visibility::Participants,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    assert_eq!(client.get_visibility(&split_id), Visibility::Participants);
+
+    assert_eq!(
+        client.try_get_split(&split_id).err(),
+        Some(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_get_participants(&split_id),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_get_split_as(&split_id, &stranger).err(),
+        Some(Ok(Error::AccessDenied))
+    );
+
+    // Members must authorize the read
+    let split = client.get_split_as(&split_id, &participant);
+    assert_eq!(split.id, split_id);
+    assert_eq!(env.auths()[0].0, participant);
+    assert_eq!(client.get_participants_as(&split_id, &creator).len(), 1);
+    assert_eq!(
+        client
+            .get_participant_status(&split_id, &participant)
+            .share_amount,
+        10
+    );
+
+    // Notes, labels and history are gated the same way
+    client.add_note(&split_id, &String::from_str(&env, "venue booked"));
+    assert_eq!(
+        client.try_get_notes(&split_id),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_get_notes_as(&split_id, &stranger),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(client.get_notes_as(&split_id, &participant).len(), 1);
+    assert_eq!(
+        client.try_get_participant_label(&split_id, &participant),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.get_participant_label_as(&split_id, &participant, &creator),
+        None
+    );
+    assert_eq!(
+        client.try_get_transfer_history(&split_id, &0),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client
+            .get_transfer_history_as(&split_id, &0, &participant)
+            .len(),
+        0
+    );
+
+    // Listings leave restricted splits out, except for their members
+    let public = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    let listed = client.get_splits_by_creator(&creator, &0);
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed.get(0).unwrap().id, public);
+    assert_eq!(
+        client
+            .get_splits_by_creator_as(&creator, &0, &participant)
+            .len(),
+        2
+    );
+    assert_eq!(
+        client
+            .get_splits_by_creator_as(&creator, &0, &stranger)
+            .len(),
+        1
+    );
+    assert_eq!(client.get_active_splits_for(&participant, &0).len(), 2);
+    assert_eq!(client.get_active_splits_for(&creator, &0).len(), 2);
+}
+
+#[test]
+fn test_find_split_returns_none_instead_of_trapping() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    assert!(client.find_split(&1).is_none());
+    assert!(!client.split_exists(&1));
+
+    let public = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(client.find_split(&public).unwrap().id, public);
+    assert!(client.split_exists(&public));
+
+    // A restricted split exists but isn't shown
+    let options = SplitOptions {
+        visibility: Visibility::Participants,
+        ..Default::default()
+    };
+    let hidden = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    assert!(client.find_split(&hidden).is_none());
+    assert!(client.split_exists(&hidden));
+}
+
+// ============================================
+// Batched Read Tests
+// ============================================
+
+#[test]
+fn test_get_splits_skips_missing_and_restricted_ids() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let first = create_split_with_shares(&env, &client, &creator, &[(&alice, 30), (&bob, 30)]);
+    let second = create_split_with_shares(&env, &client, &creator, &[(&alice, 10)]);
+    let options = SplitOptions {
+        visibility: Visibility::Participants,
+        ..Default::default()
+    };
+    let hidden = create_split_with_options_for(&env, &client, &creator, &alice, 10, &options);
+    client.deposit(&first, &alice, &30);
+
+    let ids = Vec::from_array(&env, [second, 99, hidden, first]);
+    let splits = client.get_splits(&ids);
+    assert_eq!(splits.len(), 2);
+    assert_eq!(splits.get(0).unwrap().id, second);
+    assert_eq!(splits.get(1).unwrap().id, first);
+
+    let summaries = client.get_summaries(&ids);
+    assert_eq!(summaries.len(), 2);
+    assert_eq!(
+        summaries.get(1).unwrap(),
+        SplitSummary {
+            id: first,
+            creator: creator.clone(),
+            status: SplitStatus::Active,
+            total_amount: 60,
+            amount_collected: 30,
+            participant_count: 2,
+            paid_count: 1,
+            deadline: None,
+            frozen: false,
+        }
+    );
+}
+
+#[test]
+fn test_active_splits_for_a_wallet_home_screen() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let own = create_split_with_shares(&env, &client, &alice, &[(&bob, 40)]);
+    let owing = create_split_with_shares(&env, &client, &creator, &[(&alice, 30), (&bob, 30)]);
+    let settled = create_split_with_shares(&env, &client, &creator, &[(&alice, 10)]);
+    client.deposit(&owing, &alice, &20);
+    client.deposit(&settled, &alice, &10);
+    assert_eq!(client.get_split(&settled).status, SplitStatus::Released);
+
+    let active = client.get_active_splits_for(&alice, &0);
+    assert_eq!(active.len(), 2);
+
+    let first = active.get(0).unwrap();
+    assert_eq!(first.summary.id, own);
+    assert!(first.is_creator);
+    assert_eq!(first.remaining, 0);
+
+    let second = active.get(1).unwrap();
+    assert_eq!(second.summary.id, owing);
+    assert!(!second.is_creator);
+    assert_eq!(second.remaining, 10);
+    assert_eq!(second.summary.amount_collected, 20);
+
+    assert_eq!(
+        client
+            .get_active_splits_for(&Address::generate(&env), &0)
+            .len(),
+        0
+    );
+
+    // A split Alice created and is listed in shows once, and a long list
+    // spills onto the next page
+    env.budget().reset_unlimited();
+    for _ in 0..20 {
+        create_split_with_shares(&env, &client, &alice, &[(&alice, 5), (&bob, 5)]);
+    }
+    let first = client.get_active_splits_for(&alice, &0);
+    assert_eq!(first.len(), 20);
+    assert_eq!(first.get(0).unwrap().summary.id, own);
+    let second = client.get_active_splits_for(&alice, &1);
+    assert_eq!(second.len(), 2);
+    assert_eq!(second.get(1).unwrap().summary.id, owing);
+    assert_eq!(client.get_active_splits_for(&alice, &2).len(), 0);
+}
+
+#[test]
+fn test_status_index_follows_transitions() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let paid = create_split_with_shares(&env, &client, &creator, &[(&alice, 20)]);
+    let started = create_split_with_shares(&env, &client, &creator, &[(&alice, 20)]);
+    let dropped = create_split_with_shares(&env, &client, &creator, &[(&alice, 20)]);
+    let ids = |status: SplitStatus| client.get_split_ids_by_status(&status, &0);
+    assert_eq!(
+        ids(SplitStatus::Pending),
+        Vec::from_array(&env, [paid, started, dropped])
+    );
+
+    client.deposit(&paid, &alice, &20);
+    client.deposit(&started, &alice, &5);
+    client.cancel_split(&dropped, &String::from_str(&env, "plans changed"), &None);
+
+    assert_eq!(ids(SplitStatus::Pending).len(), 0);
+    assert_eq!(ids(SplitStatus::Active), Vec::from_array(&env, [started]));
+    assert_eq!(ids(SplitStatus::Released), Vec::from_array(&env, [paid]));
+    assert_eq!(
+        ids(SplitStatus::Cancelled),
+        Vec::from_array(&env, [dropped])
+    );
+    assert_eq!(ids(SplitStatus::Completed).len(), 0);
+}
+
+#[test]
+fn test_splits_created_between_timestamps() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let day = 86_400;
+
+    let mut created = std::vec::Vec::new();
+    for (at, creator) in [
+        (day + 10, &alice),
+        (day + 500, &bob),
+        (3 * day, &alice),
+        (9 * day, &alice),
+    ] {
+        env.ledger().with_mut(|li| li.timestamp = at);
+        created.push(create_split_with_shares(
+            &env,
+            &client,
+            creator,
+            &[(&carol, 10)],
+        ));
+    }
+    let ids = |splits: Vec<Split>| -> std::vec::Vec<u64> { splits.iter().map(|s| s.id).collect() };
+
+    // The first day is trimmed to the exact timestamp
+    let all = client.get_splits_created_between(&None, &(day + 100), &(3 * day), &0);
+    assert_eq!(ids(all), [created[1], created[2]]);
+
+    let mine = client.get_splits_created_between(&Some(alice.clone()), &0, &(10 * day), &0);
+    assert_eq!(ids(mine), [created[0], created[2], created[3]]);
+
+    // A full day spills onto the next page; the test host charges the
+    // whole run against one budget, so lift it for the bulk creation
+    env.budget().reset_unlimited();
+    env.ledger().with_mut(|li| li.timestamp = 20 * day);
+    let mut busy = std::vec::Vec::new();
+    for _ in 0..21 {
+        busy.push(create_split_with_shares(
+            &env,
+            &client,
+            &bob,
+            &[(&carol, 10)],
+        ));
+    }
+    let first = client.get_splits_created_between(&None, &(20 * day), &(21 * day), &0);
+    assert_eq!(ids(first), busy[..20]);
+    let second = client.get_splits_created_between(&None, &(20 * day), &(21 * day), &1);
+    assert_eq!(ids(second), busy[20..]);
+
+    // Restricted splits don't take up room on a page
+    env.ledger().with_mut(|li| li.timestamp = 30 * day);
+    let options = SplitOptions {
+        visibility: Visibility::Participants,
+        ..Default::default()
+    };
+    for _ in 0..5 {
+        create_split_with_options_for(&env, &client, &bob, &carol, 10, &options);
+    }
+    let mut open = std::vec::Vec::new();
+    for _ in 0..20 {
+        open.push(create_split_with_shares(
+            &env,
+            &client,
+            &bob,
+            &[(&carol, 10)],
+        ));
+    }
+    let page = client.get_splits_created_between(&None, &(30 * day), &(30 * day), &0);
+    assert_eq!(ids(page), open);
+
+    assert_eq!(
+        client
+            .try_get_splits_created_between(&None, &(2 * day), &day, &0)
+            .err(),
+        Some(Ok(Error::InvalidDeadline))
+    );
+    assert_eq!(
+        client
+            .try_get_splits_created_between(&None, &0, &(400 * day), &0)
+            .err(),
+        Some(Ok(Error::InvalidDeadline))
+    );
+}
+
+// ============================================
+// Total Owed Tests
+// ============================================
+
+#[test]
+fn test_address_stats_track_lifetime_activity() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&bob, &150);
+    let dinner = create_split_with_shares(&env, &client, &alice, &[(&bob, 100)]);
+    let taxi = create_split_with_shares(&env, &client, &alice, &[(&bob, 50)]);
+
+    // Bob pays the dinner off over three days and the taxi after one
+    client.deposit(&dinner, &bob, &40);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.deposit(&taxi, &bob, &50);
+    env.ledger().with_mut(|li| li.timestamp += 2 * 86_400);
+    client.deposit(&dinner, &bob, &60);
+
+    assert_eq!(
+        client.get_address_stats(&bob),
+        AddressStats {
+            splits_joined: 2,
+            total_paid: 150,
+            shares_paid: 2,
+            days_to_pay_total: 4,
+            avg_days_to_pay: 2,
+            ..Default::default()
+        }
+    );
+    assert_eq!(
+        client.get_address_stats(&alice),
+        AddressStats {
+            splits_created: 2,
+            total_received: 150,
+            ..Default::default()
+        }
+    );
+}
+
+#[test]
+fn test_get_total_owed_across_open_splits() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    let dinner = create_split_with_shares(&env, &client, &creator, &[(&alice, 30), (&bob, 30)]);
+    create_split_with_shares(&env, &client, &creator, &[(&alice, 12)]);
+    let taxi = create_split_with_shares(&env, &client, &creator, &[(&alice, 8), (&bob, 8)]);
+
+    token_admin_client.mint(&alice, &10);
+    client.deposit(&dinner, &alice, &10);
+    client.cancel_split(&taxi, &String::from_str(&env, "plans changed"), &None);
+
+    assert_eq!(
+        client.get_total_owed(&alice, &0),
+        OwedSummary {
+            total: 32,
+            split_count: 2,
+        }
+    );
+    assert_eq!(client.get_total_owed(&bob, &0).total, 30);
+    assert_eq!(client.get_total_owed(&creator, &0).split_count, 0);
+
+    // The cancelled taxi left both indexes
+    let index = |address: &Address| {
+        env.as_contract(&client.address, || {
+            storage::get_participant_page(&env, address, 0)
+        })
+    };
+    assert_eq!(index(&alice).len(), 2);
+    assert_eq!(index(&bob), Vec::from_array(&env, [dinner]));
+
+    // Bob's open splits spill onto a second page, and the spending cap
+    // still counts both
+    env.budget().reset_unlimited();
+    for _ in 0..20 {
+        create_split_with_shares(&env, &client, &creator, &[(&bob, 1)]);
+    }
+    assert_eq!(client.get_total_owed(&bob, &0).total, 49);
+    assert_eq!(client.get_total_owed(&bob, &1).total, 1);
+    assert_eq!(client.get_total_owed(&bob, &2).split_count, 0);
+    client.set_spending_cap(&bob, &51);
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(bob.clone());
+    let amounts = Vec::from_array(&env, [2i128]);
+    let description = String::from_str(&env, "Snacks");
+    assert_eq!(
+        client.try_create_split(&creator, &description, &2, &addresses, &amounts),
+        Err(Ok(Error::SpendingCapExceeded))
+    );
+}
+
+#[test]
+fn test_spending_cap_blocks_new_obligations() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_spending_cap(&alice, &0),
+        Err(Ok(Error::InvalidAmount))
+    );
+    client.set_spending_cap(&alice, &50);
+    assert_eq!(client.get_spending_cap(&alice), Some(50));
+
+    let dinner = create_split_with_shares(&env, &client, &creator, &[(&alice, 30), (&bob, 30)]);
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(alice.clone());
+    let mut amounts = Vec::new(&env);
+    amounts.push_back(30i128);
+    let description = String::from_str(&env, "Concert");
+    assert_eq!(
+        client.try_create_split(&creator, &description, &30, &addresses, &amounts),
+        Err(Ok(Error::SpendingCapExceeded))
+    );
+
+    // Paying down what's owed makes room again
+    token_admin_client.mint(&alice, &10);
+    client.deposit(&dinner, &alice, &10);
+    client.create_split(&creator, &description, &30, &addresses, &amounts);
+    assert_eq!(client.get_total_owed(&alice, &0).total, 50);
+
+    // Listing yourself is never capped
+    client.set_spending_cap(&creator, &1);
+    create_split_with_shares(&env, &client, &creator, &[(&creator, 40)]);
+
+    client.clear_spending_cap(&alice);
+    assert_eq!(client.get_spending_cap(&alice), None);
+    create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+}
+
+// ============================================
+// Participant View Tests
+// ============================================
+
+#[test]
+fn test_get_participants_computes_progress() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let now = env.ledger().timestamp();
+
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(alice.clone());
+    addresses.push_back(bob.clone());
+    let mut shares = Vec::new(&env);
+    shares.push_back(300i128);
+    shares.push_back(100i128);
+    let options = SplitOptions {
+        deadline: Some(now + 50),
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Cabin"),
+        &400,
+        &addresses,
+        &shares,
+        &options,
+    );
+
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &100);
+    client.deposit(&split_id, &alice, &100);
+    client.deposit(&split_id, &bob, &100);
+
+    let views = client.get_participants(&split_id);
+    assert_eq!(
+        views.get(0).unwrap(),
+        ParticipantView {
+            address: alice,
+            label: None,
+            share: 300,
+            paid: 100,
+            remaining: 200,
+            pct_paid: 3_333,
+            overdue: false,
+            settled_offchain: 0,
+            forgiven: 0,
+        }
+    );
+    assert_eq!(views.get(1).unwrap().pct_paid, 10_000);
+
+    // Past the deadline only the unpaid share is overdue
+    env.ledger().with_mut(|li| li.timestamp = now + 51);
+    let views = client.get_participants(&split_id);
+    assert!(views.get(0).unwrap().overdue);
+    assert!(!views.get(1).unwrap().overdue);
+
+    assert_eq!(
+        client.try_get_participants(&99),
+        Err(Ok(Error::SplitNotFound))
+    );
+}
+
+#[test]
+fn test_creator_labels_participants() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 10)]);
+
+    let mom = String::from_str(&env, "Mom");
+    client.set_participant_label(&split_id, &alice, &mom, &None);
+    assert_eq!(
+        client.get_participant_label(&split_id, &alice),
+        Some(mom.clone())
+    );
+    assert_eq!(
+        client.get_participants(&split_id).get(0).unwrap().label,
+        Some(mom)
+    );
+
+    assert_eq!(
+        client.try_set_participant_label(
+            &split_id,
+            &stranger,
+            &String::from_str(&env, "Who"),
+            &None,
+        ),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+    assert_eq!(
+        client.try_set_participant_label(
+            &split_id,
+            &alice,
+            &String::from_str(&env, "A label that is far too long to show"),
+            &None,
+        ),
+        Err(Ok(Error::LabelTooLong))
+    );
+
+    // An empty label clears it
+    client.set_participant_label(&split_id, &alice, &String::from_str(&env, ""), &None);
+    assert_eq!(client.get_participant_label(&split_id, &alice), None);
+}
+
+#[test]
+fn test_creator_appends_notes() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 10)]);
+    assert_eq!(client.get_notes(&split_id).len(), 0);
+
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    let wine = String::from_str(&env, "added the second bottle of wine");
+    client.add_note(&split_id, &wine);
+    assert_eq!(env.auths()[0].0, creator);
+    env.ledger().with_mut(|l| l.timestamp = 2_000);
+    client.add_note(&split_id, &String::from_str(&env, "waiting on Dan"));
+
+    let notes = client.get_notes(&split_id);
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes.get(0).unwrap().text, wine);
+    assert_eq!(notes.get(0).unwrap().timestamp, 1_000);
+    assert_eq!(notes.get(1).unwrap().timestamp, 2_000);
+
+    assert_eq!(
+        client.try_add_note(&split_id, &String::from_str(&env, "")),
+        Err(Ok(Error::EmptyNote))
+    );
+    let long = [b'a'; MAX_NOTE_LEN as usize + 1];
+    assert_eq!(
+        client.try_add_note(&split_id, &String::from_bytes(&env, &long)),
+        Err(Ok(Error::NoteTooLong))
+    );
+
+    // The log is bounded
+    for _ in notes.len()..MAX_NOTES {
+        client.add_note(&split_id, &String::from_str(&env, "again"));
+    }
+    assert_eq!(
+        client.try_add_note(&split_id, &String::from_str(&env, "one more")),
+        Err(Ok(Error::TooManyNotes))
+    );
+    assert_eq!(client.get_notes(&split_id).len(), MAX_NOTES);
+}
+
+// ============================================
+// Escrow Total Tests
+// ============================================
+
+#[test]
+fn test_total_escrowed_tracks_deposits_and_payouts() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+    let options = SplitOptions {
+        mode: SplitMode::PassThrough,
+        ..Default::default()
+    };
+    let pass_through = create_split_with_options_for(&env, &client, &creator, &bob, 25, &options);
+
+    token_admin_client.mint(&alice, &60);
+    token_admin_client.mint(&bob, &65);
+
+    client.deposit(&split_id, &alice, &60);
+    client.deposit(&pass_through, &bob, &25);
+    assert_eq!(client.get_total_escrowed(&token_id), 60);
+    assert_eq!(token_client.balance(&client.address), 60);
+
+    client.release_partial(&split_id);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+
+    client.deposit(&split_id, &bob, &40);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+    assert_eq!(token_client.balance(&client.address), 0);
+}
+
+// ============================================
+// Category Tests
+// ============================================
+
+#[test]
+fn test_get_splits_by_category() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let food = SplitOptions {
+        category: SplitCategory::Food,
+        ..Default::default()
+    };
+    let dinner = create_split_with_options_for(&env, &client, &creator, &participant, 10, &food);
+    let lunch = create_split_with_options_for(&env, &client, &creator, &participant, 20, &food);
+    let other = create_split_with_shares(&env, &client, &creator, &[(&participant, 30)]);
+
+    let splits = client.get_splits_by_category(&creator, &SplitCategory::Food, &0);
+    assert_eq!(splits.len(), 2);
+    assert_eq!(splits.get(0).unwrap().id, dinner);
+    assert_eq!(splits.get(1).unwrap().id, lunch);
+
+    // Plain create_split files under Other
+    let splits = client.get_splits_by_category(&creator, &SplitCategory::Other, &0);
+    assert_eq!(splits.len(), 1);
+    assert_eq!(splits.get(0).unwrap().id, other);
+    assert_eq!(splits.get(0).unwrap().category, SplitCategory::Other);
+
+    // Past the last page is empty, and other creators are unaffected
+    assert_eq!(
+        client
+            .get_splits_by_category(&creator, &SplitCategory::Food, &1)
+            .len(),
+        0
+    );
+    assert_eq!(
+        client
+            .get_splits_by_category(&participant, &SplitCategory::Food, &0)
+            .len(),
+        0
+    );
+}
+
+// ============================================
+// Tag Tests
+// ============================================
+
+#[test]
+fn test_get_splits_by_tag() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let mut tags = Vec::new(&env);
+    tags.push_back(symbol_short!("lisbon"));
+    tags.push_back(symbol_short!("hotel"));
+    let hotel_options = SplitOptions {
+        tags: Some(tags),
+        ..Default::default()
+    };
+
+    let mut tags = Vec::new(&env);
+    tags.push_back(symbol_short!("lisbon"));
+    let dinner_options = SplitOptions {
+        tags: Some(tags),
+        ..Default::default()
+    };
+
+    let hotel =
+        create_split_with_options_for(&env, &client, &creator, &participant, 300, &hotel_options);
+    let dinner =
+        create_split_with_options_for(&env, &client, &creator, &participant, 40, &dinner_options);
+
+    let trip = client.get_splits_by_tag(&creator, &symbol_short!("lisbon"), &0);
+    assert_eq!(trip.len(), 2);
+    assert_eq!(trip.get(0).unwrap().id, hotel);
+    assert_eq!(trip.get(1).unwrap().id, dinner);
+
+    let hotels = client.get_splits_by_tag(&creator, &symbol_short!("hotel"), &0);
+    assert_eq!(hotels.len(), 1);
+    assert_eq!(hotels.get(0).unwrap().tags.len(), 2);
+}
+
+#[test]
+fn test_too_many_tags() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+
+    let mut tags = Vec::new(&env);
+    for tag in ["a", "b", "c", "d", "e", "f"] {
+        tags.push_back(Symbol::new(&env, tag));
+    }
+    let options = SplitOptions {
+        tags: Some(tags),
+        ..Default::default()
+    };
+    let mut addresses = Vec::new(&env);
+    addresses.push_back(participant);
+    let mut shares = Vec::new(&env);
+    shares.push_back(10i128);
+
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Tagged"),
+            &10,
+            &addresses,
+            &shares,
+            &options,
+        ),
+        Err(Ok(Error::TooManyTags))
+    );
+}
+
+// ============================================
+// Payment Reminder Tests
+// ============================================
+
+#[test]
+fn test_poke_emits_reminder_after_interval() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let paid = Address::generate(&env);
+    let unpaid = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&paid, 50), (&unpaid, 50)]);
+
+    token_admin_client.mint(&paid, &50);
+    client.deposit(&split_id, &paid, &50);
+
+    client.set_reminder_interval(&3_600);
+    assert_eq!(client.try_poke(&split_id), Err(Ok(Error::ReminderTooSoon)));
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    let reminded = client.poke(&split_id);
+    assert_eq!(reminded.len(), 1);
+    assert_eq!(reminded.get(0).unwrap(), unpaid);
+
+    // The interval restarts from the last reminder
+    assert_eq!(client.try_poke(&split_id), Err(Ok(Error::ReminderTooSoon)));
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    assert_eq!(client.poke(&split_id).len(), 1);
+}
+
+#[test]
+fn test_poke_rejects_cancelled_split() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 50)]);
+
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    assert_eq!(client.try_poke(&split_id), Err(Ok(Error::SplitCancelled)));
+}
+
+// ============================================
+// Expiry Tests
+// ============================================
+
+#[test]
+fn test_expiry_bounty_paid_from_creator_stake_for_eligible_splits() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    run_admin_action(&env, &client, &AdminAction::SetExpiryBounty(5));
+
+    let keeper = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&creator, &25);
+    token_admin_client.mint(&participant, &4);
+    let now = env.ledger().timestamp();
+    let options = SplitOptions {
+        deadline: Some(now + 100),
+        ..Default::default()
+    };
+    let first = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    let second = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    let own = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    let young = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    let empty = create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    // Each split with a deadline takes the bounty from its creator
+    assert_eq!(client.get_bounty_stake(&first), 5);
+    assert_eq!(token_client.balance(&creator), 0);
+    // A split that can't expire puts nothing up
+    let open_ended = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(client.get_bounty_stake(&open_ended), 0);
+    for split_id in [first, second, own, young] {
+        client.deposit(&split_id, &participant, &1);
+    }
+
+    // Nothing for a split that isn't eligible yet
+    assert_eq!(
+        client.try_expire_with_bounty(&first, &keeper),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+
+    // Past its deadline but too young to pay out, so the creator gets it back
+    env.ledger().with_mut(|li| li.timestamp = now + 101);
+    assert_eq!(client.expire_with_bounty(&young, &keeper), 0);
+    assert_eq!(client.get_split(&young).status, SplitStatus::Expired);
+    assert_eq!(token_client.balance(&creator), 5);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp = now + MIN_BOUNTY_AGE);
+    // Unfunded splits and a creator expiring their own split earn nothing
+    assert_eq!(client.expire_with_bounty(&empty, &keeper), 0);
+    assert_eq!(client.expire_with_bounty(&own, &creator), 0);
+    assert_eq!(token_client.balance(&creator), 15);
+    assert_eq!(client.expire_with_bounty(&first, &keeper), 5);
+    assert_eq!(
+        client.try_expire_with_bounty(&first, &keeper),
+        Err(Ok(Error::SplitExpired))
+    );
+    // Raising the bounty later doesn't pay out more than the split put up
+    run_admin_action(&env, &client, &AdminAction::SetExpiryBounty(50));
+    assert_eq!(client.expire_with_bounty(&second, &keeper), 5);
+    assert_eq!(token_client.balance(&keeper), 10);
+    assert_eq!(token_client.balance(&creator), 15);
+    // Only the participant's refundable deposits are left
+    assert_eq!(client.get_total_escrowed(&token_id), 4);
+}
+
+#[test]
+fn test_bounty_stake_goes_back_on_release_or_cancel() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    run_admin_action(&env, &client, &AdminAction::SetExpiryBounty(5));
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&creator, &10);
+    token_admin_client.mint(&alice, &10);
+    let options = SplitOptions {
+        deadline: Some(env.ledger().timestamp() + 100),
+        ..Default::default()
+    };
+
+    let released = create_split_with_options_for(&env, &client, &creator, &alice, 10, &options);
+    client.deposit(&released, &alice, &10);
+    assert_eq!(client.get_split(&released).status, SplitStatus::Released);
+    assert_eq!(client.get_bounty_stake(&released), 0);
+    assert_eq!(token_client.balance(&creator), 20);
+
+    let cancelled = create_split_with_options_for(&env, &client, &creator, &alice, 10, &options);
+    assert_eq!(
+        client.try_reclaim_bounty_stake(&cancelled),
+        Err(Ok(Error::NotRefundable))
+    );
+    client.cancel_split(&cancelled, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(client.reclaim_bounty_stake(&cancelled), 5);
+    assert_eq!(
+        client.try_reclaim_bounty_stake(&cancelled),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+    assert_eq!(token_client.balance(&creator), 20);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+#[test]
+fn test_expire_batch_reports_per_split_results() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let now = env.ledger().timestamp();
+
+    let short = SplitOptions {
+        deadline: Some(now + 100),
+        ..Default::default()
+    };
+    let long = SplitOptions {
+        deadline: Some(now + 10_000),
+        ..Default::default()
+    };
+    let stale = create_split_with_options_for(&env, &client, &creator, &participant, 10, &short);
+    let fresh = create_split_with_options_for(&env, &client, &creator, &participant, 10, &long);
+    let open_ended = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+
+    env.ledger().with_mut(|li| li.timestamp = now + 101);
+
+    let mut ids = Vec::new(&env);
+    ids.push_back(stale);
+    ids.push_back(fresh);
+    ids.push_back(open_ended);
+    ids.push_back(999);
+    let results = client.expire_batch(&ids);
+
+    assert_eq!(results.len(), 4);
+    assert!(results.get(0).unwrap().success);
+    assert_eq!(
+        results.get(1).unwrap().error,
+        Error::DeadlineNotReached as u32
+    );
+    assert_eq!(
+        results.get(2).unwrap().error,
+        Error::DeadlineNotReached as u32
+    );
+    assert_eq!(results.get(3).unwrap().error, Error::SplitNotFound as u32);
+
+    assert_eq!(client.get_split(&stale).status, SplitStatus::Expired);
+    assert_eq!(client.get_split(&fresh).status, SplitStatus::Pending);
+    assert_eq!(
+        client.try_expire_split(&stale),
+        Err(Ok(Error::SplitExpired))
+    );
+}
+
+#[test]
+fn test_ledger_sequence_deadline_expires_split() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &100);
+    let sequence = env.ledger().sequence();
+
+    let past = SplitOptions {
+        deadline_ledger: Some(sequence),
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Late"),
+            &10,
+            &Vec::from_array(&env, [participant.clone()]),
+            &Vec::from_array(&env, [10i128]),
+            &past,
+        ),
+        Err(Ok(Error::InvalidDeadline))
+    );
+
+    // A far-off timestamp doesn't keep the split open past its ledger deadline
+    let options = SplitOptions {
+        deadline: Some(env.ledger().timestamp() + 1_000_000),
+        deadline_ledger: Some(sequence + 50),
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+    assert_eq!(client.get_deadline_ledger(&split_id), Some(sequence + 50));
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = sequence + 50);
+    assert_eq!(
+        client.try_expire_split(&split_id),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number = sequence + 51);
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &10),
+        Err(Ok(Error::SplitExpired))
+    );
+    client.expire_split(&split_id);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Expired);
+}
+
+#[test]
+fn test_creator_can_only_push_deadlines_back() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &100);
+    let now = env.ledger().timestamp();
+    let sequence = env.ledger().sequence();
+
+    let options = SplitOptions {
+        deadline: Some(now + 100),
+        deadline_ledger: Some(sequence + 10),
+        max_extensions: Some(2),
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+
+    // Shortening, repeating or giving nothing are all rejected
+    assert_eq!(
+        client.try_extend_deadline(&split_id, &Some(now + 50), &None, &None),
+        Err(Ok(Error::InvalidDeadline))
+    );
+    assert_eq!(
+        client.try_extend_deadline(&split_id, &None, &Some(sequence + 10), &None),
+        Err(Ok(Error::InvalidDeadline))
+    );
+    assert_eq!(
+        client.try_extend_deadline(&split_id, &None, &None, &None),
+        Err(Ok(Error::InvalidDeadline))
+    );
+
+    client.extend_deadline(&split_id, &Some(now + 200), &None, &None);
+    assert_eq!(client.get_split(&split_id).deadline, Some(now + 200));
+    let event = env.events().all().last().unwrap();
+    let topic: Symbol = event.1.get(0).unwrap().try_into_val(&env).unwrap();
+    assert_eq!(topic, symbol_short!("extended"));
+    let (_, _, data): (u64, u64, Val) = event.2.try_into_val(&env).unwrap();
+    let data: (u64, Option<u64>, Option<u32>, u32) = data.try_into_val(&env).unwrap();
+    assert_eq!(data, (split_id, Some(now + 200), Some(sequence + 10), 1));
+
+    // The old timestamp has passed but the split is still open for deposits
+    env.ledger().with_mut(|li| li.timestamp = now + 150);
+    client.extend_deadline(&split_id, &None, &Some(sequence + 20), &None);
+    client.deposit(&split_id, &participant, &4);
+
+    assert_eq!(
+        client.try_extend_deadline(&split_id, &Some(now + 300), &None, &None),
+        Err(Ok(Error::ExtensionLimitReached))
+    );
+
+    // A split without a deadline has nothing to extend
+    let open_ended = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(
+        client.try_extend_deadline(&open_ended, &Some(now + 1_000), &None, &None),
+        Err(Ok(Error::InvalidDeadline))
+    );
+}
+
+#[test]
+fn test_cancel_inactive_clears_unfunded_splits() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &100);
+
+    let idle = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    let busy = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    client.deposit(&busy, &participant, &5);
+    assert_eq!(
+        client.try_cancel_inactive(&idle),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    // Setting the window takes the timelock delay, which is far past one hour
+    run_admin_action(&env, &client, &AdminAction::SetActivationWindow(3_600));
+    let fresh = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    assert_eq!(
+        client.try_cancel_inactive(&fresh),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+    assert_eq!(
+        client.try_cancel_inactive(&busy),
+        Err(Ok(Error::HasDeposits))
+    );
+
+    client.cancel_inactive(&idle);
+    assert_eq!(client.get_split(&idle).status, SplitStatus::Cancelled);
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    client.cancel_inactive(&fresh);
+    assert_eq!(
+        client.try_cancel_inactive(&fresh),
+        Err(Ok(Error::SplitCancelled))
+    );
+}
+
+#[test]
+fn test_deposit_after_deadline_fails() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let now = env.ledger().timestamp();
+
+    let options = SplitOptions {
+        deadline: Some(now + 100),
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 10, &options);
+
+    token_admin_client.mint(&participant, &10);
+    env.ledger().with_mut(|li| li.timestamp = now + 101);
+    assert_eq!(
+        client.try_deposit(&split_id, &participant, &10),
+        Err(Ok(Error::SplitExpired))
+    );
+}
+
+// ============================================
+// Completion Hook Tests
+// ============================================
+
+/// Hook that records the last completion it was told about
+mod hook_recorder {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env};
+
+    #[contract]
+    pub struct HookRecorder;
+
+    #[contractimpl]
+    impl HookRecorder {
+        pub fn on_split_completed(env: Env, split_id: u64, total: i128) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(split_id, total));
+        }
+
+        pub fn last(env: Env) -> Option<(u64, i128)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+/// Hook that always traps
+mod broken_hook {
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    #[contract]
+    pub struct BrokenHook;
+
+    #[contractimpl]
+    impl BrokenHook {
+        pub fn on_split_completed(_env: Env, _split_id: u64, _total: i128) {
+            panic!("hook exploded");
+        }
+    }
+}
+
+#[test]
+fn test_completion_hook_called_on_release() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let hook_id = env.register_contract(None, hook_recorder::HookRecorder);
+    let hook = hook_recorder::HookRecorderClient::new(&env, &hook_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let options = SplitOptions {
+        completion_hook: Some(hook_id.clone()),
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+
+    token_admin_client.mint(&participant, &100);
+    client.deposit(&split_id, &participant, &60);
+    assert_eq!(hook.last(), None);
+
+    client.deposit(&split_id, &participant, &40);
+    assert_eq!(hook.last(), Some((split_id, 100)));
+}
+
+#[test]
+fn test_broken_completion_hook_does_not_block_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let hook_id = env.register_contract(None, broken_hook::BrokenHook);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let options = SplitOptions {
+        completion_hook: Some(hook_id),
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+
+    token_admin_client.mint(&participant, &100);
+    client.deposit(&split_id, &participant, &100);
+
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 100);
+}
+
+// ============================================
+// Scheduled Pull Tests
+// ============================================
+
+#[test]
+fn test_process_due_pulls_installments_from_allowances() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100), (&bob, 50)]);
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &50);
+    token_client.approve(&alice, &client.address, &100, &1000);
+
+    // Bob consents to the schedule but never approves the allowance
+    let day = 86_400;
+    let start = env.ledger().timestamp() + day;
+    client.set_pull_schedule(&split_id, &alice, &40, &day, &start);
+    client.set_pull_schedule(&split_id, &bob, &50, &day, &start);
+    assert_eq!(client.process_due(&split_id).len(), 0);
+
+    env.ledger().with_mut(|li| li.timestamp = start);
+    let results = client.process_due(&split_id);
+    assert_eq!(
+        results,
+        Vec::from_array(
+            &env,
+            [
+                PullResult {
+                    participant: alice.clone(),
+                    amount: 40,
+                    error: 0,
+                },
+                PullResult {
+                    participant: bob.clone(),
+                    amount: 0,
+                    error: Error::NoFundsAvailable as u32,
+                },
+            ]
+        )
+    );
+    // Not due again until the next interval
+    assert_eq!(client.process_due(&split_id).len(), 1);
+
+    env.ledger().with_mut(|li| li.timestamp += 2 * day);
+    client.process_due(&split_id);
+    client.process_due(&split_id);
+    assert_eq!(
+        client
+            .get_split(&split_id)
+            .participants
+            .get(0)
+            .unwrap()
+            .amount_paid,
+        100
+    );
+    assert_eq!(client.get_pull_schedule(&split_id, &alice), None);
+    assert_eq!(token_client.balance(&alice), 0);
+
+    // Bob pays by hand instead, which closes his schedule on the next run
+    client.deposit(&split_id, &bob, &50);
+    assert_eq!(client.process_due(&split_id).len(), 0);
+    assert_eq!(client.get_pull_schedule(&split_id, &bob), None);
+}
+
+// ============================================
+// Share Token Tests
+// ============================================
+
+/// Share token companion that moves whole positions on transfer
+mod share_ledger {
+    use crate::SplitEscrowContractClient;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct ShareLedger;
+
+    #[contractimpl]
+    impl ShareLedger {
+        pub fn init(env: Env, escrow: Address) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("escrow"), &escrow);
+        }
+
+        pub fn mint(env: Env, split_id: u64, to: Address, amount: i128) {
+            env.storage().instance().set(&(split_id, to), &amount);
+        }
+
+        pub fn balance(env: Env, split_id: u64, holder: Address) -> i128 {
+            env.storage()
+                .instance()
+                .get(&(split_id, holder))
+                .unwrap_or(0)
+        }
+
+        pub fn transfer(env: Env, split_id: u64, from: Address, to: Address) {
+            from.require_auth();
+            to.require_auth();
+            let amount = Self::balance(env.clone(), split_id, from.clone());
+            env.storage().instance().remove(&(split_id, from.clone()));
+            env.storage()
+                .instance()
+                .set(&(split_id, to.clone()), &amount);
+
+            let escrow: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("escrow"))
+                .unwrap();
+            SplitEscrowContractClient::new(&env, &escrow).transfer_share(&split_id, &from, &to);
+        }
+    }
+}
+
+#[test]
+fn test_share_token_transfer_hands_over_the_position() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let ledger_id = env.register_contract(None, share_ledger::ShareLedger);
+    let ledger = share_ledger::ShareLedgerClient::new(&env, &ledger_id);
+    ledger.init(&client.address);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let options = SplitOptions {
+        tokenized: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Options split"),
+            &60,
+            &Vec::from_array(&env, [alice.clone()]),
+            &Vec::from_array(&env, [60i128]),
+            &options,
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetShareToken(ledger_id.clone()),
+    );
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 60, &options);
+    assert!(client.is_tokenized(&split_id));
+    assert_eq!(ledger.balance(&split_id, &alice), 60);
+
+    token_admin_client.mint(&alice, &20);
+    token_admin_client.mint(&bob, &40);
+    client.deposit(&split_id, &alice, &20);
+    let schedule = PullSchedule {
+        amount: 10,
+        interval: 3600,
+        next_due: 3600,
+    };
+    env.as_contract(&client.address, || {
+        storage::set_pull_schedule(&env, split_id, &alice, &schedule);
+        storage::set_lapsed(&env, split_id, &alice, true);
+    });
+
+    // Bob takes over Alice's share, along with what she already paid, and
+    // has to agree to it
+    ledger.transfer(&split_id, &alice, &bob);
+    assert!(env.auths().iter().any(|(address, _)| *address == bob));
+    env.as_contract(&client.address, || {
+        assert_eq!(storage::get_pull_schedule(&env, split_id, &alice), None);
+        assert_eq!(
+            storage::get_pull_schedule(&env, split_id, &bob),
+            Some(schedule.clone())
+        );
+        assert!(!storage::is_lapsed(&env, split_id, &alice));
+        assert!(storage::is_lapsed(&env, split_id, &bob));
+        storage::set_lapsed(&env, split_id, &bob, false);
+    });
+    let taken_over = client.get_split(&split_id).participants.get(0).unwrap();
+    assert_eq!(taken_over.address, bob);
+    assert_eq!(taken_over.amount_paid, 20);
+    assert_eq!(client.get_total_owed(&alice, &0).total, 0);
+    assert_eq!(client.get_total_owed(&bob, &0).total, 40);
+    assert_eq!(
+        client.try_deposit(&split_id, &alice, &1),
+        Err(Ok(Error::ParticipantNotFound))
+    );
+
+    client.deposit(&split_id, &bob, &40);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 60);
+
+    // A position on a closed split doesn't show up as open for its new holder
+    let carol = Address::generate(&env);
+    let dropped = create_split_with_options_for(&env, &client, &creator, &alice, 30, &options);
+    client.cancel_split(&dropped, &String::from_str(&env, "called off"), &None);
+    ledger.transfer(&dropped, &alice, &carol);
+    env.as_contract(&client.address, || {
+        assert_eq!(storage::get_participant_page(&env, &carol, 0).len(), 0);
+    });
+}
+
+// ============================================
+// Timelock Tests
+// ============================================
+
+/// Helper to schedule an admin action, wait out the delay and execute it
+fn run_admin_action(env: &Env, client: &SplitEscrowContractClient, action: &AdminAction) {
+    let action_id = client.schedule_action(action);
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    client.execute_action(&action_id);
+}
+
+#[test]
+fn test_roles_split_the_admin_key() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let pauser = Address::generate(&env);
+    let fee_manager = Address::generate(&env);
+    client.grant_role(&Role::Pauser, &pauser);
+    client.grant_role(&Role::FeeManager, &fee_manager);
+    assert!(client.has_role(&Role::Pauser, &pauser));
+    assert!(!client.has_role(&Role::FeeManager, &pauser));
+    assert!(client.has_role(&Role::FeeManager, &admin));
+
+    // The pause key pauses, and can't touch fees
+    client.set_paused(&pauser, &true);
+    assert!(client.get_pause_flags().create);
+    assert_eq!(
+        client.try_set_paused(&fee_manager, &false),
+        Err(Ok(Error::AccessDenied))
+    );
+
+    // Only the admin takes a brake off again
+    assert_eq!(
+        client.try_set_paused(&pauser, &false),
+        Err(Ok(Error::AccessDenied))
+    );
+    client.set_paused(&admin, &false);
+    let release_only = PauseFlags {
+        release: true,
+        ..Default::default()
+    };
+    client.set_pause_flags(&pauser, &release_only);
+    client.set_pause_flags(
+        &pauser,
+        &PauseFlags {
+            deposit: true,
+            ..release_only.clone()
+        },
+    );
+    assert_eq!(
+        client.try_set_pause_flags(&pauser, &release_only),
+        Err(Ok(Error::AccessDenied))
+    );
+    client.set_pause_flags(&admin, &PauseFlags::default());
+
+    let split_id = create_split_with_shares(
+        &env,
+        &client,
+        &Address::generate(&env),
+        &[(&Address::generate(&env), 100)],
+    );
+    client.freeze_split(&pauser, &split_id);
+    assert_eq!(
+        client.try_unfreeze_split(&pauser, &split_id),
+        Err(Ok(Error::AccessDenied))
+    );
+    client.unfreeze_split(&admin, &split_id);
+
+    let schedule = AdminAction::SetFeeSchedule(FeeSchedule {
+        recipient: Address::generate(&env),
+        tiers: Vec::from_array(
+            &env,
+            [FeeTier {
+                min_total: 0,
+                bps: 100,
+            }],
+        ),
+    });
+    assert_eq!(
+        client.try_schedule_action_as(&pauser, &schedule),
+        Err(Ok(Error::AccessDenied))
+    );
+
+    // The fee key queues fee changes behind the same timelock, and nothing else
+    let action_id = client.schedule_action_as(&fee_manager, &schedule);
+    assert_eq!(
+        client.try_schedule_action_as(&fee_manager, &AdminAction::SetTimelockDelay(0)),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_execute_action_as(&fee_manager, &action_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    client.execute_action_as(&fee_manager, &action_id);
+    assert_eq!(client.get_fee_bps(&1_000), 100);
+
+    let removal = client.schedule_action_as(&fee_manager, &AdminAction::RemoveFeeSchedule);
+    client.cancel_action_as(&fee_manager, &removal);
+    assert_eq!(client.get_scheduled_action(&removal), None);
+
+    client.revoke_role(&Role::Pauser, &pauser);
+    assert_eq!(
+        client.try_set_paused(&pauser, &true),
+        Err(Ok(Error::AccessDenied))
+    );
+}
+
+#[test]
+fn test_timelocked_action_waits_for_delay() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let config = FeeConfig {
+        module: Address::generate(&env),
+        recipient: Address::generate(&env),
+    };
+    let action_id = client.schedule_action(&AdminAction::SetFeeModule(config.clone()));
+
+    let delay = client.get_timelock_delay();
+    assert_eq!(
+        client.get_scheduled_action(&action_id).unwrap().ready_at,
+        env.ledger().timestamp() + delay
+    );
+    assert_eq!(
+        client.try_execute_action(&action_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+    assert_eq!(client.get_fee_module(), None);
+
+    env.ledger().with_mut(|li| li.timestamp += delay);
+    client.execute_action(&action_id);
+    assert_eq!(client.get_fee_module(), Some(config));
+
+    // Executed actions leave the queue
+    assert_eq!(client.get_scheduled_action(&action_id), None);
+    assert_eq!(
+        client.try_execute_action(&action_id),
+        Err(Ok(Error::ActionNotFound))
+    );
+}
+
+#[test]
+fn test_cancelled_action_cannot_execute() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let action_id = client.schedule_action(&AdminAction::SetTimelockDelay(MIN_TIMELOCK_DELAY));
+    client.cancel_action(&action_id);
+
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_action(&action_id),
+        Err(Ok(Error::ActionNotFound))
+    );
+
+    // The delay can shrink, but never below the floor
+    let too_short = client.schedule_action(&AdminAction::SetTimelockDelay(60));
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_action(&too_short),
+        Err(Ok(Error::InvalidConfig))
+    );
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetTimelockDelay(MIN_TIMELOCK_DELAY),
+    );
+    assert_eq!(client.get_timelock_delay(), MIN_TIMELOCK_DELAY);
+}
+
+// ============================================
+// Fee Module Tests
+// ============================================
+
+/// Fee module charging a flat 1%
+mod one_percent_fee {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct OnePercentFee;
+
+    #[contractimpl]
+    impl OnePercentFee {
+        pub fn compute_fee(_env: Env, _split_id: u64, _token: Address, amount: i128) -> i128 {
+            amount / 100
+        }
+    }
+}
+
+/// Fee module that asks for more than the release amount
+mod greedy_fee {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct GreedyFee;
+
+    #[contractimpl]
+    impl GreedyFee {
+        pub fn compute_fee(_env: Env, _split_id: u64, _token: Address, amount: i128) -> i128 {
+            amount + 1
+        }
+    }
+}
+
+#[test]
+fn test_fee_module_applied_at_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let module = env.register_contract(None, one_percent_fee::OnePercentFee);
+    let treasury = Address::generate(&env);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeModule(FeeConfig {
+            module,
+            recipient: treasury.clone(),
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+
+    token_admin_client.mint(&participant, &1_000);
+    client.deposit(&split_id, &participant, &1_000);
+
+    assert_eq!(token_client.balance(&creator), 990);
+    assert_eq!(token_client.balance(&treasury), 10);
+
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(split.amount_released, 1_000);
+    assert_eq!(split.fees_paid, 10);
+}
+
+#[test]
+fn test_fee_schedule_charges_larger_splits_less() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let treasury = Address::generate(&env);
+    let tier = |min_total, bps| FeeTier { min_total, bps };
+    let schedule = |tiers: &[FeeTier]| FeeSchedule {
+        recipient: treasury.clone(),
+        tiers: Vec::from_slice(&env, tiers),
+    };
+    let set = |s: &FeeSchedule| {
+        let id = client.schedule_action(&AdminAction::SetFeeSchedule(s.clone()));
+        env.ledger()
+            .with_mut(|li| li.timestamp += client.get_timelock_delay());
+        client.try_execute_action(&id)
+    };
+
+    // Tiers must start at zero and rise
+    assert_eq!(
+        set(&schedule(&[tier(100, 50)])),
+        Err(Ok(Error::InvalidConfig))
+    );
+    assert_eq!(
+        set(&schedule(&[tier(0, 100), tier(0, 50)])),
+        Err(Ok(Error::InvalidConfig))
+    );
+    let tiers = schedule(&[tier(0, 100), tier(1_000, 50), tier(10_000, 25)]);
+    set(&tiers).unwrap().unwrap();
+    assert_eq!(client.get_fee_schedule(), Some(tiers));
+    assert_eq!(client.get_fee_bps(&999), 100);
+    assert_eq!(client.get_fee_bps(&1_000), 50);
+    assert_eq!(client.get_fee_bps(&50_000), 25);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &10_800);
+    let small = create_split_with_shares(&env, &client, &creator, &[(&participant, 800)]);
+    let large = create_split_with_shares(&env, &client, &creator, &[(&participant, 10_000)]);
+    client.deposit(&small, &participant, &800);
+    client.deposit(&large, &participant, &10_000);
+
+    assert_eq!(client.get_split(&small).fees_paid, 8);
+    assert_eq!(client.get_split(&large).fees_paid, 25);
+    assert_eq!(token_client.balance(&treasury), 33);
+    assert_eq!(token_client.balance(&creator), 10_767);
+}
+
+#[test]
+fn test_abandoned_split_refunds_everyone() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let day = 86_400;
+    let too_short = client.schedule_action(&AdminAction::SetAbandonTimeout(day));
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_action(&too_short),
+        Err(Ok(Error::InvalidConfig))
+    );
+    run_admin_action(&env, &client, &AdminAction::SetAbandonTimeout(60 * day));
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+    token_admin_client.mint(&alice, &60);
+    token_admin_client.mint(&bob, &40);
+
+    // Releases are paused, so the funded split sits at Completed
+    client.set_pause_flags(
+        &admin,
+        &PauseFlags {
+            release: true,
+            ..Default::default()
+        },
+    );
+    client.deposit(&split_id, &alice, &60);
+    client.deposit(&split_id, &bob, &40);
+    client.set_pause_flags(&admin, &PauseFlags::default());
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+
+    env.ledger().with_mut(|li| li.timestamp += 59 * day);
+    assert_eq!(
+        client.try_refund_abandoned(&split_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += day);
+    assert_eq!(client.refund_abandoned(&split_id), 100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Cancelled);
+    assert_eq!(token_client.balance(&alice), 0);
+
+    // Payouts go through the refund cursor, not the cancelling call
+    assert_eq!(client.process_refunds(&split_id, &1), 1);
+    assert_eq!(token_client.balance(&alice), 60);
+    assert_eq!(client.claim_refund(&split_id, &bob), 40);
+    assert_eq!(client.process_refunds(&split_id, &1), 0);
+    assert_eq!(token_client.balance(&bob), 40);
+    assert_eq!(token_client.balance(&creator), 0);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+
+    assert_eq!(
+        client.try_refund_abandoned(&split_id),
+        Err(Ok(Error::NotRefundable))
+    );
+}
+
+#[test]
+fn test_settled_iou_split_cannot_be_abandoned() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let day = 86_400;
+    run_admin_action(&env, &client, &AdminAction::SetAbandonTimeout(60 * day));
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let options = SplitOptions {
+        mode: SplitMode::Iou,
+        ..Default::default()
+    };
+    let split_id =
+        create_split_with_options_for(&env, &client, &creator, &participant, 100, &options);
+    client.mark_paid(&split_id, &participant, &100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+
+    env.ledger().with_mut(|li| li.timestamp += 61 * day);
+    assert_eq!(
+        client.try_refund_abandoned(&split_id),
+        Err(Ok(Error::UnsupportedMode))
+    );
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+}
+
+#[test]
+fn test_parked_split_pays_late_penalty_to_participants() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let day = 86_400;
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetLatePenalty(LatePenalty {
+            grace: day,
+            bps_per_day: 100,
+            max_bps: 500,
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let bob_wallet = Address::generate(&env);
+    let split_id =
+        create_split_with_shares(&env, &client, &creator, &[(&alice, 6_000), (&bob, 4_000)]);
+    token_admin_client.mint(&alice, &6_000);
+    token_admin_client.mint(&bob, &4_000);
+    client.set_refund_address(&bob, &bob_wallet);
+
+    // Releases are paused, so the funded split sits at Completed
+    client.set_pause_flags(
+        &admin,
+        &PauseFlags {
+            release: true,
+            ..Default::default()
+        },
+    );
+    client.deposit(&split_id, &alice, &6_000);
+    client.deposit(&split_id, &bob, &4_000);
+    client.set_pause_flags(&admin, &PauseFlags::default());
+    assert_eq!(client.quote_release(&split_id).net, 10_000);
+
+    // Two days and a bit past the grace period counts as three days
+    env.ledger()
+        .with_mut(|li| li.timestamp += day + 2 * day + 1);
+    assert_eq!(client.quote_release(&split_id).net, 9_700);
+    client.release_funds(&split_id);
+
+    assert_eq!(token_client.balance(&creator), 9_700);
+    assert_eq!(token_client.balance(&alice), 180);
+    // Bob's cut follows his refund address
+    assert_eq!(token_client.balance(&bob), 0);
+    assert_eq!(token_client.balance(&bob_wallet), 120);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+#[test]
+fn test_fee_exempt_creators_release_for_free() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let module = env.register_contract(None, one_percent_fee::OnePercentFee);
+    let treasury = Address::generate(&env);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeModule(FeeConfig {
+            module,
+            recipient: treasury.clone(),
+        }),
+    );
+    let partner = Address::generate(&env);
+    let exemption = FeeExemption::Creator(partner.clone());
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::AddFeeExemption(exemption.clone()),
+    );
+    assert!(client.is_fee_exempt(&exemption));
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &2_000);
+    let free = create_split_with_shares(&env, &client, &partner, &[(&participant, 1_000)]);
+    let charged = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+    client.deposit(&free, &participant, &1_000);
+    client.deposit(&charged, &participant, &1_000);
+
+    assert_eq!(token_client.balance(&partner), 1_000);
+    assert_eq!(token_client.balance(&creator), 990);
+    assert_eq!(token_client.balance(&treasury), 10);
+
+    // Exempting the escrow token makes every release free
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::AddFeeExemption(FeeExemption::Token(token_id.clone())),
+    );
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::RemoveFeeExemption(exemption.clone()),
+    );
+    assert!(!client.is_fee_exempt(&exemption));
+    token_admin_client.mint(&participant, &1_000);
+    let promo = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+    client.deposit(&promo, &participant, &1_000);
+    assert_eq!(client.get_split(&promo).fees_paid, 0);
+}
+
+#[test]
+fn test_fee_overrides_beat_the_module_before_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    // With no module or schedule there's nobody to pay a fee to
+    let promoted = Address::generate(&env);
+    let early = client.schedule_action(&AdminAction::SetFeeOverride(FeeOverride {
+        target: FeeTarget::Creator(promoted.clone()),
+        bps: 50,
+    }));
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_action(&early),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    let module = env.register_contract(None, one_percent_fee::OnePercentFee);
+    let treasury = Address::generate(&env);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeModule(FeeConfig {
+            module,
+            recipient: treasury.clone(),
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    token_admin_client.mint(&participant, &3_000);
+    let promo = create_split_with_shares(&env, &client, &promoted, &[(&participant, 1_000)]);
+    let goodwill = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+    let regular = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+
+    // A free promotion for one creator, a cheaper rate for one split
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeOverride(FeeOverride {
+            target: FeeTarget::Creator(promoted.clone()),
+            bps: 0,
+        }),
+    );
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeOverride(FeeOverride {
+            target: FeeTarget::Split(goodwill),
+            bps: 50,
+        }),
+    );
+    assert_eq!(
+        client.get_fee_override(&FeeTarget::Split(goodwill)),
+        Some(50)
+    );
+
+    client.set_pause_flags(
+        &admin,
+        &PauseFlags {
+            release: true,
+            ..Default::default()
+        },
+    );
+    for split_id in [promo, goodwill, regular] {
+        client.deposit(&split_id, &participant, &1_000);
+    }
+    client.set_pause_flags(&admin, &PauseFlags::default());
+    assert_eq!(client.quote_release(&promo).fee, 0);
+    assert_eq!(client.quote_release(&goodwill).fee, 5);
+    assert_eq!(client.quote_release(&regular).fee, 10);
+    for split_id in [promo, goodwill, regular] {
+        client.release_funds(&split_id);
+    }
+    assert_eq!(token_client.balance(&promoted), 1_000);
+    assert_eq!(token_client.balance(&creator), 995 + 990);
+    assert_eq!(token_client.balance(&treasury), 15);
+
+    // A released split's fee can't be changed any more
+    let late = client.schedule_action(&AdminAction::SetFeeOverride(FeeOverride {
+        target: FeeTarget::Split(regular),
+        bps: 0,
+    }));
+    env.ledger()
+        .with_mut(|li| li.timestamp += client.get_timelock_delay());
+    assert_eq!(
+        client.try_execute_action(&late),
+        Err(Ok(Error::SplitReleased))
+    );
+}
+
+#[test]
+fn test_invalid_fee_blocks_release_until_module_removed() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let module = env.register_contract(None, greedy_fee::GreedyFee);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeModule(FeeConfig {
+            module,
+            recipient: Address::generate(&env),
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 100)]);
+
+    token_admin_client.mint(&participant, &100);
+    client.deposit(&split_id, &participant, &100);
+
+    // The deposit lands but the automatic release is refused, leaving the
+    // split at Completed
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::InvalidFee))
+    );
+
+    run_admin_action(&env, &client, &AdminAction::RemoveFeeModule);
+    assert_eq!(client.get_fee_module(), None);
+    client.release_funds(&split_id);
+    assert_eq!(token_client.balance(&creator), 100);
+}
+
+#[test]
+fn test_quotes_match_actual_deposit_and_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let module = env.register_contract(None, one_percent_fee::OnePercentFee);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeModule(FeeConfig {
+            module,
+            recipient: Address::generate(&env),
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 1_000)]);
+    token_admin_client.mint(&participant, &1_000);
+
+    // A partial deposit only moves the split to Active
+    let quote = client.quote_deposit(&split_id, &participant, &400);
+    assert_eq!(
+        quote,
+        DepositQuote {
+            accepted: 400,
+            round_up: 0,
+            remaining_share: 600,
+            status: SplitStatus::Active,
+            released: 0,
+            fee: 0,
+        }
+    );
+    assert_eq!(
+        client.try_quote_release(&split_id),
+        Err(Ok(Error::SplitNotFunded))
+    );
+    client.deposit(&split_id, &participant, &400);
+    assert_eq!(client.get_split(&split_id).status, quote.status);
+
+    // The completing deposit auto-releases, net of the 1% fee
+    let quote = client.quote_deposit(&split_id, &participant, &600);
+    assert_eq!(quote.status, SplitStatus::Released);
+    assert_eq!(quote.released, 1_000);
+    assert_eq!(quote.fee, 10);
+    assert_eq!(
+        client.try_quote_deposit(&split_id, &participant, &601),
+        Err(Ok(Error::ExceedsRemaining))
+    );
+
+    // Quoting changed nothing
+    assert_eq!(client.get_split(&split_id).amount_collected, 400);
+
+    client.deposit(&split_id, &participant, &600);
+    assert_eq!(token_client.balance(&creator), quote.released - quote.fee);
+}
+
+#[test]
+fn test_preflight_release_lists_the_transfers_made() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let module = env.register_contract(None, one_percent_fee::OnePercentFee);
+    let fee_recipient = Address::generate(&env);
+    run_admin_action(
+        &env,
+        &client,
+        &AdminAction::SetFeeModule(FeeConfig {
+            module,
+            recipient: fee_recipient.clone(),
+        }),
+    );
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &1_000);
+    let options = SplitOptions {
+        release_delay: 3_600,
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 1_000, &options);
+    assert_eq!(
+        client.try_preflight_release(&split_id),
+        Err(Ok(Error::SplitNotFunded))
+    );
+    client.deposit(&split_id, &alice, &1_000);
+
+    let preflight = client.preflight_release(&split_id);
+    assert_eq!(preflight.retained, 0);
+    assert_eq!(preflight.status, SplitStatus::Released);
+
+    client.release_funds(&split_id);
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    client.execute_release(&split_id);
+
+    let history = client.get_transfer_history(&split_id, &0);
+    assert_eq!(history.len(), 2);
+    for (planned, made) in preflight.transfers.iter().zip(history.iter()) {
+        assert_eq!(
+            (planned.kind, planned.to, planned.amount),
+            (made.kind, made.to, made.amount)
+        );
+    }
+    assert_eq!(preflight.transfers.get(0).unwrap().to, fee_recipient);
+    assert_eq!(preflight.transfers.get(1).unwrap().amount, 990);
+}
+
+// ============================================
+// Multi-Token Deposit Tests
+// ============================================
+
+/// Oracle pricing every token at two reference units
+mod double_oracle {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    #[contract]
+    pub struct DoubleOracle;
+
+    #[contractimpl]
+    impl DoubleOracle {
+        pub fn to_reference(_env: Env, _token: Address, amount: i128) -> i128 {
+            amount * 2
+        }
+    }
+}
+
+/// Helper to register a second token and approve it through the timelock
+fn accept_foreign_token(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+) -> (token::Client<'static>, token::StellarAssetClient<'static>) {
+    let foreign_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    let oracle = env.register_contract(None, double_oracle::DoubleOracle);
+
+    run_admin_action(env, client, &AdminAction::SetOracle(oracle));
+    run_admin_action(env, client, &AdminAction::AcceptToken(foreign_id.clone()));
+
+    (
+        token::Client::new(env, &foreign_id),
+        token::StellarAssetClient::new(env, &foreign_id),
+    )
+}
+
+#[test]
+fn test_foreign_deposit_is_credited_and_forwarded() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let (foreign, foreign_admin) = accept_foreign_token(&env, &client);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+
+    foreign_admin.mint(&alice, &30);
+    token_admin_client.mint(&bob, &40);
+
+    assert_eq!(
+        client.deposit_with_token(&split_id, &alice, &foreign.address, &30),
+        60
+    );
+    assert!(client.get_participant_status(&split_id, &alice).has_paid);
+    assert_eq!(client.get_total_escrowed(&foreign.address), 30);
+
+    // Bob's deposit completes the split; each token goes to the creator
+    client.deposit(&split_id, &bob, &40);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 40);
+    assert_eq!(foreign.balance(&creator), 30);
+    assert_eq!(client.get_total_escrowed(&foreign.address), 0);
+}
+
+#[test]
+fn test_foreign_deposit_rules() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let (foreign, foreign_admin) = accept_foreign_token(&env, &client);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 50)]);
+    foreign_admin.mint(&alice, &100);
+
+    // 26 foreign tokens are worth 52, more than the 50 owed
+    assert_eq!(
+        client.try_deposit_with_token(&split_id, &alice, &foreign.address, &26),
+        Err(Ok(Error::ExceedsRemaining))
+    );
+
+    let unknown = env
+        .register_stellar_asset_contract_v2(Address::generate(&env))
+        .address();
+    assert_eq!(
+        client.try_deposit_with_token(&split_id, &alice, &unknown, &5),
+        Err(Ok(Error::TokenNotAccepted))
+    );
+
+    // Refunds hand back the deposited token, not its reference value
+    client.deposit_with_token(&split_id, &alice, &foreign.address, &10);
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+    client.claim_refund(&split_id, &alice);
+    assert_eq!(foreign.balance(&alice), 100);
+    assert_eq!(client.get_split(&split_id).amount_collected, 0);
+}
+
+// ============================================
+// Payout Swap Tests
+// ============================================
+
+/// Router paying out 90% of the input from its own stock of `token_out`
+mod ninety_percent_router {
+    use soroban_sdk::{contract, contractimpl, token, Address, Env};
+
+    #[contract]
+    pub struct NinetyPercentRouter;
+
+    #[contractimpl]
+    impl NinetyPercentRouter {
+        pub fn swap(
+            env: Env,
+            from: Address,
+            token_in: Address,
+            token_out: Address,
+            amount_in: i128,
+            _min_out: i128,
+            to: Address,
+        ) -> i128 {
+            let router = env.current_contract_address();
+            token::Client::new(&env, &token_in).transfer_from(&router, &from, &router, &amount_in);
+
+            let amount_out = amount_in * 9 / 10;
+            token::Client::new(&env, &token_out).transfer(&router, &to, &amount_out);
+            amount_out
+        }
+    }
+}
+
+/// Router that pays out 90% like `NinetyPercentRouter` but claims it paid
+/// the whole input
+mod boasting_router {
+    use soroban_sdk::{contract, contractimpl, Address, Env};
+
+    use super::ninety_percent_router::NinetyPercentRouter;
+
+    #[contract]
+    pub struct BoastingRouter;
+
+    #[contractimpl]
+    impl BoastingRouter {
+        pub fn swap(
+            env: Env,
+            from: Address,
+            token_in: Address,
+            token_out: Address,
+            amount_in: i128,
+            min_out: i128,
+            to: Address,
+        ) -> i128 {
+            NinetyPercentRouter::swap(env, from, token_in, token_out, amount_in, min_out, to);
+            amount_in
+        }
+    }
+}
+
+/// Helper to set up a EURC-like token and a router stocked with it
+fn setup_swap_router(env: &Env, client: &SplitEscrowContractClient) -> token::Client<'static> {
+    let router = env.register_contract(None, ninety_percent_router::NinetyPercentRouter);
+    stock_swap_router(env, client, router)
+}
+
+/// Helper to stock `router` with a EURC-like token and register it
+fn stock_swap_router(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    router: Address,
+) -> token::Client<'static> {
+    let eurc_id = env
+        .register_stellar_asset_contract_v2(Address::generate(env))
+        .address();
+    token::StellarAssetClient::new(env, &eurc_id).mint(&router, &1_000);
+    run_admin_action(env, client, &AdminAction::SetSwapRouter(router));
+    token::Client::new(env, &eurc_id)
+}
+
+#[test]
+fn test_payout_asset_swaps_at_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let eurc = setup_swap_router(&env, &client);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &landlord, &[(&tenant, 500)]);
+    client.set_payout_asset(&split_id, &eurc.address, &450);
+
+    // Full funding waits for an explicit release instead of auto-releasing
+    token_admin_client.mint(&tenant, &500);
+    client.deposit(&split_id, &tenant, &500);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+
+    client.release_funds(&split_id);
+    assert_eq!(eurc.balance(&landlord), 450);
+    assert_eq!(token_client.balance(&landlord), 0);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+}
+
+#[test]
+fn test_payout_swap_below_min_out_fails_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let eurc = setup_swap_router(&env, &client);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &landlord, &[(&tenant, 500)]);
+    client.set_payout_asset(&split_id, &eurc.address, &451);
+
+    token_admin_client.mint(&tenant, &500);
+    client.deposit(&split_id, &tenant, &500);
+
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::SlippageExceeded))
+    );
+    assert_eq!(token_client.balance(&client.address), 500);
+
+    // Falling back to the escrow token always works
+    client.clear_payout_asset(&split_id);
+    client.release_funds(&split_id);
+    assert_eq!(token_client.balance(&landlord), 500);
+}
+
+#[test]
+fn test_payout_swap_checks_what_arrived() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let router = env.register_contract(None, boasting_router::BoastingRouter);
+    let eurc = stock_swap_router(&env, &client, router);
+
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &landlord, &[(&tenant, 500)]);
+    client.set_payout_asset(&split_id, &eurc.address, &460);
+    token_admin_client.mint(&tenant, &500);
+
+    // Half the split owes half the minimum: 225 of 230 isn't enough
+    client.deposit(&split_id, &tenant, &250);
+    assert_eq!(
+        client.try_release_partial(&split_id),
+        Err(Ok(Error::SlippageExceeded))
+    );
+
+    // At 450 the scaled minimum is 225, which the router really pays
+    client.set_payout_asset(&split_id, &eurc.address, &450);
+    client.release_partial(&split_id);
+    assert_eq!(eurc.balance(&landlord), 225);
+}
+
+// ============================================
+// Claimable Payout Tests
+// ============================================
+
+#[test]
+fn test_claimable_payout_waits_for_recipient() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&tenant, 300)]);
+    client.set_claimable_payout(&split_id, &landlord, &100);
+
+    token_admin_client.mint(&tenant, &300);
+    client.deposit(&split_id, &tenant, &300);
+
+    // Released, but parked rather than pushed
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&landlord), 0);
+    let bucket = client.get_payout_bucket(&split_id).unwrap();
+    assert_eq!(bucket.amount, 300);
+    assert_eq!(bucket.recipient, landlord);
+    assert_eq!(client.get_total_escrowed(&token_id), 300);
+
+    assert_eq!(client.claim_payout(&split_id), 300);
+    assert_eq!(token_client.balance(&landlord), 300);
+    assert_eq!(client.get_payout_bucket(&split_id), None);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+#[test]
+fn test_unclaimed_payout_returns_to_creator_after_expiry() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let landlord = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&tenant, 300)]);
+    client.set_claimable_payout(&split_id, &landlord, &100);
+
+    token_admin_client.mint(&tenant, &300);
+    client.deposit(&split_id, &tenant, &300);
+
+    assert_eq!(
+        client.try_reclaim_payout(&split_id),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp += 101);
+    assert_eq!(
+        client.try_claim_payout(&split_id),
+        Err(Ok(Error::PayoutExpired))
+    );
+    assert_eq!(client.get_total_escrowed(&token_id), 300);
+    assert_eq!(client.reclaim_payout(&split_id), 300);
+    assert_eq!(token_client.balance(&creator), 300);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+// ============================================
+// Receipt Reference Tests
+// ============================================
+
+#[test]
+fn test_set_receipt_ref() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 100)]);
+
+    assert_eq!(client.get_split(&split_id).receipt_ref, None);
+
+    let cid = Bytes::from_array(&env, &[0x12u8; 34]);
+    client.set_receipt_ref(&split_id, &cid, &None);
+    assert_eq!(client.get_split(&split_id).receipt_ref, Some(cid));
+
+    assert_eq!(
+        client.try_set_receipt_ref(&split_id, &Bytes::new(&env), &None),
+        Err(Ok(Error::InvalidReceiptRef))
+    );
+    assert_eq!(
+        client.try_set_receipt_ref(&split_id, &Bytes::from_array(&env, &[1u8; 65]), &None),
+        Err(Ok(Error::InvalidReceiptRef))
+    );
+}
+
+#[test]
+fn test_unpaid_invitations_lapse_and_can_be_reassigned_or_rebalanced() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let day = 86_400;
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let dave = Address::generate(&env);
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Ski chalet"),
+        &300,
+        &Vec::from_array(&env, [alice.clone(), bob.clone(), carol.clone()]),
+        &Vec::from_array(&env, [100i128, 100, 100]),
+        &SplitOptions {
+            invite_window: 2 * day,
+            ..Default::default()
+        },
+    );
+    token_admin_client.mint(&alice, &150);
+    token_admin_client.mint(&bob, &100);
+    token_admin_client.mint(&dave, &150);
+    client.deposit(&split_id, &alice, &100);
+
+    assert_eq!(
+        client.try_lapse_invitation(&split_id, &bob),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+    env.ledger().with_mut(|li| li.timestamp += 2 * day);
+    assert_eq!(
+        client.try_lapse_invitation(&split_id, &alice),
+        Err(Ok(Error::HasDeposits))
+    );
+    assert_eq!(client.lapse_invitation(&split_id, &bob), 100);
+    assert!(client.is_lapsed(&split_id, &bob));
+    assert_eq!(
+        client.try_deposit(&split_id, &bob, &100),
+        Err(Ok(Error::ParticipantLapsed))
+    );
+
+    // Bob's place goes to Dave
+    client.reassign_slot(&split_id, &bob, &dave);
+    client.deposit(&split_id, &dave, &100);
+
+    // Carol's share is spread over Alice and Dave
+    assert_eq!(
+        client.try_rebalance_slot(&split_id, &carol),
+        Err(Ok(Error::ActionNotReady))
+    );
+    client.lapse_invitation(&split_id, &carol);
+    assert_eq!(client.rebalance_slot(&split_id, &carol), 100);
+    let split = client.get_split(&split_id);
+    let shares: std::vec::Vec<(Address, i128)> = split
+        .participants
+        .iter()
+        .map(|p| (p.address, p.share_amount))
+        .collect();
+    assert_eq!(
+        shares,
+        [(alice.clone(), 150), (dave.clone(), 150), (carol, 0)]
+    );
+    assert_eq!(split.total_amount, 300);
+
+    client.deposit(&split_id, &alice, &50);
+    client.deposit(&split_id, &dave, &50);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+}
+
+#[test]
+fn test_rebalance_respects_spending_caps() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Ski chalet"),
+        &300,
+        &Vec::from_array(&env, [alice.clone(), bob.clone(), carol.clone()]),
+        &Vec::from_array(&env, [100i128, 100, 100]),
+        &SplitOptions {
+            invite_window: 86_400,
+            ..Default::default()
+        },
+    );
+    env.ledger().with_mut(|li| li.timestamp += 86_400);
+    client.lapse_invitation(&split_id, &carol);
+
+    // Alice agreed to owe at most 120, so Carol's share can't land on her
+    client.set_spending_cap(&alice, &120);
+    assert_eq!(
+        client.try_rebalance_slot(&split_id, &carol),
+        Err(Ok(Error::SpendingCapExceeded))
+    );
+    let split = client.get_split(&split_id);
+    assert_eq!(split.participants.get(0).unwrap().share_amount, 100);
+    assert_eq!(split.participants.get(2).unwrap().share_amount, 100);
+
+    client.set_spending_cap(&alice, &150);
+    assert_eq!(client.rebalance_slot(&split_id, &carol), 100);
+    assert_eq!(client.get_total_owed(&alice, &0).total, 150);
+}
+
+#[test]
+fn test_progress_has_what_a_progress_bar_needs() {
+    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let carol = Address::generate(&env);
+    let now = env.ledger().timestamp();
+    let sequence = env.ledger().sequence();
+    let options = SplitOptions {
+        deadline: Some(now + 3_600),
+        deadline_ledger: Some(sequence + 700),
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Cabin"),
+        &300,
+        &Vec::from_array(&env, [alice.clone(), bob.clone(), carol]),
+        &Vec::from_array(&env, [100i128, 100, 100]),
+        &options,
+    );
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &50);
+    client.deposit(&split_id, &alice, &100);
+    client.deposit(&split_id, &bob, &50);
+
+    env.ledger().with_mut(|li| {
+        li.timestamp += 600;
+        li.sequence_number += 100;
+    });
+    assert_eq!(
+        client.get_progress(&split_id),
+        SplitProgress {
+            collected: 150,
+            total: 300,
+            funded_bps: 5_000,
+            paid_count: 1,
+            unpaid_count: 2,
+            seconds_left: Some(3_000),
+            ledgers_left: Some(600),
+        }
+    );
+
+    // Past the deadlines the clocks stop at zero
+    env.ledger().with_mut(|li| {
+        li.timestamp += 10_000;
+        li.sequence_number += 1_000;
+    });
+    let progress = client.get_progress(&split_id);
+    assert_eq!(
+        (progress.seconds_left, progress.ledgers_left),
+        (Some(0), Some(0))
+    );
+}
+
+#[test]
+fn test_terms_hash_commits_to_creation_terms() {
+    use soroban_sdk::xdr::ToXdr;
+
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+
+    let event = env.events().all().iter().find(|e| {
+        let topic: Symbol = e.1.get(0).unwrap().try_into_val(&env).unwrap();
+        topic == symbol_short!("terms")
+    });
+    let (_, _, data): (u64, u64, Val) = event.unwrap().2.try_into_val(&env).unwrap();
+    let (_, announced): (u64, BytesN<32>) = data.try_into_val(&env).unwrap();
+    let hash = client.get_terms_hash(&split_id).unwrap();
+    assert_eq!(announced, hash);
+
+    // Anyone can rebuild the hash from the terms on chain
+    let terms = client.get_split_terms(&split_id);
+    assert_eq!(terms.token, token_id);
+    assert_eq!(terms.participants, Vec::from_array(&env, [alice, bob]));
+    assert_eq!(terms.shares, Vec::from_array(&env, [60i128, 40]));
+    let rebuilt: BytesN<32> = env.crypto().sha256(&terms.to_xdr(&env)).into();
+    assert_eq!(rebuilt, hash);
+
+    // Changing the shares later doesn't move the committed hash
+    client.reduce_total(&split_id, &50, &None);
+    let changed = client.get_split_terms(&split_id);
+    let rehashed: BytesN<32> = env.crypto().sha256(&changed.to_xdr(&env)).into();
+    assert_ne!(rehashed, hash);
+    assert_eq!(client.get_terms_hash(&split_id), Some(hash));
+}
+
+#[test]
+fn test_metadata_uri_set_and_cleared() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&participant, 100)]);
+
+    assert_eq!(client.get_split(&split_id).metadata_uri, None);
+
+    let uri = String::from_str(
+        &env,
+        "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    );
+    client.set_metadata_uri(&split_id, &uri, &None);
+    assert_eq!(client.get_split(&split_id).metadata_uri, Some(uri.clone()));
+
+    let event = env.events().all().last().unwrap();
+    let (_, _, data): (u64, u64, Val) = event.2.try_into_val(&env).unwrap();
+    let (id, announced): (u64, String) = data.try_into_val(&env).unwrap();
+    assert_eq!((id, announced), (split_id, uri));
+
+    let long = String::from_bytes(&env, &[b'a'; 257]);
+    assert_eq!(
+        client.try_set_metadata_uri(&split_id, &long, &None),
+        Err(Ok(Error::UriTooLong))
+    );
+
+    client.set_metadata_uri(&split_id, &String::from_str(&env, ""), &None);
+    assert_eq!(client.get_split(&split_id).metadata_uri, None);
+}
+
+#[test]
+fn test_stale_nonce_is_rejected() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    let seen = client.get_split(&split_id).nonce;
+
+    // The first phone edits against the version it read
+    let uri = String::from_str(&env, "ipfs://receipt");
+    client.set_metadata_uri(&split_id, &uri, &Some(seen));
+    assert_eq!(client.get_split(&split_id).nonce, seen + 1);
+
+    // The second phone still holds the old version and is turned away
+    assert_eq!(
+        client.try_set_participant_label(
+            &split_id,
+            &alice,
+            &String::from_str(&env, "Mom"),
+            &Some(seen),
+        ),
+        Err(Ok(Error::StaleNonce))
+    );
+    assert_eq!(
+        client.try_cancel_split(
+            &split_id,
+            &String::from_str(&env, "plans changed"),
+            &Some(seen),
+        ),
+        Err(Ok(Error::StaleNonce))
+    );
+
+    // Side edits bump the nonce too, and skipping the check still works
+    client.set_participant_label(
+        &split_id,
+        &alice,
+        &String::from_str(&env, "Mom"),
+        &Some(seen + 1),
+    );
+    assert_eq!(client.get_split(&split_id).nonce, seen + 2);
+    client.set_metadata_uri(&split_id, &String::from_str(&env, ""), &None);
+    assert_eq!(client.get_split(&split_id).nonce, seen + 3);
+}
+
+// ============================================
+// Reference Code Tests
+// ============================================
+
+/// Copy a short code out of a soroban `String`
+fn code_bytes(code: &String) -> std::vec::Vec<u8> {
+    let mut buf = [0u8; 32];
+    let len = code.len() as usize;
+    code.copy_into_slice(&mut buf[..len]);
+    buf[..len].to_vec()
+}
+
+#[test]
+fn test_split_resolves_by_reference_code() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    let first = create_split_with_shares(&env, &client, &creator, &[(&participant, 10)]);
+    let second = create_split_with_shares(&env, &client, &creator, &[(&participant, 20)]);
+
+    let code = client.get_ref_code(&first);
+    let bytes = code_bytes(&code);
+    assert_eq!(bytes.len(), 7);
+    assert_eq!(bytes[3], b'-');
+    assert_ne!(code, client.get_ref_code(&second));
+    assert_eq!(client.get_split_by_code(&code).id, first);
+
+    // Read aloud and typed back in lower case without the hyphen
+    let mut typed: std::vec::Vec<u8> = bytes
+        .iter()
+        .filter(|c| **c != b'-')
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    let typed_code = String::from_bytes(&env, &typed);
+    assert_eq!(client.get_split_by_code(&typed_code).id, first);
+
+    // A single wrong character fails the check digit
+    typed[1] = if typed[1] == b'z' { b'y' } else { b'z' };
+    assert_eq!(
+        client
+            .try_get_split_by_code(&String::from_bytes(&env, &typed))
+            .err(),
+        Some(Ok(Error::SplitNotFound))
+    );
+    assert_eq!(
+        client
+            .try_get_split_by_code(&String::from_str(&env, "UUU-UUU"))
+            .err(),
+        Some(Ok(Error::SplitNotFound))
+    );
+
+    // Another deployment gives the same ID a different code
+    let other =
+        SplitEscrowContractClient::new(&env, &env.register_contract(None, SplitEscrowContract));
+    other.initialize(&admin, &token_id);
+    let other_first = create_split_with_shares(&env, &other, &creator, &[(&participant, 10)]);
+    assert_eq!(other_first, first);
+    assert_ne!(other.get_ref_code(&other_first), code);
+}
+
+// ============================================
+// Bond and Dispute Tests
+// ============================================
+
+#[test]
+fn test_bond_is_locked_and_returned_at_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&creator, &50);
+    token_admin_client.mint(&alice, &100);
+
+    let iou = SplitOptions {
+        mode: SplitMode::Iou,
+        bond: 50,
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Bonded IOU"),
+            &100,
+            &Vec::from_array(&env, [alice.clone()]),
+            &Vec::from_array(&env, [100i128]),
+            &iou,
+        ),
+        Err(Ok(Error::UnsupportedMode))
+    );
+
+    let options = SplitOptions {
+        bond: 50,
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    assert_eq!(client.get_bond(&split_id), 50);
+    assert_eq!(token_client.balance(&creator), 0);
+    assert_eq!(client.get_total_escrowed(&token_id), 50);
+
+    client.deposit(&split_id, &alice, &100);
+
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(client.get_bond(&split_id), 0);
+    assert_eq!(token_client.balance(&creator), 150);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+#[test]
+#[cfg(feature = "disputes")]
+fn test_dispute_ruling_against_creator_slashes_bond() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let arbiter = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&creator, &100);
+    token_admin_client.mint(&alice, &100);
+    token_admin_client.mint(&bob, &100);
+
+    let options = SplitOptions {
+        bond: 100,
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Marketplace order"),
+        &90,
+        &Vec::from_array(&env, [alice.clone(), bob.clone()]),
+        &Vec::from_array(&env, [60i128, 30]),
+        &options,
+    );
+
+    // Nobody can rule until the admin appoints an arbiter
+    client.open_dispute(&split_id, &alice);
+    assert_eq!(
+        client.try_resolve_dispute(&split_id, &true, &10_000),
+        Err(Ok(Error::InvalidConfig))
+    );
+    assert_eq!(
+        client.try_open_dispute(&split_id, &bob),
+        Err(Ok(Error::DisputeOpen))
+    );
+    run_admin_action(&env, &client, &AdminAction::SetArbiter(arbiter));
+
+    // Full funding doesn't release a disputed split
+    client.deposit(&split_id, &alice, &60);
+    client.deposit(&split_id, &bob, &30);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::DisputeOpen))
+    );
+    assert_eq!(
+        client.try_reclaim_bond(&split_id),
+        Err(Ok(Error::NotRefundable))
+    );
+
+    assert_eq!(
+        client.try_resolve_dispute(&split_id, &true, &10_001),
+        Err(Ok(Error::InvalidSlash))
+    );
+    assert_eq!(
+        client.try_resolve_dispute(&split_id, &false, &5_000),
+        Err(Ok(Error::InvalidSlash))
+    );
+
+    client.resolve_dispute(&split_id, &true, &5_000);
+
+    // Half of 100 by 60:30 paid floors to 33 / 16; the spare stroop goes
+    // to the first-listed payer and the other half goes back to the creator
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Cancelled);
+    assert_eq!(client.get_bond(&split_id), 0);
+    assert_eq!(client.get_dispute(&split_id), None);
+    assert_eq!(token_client.balance(&alice), 40 + 34);
+    assert_eq!(token_client.balance(&bob), 70 + 16);
+    assert_eq!(token_client.balance(&creator), 50);
+
+    let slash = client.get_bond_slash(&split_id).unwrap();
+    assert_eq!(slash.slash_bps, 5_000);
+    assert_eq!(slash.slashed, 50);
+    assert_eq!(slash.returned, 50);
+    assert_eq!(
+        slash.payouts,
+        Vec::from_array(&env, [(alice.clone(), 34i128), (bob.clone(), 16)])
+    );
+
+    client.claim_refund(&split_id, &alice);
+    client.claim_refund(&split_id, &bob);
+    assert_eq!(token_client.balance(&alice), 134);
+    assert_eq!(token_client.balance(&bob), 116);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+    assert_eq!(
+        client.try_resolve_dispute(&split_id, &true, &0),
+        Err(Ok(Error::NoDispute))
+    );
+}
+
+#[test]
+#[cfg(feature = "disputes")]
+fn test_retainage_is_claimed_after_window_or_returned_on_dispute() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+    let arbiter = Address::generate(&env);
+    run_admin_action(&env, &client, &AdminAction::SetArbiter(arbiter));
+
+    let week = 7 * 86_400;
+    let options = SplitOptions {
+        retainage_bps: 1_000,
+        retainage_window: week,
+        ..Default::default()
+    };
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &200);
+    token_admin_client.mint(&bob, &100);
+
+    // Funding releases 90% to the creator and keeps 10% back
+    let roof = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    client.deposit(&roof, &alice, &100);
+    assert_eq!(client.get_split(&roof).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 90);
+    assert_eq!(client.get_retainage(&roof).unwrap().held, 10);
+    assert_eq!(client.get_total_escrowed(&token_id), 10);
+
+    assert_eq!(
+        client.try_claim_retainage(&roof),
+        Err(Ok(Error::DeadlineNotReached))
+    );
+    env.ledger().with_mut(|li| li.timestamp += week);
+
+    // Paused releases and a frozen split hold the retainage too
+    client.set_pause_flags(
+        &admin,
+        &PauseFlags {
+            release: true,
+            ..Default::default()
+        },
+    );
+    assert_eq!(
+        client.try_claim_retainage(&roof),
+        Err(Ok(Error::ContractPaused))
+    );
+    client.set_pause_flags(&admin, &PauseFlags::default());
+    client.freeze_split(&admin, &roof);
+    assert_eq!(
+        client.try_claim_retainage(&roof),
+        Err(Ok(Error::ContractPaused))
+    );
+    client.unfreeze_split(&admin, &roof);
+    assert_eq!(client.claim_retainage(&roof), 10);
+    assert_eq!(token_client.balance(&creator), 100);
+    assert_eq!(
+        client.try_claim_retainage(&roof),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+
+    // A dispute won against the creator sends the retainage back
+    let plumbing = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Plumbing"),
+        &200,
+        &Vec::from_array(&env, [alice.clone(), bob.clone()]),
+        &Vec::from_array(&env, [100i128, 100]),
+        &options,
+    );
+    client.deposit(&plumbing, &alice, &100);
+    client.deposit(&plumbing, &bob, &100);
+    assert_eq!(token_client.balance(&creator), 280);
+    client.open_dispute(&plumbing, &bob);
+    env.ledger().with_mut(|li| li.timestamp += week);
+    assert_eq!(
+        client.try_claim_retainage(&plumbing),
+        Err(Ok(Error::DisputeOpen))
+    );
+    client.resolve_dispute(&plumbing, &true, &0);
+    assert_eq!(client.get_retainage(&plumbing).unwrap().held, 0);
+    assert_eq!(token_client.balance(&alice), 10);
+    assert_eq!(token_client.balance(&bob), 10);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+}
+
+#[test]
+#[cfg(feature = "disputes")]
+fn test_dispute_ruling_for_creator_lets_split_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let arbiter = Address::generate(&env);
+    run_admin_action(&env, &client, &AdminAction::SetArbiter(arbiter));
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&creator, &20);
+    token_admin_client.mint(&alice, &100);
+
+    let options = SplitOptions {
+        bond: 20,
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    client.deposit(&split_id, &alice, &40);
+    client.open_dispute(&split_id, &alice);
+    assert_eq!(
+        client.try_release_partial(&split_id),
+        Err(Ok(Error::DisputeOpen))
+    );
+
+    // Once cleared, the split finishes and auto-releases as usual
+    client.resolve_dispute(&split_id, &false, &0);
+    client.deposit(&split_id, &alice, &60);
+
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 120);
+}
+
+#[test]
+fn test_scheduled_release_pays_out_when_due() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+
+    let now = env.ledger().timestamp();
+    assert_eq!(
+        client.try_schedule_release(&split_id, &now),
+        Err(Ok(Error::InvalidDeadline))
+    );
+    client.schedule_release(&split_id, &(now + 86_400));
+    assert_eq!(client.get_scheduled_release(&split_id), Some(now + 86_400));
+
+    // Fully funded, but the money waits for the day
+    client.deposit(&split_id, &alice, &100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+    assert_eq!(
+        client.try_execute_scheduled_release(&split_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    // Once funded, the creator can bring the day forward but not push it back
+    assert_eq!(
+        client.try_schedule_release(&split_id, &(now + 2 * 86_400)),
+        Err(Ok(Error::InvalidDeadline))
+    );
+    client.schedule_release(&split_id, &(now + 3_600));
+    assert_eq!(client.get_scheduled_release(&split_id), Some(now + 3_600));
+
+    // Anyone can trigger the payout once the time comes
+    env.ledger().with_mut(|li| li.timestamp = now + 86_400);
+    assert_eq!(client.execute_scheduled_release(&split_id), 100);
+    assert_eq!(token_client.balance(&creator), 100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(client.get_scheduled_release(&split_id), None);
+    assert_eq!(
+        client.try_execute_scheduled_release(&split_id),
+        Err(Ok(Error::ActionNotFound))
+    );
+}
 
-    // Try to release without completing deposits
-    let result = catch_unwind(AssertUnwindSafe(|| client.release_funds(&split_id)));
-    assert!(result.is_err());
+#[test]
+#[cfg(feature = "disputes")]
+fn test_release_delay_leaves_room_to_object() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let arbiter = Address::generate(&env);
+    run_admin_action(&env, &client, &AdminAction::SetArbiter(arbiter));
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let options = SplitOptions {
+        release_delay: 3_600,
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    assert_eq!(
+        client.quote_deposit(&split_id, &alice, &100).status,
+        SplitStatus::Completed
+    );
+    client.deposit(&split_id, &alice, &100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_execute_release(&split_id),
+        Err(Ok(Error::ActionNotFound))
+    );
+
+    // The request only starts the clock
+    let now = env.ledger().timestamp();
+    client.release_funds(&split_id);
+    assert_eq!(
+        client.get_release_request(&split_id).unwrap().ready_at,
+        now + 3_600
+    );
+    assert_eq!(token_client.balance(&creator), 0);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+    assert_eq!(
+        client.try_execute_release(&split_id),
+        Err(Ok(Error::ActionNotReady))
+    );
+
+    // An objection inside the window holds the payout past it
+    client.open_dispute(&split_id, &alice);
+    env.ledger().with_mut(|li| li.timestamp = now + 3_600);
+    assert_eq!(
+        client.try_execute_release(&split_id),
+        Err(Ok(Error::DisputeOpen))
+    );
+
+    client.resolve_dispute(&split_id, &false, &0);
+    assert_eq!(client.execute_release(&split_id), 100);
+    assert_eq!(token_client.balance(&creator), 100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(client.get_release_request(&split_id), None);
+}
+
+#[test]
+#[cfg(feature = "disputes")]
+fn test_objection_routes_pending_release_to_arbiter() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let arbiter = Address::generate(&env);
+    run_admin_action(&env, &client, &AdminAction::SetArbiter(arbiter));
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &60);
+    token_admin_client.mint(&bob, &40);
+
+    let options = SplitOptions {
+        release_delay: 3_600,
+        ..Default::default()
+    };
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Deposit on a villa"),
+        &100,
+        &Vec::from_array(&env, [alice.clone(), bob.clone()]),
+        &Vec::from_array(&env, [60i128, 40]),
+        &options,
+    );
+    client.deposit(&split_id, &alice, &60);
+    client.deposit(&split_id, &bob, &40);
+
+    let reason = soroban_sdk::BytesN::from_array(&env, &[7; 32]);
+    assert_eq!(
+        client.try_object_to_release(&split_id, &bob, &reason),
+        Err(Ok(Error::ActionNotFound))
+    );
+
+    client.release_funds(&split_id);
+    client.object_to_release(&split_id, &bob, &reason);
+    assert_eq!(client.get_objection(&split_id), Some(reason.clone()));
+    assert_eq!(client.get_dispute(&split_id).unwrap().opened_by, bob);
+
+    env.ledger().with_mut(|li| li.timestamp += 3_600);
+    assert_eq!(
+        client.try_execute_release(&split_id),
+        Err(Ok(Error::DisputeOpen))
+    );
+
+    // Upheld: the release never happens and everyone gets their money back
+    client.resolve_dispute(&split_id, &true, &0);
+    assert_eq!(client.get_objection(&split_id), None);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Cancelled);
+    client.claim_refund(&split_id, &alice);
+    client.claim_refund(&split_id, &bob);
+    assert_eq!(token_client.balance(&alice), 60);
+    assert_eq!(token_client.balance(&bob), 40);
+    assert_eq!(token_client.balance(&creator), 0);
+}
+
+#[test]
+fn test_confirmer_accepts_payout_plan_before_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let venue = Address::generate(&env);
+    let alice = Address::generate(&env);
+    token_admin_client.mint(&alice, &100);
+
+    let options = SplitOptions {
+        confirmer: Some(venue.clone()),
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    client.deposit(&split_id, &alice, &100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Completed);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::UnsupportedMode))
+    );
+    assert_eq!(
+        client.try_confirm_release(&split_id, &Vec::new(&env)),
+        Err(Ok(Error::ActionNotFound))
+    );
+
+    let share = |recipient: &Address, bps: u32| PayoutShare {
+        recipient: recipient.clone(),
+        bps,
+    };
+    assert_eq!(
+        client.try_propose_release(&split_id, &Vec::from_array(&env, [share(&venue, 9_000)])),
+        Err(Ok(Error::SharesMismatch))
+    );
+    let plan = Vec::from_array(&env, [share(&venue, 7_000), share(&creator, 3_000)]);
+    client.propose_release(&split_id, &plan);
+    assert_eq!(client.get_release_proposal(&split_id).unwrap().plan, plan);
+    assert_eq!(token_client.balance(&venue), 0);
+
+    // A plan swapped in after the confirmer looked is refused
+    let swapped = Vec::from_array(&env, [share(&creator, 10_000)]);
+    client.propose_release(&split_id, &swapped);
+    assert_eq!(
+        client.try_confirm_release(&split_id, &plan),
+        Err(Ok(Error::SharesMismatch))
+    );
+    client.propose_release(&split_id, &plan);
+
+    client.confirm_release(&split_id, &plan);
+    assert_eq!(
+        env.auths().first().map(|(address, _)| address.clone()),
+        Some(venue.clone())
+    );
+    assert_eq!(token_client.balance(&venue), 70);
+    assert_eq!(token_client.balance(&creator), 30);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(client.get_release_proposal(&split_id), None);
+    client.check_invariants(&split_id);
+}
+
+#[test]
+#[cfg(all(feature = "disputes", feature = "insurance"))]
+fn test_insurance_pool_covers_losses_after_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let arbiter = Address::generate(&env);
+    run_admin_action(&env, &client, &AdminAction::SetArbiter(arbiter));
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&creator, &1_000);
+    token_admin_client.mint(&alice, &1_000);
+    token_admin_client.mint(&bob, &1_000);
+
+    let insured = SplitOptions {
+        insured: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Too early"),
+            &100,
+            &Vec::from_array(&env, [alice.clone()]),
+            &Vec::from_array(&env, [100i128]),
+            &insured,
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    // 5% premium, at most 40 per claim
+    let config = InsuranceConfig {
+        premium_bps: 500,
+        max_claim: 40,
+    };
+    run_admin_action(&env, &client, &AdminAction::SetInsurance(config));
+
+    let uninsured = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    client.deposit(&uninsured, &alice, &100);
+    assert_eq!(
+        client.try_open_dispute(&uninsured, &alice),
+        Err(Ok(Error::SplitReleased))
+    );
+
+    let split_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Concert tickets"),
+        &1_000,
+        &Vec::from_array(&env, [alice.clone(), bob.clone()]),
+        &Vec::from_array(&env, [800i128, 200]),
+        &insured,
+    );
+    let other_id = client.create_split_with_options(
+        &creator,
+        &String::from_str(&env, "Parking"),
+        &200,
+        &Vec::from_array(&env, [bob.clone()]),
+        &Vec::from_array(&env, [200i128]),
+        &insured,
+    );
+    assert_eq!(client.get_insurance_pool(), 60);
+
+    // The creator collects the money and never delivers; Bob paid them
+    // directly, so none of that money went through escrow
+    client.deposit(&split_id, &alice, &800);
+    client.mark_settled_offchain(&split_id, &bob);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    client.open_dispute(&split_id, &alice);
+    client.resolve_dispute(&split_id, &true, &0);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(client.get_insurance_claim(&split_id, &alice), 800);
+    assert_eq!(client.get_insurance_claim(&split_id, &bob), 0);
+
+    // Claims are capped per participant
+    assert_eq!(client.claim_insurance(&split_id, &alice), 40);
+    assert_eq!(client.get_insurance_pool(), 20);
+    assert_eq!(token_client.balance(&alice), 1_000 - 100 - 800 + 40);
+    assert_eq!(
+        client.try_claim_insurance(&split_id, &alice),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+
+    // Losing a second dispute doesn't record the same losses again
+    client.open_dispute(&split_id, &alice);
+    client.resolve_dispute(&split_id, &true, &0);
+    assert_eq!(client.get_insurance_claim(&split_id, &alice), 0);
+
+    // Without a loss there is nothing to claim, whatever the pool holds
+    assert_eq!(client.get_insurance_claim(&other_id, &bob), 0);
+    assert_eq!(client.get_total_escrowed(&token_id), 20);
 }
 
 // ============================================
-// Partial Release and Funding Checks
+// Invariant Tests
 // ============================================
 
 #[test]
-fn test_is_fully_funded() {
+fn test_invariants_hold_through_lifecycle() {
     let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
-    let participant = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&alice, &1_000);
+    token_admin_client.mint(&bob, &1_000);
+
+    let released = create_split_with_shares(&env, &client, &creator, &[(&alice, 60), (&bob, 40)]);
+    client.check_invariants(&released);
+    client.deposit(&released, &alice, &60);
+    client.check_invariants(&released);
+    client.release_partial(&released);
+    client.check_invariants(&released);
+    client.deposit(&released, &bob, &40);
+    client.check_invariants(&released);
+
+    let refunded = create_split_with_shares(&env, &client, &creator, &[(&alice, 50), (&bob, 50)]);
+    client.deposit(&refunded, &bob, &20);
+    client.cancel_split(&refunded, &String::from_str(&env, "plans changed"), &None);
+    client.claim_refund(&refunded, &bob);
+    client.check_invariants(&refunded);
+
+    let options = SplitOptions {
+        mode: SplitMode::PassThrough,
+        ..Default::default()
+    };
+    let pass_through = create_split_with_options_for(&env, &client, &creator, &alice, 70, &options);
+    client.deposit(&pass_through, &alice, &30);
+    client.check_invariants(&pass_through);
+}
 
-    let description = String::from_str(&env, "Funding check");
+#[test]
+fn test_invariants_catch_inconsistent_split() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 60)]);
+
+    // Collected funds no participant paid and no balance backs
+    let mut split = client.get_split(&split_id);
+    split.amount_collected = 10;
+    split.status = SplitStatus::Active;
+    env.as_contract(&client.address, || {
+        storage::set_split(&env, split_id, &split);
+    });
 
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+    assert_eq!(
+        client.try_check_invariants(&split_id),
+        Err(Ok(Error::InvariantViolated))
+    );
+}
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+#[test]
+fn test_lifecycle_table_is_well_formed() {
+    let finals = [
+        SplitStatus::Released,
+        SplitStatus::Cancelled,
+        SplitStatus::Expired,
+    ];
+    for (from, to, _) in lifecycle::TRANSITIONS.iter() {
+        assert!(!finals.contains(from));
+        assert_ne!(from, to);
+    }
 
-    token_admin_client.mint(&participant, &50_0000000i128);
-    client.deposit(&split_id, &participant, &50_0000000);
+    // Every status can be reached from Pending
+    let mut reached = std::vec![SplitStatus::Pending];
+    let mut grew = true;
+    while grew {
+        grew = false;
+        for (from, to, _) in lifecycle::TRANSITIONS.iter() {
+            if reached.contains(from) && !reached.contains(to) {
+                reached.push(to.clone());
+                grew = true;
+            }
+        }
+    }
+    assert_eq!(reached.len(), 6);
+
+    assert!(lifecycle::allowed(
+        &SplitStatus::Completed,
+        &SplitStatus::Active,
+        lifecycle::Action::Reopen
+    ));
+    assert!(!lifecycle::allowed(
+        &SplitStatus::Completed,
+        &SplitStatus::Expired,
+        lifecycle::Action::Expire
+    ));
+}
 
-    let funded = client.is_fully_funded(&split_id);
-    assert!(!funded);
+// ============================================
+// Contract Account Tests
+// ============================================
 
-    token_admin_client.mint(&participant, &50_0000000i128);
-    client.deposit(&split_id, &participant, &50_0000000);
+/// Minimal smart wallet: it acts on its own behalf as the direct invoker and
+/// pre-authorizes the token transfer the escrow makes from its balance
+mod smart_wallet {
+    use crate::SplitEscrowContractClient;
+    use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+    use soroban_sdk::{contract, contractimpl, vec, Address, Env, IntoVal, String, Symbol, Vec};
+
+    #[contract]
+    pub struct SmartWallet;
+
+    #[contractimpl]
+    impl SmartWallet {
+        pub fn create(
+            env: Env,
+            escrow: Address,
+            participants: Vec<Address>,
+            shares: Vec<i128>,
+        ) -> u64 {
+            let total = shares.iter().sum();
+            SplitEscrowContractClient::new(&env, &escrow).create_split(
+                &env.current_contract_address(),
+                &String::from_str(&env, "DAO offsite"),
+                &total,
+                &participants,
+                &shares,
+            )
+        }
 
-    let funded = client.is_fully_funded(&split_id);
-    assert!(funded);
+        pub fn deposit(env: Env, escrow: Address, token: Address, split_id: u64, amount: i128) {
+            let me = env.current_contract_address();
+            env.authorize_as_current_contract(vec![
+                &env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: token,
+                        fn_name: Symbol::new(&env, "transfer"),
+                        args: (me.clone(), escrow.clone(), amount).into_val(&env),
+                    },
+                    sub_invocations: vec![&env],
+                }),
+            ]);
+            SplitEscrowContractClient::new(&env, &escrow).deposit(&split_id, &me, &amount);
+        }
+
+        pub fn cancel(env: Env, escrow: Address, split_id: u64) {
+            SplitEscrowContractClient::new(&env, &escrow).cancel_split(
+                &split_id,
+                &String::from_str(&env, "plans changed"),
+                &None,
+            );
+        }
+
+        pub fn refund(env: Env, escrow: Address, split_id: u64) -> i128 {
+            SplitEscrowContractClient::new(&env, &escrow)
+                .claim_refund(&split_id, &env.current_contract_address())
+        }
+    }
 }
 
 #[test]
-fn test_release_partial() {
+fn test_contract_accounts_work_end_to_end() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let dao = env.register_contract(None, smart_wallet::SmartWallet);
+    let member = env.register_contract(None, smart_wallet::SmartWallet);
+    let dao_wallet = smart_wallet::SmartWalletClient::new(&env, &dao);
+    let member_wallet = smart_wallet::SmartWalletClient::new(&env, &member);
+    token_admin_client.mint(&member, &200);
+
+    // From here on every require_auth is checked for real
+    env.set_auths(&[]);
+
+    // Cancelled: the member contract gets its deposit back
+    let members = Vec::from_array(&env, [member.clone()]);
+    let shares = Vec::from_array(&env, [100i128]);
+    let split_id = dao_wallet.create(&client.address, &members, &shares);
+    assert_eq!(client.get_split(&split_id).creator, dao);
+    member_wallet.deposit(&client.address, &token_id, &split_id, &40);
+    dao_wallet.cancel(&client.address, &split_id);
+    assert_eq!(member_wallet.refund(&client.address, &split_id), 40);
+    assert_eq!(token_client.balance(&member), 200);
+
+    // Funded: the payout lands in the creator contract
+    let split_id = dao_wallet.create(&client.address, &members, &shares);
+    member_wallet.deposit(&client.address, &token_id, &split_id, &100);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&dao), 100);
+    assert_eq!(token_client.balance(&member), 100);
+
+    // Nobody else can move the member's funds
+    let split_id = dao_wallet.create(&client.address, &members, &shares);
+    assert!(client.try_deposit(&split_id, &member, &10).is_err());
+}
+
+// ============================================
+// Test Fixture Tests
+// ============================================
+
+#[test]
+fn test_fixture_builds_a_consistent_split() {
+    use crate::testutils::{create_token, EscrowFixture, ParticipantBuilder, SplitBuilder};
+
+    let fixture = EscrowFixture::new();
+    let env = &fixture.env;
+    let creator = Address::generate(env);
+    let alice = Address::generate(env);
+    let bob = Address::generate(env);
+
+    let split = SplitBuilder::new(env, 7, &creator)
+        .participant(ParticipantBuilder::new(&alice, 60).paid(60).build())
+        .participant(ParticipantBuilder::new(&bob, 40).paid(10).build())
+        .build();
+    assert_eq!(split.total_amount, 100);
+    assert_eq!(split.amount_collected, 70);
+    assert_eq!(split.status, SplitStatus::Active);
+
+    // Back the collected amount with tokens and held balances
+    fixture.fund(&fixture.client.address, 70);
+    fixture.store_split(&split);
+    env.as_contract(&fixture.client.address, || {
+        storage::add_held_balance(env, 7, &alice, 60);
+        storage::add_held_balance(env, 7, &bob, 10);
+    });
+    fixture.client.check_invariants(&7);
+
+    let other = create_token(env, &[&alice, &bob], 500);
+    assert_eq!(token::Client::new(env, &other).balance(&bob), 500);
+}
+
+// ============================================
+// Storage Migration Tests
+// ============================================
+
+/// Helper to write a split in the pre-versioning layout, as an old
+/// deployment would have left it
+fn store_legacy_split(
+    env: &Env,
+    client: &SplitEscrowContractClient,
+    split_id: u64,
+    creator: &Address,
+    participant: &Address,
+    share: i128,
+) {
+    let mut participants = Vec::new(env);
+    participants.push_back(Participant {
+        address: participant.clone(),
+        share_amount: share,
+        amount_paid: 0,
+        has_paid: false,
+    });
+
+    let legacy = SplitV1 {
+        id: split_id,
+        creator: creator.clone(),
+        description: String::from_str(env, "Old dinner"),
+        total_amount: share,
+        amount_collected: 0,
+        amount_released: 0,
+        participants,
+        status: SplitStatus::Pending,
+        created_at: 0,
+    };
+
+    env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Split(split_id), &legacy);
+    });
+}
+
+#[test]
+fn test_legacy_split_reads_as_current_layout() {
     let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
     let participant = Address::generate(&env);
+    store_legacy_split(&env, &client, 7, &creator, &participant, 100);
 
-    let description = String::from_str(&env, "Partial release");
+    let split = client.get_split(&7);
+    assert_eq!(split.description, String::from_str(&env, "Old dinner"));
+    assert_eq!(split.mode, SplitMode::Escrow);
+    assert_eq!(split.category, SplitCategory::Other);
+    assert_eq!(split.fees_paid, 0);
+    assert!(split.tags.is_empty());
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
+    // Old splits keep working end to end
+    token_admin_client.mint(&participant, &100);
+    client.deposit(&7, &participant, &100);
 
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+    assert_eq!(client.get_split(&7).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 100);
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+    // The deposit saved the split, which rewrote it in the new layout
+    assert!(!client.migrate_split(&7));
+}
 
-    token_admin_client.mint(&participant, &60_0000000i128);
-    client.deposit(&split_id, &participant, &60_0000000);
+#[test]
+fn test_migrate_split() {
+    let (env, admin, token_id, client, _token_client, _token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
 
-    let released = client.release_partial(&split_id);
-    assert_eq!(released, 60_0000000);
+    let creator = Address::generate(&env);
+    let participant = Address::generate(&env);
+    store_legacy_split(&env, &client, 3, &creator, &participant, 50);
 
-    let split = client.get_split(&split_id);
-    assert_eq!(split.status, SplitStatus::Active);
-    assert_eq!(split.amount_released, 60_0000000);
+    assert!(client.migrate_split(&3));
+    assert!(!client.migrate_split(&3));
 
-    let creator_balance = token_client.balance(&creator);
-    assert_eq!(creator_balance, 60_0000000);
+    let stored = env.as_contract(&client.address, || {
+        env.storage()
+            .persistent()
+            .get::<_, StoredSplit>(&DataKey::Split(3))
+            .unwrap()
+    });
+    let StoredSplit::V2(split) = stored;
+    assert_eq!(split.total_amount, 50);
+    assert!(split.metadata_uri.is_none());
+    assert_eq!(split.participants.get(0).unwrap().address, participant);
 
-    token_admin_client.mint(&participant, &40_0000000i128);
-    client.deposit(&split_id, &participant, &40_0000000);
+    assert_eq!(client.try_migrate_split(&99), Err(Ok(Error::SplitNotFound)));
+}
 
-    let split = client.get_split(&split_id);
-    assert_eq!(split.status, SplitStatus::Released);
-    assert_eq!(split.amount_released, 100_0000000);
+#[test]
+fn test_import_split_from_older_deployment() {
+    use crate::testutils::{ParticipantBuilder, SplitBuilder};
+
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
+    initialize_contract(&client, &admin, &token_id);
+
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    token_admin_client.mint(&admin, &70);
+    token_admin_client.mint(&bob, &30);
+
+    let split = SplitBuilder::new(&env, 40, &creator)
+        .participant(ParticipantBuilder::new(&alice, 60).paid(60).build())
+        .participant(ParticipantBuilder::new(&bob, 40).paid(10).build())
+        .build();
+    let mut broken = split.clone();
+    broken.total_amount = 99;
+    assert_eq!(
+        client.try_import_split(&broken),
+        Err(Ok(Error::SharesMismatch))
+    );
+
+    // The status has to agree with what was paid
+    let mut premature = split.clone();
+    premature.status = SplitStatus::Completed;
+    assert_eq!(
+        client.try_import_split(&premature),
+        Err(Ok(Error::InvalidConfig))
+    );
+    let pending = SplitBuilder::new(&env, 40, &creator)
+        .participant(ParticipantBuilder::new(&alice, 60).paid(60).build())
+        .status(SplitStatus::Pending)
+        .build();
+    assert_eq!(
+        client.try_import_split(&pending),
+        Err(Ok(Error::InvalidConfig))
+    );
+    let mut unreleased = SplitBuilder::new(&env, 40, &creator)
+        .participant(ParticipantBuilder::new(&alice, 60).paid(60).build())
+        .status(SplitStatus::Released)
+        .build();
+    unreleased.amount_released = 20;
+    assert_eq!(
+        client.try_import_split(&unreleased),
+        Err(Ok(Error::InvalidConfig))
+    );
+
+    // The escrowed 70 comes from the admin and is held as each paid it
+    let mut stale = split.clone();
+    stale.nonce = 9;
+    client.import_split(&stale);
+    assert_eq!(token_client.balance(&admin), 0);
+    assert_eq!(client.get_split(&40).amount_collected, 70);
+    assert_eq!(client.get_split(&40).nonce, 1);
+    assert_eq!(client.get_splits_by_creator(&creator, &0).len(), 1);
+    client.check_invariants(&40);
+
+    // It can't be imported over, and new IDs continue after it
+    assert_eq!(
+        client.try_import_split(&split),
+        Err(Ok(Error::InvalidConfig))
+    );
+    let next = create_split_with_shares(&env, &client, &creator, &[(&alice, 5)]);
+    assert_eq!(next, 41);
+
+    client.deposit(&40, &bob, &30);
+    assert_eq!(token_client.balance(&creator), 100);
 }
 
 // ============================================
-// Event Emission Tests
+// Charity Round-Up Tests
 // ============================================
 
 #[test]
-fn test_events_emitted_on_auto_release() {
-    let (env, admin, token_id, client, _token_client, token_admin_client) = setup_test();
+fn test_round_ups_go_to_charity_at_release() {
+    let (env, admin, token_id, client, token_client, token_admin_client) = setup_test();
     initialize_contract(&client, &admin, &token_id);
 
     let creator = Address::generate(&env);
-    let participant = Address::generate(&env);
-
-    let description = String::from_str(&env, "Event check");
+    let alice = Address::generate(&env);
+    let charity = Address::generate(&env);
+    let options = SplitOptions {
+        round_up_unit: 10,
+        round_up_charity: Some(charity.clone()),
+        ..Default::default()
+    };
+    let split_id = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    token_admin_client.mint(&alice, &200);
+
+    // 45 is charged as 50, and the extra 5 isn't part of the bill
+    let quote = client.quote_deposit(&split_id, &alice, &45);
+    assert_eq!((quote.accepted, quote.round_up), (45, 5));
+    client.deposit(&split_id, &alice, &45);
+    assert_eq!(token_client.balance(&alice), 150);
+    assert_eq!(client.get_round_up_held(&split_id), 5);
+    assert_eq!(client.get_split(&split_id).amount_collected, 45);
+    assert_eq!(client.get_held_balance(&split_id, &alice), 45);
+
+    client.deposit(&split_id, &alice, &55);
+    assert_eq!(client.get_split(&split_id).status, SplitStatus::Released);
+    assert_eq!(token_client.balance(&creator), 100);
+    assert_eq!(token_client.balance(&charity), 10);
+    assert_eq!(client.get_round_up_held(&split_id), 0);
+    assert_eq!(client.get_total_escrowed(&token_id), 0);
+    let log = client.get_transfer_history(&split_id, &0);
+    assert_eq!(log.last().unwrap().kind, TransferKind::Donation);
+
+    // A refund hands the round-up back with the deposit
+    let cancelled = create_split_with_options_for(&env, &client, &creator, &alice, 100, &options);
+    client.deposit(&cancelled, &alice, &7);
+    client.cancel_split(&cancelled, &String::from_str(&env, "plans changed"), &None);
+    assert_eq!(client.claim_refund(&cancelled, &alice), 10);
+    assert_eq!(token_client.balance(&alice), 90);
+
+    let no_charity = SplitOptions {
+        round_up_unit: 10,
+        ..Default::default()
+    };
+    assert_eq!(
+        client.try_create_split_with_options(
+            &creator,
+            &String::from_str(&env, "Dinner"),
+            &100,
+            &Vec::from_array(&env, [alice.clone()]),
+            &Vec::from_array(&env, [100]),
+            &no_charity,
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+}
 
-    let mut addresses = Vec::new(&env);
-    addresses.push_back(participant.clone());
+// ============================================
+// Reentrancy Tests
+// ============================================
 
-    let mut shares = Vec::new(&env);
-    shares.push_back(100_0000000i128);
+/// Token that tries to call back into the escrow on every outgoing transfer
+mod reentrant_token {
+    use crate::SplitEscrowContractClient;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    #[contract]
+    pub struct ReentrantToken;
+
+    #[contractimpl]
+    impl ReentrantToken {
+        pub fn init(env: Env, escrow: Address, split_id: u64) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("escrow"), &escrow);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("split"), &split_id);
+        }
 
-    let split_id = client.create_split(&creator, &description, &100_0000000, &addresses, &shares);
+        pub fn mint(env: Env, to: Address, amount: i128) {
+            let balance = Self::balance(env.clone(), to.clone());
+            env.storage().instance().set(&to, &(balance + amount));
+        }
 
-    token_admin_client.mint(&participant, &100_0000000i128);
-    client.deposit(&split_id, &participant, &100_0000000);
+        pub fn balance(env: Env, id: Address) -> i128 {
+            env.storage().instance().get(&id).unwrap_or(0)
+        }
 
-    let events = env.events().all();
-    let mut has_completed = false;
-    let mut has_released = false;
+        pub fn decimals(_env: Env) -> u32 {
+            7
+        }
 
-    for i in 0..events.len() {
-        let event = events.get(i).unwrap();
-        let topics = &event.1;
-        let data = &event.2;
+        pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+            from.require_auth();
+            let from_balance = Self::balance(env.clone(), from.clone());
+            env.storage()
+                .instance()
+                .set(&from, &(from_balance - amount));
+            Self::mint(env.clone(), to, amount);
+
+            let escrow: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("escrow"))
+                .unwrap();
+            if from != escrow {
+                return;
+            }
+            let split_id: u64 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("split"))
+                .unwrap();
+            let escrow_client = SplitEscrowContractClient::new(&env, &escrow);
+            let refund = escrow_client.try_claim_refund(&split_id, &from);
+            let release = escrow_client.try_release_funds(&split_id);
+            let attempts: u32 = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("tries"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("tries"), &(attempts + 1));
+            if refund.is_ok() || release.is_ok() {
+                env.storage()
+                    .instance()
+                    .set(&symbol_short!("got_in"), &true);
+            }
+        }
 
-        let topic: Symbol = topics.get(0).unwrap().try_into_val(&env).unwrap();
-        if topic == symbol_short!("completed") {
-            let payload: (u64, i128) = data.try_into_val(&env).unwrap();
-            assert_eq!(payload.0, split_id);
-            assert_eq!(payload.1, 100_0000000);
-            has_completed = true;
+        pub fn got_in(env: Env) -> bool {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("got_in"))
+                .unwrap_or(false)
         }
-        if topic == symbol_short!("released") {
-            let payload: (u64, Address, i128, u64) = data.try_into_val(&env).unwrap();
-            assert_eq!(payload.0, split_id);
-            assert_eq!(payload.1, creator);
-            assert_eq!(payload.2, 100_0000000);
-            has_released = true;
+
+        pub fn tries(env: Env) -> u32 {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("tries"))
+                .unwrap_or(0)
         }
     }
+}
 
-    assert!(has_completed);
-    assert!(has_released);
+fn setup_reentrant_escrow() -> (
+    Env,
+    SplitEscrowContractClient<'static>,
+    reentrant_token::ReentrantTokenClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register_contract(None, SplitEscrowContract);
+    let client = SplitEscrowContractClient::new(&env, &contract_id);
+    let token_id = env.register_contract(None, reentrant_token::ReentrantToken);
+    let token = reentrant_token::ReentrantTokenClient::new(&env, &token_id);
+    client.initialize(&Address::generate(&env), &token_id);
+    (env, client, token)
+}
+
+#[test]
+fn test_reentrant_token_cannot_double_refund() {
+    let (env, client, token) = setup_reentrant_escrow();
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 100)]);
+    token.init(&client.address, &split_id);
+
+    token.mint(&alice, &60);
+    client.deposit(&split_id, &alice, &60);
+    client.cancel_split(&split_id, &String::from_str(&env, "plans changed"), &None);
+
+    // The token's callback is turned away and the refund happens once
+    assert_eq!(client.claim_refund(&split_id, &alice), 60);
+    assert_eq!(token.tries(), 1);
+    assert!(!token.got_in());
+    assert_eq!(token.balance(&alice), 60);
+    assert_eq!(token.balance(&client.address), 0);
+    assert_eq!(client.get_held_balance(&split_id, &alice), 0);
+    assert_eq!(client.get_total_escrowed(&token.address), 0);
+    assert_eq!(
+        client.try_claim_refund(&split_id, &alice),
+        Err(Ok(Error::NoFundsAvailable))
+    );
+}
+
+#[test]
+fn test_reentrant_token_cannot_double_release() {
+    let (env, client, token) = setup_reentrant_escrow();
+    let creator = Address::generate(&env);
+    let alice = Address::generate(&env);
+    let bob = Address::generate(&env);
+    let split_id = create_split_with_shares(&env, &client, &creator, &[(&alice, 40), (&bob, 60)]);
+    token.init(&client.address, &split_id);
+
+    token.mint(&alice, &40);
+    token.mint(&bob, &60);
+    client.deposit(&split_id, &alice, &40);
+    client.deposit(&split_id, &bob, &60);
+
+    // The last deposit released everything exactly once
+    assert_eq!(token.tries(), 1);
+    assert!(!token.got_in());
+    assert_eq!(token.balance(&creator), 100);
+    assert_eq!(token.balance(&client.address), 0);
+    let split = client.get_split(&split_id);
+    assert_eq!(split.status, SplitStatus::Released);
+    assert_eq!(split.amount_released, 100);
+    assert_eq!(client.get_total_escrowed(&token.address), 0);
+    assert_eq!(
+        client.try_release_funds(&split_id),
+        Err(Ok(Error::SplitReleased))
+    );
 }
 
 // ============================================
@@ -0,0 +1,208 @@
+//! # Test Fixtures for Split Escrow Contract
+//!
+//! I'm exposing these behind the `testutils` feature so contracts that
+//! integrate with the escrow can build the same fixtures our own tests use
+//! instead of copying the setup boilerplate.
+
+use soroban_sdk::{testutils::Address as _, token, Address, Env, String, Vec};
+
+use crate::types::{MaybeHash, Participant, Split, SplitCategory, SplitMode, SplitStatus};
+use crate::{SplitEscrowContract, SplitEscrowContractClient};
+
+/// Builder for a `Participant` record
+///
+/// `has_paid` is derived from the amounts, so a built participant is always
+/// consistent with itself.
+pub struct ParticipantBuilder {
+    address: Address,
+    share_amount: i128,
+    amount_paid: i128,
+}
+
+impl ParticipantBuilder {
+    pub fn new(address: &Address, share_amount: i128) -> Self {
+        Self {
+            address: address.clone(),
+            share_amount,
+            amount_paid: 0,
+        }
+    }
+
+    /// Amount this participant has already paid
+    pub fn paid(mut self, amount: i128) -> Self {
+        self.amount_paid = amount;
+        self
+    }
+
+    pub fn build(self) -> Participant {
+        Participant {
+            has_paid: self.amount_paid >= self.share_amount,
+            address: self.address,
+            share_amount: self.share_amount,
+            amount_paid: self.amount_paid,
+        }
+    }
+}
+
+/// Builder for a `Split` record
+///
+/// I'm deriving `total_amount` and `amount_collected` from the participants
+/// and the status from how much was collected, so the defaults pass
+/// `check_invariants`. Override the status explicitly for other states.
+pub struct SplitBuilder {
+    env: Env,
+    id: u64,
+    creator: Address,
+    description: String,
+    participants: Vec<Participant>,
+    status: Option<SplitStatus>,
+    mode: SplitMode,
+    category: SplitCategory,
+    deadline: Option<u64>,
+}
+
+impl SplitBuilder {
+    pub fn new(env: &Env, id: u64, creator: &Address) -> Self {
+        Self {
+            env: env.clone(),
+            id,
+            creator: creator.clone(),
+            description: String::from_str(env, "Test split"),
+            participants: Vec::new(env),
+            status: None,
+            mode: SplitMode::Escrow,
+            category: SplitCategory::Other,
+            deadline: None,
+        }
+    }
+
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = String::from_str(&self.env, description);
+        self
+    }
+
+    pub fn participant(mut self, participant: Participant) -> Self {
+        self.participants.push_back(participant);
+        self
+    }
+
+    pub fn status(mut self, status: SplitStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    pub fn mode(mut self, mode: SplitMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn category(mut self, category: SplitCategory) -> Self {
+        self.category = category;
+        self
+    }
+
+    pub fn deadline(mut self, deadline: u64) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    pub fn build(self) -> Split {
+        let mut total_amount = 0;
+        let mut amount_collected = 0;
+        for p in self.participants.iter() {
+            total_amount += p.share_amount;
+            amount_collected += p.amount_paid;
+        }
+
+        let status = self.status.unwrap_or(if amount_collected == 0 {
+            SplitStatus::Pending
+        } else if amount_collected < total_amount {
+            SplitStatus::Active
+        } else {
+            SplitStatus::Completed
+        });
+
+        Split {
+            id: self.id,
+            creator: self.creator,
+            description: self.description,
+            description_hash: MaybeHash::None,
+            total_amount,
+            amount_collected,
+            amount_released: 0,
+            fees_paid: 0,
+            participants: self.participants,
+            status,
+            mode: self.mode,
+            category: self.category,
+            receipt_ref: None,
+            tags: Vec::new(&self.env),
+            deadline: self.deadline,
+            completion_hook: None,
+            created_at: self.env.ledger().timestamp(),
+            metadata_uri: None,
+            nonce: 0,
+        }
+    }
+}
+
+/// Deploy a SEP-41 token and mint `amount` to each of `holders`
+pub fn create_token(env: &Env, holders: &[&Address], amount: i128) -> Address {
+    let issuer = Address::generate(env);
+    let token_id = env.register_stellar_asset_contract_v2(issuer).address();
+    let admin = token::StellarAssetClient::new(env, &token_id);
+    for holder in holders {
+        admin.mint(holder, &amount);
+    }
+    token_id
+}
+
+/// An escrow deployment that is already initialized
+pub struct EscrowFixture<'a> {
+    pub env: Env,
+    pub admin: Address,
+    pub token: Address,
+    pub client: SplitEscrowContractClient<'a>,
+}
+
+impl EscrowFixture<'_> {
+    /// Register and initialize the escrow against a fresh token
+    ///
+    /// All auths are mocked, including nested ones, so fixtures can drive
+    /// the escrow from inside other contracts.
+    pub fn new() -> Self {
+        let env = Env::default();
+        env.mock_all_auths_allowing_non_root_auth();
+
+        let admin = Address::generate(&env);
+        let token = create_token(&env, &[], 0);
+        let contract_id = env.register_contract(None, SplitEscrowContract);
+        let client = SplitEscrowContractClient::new(&env, &contract_id);
+        client.initialize(&admin, &token);
+
+        Self {
+            env,
+            admin,
+            token,
+            client,
+        }
+    }
+
+    /// Mint the escrow's token to `to`
+    pub fn fund(&self, to: &Address, amount: i128) {
+        token::StellarAssetClient::new(&self.env, &self.token).mint(to, &amount);
+    }
+
+    /// Write `split` straight into the escrow's storage, bypassing validation
+    pub fn store_split(&self, split: &Split) {
+        self.env.as_contract(&self.client.address, || {
+            crate::storage::set_split(&self.env, split.id, split);
+        });
+    }
+}
+
+impl Default for EscrowFixture<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
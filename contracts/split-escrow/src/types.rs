@@ -6,111 +6,1653 @@
 //! This module includes both the original types and the enhanced escrow
 //! types as specified in issue #59.
 
-use soroban_sdk::{contracterror, contracttype, Address, Env, String, Vec};
-use soroban_sdk::{Address, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, Bytes, BytesN, Env, String, Symbol, Vec};
+
+// ============================================
+// Original Types (preserved for compatibility)
+// ============================================
+
+/// Status of a split throughout its lifecycle
+///
+/// I designed these states to cover the full lifecycle:
+/// - Pending: Created but no deposits yet
+/// - Active: At least one deposit received
+/// - Completed: All participants have paid their share
+/// - Released: Funds have been released to the creator
+/// - Cancelled: Split was cancelled, refunds may be needed
+/// - Expired: The deadline passed before the split was fully funded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SplitStatus {
+    Pending,
+    Active,
+    Completed,
+    Released,
+    Cancelled,
+    Expired,
+}
+
+/// How a split moves (or doesn't move) money
+///
+/// - Escrow: deposits are held by the contract until release
+/// - Iou: no tokens move; the split only records who owes what and
+///   payments are confirmed by both parties
+/// - PassThrough: each deposit is forwarded to the creator in the same
+///   call, so the contract only tracks progress
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum SplitMode {
+    #[default]
+    Escrow,
+    Iou,
+    PassThrough,
+}
+
+/// What happens to the creator's own share when they're also a payer
+///
+/// - Owed: the creator deposits into their own split like anyone else
+/// - Credited: the share counts as settled without moving any tokens,
+///   as if the creator had written it off
+/// - Collected: the share is pulled from the creator during creation
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CreatorShare {
+    #[default]
+    Owed,
+    Credited,
+    Collected,
+}
+
+/// Spending category of a split, used by budgeting views
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SplitCategory {
+    Food,
+    Rent,
+    Travel,
+    Utilities,
+    #[default]
+    Other,
+}
+
+/// A participant in a split
+///
+/// I'm tracking both the owed amount and paid amount separately
+/// to support partial payments and payment verification.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Participant {
+    /// The participant's Stellar address
+    pub address: Address,
+
+    /// The amount this participant owes
+    pub share_amount: i128,
+
+    /// The amount this participant has paid so far
+    pub amount_paid: i128,
+
+    /// Whether the participant has fully paid their share
+    pub has_paid: bool,
+}
+
+/// How a split's amounts should be rendered
+///
+/// I'm capturing the token's decimals when the split is created so clients
+/// never have to guess how many stroops make one unit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitDisplay {
+    /// Decimals reported by the escrow token
+    pub decimals: u32,
+
+    /// Ticker chosen by the creator, if any
+    pub symbol: Option<String>,
+}
+
+/// Read model of a participant with the derived numbers clients need
+///
+/// I'm computing this on the fly in `get_participants`; nothing here is
+/// stored, so it can never disagree with the split itself.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParticipantView {
+    pub address: Address,
+
+    /// Display label the creator gave this participant, if any
+    pub label: Option<String>,
+
+    /// The amount this participant owes in total
+    pub share: i128,
+
+    /// The amount paid so far
+    pub paid: i128,
+
+    /// `share - paid`, never negative
+    pub remaining: i128,
+
+    /// Progress in basis points (10_000 = fully paid)
+    pub pct_paid: u32,
+
+    /// The split's deadline has passed with this share still unpaid
+    pub overdue: bool,
+
+    /// Part of `paid` the creator marked as settled outside the contract
+    pub settled_offchain: i128,
+
+    /// Unpaid amount the creator forgave; `share` no longer includes it
+    pub forgiven: i128,
+}
+
+/// A 32-byte hash that may be absent
+///
+/// I'd use `Option<BytesN<32>>`, but `#[contracttype]` structs can't hold
+/// one in this SDK version. Spelling the option out still lets the type
+/// enforce the length.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum MaybeHash {
+    #[default]
+    None,
+    Some(BytesN<32>),
+}
+
+impl MaybeHash {
+    /// The hash, if there is one
+    pub fn get(&self) -> Option<&BytesN<32>> {
+        match self {
+            MaybeHash::None => None,
+            MaybeHash::Some(hash) => Some(hash),
+        }
+    }
+}
+
+/// A bill split record
+///
+/// I'm storing all split data in a single struct for atomic operations.
+/// The participants vector allows any number of people to share a bill.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Split {
+    /// Unique identifier for this split
+    pub id: u64,
+
+    /// The address that created this split
+    pub creator: Address,
+
+    /// Human-readable description (e.g., "Dinner at Joe's")
+    pub description: String,
+
+    /// SHA-256 of the description for private splits (description is empty)
+    pub description_hash: MaybeHash,
+
+    /// Total amount to be split among participants
+    pub total_amount: i128,
+
+    /// Amount collected so far from participants
+    pub amount_collected: i128,
+
+    /// Amount already released out of escrow (including fees)
+    pub amount_released: i128,
+
+    /// Portion of `amount_released` withheld as fees
+    pub fees_paid: i128,
+
+    /// List of participants and their share details
+    pub participants: Vec<Participant>,
+
+    /// Current status of the split
+    pub status: SplitStatus,
+
+    /// Whether funds are escrowed or only tracked
+    pub mode: SplitMode,
+
+    /// Spending category for budgeting views
+    pub category: SplitCategory,
+
+    /// Off-chain receipt reference (e.g. an IPFS CID or content hash)
+    pub receipt_ref: Option<Bytes>,
+
+    /// Short free-form tags (e.g. "lisbon24") for grouping splits
+    pub tags: Vec<Symbol>,
+
+    /// Unix timestamp after which an underfunded split can be expired
+    pub deadline: Option<u64>,
+
+    /// Contract notified via `on_split_completed` when the split completes
+    pub completion_hook: Option<Address>,
+
+    /// Timestamp when the split was created
+    pub created_at: u64,
+
+    /// Link to rich off-chain data (photos, itemized receipt, group chat)
+    pub metadata_uri: Option<String>,
+
+    /// Version counter bumped on every write, for optimistic concurrency
+    pub nonce: u64,
+}
+
+/// The split layout written before versioned storage existed
+///
+/// I'm keeping this frozen so entries created by older deployments can
+/// still be decoded and upgraded after a contract upgrade. Never change it.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SplitV1 {
+    pub id: u64,
+    pub creator: Address,
+    pub description: String,
+    pub total_amount: i128,
+    pub amount_collected: i128,
+    pub amount_released: i128,
+    pub participants: Vec<Participant>,
+    pub status: SplitStatus,
+    pub created_at: u64,
+}
+
+/// A split as it sits in storage, tagged with its layout version
+///
+/// New layouts get a new variant; `migration::upgrade` knows how to turn
+/// every older variant into the current `Split`.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub enum StoredSplit {
+    V2(Split),
+}
+
+/// Optional settings accepted by `create_split_with_options`
+///
+/// I'm deriving `Default` so callers only spell out the settings they
+/// actually change; the defaults match plain `create_split`.
+#[contracttype]
+#[derive(Clone, Debug, Default)]
+pub struct SplitOptions {
+    /// How the split handles money
+    pub mode: SplitMode,
+
+    /// Store only this SHA-256 of the description instead of the plaintext
+    pub description_hash: MaybeHash,
+
+    /// Spending category (defaults to Other)
+    pub category: SplitCategory,
+
+    /// Up to `MAX_TAGS` distinct tags to index the split under
+    pub tags: Option<Vec<Symbol>>,
+
+    /// Unix timestamp deadline for collecting every share
+    pub deadline: Option<u64>,
+
+    /// Ledger sequence deadline; with both set, whichever passes first wins
+    pub deadline_ledger: Option<u32>,
+
+    /// Cap on how many times the creator may extend the deadlines
+    pub max_extensions: Option<u32>,
+
+    /// Escrow-token bond the creator locks until release (0 for none)
+    pub bond: i128,
+
+    /// Forward each deposit to the creator as it arrives (escrow mode only)
+    pub reimburse: bool,
+
+    /// Seconds between a release request and its execution (0 for none)
+    pub release_delay: u64,
+
+    /// Address that must confirm the creator's payout plan before release
+    pub confirmer: Option<Address>,
+
+    /// Pay the insurance premium so participants can claim from the pool
+    pub insured: bool,
+
+    /// Ticker clients should show next to amounts (e.g. "USDC")
+    pub display_symbol: Option<String>,
+
+    /// Only accept deposits that pay a participant's whole remaining share
+    pub require_full_payment: bool,
+
+    /// Who can read the split through the views
+    pub visibility: Visibility,
+
+    /// Off-chain settlements also need the participant's authorization
+    pub confirm_offchain: bool,
+
+    /// Contract implementing `CompletionHook` to call on completion
+    pub completion_hook: Option<Address>,
+
+    /// Leave the participant list open for up to this many people to join
+    /// (0 for a fixed list)
+    pub max_headcount: u32,
+
+    /// Let anyone `contribute` toward the total, not just listed participants
+    pub crowdfund: bool,
+
+    /// Rent-token budget the creator sets aside to pay keepers who keep
+    /// the split's entries alive (0 for none)
+    pub rent_budget: i128,
+
+    /// Mint each participant's obligation as a transferable share token
+    pub tokenized: bool,
+
+    /// Client-chosen 32-byte key that makes retrying the creation safe
+    pub idempotency_key: MaybeHash,
+
+    /// Round each deposit up to a multiple of this and give the difference
+    /// to `round_up_charity` at release (0 for no round-up)
+    pub round_up_unit: i128,
+
+    /// Who receives the round-ups; required with `round_up_unit`
+    pub round_up_charity: Option<Address>,
+
+    /// SHA-256 of a salt and each participant's email or phone, in
+    /// participant order (an empty entry skips that participant)
+    pub contact_hashes: Option<Vec<Bytes>>,
+
+    /// Share of each release, in basis points, kept in escrow until the
+    /// confirmation window ends (0 for none; escrow mode only)
+    pub retainage_bps: u32,
+
+    /// Seconds after a release before the creator can claim the retainage
+    pub retainage_window: u64,
+
+    /// Seconds after creation before an unpaid participant's slot can
+    /// lapse (0 for never)
+    pub invite_window: u64,
+
+    /// How the creator's own share is settled if they're listed as a payer
+    pub creator_share: CreatorShare,
+}
+
+/// Part of a split's releases held back for a final confirmation window
+///
+/// The creator claims `held` once `claimable_at` passes. If a dispute is
+/// decided against them first, it goes back to the participants instead.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Retainage {
+    /// Share of each release held back, in basis points
+    pub bps: u32,
+
+    /// Seconds after a release before the retainage can be claimed
+    pub window: u64,
+
+    /// Escrow-token amount held back so far
+    pub held: i128,
+
+    /// When the creator can claim what's held (restarts on each release)
+    pub claimable_at: u64,
+}
+
+/// A split's charity round-up
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundUp {
+    /// Each deposit is rounded up to a multiple of this
+    pub unit: i128,
+
+    /// Who receives the round-ups when the split is released
+    pub charity: Address,
+}
+
+/// Admin-managed parameters of creator-funded rent budgets
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentConfig {
+    /// Token rent budgets are paid in, typically the native XLM contract
+    pub token: Address,
+
+    /// What a keeper is paid from the budget for each due bump
+    pub keeper_fee: i128,
+}
+
+/// What's left of a split's rent budget
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RentBudget {
+    /// Unspent budget
+    pub remaining: i128,
+
+    /// Token the budget was funded in, whatever the rent config says now
+    pub token: Address,
+
+    /// Ledger sequence of the last bump (or of creation)
+    pub last_bump: u32,
+}
+
+/// The terms a split was created with, as committed to by its terms hash
+///
+/// The terms hash is the SHA-256 of this struct's XDR. Invoices and QR
+/// codes can carry the hash and anyone can rebuild it from the chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitTerms {
+    /// The escrow contract the split lives in
+    pub contract: Address,
+
+    /// Split ID
+    pub split_id: u64,
+
+    /// Who created the split
+    pub creator: Address,
+
+    /// Token the split is collected in
+    pub token: Address,
+
+    /// Total the split collects
+    pub total_amount: i128,
+
+    /// Participants, in order
+    pub participants: Vec<Address>,
+
+    /// Each participant's share, in participant order
+    pub shares: Vec<i128>,
+
+    /// Unix timestamp deadline, if any
+    pub deadline: Option<u64>,
+
+    /// Ledger sequence deadline, if any
+    pub deadline_ledger: Option<u32>,
+
+    /// The private split's description hash, or else the SHA-256 of the
+    /// description's XDR
+    pub description_hash: BytesN<32>,
+}
+
+/// How close a split's storage is to expiring
+///
+/// Contracts can't read TTLs on-chain, so `live_until` is a lower bound the
+/// contract keeps as it writes and bumps the split. The real entry may live
+/// longer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitHealth {
+    /// Ledger the split's entries live until, at the least
+    pub live_until: u32,
+
+    /// Ledgers from now until `live_until`
+    pub ledgers_left: u32,
+
+    /// Whether the split is inside the TTL threshold and should be bumped
+    pub bump_recommended: bool,
+
+    /// Whether `bump_split` would accept a bump right now
+    pub can_bump: bool,
+}
+
+/// The numbers a split's progress bar needs
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitProgress {
+    /// Collected so far
+    pub collected: i128,
+
+    /// Total the split collects
+    pub total: i128,
+
+    /// Share of the total collected, in basis points (10_000 = funded)
+    pub funded_bps: u32,
+
+    /// Participants who have paid their whole share
+    pub paid_count: u32,
+
+    /// Participants who still owe something
+    pub unpaid_count: u32,
+
+    /// Seconds until the timestamp deadline (0 once passed, `None` without one)
+    pub seconds_left: Option<u64>,
+
+    /// Ledgers until the ledger deadline (0 once passed, `None` without one)
+    pub ledgers_left: Option<u32>,
+}
+
+/// A parent split's progress together with its children
+///
+/// Cancelled and expired children are left out of the sums.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FamilyProgress {
+    /// Parent total plus the totals of its live children
+    pub total: i128,
+
+    /// Everything collected on the parent and its live children
+    pub collected: i128,
+
+    /// Number of live children
+    pub children: u32,
+
+    /// How many of them are fully funded or released
+    pub children_done: u32,
+}
+
+/// Why tokens left escrow
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferKind {
+    /// Payout of a funded split
+    Release,
+    /// Payout of part of what a split has collected so far
+    PartialRelease,
+    /// Deposit returned to the participant who made it
+    Refund,
+    /// Release fee sent to the fee recipient
+    Fee,
+    /// Creator bond returned
+    BondReturn,
+    /// Share of a slashed bond paid to a participant
+    BondSlash,
+    /// Compensation paid from the insurance pool
+    Insurance,
+    /// Payout bucket claimed by its recipient or reclaimed by the creator
+    Claim,
+    /// Keeper fee or unused remainder paid out of a rent budget
+    Rent,
+    /// Deposit round-ups given to the split's charity
+    Donation,
+    /// Late-release penalty handed back to a participant
+    Penalty,
+}
+
+/// How a unit-priced split divides its total
+///
+/// `units[i]` belongs to the i-th participant. Until the creator locks the
+/// units, they can still change and nobody can deposit.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitShares {
+    /// Whole units (nights, seats, items) per participant
+    pub units: Vec<u32>,
+
+    /// What one unit costs, from the latest division of the total
+    pub unit_price: i128,
+
+    /// Whether the units are fixed and deposits are open
+    pub locked: bool,
+}
+
+/// Penalty a creator pays for leaving a completed split unreleased
+///
+/// The penalty comes out of the payout and goes back to the participants.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LatePenalty {
+    /// Seconds a split may sit completed (after any release delay) before
+    /// the penalty starts
+    pub grace: u64,
+
+    /// Penalty added for each day, or part of a day, past the grace period
+    pub bps_per_day: u32,
+
+    /// Most the penalty can reach, in basis points of the payout
+    pub max_bps: u32,
+}
+
+/// A timestamped note the creator appended to a split
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitNote {
+    /// What the creator wrote
+    pub text: String,
+
+    /// When it was added
+    pub timestamp: u64,
+}
+
+/// One outbound transfer in a split's history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransferRecord {
+    /// Why the tokens moved
+    pub kind: TransferKind,
+
+    /// Who received them
+    pub to: Address,
+
+    /// Token that was sent
+    pub token: Address,
+
+    /// Amount sent
+    pub amount: i128,
+
+    /// Ledger timestamp of the transfer
+    pub timestamp: u64,
+}
+
+/// What one contributor has put toward a crowdfunded split
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Contribution {
+    /// The contributing address
+    pub contributor: Address,
+
+    /// Sum of everything they've contributed
+    pub amount: i128,
+}
+
+/// Per-item outcome of a batch operation
+///
+/// I'm reporting the numeric `Error` code instead of aborting so one bad ID
+/// doesn't undo the rest of the batch. `error` is 0 when `success` is true.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchResult {
+    /// The split this result refers to
+    pub split_id: u64,
+
+    /// Whether the operation was applied
+    pub success: bool,
+
+    /// The `Error` code explaining a skip (0 on success)
+    pub error: u32,
+}
+
+/// A participant's standing consent to have deposits pulled on a schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PullSchedule {
+    /// Amount pulled at each due date (less if less is owed)
+    pub amount: i128,
+
+    /// Seconds between due dates
+    pub interval: u64,
+
+    /// Timestamp of the next due date
+    pub next_due: u64,
+}
+
+/// Outcome of one participant's scheduled pull in `process_due`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PullResult {
+    /// The participant the pull was for
+    pub participant: Address,
+
+    /// Amount pulled (0 on failure)
+    pub amount: i128,
+
+    /// The `Error` code explaining a failed pull (0 on success)
+    pub error: u32,
+}
+
+/// Declare `Error` and the spec entries that publish its codes
+///
+/// A contract spec error enum holds at most 50 cases, so `Error` itself
+/// isn't exported. Its first 50 codes are published as `Error`, the name
+/// function specs refer to, and the rest as `ErrorExt`, both generated from
+/// the one list below so they can't drift apart.
+macro_rules! contract_errors {
+    (
+        $(#[$meta:meta])*
+        pub enum Error {
+            $($(#[$a_meta:meta])* $a:ident = $a_code:tt,)*
+        }
+        $(#[$ext_meta:meta])*
+        pub enum ErrorExt {
+            $($(#[$b_meta:meta])* $b:ident = $b_code:tt,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[contracterror(export = false)]
+        #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+        #[repr(u32)]
+        pub enum Error {
+            $($(#[$a_meta])* $a = $a_code,)*
+            $($(#[$b_meta])* $b = $b_code,)*
+        }
+
+        /// Spec-only mirrors of `Error`, split to fit the 50-case limit
+        pub mod error_spec {
+            use soroban_sdk::contracterror;
+
+            $(#[$meta])*
+            #[contracterror]
+            #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+            pub enum Error {
+                $($(#[$a_meta])* $a = $a_code,)*
+            }
+
+            $(#[$ext_meta])*
+            #[contracterror]
+            #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+            pub enum ErrorExt {
+                $($(#[$b_meta])* $b = $b_code,)*
+            }
+        }
+    };
+}
+
+contract_errors! {
+    /// Contract errors
+    pub enum Error {
+        SplitNotFound = 1,
+        SplitCancelled = 2,
+        SplitReleased = 3,
+        SplitNotFunded = 4,
+        SplitFullyFunded = 5,
+        NoFundsAvailable = 6,
+        InvalidAmount = 7,
+        ParticipantNotFound = 8,
+        GroupNotFound = 9,
+        NotGroupMember = 10,
+        /// The split already belongs to a group, an event or a parent split
+        SplitAlreadyGrouped = 11,
+        UnsupportedMode = 12,
+        NotPrivate = 13,
+        DescriptionTooLong = 14,
+        InvalidHash = 15,
+        InvalidReceiptRef = 16,
+        ReminderTooSoon = 17,
+        InvalidConfig = 18,
+        SplitExpired = 19,
+        DeadlineNotReached = 20,
+        FeeModuleFailed = 21,
+        InvalidFee = 22,
+        ActionNotFound = 23,
+        ActionNotReady = 24,
+        ContractPaused = 25,
+        InvalidParticipants = 26,
+        SharesMismatch = 27,
+        PlaintextDescription = 28,
+        InvalidDeadline = 29,
+        TooManyTags = 30,
+        DuplicateTag = 31,
+        ExceedsRemaining = 32,
+        NotRefundable = 33,
+        TokenNotAccepted = 34,
+        OracleFailed = 35,
+        SwapFailed = 36,
+        SlippageExceeded = 37,
+        PayoutExpired = 38,
+        InvariantViolated = 39,
+        ExtensionLimitReached = 40,
+        DisputeOpen = 41,
+        NoDispute = 42,
+        InvalidSlash = 43,
+        TooManyOpenSplits = 44,
+        LabelTooLong = 45,
+        InvalidSymbol = 46,
+        PartialPaymentNotAllowed = 47,
+        DepositTooSmall = 48,
+        HasDeposits = 49,
+        AccessDenied = 50,
+    }
+    /// Contract errors past the first 50 codes
+    pub enum ErrorExt {
+        /// The caller's `expected_nonce` is behind the split's
+        StaleNonce = 51,
+        /// A bridge already credited this classic payment
+        PaymentAlreadyCredited = 52,
+        /// The new share would put an address past its spending cap
+        SpendingCapExceeded = 53,
+        /// A split already holds `MAX_NOTES` notes
+        TooManyNotes = 54,
+        /// A note is longer than `MAX_NOTE_LEN` bytes
+        NoteTooLong = 55,
+        /// A note has no text
+        EmptyNote = 56,
+        /// A metadata URI is longer than `MAX_METADATA_URI_LEN` bytes
+        UriTooLong = 57,
+        /// No event has this ID
+        EventNotFound = 58,
+        /// An event already holds `MAX_EVENT_SPLITS` splits
+        TooManyEventSplits = 59,
+        /// An event name is longer than `MAX_EVENT_NAME_LEN` bytes
+        EventNameTooLong = 60,
+        /// A parent split already has `MAX_CHILD_SPLITS` children
+        TooManyChildSplits = 61,
+        /// The split isn't a staged split waiting for its creator
+        NotAwaitingAcceptance = 62,
+        /// A unit-priced split's units are already locked
+        UnitsLocked = 63,
+        /// The participant's invitation lapsed, so they can't deposit
+        ParticipantLapsed = 64,
+        /// A group already holds `MAX_GROUP_SPLITS` splits
+        TooManyGroupSplits = 65,
+        /// A split can't be cancelled without a reason
+        CancelReasonRequired = 66,
+    }
+}
+
+/// Configuration for the contract
+///
+/// I'm keeping this minimal for now but it can be extended
+/// to include fee settings, limits, etc.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractConfig {
+    /// The contract administrator address
+    pub admin: Address,
+
+    /// Whether the contract is paused
+    pub is_paused: bool,
+
+    /// Remaining TTL, in ledgers, below which a touched entry is extended
+    pub ttl_threshold: u32,
+
+    /// TTL, in ledgers, a touched entry is extended to
+    pub ttl_extend_to: u32,
+}
+
+/// The headline numbers of a split, for dashboards listing many at once
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitSummary {
+    /// The split's ID
+    pub id: u64,
+
+    /// The split's creator
+    pub creator: Address,
+
+    /// Current status
+    pub status: SplitStatus,
+
+    /// Total the split collects
+    pub total_amount: i128,
+
+    /// Collected so far
+    pub amount_collected: i128,
+
+    /// Number of participants
+    pub participant_count: u32,
+
+    /// How many of them have paid their whole share
+    pub paid_count: u32,
+
+    /// Unix timestamp deadline, if any
+    pub deadline: Option<u64>,
+
+    /// Whether the admin froze the split
+    pub frozen: bool,
+}
+
+/// One open split on an address's dashboard
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActiveSplit {
+    /// The split's headline numbers
+    pub summary: SplitSummary,
+
+    /// Whether the address created the split
+    pub is_creator: bool,
+
+    /// What the address still owes on it (0 if it only created the split)
+    pub remaining: i128,
+}
+
+/// What an address still owes across its open splits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OwedSummary {
+    /// Sum of unpaid shares
+    pub total: i128,
+
+    /// Number of open splits with an unpaid share
+    pub split_count: u32,
+}
+
+/// Outcome of a deposit, as previewed by `quote_deposit`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositQuote {
+    /// Amount the deposit would credit toward the participant's share
+    pub accepted: i128,
+
+    /// Round-up pulled on top of `accepted` for the split's charity
+    pub round_up: i128,
+
+    /// What the participant would still owe afterwards
+    pub remaining_share: i128,
+
+    /// Split status once the deposit (and any auto-release) is done
+    pub status: SplitStatus,
+
+    /// Gross amount that would leave escrow for the creator in the same call
+    pub released: i128,
+
+    /// Release fee withheld from `released`
+    pub fee: i128,
+}
+
+/// Outcome of a release, as previewed by `quote_release`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseQuote {
+    /// Gross amount leaving escrow
+    pub amount: i128,
+
+    /// Portion withheld by the fee module
+    pub fee: i128,
+
+    /// What the creator would receive
+    pub net: i128,
+
+    /// Split status after the release
+    pub status: SplitStatus,
+}
+
+/// Every transfer a release would make, as previewed by `preflight_release`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleasePreflight {
+    /// The transfers, in the order the release makes them
+    ///
+    /// Each is stamped with the current ledger time. A swapped payout is
+    /// listed as the escrow token going into the swap.
+    pub transfers: Vec<TransferRecord>,
+
+    /// Escrow token that stays in the contract, waiting in a claimable
+    /// payout bucket
+    pub retained: i128,
+
+    /// Split status after the release
+    pub status: SplitStatus,
+}
+
+/// Everything a frontend or explorer needs to introspect a deployment
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ContractInfo {
+    /// Contract semver, matching the `version` contract metadata entry
+    pub version: String,
+    pub admin: Address,
+    pub token: Address,
+    /// Whether new splits and deposits are currently blocked
+    pub paused: bool,
+    /// Registered fee module; `None` means releases are free
+    pub fee_module: Option<Address>,
+    pub fee_recipient: Option<Address>,
+    pub rounding_policy: RoundingPolicy,
+    pub timelock_delay: u64,
+}
+
+/// Independent switches for pausing parts of the contract
+///
+/// `set_paused(true)` still blocks creation and deposits on top of these,
+/// so an incident can also stop releases or refunds, or leave refunds open
+/// while everything else is stopped.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PauseFlags {
+    /// Block new splits
+    pub create: bool,
+
+    /// Block deposits, contributions and payment confirmations
+    pub deposit: bool,
+
+    /// Block releases and payout claims; funded splits wait at Completed
+    pub release: bool,
+
+    /// Block cancellations and refunds
+    pub refund: bool,
+}
+
+/// How long the circuit breaker counts before starting over
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreakerWindow {
+    /// Count per ledger
+    Ledger,
+
+    /// Count per UTC day
+    Day,
+}
+
+/// Caps that trip the circuit breaker (a 0 cap is not checked)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BreakerConfig {
+    /// What the counts below are per
+    pub window: BreakerWindow,
+
+    /// Most splits created per window
+    pub max_creations: u32,
+
+    /// Most escrow token released per window
+    pub max_volume: i128,
+
+    /// Releases of at least this much are held while tripped (0 holds all)
+    pub large_release: i128,
+}
+
+/// What the circuit breaker has counted in the current window
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct BreakerState {
+    /// Ledger sequence or day number of the window being counted
+    pub window_id: u64,
+
+    /// Splits created in the window
+    pub creations: u32,
+
+    /// Escrow token released in the window
+    pub volume: i128,
+
+    /// Whether a cap was hit; stays set until the admin resets it
+    pub tripped: bool,
+}
+
+/// Where the sub-stroop remainder of a proportional amount ends up
+///
+/// - TruncateToPayee: the carved-out part (e.g. a fee) rounds down and the
+///   payee keeps the dust
+/// - RoundToFeePool: the carved-out part rounds up, so the dust goes to the
+///   fee recipient
+/// - AssignToCreator: the carved-out part rounds down and the dust is paid
+///   to the split creator rather than the payee
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RoundingPolicy {
+    #[default]
+    TruncateToPayee,
+    RoundToFeePool,
+    AssignToCreator,
+}
+
+/// Who can read a split through the contract's views
+///
+/// - Public: anyone
+/// - Participants: only the creator and listed participants, proven by auth
+///
+/// This only gates the views; storage and events stay public on-chain, so
+/// it keeps bill details away from casual simulation, not from indexers.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Visibility {
+    #[default]
+    Public,
+    Participants,
+}
+
+/// Smallest deposit accepted in one transaction
+///
+/// - None: any positive amount is accepted
+/// - Absolute: a fixed amount in escrow-token stroops
+/// - ShareBps: a fraction of the depositing participant's share, in basis
+///   points
+///
+/// A participant can always pay off a remainder smaller than the minimum.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum MinDeposit {
+    #[default]
+    None,
+    Absolute(i128),
+    ShareBps(u32),
+}
+
+/// A deposit made in a token other than the escrow token
+///
+/// I'm keeping both the native amount (what gets forwarded or refunded)
+/// and the reference credit it earned at deposit time, so later oracle
+/// moves never change what a participant is credited with.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ForeignDeposit {
+    pub token: Address,
+
+    /// Amount held, in the deposited token's units
+    pub amount: i128,
+
+    /// Value credited toward the share, in escrow token units
+    pub credited: i128,
+}
+
+/// The asset a split's creator asked to be paid in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutAsset {
+    pub token: Address,
+
+    /// Least amount of `token` accepted for the whole escrow-token payout
+    pub min_out: i128,
+}
+
+/// Where a split's releases wait to be claimed
+///
+/// I'm using this for payees who can't receive a pushed transfer (for
+/// example a plain Stellar account without a trustline yet).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimablePayout {
+    /// Who may claim released funds
+    pub recipient: Address,
+
+    /// Seconds the recipient has to claim after each release
+    pub window: u64,
+}
+
+/// Released funds waiting for their recipient
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutBucket {
+    pub recipient: Address,
+
+    /// Escrow token amount waiting to be claimed
+    pub amount: i128,
+
+    /// After this timestamp the creator can take the funds back
+    pub expires_at: u64,
+}
+
+/// Fee module registration
+///
+/// I'm storing the recipient next to the module so a module can be swapped
+/// without touching where fees end up, and vice versa.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeConfig {
+    /// Contract implementing `FeeModule`
+    pub module: Address,
+
+    /// Address that receives collected fees
+    pub recipient: Address,
+}
+
+/// One band of the tiered fee schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeTier {
+    /// Smallest split total, in escrow-token units, the band applies to
+    pub min_total: i128,
+
+    /// Fee charged on releases of splits in the band, in basis points
+    pub bps: u32,
+}
+
+/// Built-in fee schedule that charges larger splits a lower rate
+///
+/// Tiers are ordered by `min_total`, starting at 0, and a split pays the
+/// rate of the last tier its total reaches.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeSchedule {
+    /// Address that receives collected fees
+    pub recipient: Address,
+
+    /// Fee bands, lowest first
+    pub tiers: Vec<FeeTier>,
+}
+
+/// Lifetime counters for an address
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AddressStats {
+    /// Splits the address created
+    pub splits_created: u32,
+
+    /// Splits the address was a participant in
+    pub splits_joined: u32,
+
+    /// Everything the address paid toward its shares
+    pub total_paid: i128,
+
+    /// Escrow-token payouts the address received
+    pub total_received: i128,
+
+    /// Shares the address paid in full
+    pub shares_paid: u32,
+
+    /// Days from split creation to full payment, summed over those shares
+    pub days_to_pay_total: u64,
+
+    /// Average days from split creation to full payment (rounded down)
+    pub avg_days_to_pay: u64,
+}
+
+/// A narrower duty the admin can hand to another key
+///
+/// The admin holds every role implicitly.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// May pause the contract and freeze single splits, but not undo either
+    Pauser,
+
+    /// May queue, execute and cancel fee changes
+    FeeManager,
+
+    /// May credit deposits paid from classic Stellar accounts
+    Bridge,
+}
+
+/// Who or what releases are never charged a fee for
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeExemption {
+    /// Every split this address creates, such as a partner app
+    Creator(Address),
+
+    /// Every release paid in this token, such as a promotional asset
+    Token(Address),
+}
+
+/// Audit record of a deposit a bridge credited from a classic payment
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BridgeCredit {
+    /// The bridge that verified the payment and paid it in
+    pub bridge: Address,
+
+    /// Split the payment was credited to
+    pub split_id: u64,
+
+    /// Participant the payment was credited to
+    pub participant: Address,
+
+    /// Amount credited
+    pub amount: i128,
+
+    /// Timestamp when the credit was made
+    pub credited_at: u64,
+}
+
+/// What a fee override applies to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FeeTarget {
+    /// One split, such as a goodwill credit after a dispute
+    Split(u64),
+
+    /// Every split this address creates, such as a promotion
+    Creator(Address),
+}
+
+/// A fixed release fee rate that replaces the module and schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeeOverride {
+    /// The split or creator the rate applies to
+    pub target: FeeTarget,
+
+    /// Rate charged on each release, in basis points (0 for free)
+    pub bps: u32,
+}
+
+/// Audit record of a creator bond slashed by a dispute ruling
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondSlash {
+    /// Share of the bond the arbiter slashed, in basis points
+    pub slash_bps: u32,
+
+    /// Amount taken from the bond and paid to participants
+    pub slashed: i128,
+
+    /// Amount of the bond returned to the creator
+    pub returned: i128,
+
+    /// What each participant who paid in received
+    pub payouts: Vec<(Address, i128)>,
+
+    /// Timestamp of the ruling
+    pub slashed_at: u64,
+}
+
+/// Admin-managed parameters of the insurance pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuranceConfig {
+    /// Premium an insured split's creator pays into the pool, in basis
+    /// points of the split total
+    pub premium_bps: u32,
+
+    /// Most a single participant can draw from the pool per split
+    pub max_claim: i128,
+}
+
 // ============================================
-// Original Types (preserved for compatibility)
+// Timelock Types
 // ============================================
 
-/// Status of a split throughout its lifecycle
+/// A sensitive admin operation that must wait out the timelock delay
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdminAction {
+    /// Replace the contract code with the uploaded wasm of this hash
+    Upgrade(BytesN<32>),
+
+    /// Register a fee module and fee recipient
+    SetFeeModule(FeeConfig),
+
+    /// Unregister the fee module
+    RemoveFeeModule,
+
+    /// Change the timelock delay itself (in seconds)
+    SetTimelockDelay(u64),
+
+    /// Hand the admin role to another address (e.g. a governance contract)
+    SetAdmin(Address),
+
+    /// Change where rounding dust goes in proportional payouts
+    SetRoundingPolicy(RoundingPolicy),
+
+    /// Set the price oracle used to value deposits in other tokens
+    SetOracle(Address),
+
+    /// Let participants deposit in this token
+    AcceptToken(Address),
+
+    /// Stop accepting deposits in this token
+    RemoveToken(Address),
+
+    /// Set the swap router used to pay creators in another asset
+    SetSwapRouter(Address),
+
+    /// Set the arbiter who rules on disputes
+    SetArbiter(Address),
+
+    /// Set the insurance pool's premium and claim limit
+    SetInsurance(InsuranceConfig),
+
+    /// Cap how many open splits one creator can have (0 removes the cap)
+    SetMaxOpenSplits(u32),
+
+    /// Set the smallest deposit accepted per transaction
+    SetMinDeposit(MinDeposit),
+
+    /// Seconds a split may sit without deposits before anyone can cancel
+    /// it (0 disables the window)
+    SetActivationWindow(u64),
+
+    /// Let this address stage splits on behalf of creators
+    AddOperator(Address),
+
+    /// Take the operator role away from this address
+    RemoveOperator(Address),
+
+    /// Let this contract deposit for participants on every split
+    AddDepositor(Address),
+
+    /// Take this contract off the deployment-wide depositor allowlist
+    RemoveDepositor(Address),
+
+    /// Set the circuit breaker's caps
+    SetBreaker(BreakerConfig),
+
+    /// Set the token and keeper fee of creator-funded rent budgets
+    SetRentConfig(RentConfig),
+
+    /// Register the companion contract that issues share tokens
+    SetShareToken(Address),
+
+    /// Set the tiered fee schedule used when no fee module is registered
+    SetFeeSchedule(FeeSchedule),
+
+    /// Remove the tiered fee schedule
+    RemoveFeeSchedule,
+
+    /// Set the bounty paid for expiring a split (0 to stop paying one)
+    SetExpiryBounty(i128),
+
+    /// Set the penalty for parking a completed split (a `max_bps` of 0
+    /// turns it off)
+    SetLatePenalty(LatePenalty),
+
+    /// Seconds a completed split can go unreleased before anyone may
+    /// refund it (0 turns the switch off)
+    SetAbandonTimeout(u64),
+
+    /// Stop charging fees for a creator or token
+    AddFeeExemption(FeeExemption),
+
+    /// Charge a creator or token fees again
+    RemoveFeeExemption(FeeExemption),
+
+    /// Share of paid participants, in basis points, who must approve
+    /// before a creator cancels a split with deposits (0 turns it off)
+    SetCancelQuorum(u32),
+
+    /// Charge one split or creator a fixed fee rate
+    SetFeeOverride(FeeOverride),
+
+    /// Go back to the usual fees for a split or creator
+    RemoveFeeOverride(FeeTarget),
+}
+
+/// A participant's complaint against a split's creator
 ///
-/// I designed these states to cover the full lifecycle:
-/// - Pending: Created but no deposits yet
-/// - Active: At least one deposit received
-/// - Completed: All participants have paid their share
-/// - Released: Funds have been released to the creator
-/// - Cancelled: Split was cancelled, refunds may be needed
+/// While one is open the split can't be released, so the creator's bond
+/// stays locked until the arbiter rules.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub enum SplitStatus {
-    Pending,
-    Active,
-    Completed,
-    Released,
-    Cancelled,
+pub struct Dispute {
+    /// The participant who opened the dispute
+    pub opened_by: Address,
+
+    /// Timestamp when the dispute was opened
+    pub opened_at: u64,
 }
 
-/// A participant in a split
+/// A release waiting out its split's `release_delay`
 ///
-/// I'm tracking both the owed amount and paid amount separately
-/// to support partial payments and payment verification.
+/// Participants can still open a dispute until `ready_at`, which blocks
+/// `execute_release` until the arbiter rules.
 #[contracttype]
-#[derive(Clone, Debug)]
-pub struct Participant {
-    /// The participant's Stellar address
-    pub address: Address,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseRequest {
+    /// Timestamp when `release_funds` was called
+    pub requested_at: u64,
 
-    /// The amount this participant owes
-    pub share_amount: i128,
+    /// Earliest timestamp `execute_release` can pay out
+    pub ready_at: u64,
+}
 
-    /// The amount this participant has paid so far
-    pub amount_paid: i128,
+/// One recipient's cut of a proposed payout, in basis points
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayoutShare {
+    pub recipient: Address,
+    pub bps: u32,
+}
 
-    /// Whether the participant has fully paid their share
-    pub has_paid: bool,
+/// A payout plan the creator proposed, waiting for the split's confirmer
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReleaseProposal {
+    /// How the net escrow-token payout is divided; sums to 10 000 bps
+    pub plan: Vec<PayoutShare>,
+
+    /// Timestamp of the latest proposal
+    pub proposed_at: u64,
 }
 
-/// A bill split record
+/// A higher total the creator proposed, waiting for participants to accept
 ///
-/// I'm storing all split data in a single struct for atomic operations.
-/// The participants vector allows any number of people to share a bill.
+/// Deposits keep counting against the current shares until everyone in
+/// `pending` has accepted.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TotalIncrease {
+    /// The proposed total
+    pub new_total: i128,
+
+    /// Proposed share for each participant, keyed by address
+    pub shares: Vec<IncreasedShare>,
+
+    /// Participants whose share grows and who haven't accepted yet
+    pub pending: Vec<Address>,
+
+    /// Timestamp of the proposal
+    pub proposed_at: u64,
+}
+
+/// One participant's share under a proposed total increase
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncreasedShare {
+    pub participant: Address,
+    pub share: i128,
+}
+
+/// An admin action waiting in the timelock queue
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledAction {
+    /// Unique identifier for this scheduled action
+    pub id: u64,
+
+    /// What will happen on execution
+    pub action: AdminAction,
+
+    /// Earliest timestamp the action can be executed
+    pub ready_at: u64,
+}
+
+// ============================================
+// Group Types
+// ============================================
+
+/// A standing group of people who split bills together
+///
+/// I'm keeping the split IDs on the group so group-wide views can walk
+/// every bill without scanning the whole split ID space.
 #[contracttype]
 #[derive(Clone, Debug)]
-pub struct Split {
-    /// Unique identifier for this split
+pub struct Group {
+    /// Unique identifier for this group
     pub id: u64,
 
-    /// The address that created this split
+    /// The address that created this group
     pub creator: Address,
 
-    /// Human-readable description (e.g., "Dinner at Joe's")
-    pub description: String,
+    /// Addresses that belong to the group (the creator is always included)
+    pub members: Vec<Address>,
 
-    /// Total amount to be split among participants
-    pub total_amount: i128,
+    /// Splits attached to this group
+    pub split_ids: Vec<u64>,
 
-    /// Amount collected so far from participants
-    pub amount_collected: i128,
+    /// Timestamp when the group was created
+    pub created_at: u64,
+}
 
-    /// Amount already released to the creator
-    pub amount_released: i128,
+/// A single direct payment in a simplified settlement plan
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settlement {
+    /// The member who pays
+    pub from: Address,
 
-    /// List of participants and their share details
-    pub participants: Vec<Participant>,
+    /// The member who receives
+    pub to: Address,
 
-    /// Current status of the split
-    pub status: SplitStatus,
+    /// Amount to pay
+    pub amount: i128,
+}
 
-    /// Timestamp when the split was created
-    pub created_at: u64,
+/// Which collection of splits a settlement report covers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SettlementScope {
+    Group(u64),
+    Event(u64),
 }
 
-/// Contract errors
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-#[repr(u32)]
-pub enum Error {
-    SplitNotFound = 1,
-    SplitCancelled = 2,
-    SplitReleased = 3,
-    SplitNotFunded = 4,
-    SplitFullyFunded = 5,
-    NoFundsAvailable = 6,
-    InvalidAmount = 7,
+/// One member's net position in a settlement report
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NetPosition {
+    /// The member's address
+    pub address: Address,
+
+    /// Positive when the member is owed money, negative when they owe it
+    pub net: i128,
 }
 
-/// Configuration for the contract
+/// One member's running totals across every split in a group
 ///
-/// I'm keeping this minimal for now but it can be extended
-/// to include fee settings, limits, etc.
+/// `net = paid + fronted - owed - received`: positive means the member has
+/// carried more than their share, negative means others covered them.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MemberStanding {
+    /// The member's address
+    pub address: Address,
+
+    /// Sum of the member's shares
+    pub owed: i128,
+
+    /// What the member has paid toward those shares
+    pub paid: i128,
+
+    /// Totals of the bills the member created (and so fronted)
+    pub fronted: i128,
+
+    /// What was paid in toward those bills
+    pub received: i128,
+
+    /// Positive when the group owes the member, negative when they owe it
+    pub net: i128,
+}
+
+/// Everything a "settle up" screen needs for a group or event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReport {
+    /// Every member with a nonzero net position
+    pub positions: Vec<NetPosition>,
+
+    /// Suggested payments that bring every position to zero
+    pub payments: Vec<Settlement>,
+}
+
+// ============================================
+// Event Types
+// ============================================
+
+/// A named occasion, such as a trip, that collects several splits
+///
+/// Unlike a group there's no member list: whoever appears on one of the
+/// attached splits is part of the event.
 #[contracttype]
 #[derive(Clone, Debug)]
-pub struct ContractConfig {
-    /// The contract administrator address
-    pub admin: Address,
+pub struct SplitEvent {
+    /// Unique identifier for this event
+    pub id: u64,
 
-    /// Whether the contract is paused
-    pub is_paused: bool,
+    /// The address that created this event
+    pub creator: Address,
+
+    /// Display name, e.g. "Lisbon trip"
+    pub name: String,
+
+    /// Splits attached to this event
+    pub split_ids: Vec<u64>,
+
+    /// Timestamp when the event was created
+    pub created_at: u64,
+}
+
+/// Totals across every live split in an event
+///
+/// Cancelled and expired splits are left out, since nobody owes anything
+/// on them any more.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventSummary {
+    /// Sum of the split totals
+    pub total: i128,
+
+    /// Sum of what participants have paid so far
+    pub collected: i128,
+
+    /// Number of splits counted
+    pub split_count: u32,
+
+    /// How many of those are fully funded or released
+    pub completed_count: u32,
+
+    /// `collected` as basis points of `total`
+    pub progress_bps: u32,
+}
+
+/// One person's position across an event's live splits
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventMember {
+    /// The member's address
+    pub address: Address,
+
+    /// Sum of the member's shares
+    pub owed: i128,
+
+    /// Sum of what the member has paid toward those shares
+    pub paid: i128,
 }
 
 // ============================================
@@ -333,20 +1875,3 @@ pub fn create_escrow(
         created_at: env.ledger().timestamp(),
     }
 }
-
-#[derive(Clone)]
-pub struct Participant {
-    pub address: Address,
-    pub amount_owed: i128,
-    pub paid: bool,
-}
-
-#[derive(Clone)]
-pub struct SplitEscrow {
-    pub split_id: String,
-    pub creator: Address,
-    pub participants: Vec<Participant>,
-    pub deadline: u64,
-    pub total_amount: i128,
-    pub active: bool,
-}
@@ -0,0 +1,51 @@
+//! # Events Module for Split Governance Contract
+//!
+//! I'm emitting one event per proposal lifecycle step so signers can follow
+//! pending admin operations from the event stream.
+
+use soroban_sdk::{symbol_short, Address, Env, Symbol, Vec};
+
+/// Emit when a signer proposes a call
+pub fn emit_proposed(
+    env: &Env,
+    proposal_id: u64,
+    proposer: &Address,
+    target: &Address,
+    function: &Symbol,
+) {
+    env.events().publish(
+        (symbol_short!("proposed"),),
+        (
+            proposal_id,
+            proposer.clone(),
+            target.clone(),
+            function.clone(),
+        ),
+    );
+}
+
+/// Emit when a signer approves a proposal
+pub fn emit_approved(env: &Env, proposal_id: u64, signer: &Address, approvals: u32) {
+    env.events().publish(
+        (symbol_short!("approved"),),
+        (proposal_id, signer.clone(), approvals),
+    );
+}
+
+/// Emit when a proposal's call is made
+pub fn emit_executed(env: &Env, proposal_id: u64) {
+    env.events()
+        .publish((symbol_short!("executed"),), (proposal_id,));
+}
+
+/// Emit when the proposer withdraws a proposal
+pub fn emit_cancelled(env: &Env, proposal_id: u64) {
+    env.events()
+        .publish((symbol_short!("cancelled"),), (proposal_id,));
+}
+
+/// Emit when a rotation proposal replaces the signer set
+pub fn emit_rotated(env: &Env, signers: &Vec<Address>, threshold: u32) {
+    env.events()
+        .publish((symbol_short!("rotated"),), (signers.clone(), threshold));
+}
@@ -0,0 +1,281 @@
+//! # Split Governance Contract
+//!
+//! I designed this as a small companion to the split escrow: it holds the
+//! escrow's admin role and only calls admin functions once M of its N
+//! signers have approved, so a single compromised key can't drain or brick
+//! the system.
+//!
+//! ## Core Functionality
+//! - Configure a signer set and approval threshold once
+//! - Propose any call on a target contract (e.g. the escrow's
+//!   `schedule_action`)
+//! - Collect approvals and execute once the threshold is met
+//! - Rotate the signer set and threshold through the same approval flow
+//! - Let proposers withdraw proposals, and let stale ones expire
+
+#![no_std]
+
+use soroban_sdk::{contract, contractimpl, vec, Address, Env, IntoVal, Symbol, Val, Vec};
+
+mod events;
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+pub use types::*;
+
+/// The M-of-N governance contract
+#[contract]
+pub struct SplitGovernanceContract;
+
+#[contractimpl]
+impl SplitGovernanceContract {
+    /// Configure the signer set and the number of approvals required
+    ///
+    /// I'm requiring every signer to authorize setup so nobody can be
+    /// enrolled (and later blamed) without consent.
+    pub fn initialize(env: Env, signers: Vec<Address>, threshold: u32) -> Result<(), Error> {
+        if storage::has_signers(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        for signer in signers.iter() {
+            signer.require_auth();
+        }
+        let unique = Self::checked_signers(&env, &signers, threshold)?;
+
+        storage::set_signers(&env, &unique);
+        storage::set_threshold(&env, threshold);
+
+        Ok(())
+    }
+
+    /// Propose a call on a target contract
+    ///
+    /// The proposer's own approval is counted immediately. The governance
+    /// contract can't be the target; rotations go through
+    /// `propose_rotation`.
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) -> Result<u64, Error> {
+        proposer.require_auth();
+        Self::require_signer(&env, &proposer)?;
+        if target == env.current_contract_address() {
+            return Err(Error::InvalidProposal);
+        }
+
+        Ok(Self::create_proposal(
+            &env, proposer, target, function, args,
+        ))
+    }
+
+    /// Propose replacing the signer set and threshold
+    ///
+    /// It's approved and executed like any other proposal, against the
+    /// signer set in force at the time. Anyone being added must authorize
+    /// the proposal, just as every signer authorized setup.
+    pub fn propose_rotation(
+        env: Env,
+        proposer: Address,
+        signers: Vec<Address>,
+        threshold: u32,
+    ) -> Result<u64, Error> {
+        proposer.require_auth();
+        Self::require_signer(&env, &proposer)?;
+
+        let unique = Self::checked_signers(&env, &signers, threshold)?;
+        let current = storage::get_signers(&env);
+        for signer in unique.iter() {
+            if !current.contains(&signer) {
+                signer.require_auth();
+            }
+        }
+
+        let args = vec![&env, unique.into_val(&env), threshold.into_val(&env)];
+        Ok(Self::create_proposal(
+            &env,
+            proposer,
+            env.current_contract_address(),
+            Symbol::new(&env, "set_signers"),
+            args,
+        ))
+    }
+
+    /// Approve a pending proposal
+    ///
+    /// Returns the number of current signers who have approved it.
+    pub fn approve(env: Env, signer: Address, proposal_id: u64) -> Result<u32, Error> {
+        signer.require_auth();
+        Self::require_signer(&env, &signer)?;
+
+        let mut proposal = Self::open_proposal(&env, proposal_id)?;
+        if proposal.approvals.contains(&signer) {
+            return Err(Error::AlreadyApproved);
+        }
+
+        proposal.approvals.push_back(signer.clone());
+        storage::set_proposal(&env, &proposal);
+
+        let approvals = Self::count_approvals(&env, &proposal);
+        events::emit_approved(&env, proposal_id, &signer, approvals);
+
+        Ok(approvals)
+    }
+
+    /// Withdraw a pending proposal
+    ///
+    /// Only its proposer can, and only while they're still a signer.
+    pub fn cancel(env: Env, proposer: Address, proposal_id: u64) -> Result<(), Error> {
+        proposer.require_auth();
+        Self::require_signer(&env, &proposer)?;
+
+        let mut proposal = Self::open_proposal(&env, proposal_id)?;
+        if proposal.proposer != proposer {
+            return Err(Error::NotProposer);
+        }
+
+        proposal.cancelled = true;
+        storage::set_proposal(&env, &proposal);
+        events::emit_cancelled(&env, proposal_id);
+
+        Ok(())
+    }
+
+    /// Make the proposed call once the approval threshold is met
+    ///
+    /// Anyone may trigger execution; the approvals are what authorize it.
+    /// Only approvals from the current signer set count, so a rotation
+    /// retires the approvals of the signers it removed.
+    pub fn execute(env: Env, proposal_id: u64) -> Result<(), Error> {
+        let mut proposal = Self::open_proposal(&env, proposal_id)?;
+        if Self::count_approvals(&env, &proposal) < storage::get_threshold(&env) {
+            return Err(Error::ThresholdNotMet);
+        }
+
+        proposal.executed = true;
+        storage::set_proposal(&env, &proposal);
+
+        if proposal.target == env.current_contract_address() {
+            let signers: Vec<Address> = proposal.args.get(0).unwrap().into_val(&env);
+            let threshold: u32 = proposal.args.get(1).unwrap().into_val(&env);
+            // The signer set may have changed since the proposal was made
+            let signers = Self::checked_signers(&env, &signers, threshold)?;
+            storage::set_signers(&env, &signers);
+            storage::set_threshold(&env, threshold);
+            events::emit_rotated(&env, &signers, threshold);
+        } else {
+            env.invoke_contract::<Val>(&proposal.target, &proposal.function, proposal.args);
+        }
+
+        events::emit_executed(&env, proposal_id);
+
+        Ok(())
+    }
+
+    /// Get a proposal by ID
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Option<Proposal> {
+        storage::get_proposal(&env, proposal_id)
+    }
+
+    /// Get the signer set
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        storage::get_signers(&env)
+    }
+
+    /// Get the number of approvals required
+    pub fn get_threshold(env: Env) -> u32 {
+        storage::get_threshold(&env)
+    }
+}
+
+impl SplitGovernanceContract {
+    /// Store a new proposal with the proposer's approval and return its ID
+    fn create_proposal(
+        env: &Env,
+        proposer: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) -> u64 {
+        let proposal = Proposal {
+            id: storage::get_next_proposal_id(env),
+            proposer: proposer.clone(),
+            target,
+            function,
+            args,
+            approvals: vec![env, proposer.clone()],
+            executed: false,
+            cancelled: false,
+            expires_at: env.ledger().timestamp() + PROPOSAL_LIFETIME,
+        };
+        storage::set_proposal(env, &proposal);
+
+        events::emit_proposed(
+            env,
+            proposal.id,
+            &proposer,
+            &proposal.target,
+            &proposal.function,
+        );
+
+        proposal.id
+    }
+
+    /// Load a proposal that can still be approved or executed
+    fn open_proposal(env: &Env, proposal_id: u64) -> Result<Proposal, Error> {
+        let proposal = storage::get_proposal(env, proposal_id).ok_or(Error::ProposalNotFound)?;
+        if proposal.executed {
+            return Err(Error::AlreadyExecuted);
+        }
+        if proposal.cancelled {
+            return Err(Error::ProposalCancelled);
+        }
+        if env.ledger().timestamp() >= proposal.expires_at {
+            return Err(Error::ProposalExpired);
+        }
+        Ok(proposal)
+    }
+
+    /// How many of a proposal's approvers are current signers
+    fn count_approvals(env: &Env, proposal: &Proposal) -> u32 {
+        let signers = storage::get_signers(env);
+        proposal
+            .approvals
+            .iter()
+            .filter(|approver| signers.contains(approver))
+            .count() as u32
+    }
+
+    /// Drop duplicate signers and check the threshold fits the rest
+    fn checked_signers(
+        env: &Env,
+        signers: &Vec<Address>,
+        threshold: u32,
+    ) -> Result<Vec<Address>, Error> {
+        let mut unique = Vec::new(env);
+        for signer in signers.iter() {
+            if !unique.contains(&signer) {
+                unique.push_back(signer);
+            }
+        }
+
+        if threshold == 0 || threshold > unique.len() {
+            return Err(Error::InvalidThreshold);
+        }
+        Ok(unique)
+    }
+
+    fn require_signer(env: &Env, address: &Address) -> Result<(), Error> {
+        if storage::get_signers(env).contains(address) {
+            Ok(())
+        } else {
+            Err(Error::NotSigner)
+        }
+    }
+}
@@ -0,0 +1,97 @@
+//! # Storage Module for Split Governance Contract
+//!
+//! I'm mirroring the escrow's typed storage keys and TTL handling.
+
+use soroban_sdk::{contracttype, Address, Env, Vec};
+
+use crate::types::Proposal;
+
+/// Storage keys for the governance contract
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Addresses allowed to propose and approve
+    Signers,
+
+    /// Approvals required to execute a proposal
+    Threshold,
+
+    /// Counter for generating unique proposal IDs
+    ProposalCounter,
+
+    /// A proposal record, indexed by ID
+    Proposal(u64),
+}
+
+/// Time-to-live for persistent storage (about 1 year)
+const LEDGER_TTL_PERSISTENT: u32 = 31_536_000;
+
+/// Time-to-live bump threshold (1 day)
+const LEDGER_TTL_THRESHOLD: u32 = 86_400;
+
+/// Check if the signer set has been configured
+pub fn has_signers(env: &Env) -> bool {
+    env.storage().persistent().has(&DataKey::Signers)
+}
+
+/// Get the signer set
+pub fn get_signers(env: &Env) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Signers)
+        .expect("Signers not set")
+}
+
+/// Set the signer set
+pub fn set_signers(env: &Env, signers: &Vec<Address>) {
+    let key = DataKey::Signers;
+    env.storage().persistent().set(&key, signers);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Get the approval threshold
+pub fn get_threshold(env: &Env) -> u32 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Threshold)
+        .expect("Threshold not set")
+}
+
+/// Set the approval threshold
+pub fn set_threshold(env: &Env, threshold: u32) {
+    let key = DataKey::Threshold;
+    env.storage().persistent().set(&key, &threshold);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
+
+/// Get the next proposal ID and increment the counter
+pub fn get_next_proposal_id(env: &Env) -> u64 {
+    let key = DataKey::ProposalCounter;
+    let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+    let next = current + 1;
+    env.storage().persistent().set(&key, &next);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+    next
+}
+
+/// Get a proposal by ID
+pub fn get_proposal(env: &Env, proposal_id: u64) -> Option<Proposal> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Proposal(proposal_id))
+}
+
+/// Store a proposal
+pub fn set_proposal(env: &Env, proposal: &Proposal) {
+    let key = DataKey::Proposal(proposal.id);
+    env.storage().persistent().set(&key, proposal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, LEDGER_TTL_THRESHOLD, LEDGER_TTL_PERSISTENT);
+}
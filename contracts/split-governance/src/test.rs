@@ -0,0 +1,257 @@
+//! # Unit Tests for Split Governance Contract
+//!
+//! I'm driving a real split escrow through governance to make sure the
+//! M-of-N flow works end to end.
+
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, Env, IntoVal, Symbol,
+};
+use split_escrow::{AdminAction, SplitEscrowContract, SplitEscrowContractClient};
+
+/// Helper to deploy governance (2-of-3) as the admin of a fresh escrow
+fn setup_test() -> (
+    Env,
+    Vec<Address>,
+    SplitGovernanceContractClient<'static>,
+    SplitEscrowContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let governance_id = env.register_contract(None, SplitGovernanceContract);
+    let governance = SplitGovernanceContractClient::new(&env, &governance_id);
+
+    let signers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    governance.initialize(&signers, &2);
+
+    let escrow_id = env.register_contract(None, SplitEscrowContract);
+    let escrow = SplitEscrowContractClient::new(&env, &escrow_id);
+    escrow.initialize(&governance_id, &Address::generate(&env));
+
+    (env, signers, governance, escrow)
+}
+
+/// Helper to propose scheduling an escrow admin action
+fn propose_schedule(
+    env: &Env,
+    governance: &SplitGovernanceContractClient,
+    escrow: &SplitEscrowContractClient,
+    proposer: &Address,
+    action: AdminAction,
+) -> u64 {
+    governance.propose(
+        proposer,
+        &escrow.address,
+        &Symbol::new(env, "schedule_action"),
+        &vec![env, action.into_val(env)],
+    )
+}
+
+#[test]
+fn test_initialize_rejects_bad_threshold() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let governance_id = env.register_contract(None, SplitGovernanceContract);
+    let governance = SplitGovernanceContractClient::new(&env, &governance_id);
+
+    let signers = vec![&env, Address::generate(&env), Address::generate(&env)];
+    assert_eq!(
+        governance.try_initialize(&signers, &3),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    assert_eq!(
+        governance.try_initialize(&signers, &0),
+        Err(Ok(Error::InvalidThreshold))
+    );
+}
+
+#[test]
+fn test_admin_call_requires_threshold() {
+    let (env, signers, governance, escrow) = setup_test();
+
+    let proposal_id = propose_schedule(
+        &env,
+        &governance,
+        &escrow,
+        &signers.get(0).unwrap(),
+        AdminAction::SetTimelockDelay(60),
+    );
+
+    // One approval (the proposer's) is not enough
+    assert_eq!(
+        governance.try_execute(&proposal_id),
+        Err(Ok(Error::ThresholdNotMet))
+    );
+
+    assert_eq!(
+        governance.approve(&signers.get(1).unwrap(), &proposal_id),
+        2
+    );
+    governance.execute(&proposal_id);
+
+    // The escrow queued the action on governance's behalf
+    let scheduled = escrow.get_scheduled_action(&1).unwrap();
+    assert_eq!(scheduled.action, AdminAction::SetTimelockDelay(60));
+
+    assert!(governance.get_proposal(&proposal_id).unwrap().executed);
+    assert_eq!(
+        governance.try_execute(&proposal_id),
+        Err(Ok(Error::AlreadyExecuted))
+    );
+}
+
+#[test]
+fn test_only_signers_can_propose_and_approve() {
+    let (env, signers, governance, escrow) = setup_test();
+    let outsider = Address::generate(&env);
+
+    assert_eq!(
+        governance.try_propose(
+            &outsider,
+            &escrow.address,
+            &symbol_short!("cancel"),
+            &vec![&env],
+        ),
+        Err(Ok(Error::NotSigner))
+    );
+
+    let proposal_id = propose_schedule(
+        &env,
+        &governance,
+        &escrow,
+        &signers.get(0).unwrap(),
+        AdminAction::RemoveFeeModule,
+    );
+    assert_eq!(
+        governance.try_approve(&outsider, &proposal_id),
+        Err(Ok(Error::NotSigner))
+    );
+    assert_eq!(
+        governance.try_approve(&signers.get(0).unwrap(), &proposal_id),
+        Err(Ok(Error::AlreadyApproved))
+    );
+}
+
+#[test]
+fn test_rotation_replaces_signers_and_retires_their_approvals() {
+    let (env, signers, governance, escrow) = setup_test();
+    let (alice, bob, carol) = (
+        signers.get(0).unwrap(),
+        signers.get(1).unwrap(),
+        signers.get(2).unwrap(),
+    );
+    let dave = Address::generate(&env);
+
+    // Approved by Alice and Bob, but not executed before Alice is rotated out
+    let pending = propose_schedule(
+        &env,
+        &governance,
+        &escrow,
+        &alice,
+        AdminAction::SetTimelockDelay(60),
+    );
+    governance.approve(&bob, &pending);
+
+    assert_eq!(
+        governance.try_propose_rotation(&bob, &vec![&env, bob.clone()], &2),
+        Err(Ok(Error::InvalidThreshold))
+    );
+    assert_eq!(
+        governance.try_propose(
+            &bob,
+            &governance.address,
+            &Symbol::new(&env, "set_signers"),
+            &vec![&env],
+        ),
+        Err(Ok(Error::InvalidProposal))
+    );
+    let rotation = governance.propose_rotation(
+        &bob,
+        &vec![&env, bob.clone(), carol.clone(), dave.clone()],
+        &2,
+    );
+    assert_eq!(
+        governance.try_execute(&rotation),
+        Err(Ok(Error::ThresholdNotMet))
+    );
+    governance.approve(&carol, &rotation);
+    governance.execute(&rotation);
+
+    assert_eq!(
+        governance.get_signers(),
+        vec![&env, bob.clone(), carol.clone(), dave.clone()]
+    );
+    assert_eq!(
+        governance.try_propose(
+            &alice,
+            &escrow.address,
+            &symbol_short!("cancel"),
+            &vec![&env]
+        ),
+        Err(Ok(Error::NotSigner))
+    );
+
+    // Alice's approval no longer counts towards the pending call
+    assert_eq!(
+        governance.try_execute(&pending),
+        Err(Ok(Error::ThresholdNotMet))
+    );
+    assert_eq!(governance.approve(&dave, &pending), 2);
+    governance.execute(&pending);
+}
+
+#[test]
+fn test_proposals_can_be_cancelled_and_expire() {
+    let (env, signers, governance, escrow) = setup_test();
+    let (alice, bob) = (signers.get(0).unwrap(), signers.get(1).unwrap());
+
+    let cancelled = propose_schedule(
+        &env,
+        &governance,
+        &escrow,
+        &alice,
+        AdminAction::RemoveFeeModule,
+    );
+    assert_eq!(
+        governance.try_cancel(&bob, &cancelled),
+        Err(Ok(Error::NotProposer))
+    );
+    governance.cancel(&alice, &cancelled);
+    assert!(governance.get_proposal(&cancelled).unwrap().cancelled);
+    assert_eq!(
+        governance.try_approve(&bob, &cancelled),
+        Err(Ok(Error::ProposalCancelled))
+    );
+    assert_eq!(
+        governance.try_execute(&cancelled),
+        Err(Ok(Error::ProposalCancelled))
+    );
+
+    let stale = propose_schedule(
+        &env,
+        &governance,
+        &escrow,
+        &alice,
+        AdminAction::RemoveFeeModule,
+    );
+    governance.approve(&bob, &stale);
+    env.ledger()
+        .with_mut(|li| li.timestamp += PROPOSAL_LIFETIME);
+    assert_eq!(
+        governance.try_execute(&stale),
+        Err(Ok(Error::ProposalExpired))
+    );
+}
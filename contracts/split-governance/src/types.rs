@@ -0,0 +1,65 @@
+//! # Custom Types for Split Governance Contract
+//!
+//! I'm keeping the governance data model deliberately small: a signer set,
+//! a threshold, and proposals that each describe one contract call.
+
+use soroban_sdk::{contracterror, contracttype, Address, Symbol, Val, Vec};
+
+/// Seconds a proposal stays open for approval and execution (7 days)
+pub const PROPOSAL_LIFETIME: u64 = 7 * 24 * 60 * 60;
+
+/// A proposed call that runs once enough signers approve it
+///
+/// I'm storing the call generically (target, function, args) so the same
+/// governance contract can drive any admin function on the escrow without
+/// being redeployed when the escrow grows new ones. A proposal that targets
+/// the governance contract itself is a signer rotation: `set_signers` with
+/// the new signer set and threshold as its arguments.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Proposal {
+    /// Unique identifier for this proposal
+    pub id: u64,
+
+    /// The signer who created the proposal
+    pub proposer: Address,
+
+    /// Contract to call
+    pub target: Address,
+
+    /// Function to call on the target
+    pub function: Symbol,
+
+    /// Arguments to pass
+    pub args: Vec<Val>,
+
+    /// Signers who have approved so far (the proposer included)
+    pub approvals: Vec<Address>,
+
+    /// Whether the call has been made
+    pub executed: bool,
+
+    /// Whether the proposer withdrew it
+    pub cancelled: bool,
+
+    /// Timestamp after which it can no longer be approved or executed
+    pub expires_at: u64,
+}
+
+/// Contract errors
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    InvalidThreshold = 2,
+    NotSigner = 3,
+    ProposalNotFound = 4,
+    AlreadyApproved = 5,
+    AlreadyExecuted = 6,
+    ThresholdNotMet = 7,
+    ProposalExpired = 8,
+    ProposalCancelled = 9,
+    NotProposer = 10,
+    InvalidProposal = 11,
+}